@@ -0,0 +1,16 @@
+//! Criterion benchmarks for the ZLang interpreter
+//! Run with `cargo bench` to get numbers before and after an optimization 📈
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zlang::bench::{run_program, PROGRAMS};
+
+fn interpreter_benchmarks(c: &mut Criterion) {
+    for program in PROGRAMS {
+        c.bench_function(program.name, |b| {
+            b.iter(|| run_program(black_box(program.source)).expect("benchmark program should run"))
+        });
+    }
+}
+
+criterion_group!(benches, interpreter_benchmarks);
+criterion_main!(benches);
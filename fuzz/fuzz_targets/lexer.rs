@@ -0,0 +1,16 @@
+//! Fuzz the lexer: any byte sequence must come back as Ok or Err, never a panic.
+//! With trivia and recovery on it can't fail at all, and the tokens must spell out the source
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zlang::lexer::Lexer;
+
+fuzz_target!(|data: &[u8]| {
+    let source = String::from_utf8_lossy(data);
+    let _ = Lexer::new(&source).tokenize();
+    
+    let chars: Vec<char> = source.chars().collect();
+    let tokens = Lexer::new(&source).with_trivia().with_recovery().tokenize().expect("recovery never gives up");
+    let spelled: String = tokens.iter().map(|token| token.lexeme(&chars)).collect();
+    assert_eq!(spelled, source);
+});
@@ -0,0 +1,13 @@
+//! Fuzz lexer + parser together, the same path the playground takes before running code
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zlang::lexer::Lexer;
+use zlang::parser::Parser;
+
+fuzz_target!(|data: &[u8]| {
+    let source = String::from_utf8_lossy(data);
+    if let Ok(tokens) = Lexer::new(&source).tokenize() {
+        let _ = Parser::new(tokens).parse();
+    }
+});
@@ -0,0 +1,168 @@
+//! ZLang Achievements - Badges for playground sessions, because validation is important 🏅
+//! The server keeps a few counters per session, the badges are just rules over those counters
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use crate::json;
+use crate::lexer::Lexer;
+
+/// Sessions remembered at once, past this an old one makes room
+const MAX_SESSIONS: usize = 10_000;
+
+/// What one session has been up to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    pub programs_run: u32,
+    pub successful_runs: u32,
+    /// Runs that failed right after a run that also failed don't count twice
+    pub errors_hit: u32,
+    /// Successful runs right after a failed one
+    pub errors_fixed: u32,
+    /// Every keyword in a program that ran without errors
+    pub keywords: BTreeSet<&'static str>,
+    last_failed: bool,
+}
+
+impl Stats {
+    pub fn record(&mut self, source: &str, succeeded: bool) {
+        self.programs_run += 1;
+        if succeeded {
+            self.successful_runs += 1;
+            if self.last_failed {
+                self.errors_fixed += 1;
+            }
+            let tokens = Lexer::new(source).tokenize_partial();
+            self.keywords.extend(tokens.iter().filter_map(|token| token.token_type.keyword()));
+        } else if !self.last_failed {
+            self.errors_hit += 1;
+        }
+        self.last_failed = !succeeded;
+    }
+    
+    pub fn earned(&self) -> Vec<&'static Badge> {
+        BADGES.iter().filter(|badge| (badge.earned)(self)).collect()
+    }
+}
+
+pub struct Badge {
+    pub id: &'static str,
+    pub emoji: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    earned: fn(&Stats) -> bool,
+}
+
+pub const BADGES: &[Badge] = &[
+    Badge {
+        id: "first_run",
+        emoji: "👶",
+        title: "Hello World Era",
+        description: "Ran your very first program",
+        earned: |stats| stats.programs_run >= 1,
+    },
+    Badge {
+        id: "first_slay",
+        emoji: "💅",
+        title: "First slay",
+        description: "A program ran start to finish without a single error",
+        earned: |stats| stats.successful_runs >= 1,
+    },
+    Badge {
+        id: "comeback_arc",
+        emoji: "🔁",
+        title: "Comeback arc",
+        description: "Fixed a program that was throwing errors",
+        earned: |stats| stats.errors_fixed >= 1,
+    },
+    Badge {
+        id: "caught_the_drama",
+        emoji: "🧤",
+        title: "Caught the drama",
+        description: "Handled an error with manifest/caught",
+        earned: |stats| stats.keywords.contains("caught"),
+    },
+    Badge {
+        id: "main_character",
+        emoji: "💪",
+        title: "Main character",
+        description: "Wrote your own function with flex",
+        earned: |stats| stats.keywords.contains("flex"),
+    },
+    Badge {
+        id: "grind_era",
+        emoji: "🔂",
+        title: "In my grind era",
+        description: "Looped with grind or lowkey",
+        earned: |stats| stats.keywords.contains("grind") || stats.keywords.contains("lowkey"),
+    },
+    Badge {
+        id: "fluent",
+        emoji: "🗣️",
+        title: "Fluent in slang",
+        description: "Used 10 different keywords in working code",
+        earned: |stats| stats.keywords.len() >= 10,
+    },
+    Badge {
+        id: "chronically_online",
+        emoji: "📱",
+        title: "Chronically online",
+        description: "Ran 25 programs in one session",
+        earned: |stats| stats.programs_run >= 25,
+    },
+];
+
+/// Every session's stats, shared between the server's connections
+#[derive(Debug, Default)]
+pub struct Sessions {
+    stats: Mutex<HashMap<String, Stats>>,
+}
+
+impl Sessions {
+    pub fn record(&self, session: &str, source: &str, succeeded: bool) {
+        if !is_valid_id(session) {
+            return;
+        }
+        let mut sessions = self.stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if sessions.len() >= MAX_SESSIONS && !sessions.contains_key(session) {
+            if let Some(evicted) = sessions.keys().next().cloned() {
+                sessions.remove(&evicted);
+            }
+        }
+        sessions.entry(session.to_string()).or_default().record(source, succeeded);
+    }
+    
+    pub fn stats(&self, session: &str) -> Stats {
+        let sessions = self.stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        sessions.get(session).cloned().unwrap_or_default()
+    }
+    
+    /// The session's stats plus every badge and whether it's been earned, locked ones included
+    pub fn to_json(&self, session: &str) -> String {
+        let stats = self.stats(session);
+        let keywords: Vec<String> = stats.keywords.iter().map(|keyword| json::string(keyword)).collect();
+        let badges: Vec<String> = BADGES.iter().map(|badge| format!(
+            "{{\"id\": {}, \"emoji\": {}, \"title\": {}, \"description\": {}, \"earned\": {}}}",
+            json::string(badge.id),
+            json::string(badge.emoji),
+            json::string(badge.title),
+            json::string(badge.description),
+            (badge.earned)(&stats)
+        )).collect();
+        
+        format!(
+            "{{\"stats\": {{\"programs_run\": {}, \"successful_runs\": {}, \"errors_hit\": {}, \"errors_fixed\": {}, \"keywords\": [{}]}}, \"badges\": [{}]}}",
+            stats.programs_run,
+            stats.successful_runs,
+            stats.errors_hit,
+            stats.errors_fixed,
+            keywords.join(", "),
+            badges.join(", ")
+        )
+    }
+}
+
+/// Ids come from the browser - keep them short and boring
+pub fn is_valid_id(session: &str) -> bool {
+    !session.is_empty() && session.len() <= 64 && session.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
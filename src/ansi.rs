@@ -0,0 +1,89 @@
+//! ZLang ANSI - `colored()` and `bold()`, so your prints can finally have some personality 🌈
+//! The escapes ride along inside the string, each console decides whether to show them or strip them
+
+use std::borrow::Cow;
+use std::io::IsTerminal;
+
+/// Every color `colored()` knows, with its ANSI foreground code
+pub const COLORS: &[(&str, u8)] = &[
+    ("black", 30),
+    ("red", 31),
+    ("green", 32),
+    ("yellow", 33),
+    ("blue", 34),
+    ("magenta", 35),
+    ("purple", 35),
+    ("cyan", 36),
+    ("white", 37),
+    ("gray", 90),
+    ("grey", 90),
+];
+
+/// `text` in `color`, None when that's not a color we know. Only the foreground gets reset
+/// at the end, so it nests inside `bold()` and the other way around
+pub fn colored(text: &str, color: &str) -> Option<String> {
+    let (_, code) = COLORS.iter().find(|(name, _)| name.eq_ignore_ascii_case(color))?;
+    Some(format!("\x1b[{}m{}\x1b[39m", code, text))
+}
+
+pub fn bold(text: &str) -> String {
+    format!("\x1b[1m{}\x1b[22m", text)
+}
+
+/// `text` with its color and style escapes taken out, for output that isn't headed to a terminal
+pub fn strip(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(pieces(text).filter(|(_, escape)| !escape).map(|(piece, _)| piece).collect())
+}
+
+/// `uppercase()` for strings that might be colored - the escapes have to stay lowercase to mean anything
+pub fn uppercase(text: &str) -> String {
+    pieces(text)
+        .map(|(piece, escape)| if escape { piece.to_string() } else { piece.to_uppercase() })
+        .collect()
+}
+
+/// `text` cut into plain runs and color escapes, each piece says whether it's an escape
+fn pieces(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let escape_length = escape_at_start(rest);
+        if escape_length > 0 {
+            let (escape, after) = rest.split_at(escape_length);
+            rest = after;
+            return Some((escape, true));
+        }
+        
+        // Plain text runs up to the next escape, a stray ESC that starts nothing counts as text
+        let end = rest.char_indices()
+            .skip(1)
+            .find(|&(index, _)| escape_at_start(&rest[index..]) > 0)
+            .map_or(rest.len(), |(index, _)| index);
+        let (plain, after) = rest.split_at(end);
+        rest = after;
+        Some((plain, false))
+    })
+}
+
+/// How long the `ESC [ 1;31 m` style escape at the start of `text` is, 0 when there isn't one
+fn escape_at_start(text: &str) -> usize {
+    let Some(after) = text.strip_prefix("\x1b[") else {
+        return 0;
+    };
+    let end = after.find(|c: char| !c.is_ascii_digit() && c != ';').unwrap_or(after.len());
+    if after[end..].starts_with('m') {
+        2 + end + 1
+    } else {
+        0
+    }
+}
+
+/// Whether stdout is a terminal that wants colors - piped into a file or with NO_COLOR set, it doesn't
+pub fn stdout_wants_colors() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
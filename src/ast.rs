@@ -1,6 +1,15 @@
 //! Abstract Syntax Tree definitions for ZLang
 //! This is how we represent the structure of our code
 
+use std::sync::Arc;
+
+use crate::environment::Closure;
+use crate::deque::{self, Deque};
+use crate::heap::Heap;
+use crate::json;
+use crate::money;
+use crate::squad::{Instance, Squad};
+
 #[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub enum Expr {
@@ -18,6 +27,7 @@ pub enum Expr {
     Call {
         callee: Box<Expr>,
         arguments: Vec<Expr>,
+        line: usize,
     },
     Assign {
         name: String,
@@ -25,10 +35,214 @@ pub enum Expr {
     },
     Array(Vec<Expr>),
     Object(Vec<(String, Expr)>),
+    // `optional` for `list?[0]`, which is nil rather than an error when the list is
     Index {
         object: Box<Expr>,
         index: Box<Expr>,
+        optional: bool,
+    },
+    // `person.name` - reads a field, same as person["name"]. `optional` for `person?.name`
+    Get {
+        object: Box<Expr>,
+        name: String,
+        optional: bool,
+    },
+    // `me.score = 10` - sets a squad instance's field, for everyone holding the instance
+    Set {
+        object: Box<Expr>,
+        name: String,
+        value: Box<Expr>,
     },
+    // `result?` - unwrap an ok(...) or hand a nah(...) straight back to the caller
+    Propagate {
+        value: Box<Expr>,
+        line: usize,
+    },
+    // `score > 50 ? "pass" : "fail"` - only the branch that's picked gets evaluated
+    Conditional {
+        condition: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Box<Expr>,
+    },
+    // `1..10` and `1..=10` - the numbers from start to end as an array, counting down when end is smaller
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        inclusive: bool,
+    },
+    // `flex(x) { vibe x * 2 }` - a function without a name, as a value
+    Function {
+        params: Vec<String>,
+        // The last parameter collects any extra arguments into an array
+        rest: bool,
+        body: Arc<Vec<Stmt>>,
+    },
+    // `...items` in an array literal or a call's arguments - the array's items, one by one
+    Spread(Box<Expr>),
+}
+
+impl Expr {
+    /// ZLang source for this expression, with only the parentheses precedence needs
+    pub fn to_source(&self) -> String {
+        self.source(false)
+    }
+    
+    /// ZLang source with every operation wrapped in its own parentheses,
+    /// so `1 + 2 * 3` comes out as `(1 + (2 * 3))`
+    pub fn to_parenthesized(&self) -> String {
+        self.source(true)
+    }
+    
+    fn source(&self, parenthesize: bool) -> String {
+        let text = self.bare_source(parenthesize);
+        if parenthesize && self.is_operation() {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+    
+    /// Source without wrapping this expression itself - call arguments, array items and
+    /// indexes are already delimited, so they skip their own parentheses
+    fn bare_source(&self, parenthesize: bool) -> String {
+        // Wrap a child that binds looser than its spot needs (or every operation, when asked)
+        let child = |expr: &Expr, precedence: u8| {
+            if expr.precedence() < precedence && !(parenthesize && expr.is_operation()) {
+                format!("({})", expr.source(parenthesize))
+            } else {
+                expr.source(parenthesize)
+            }
+        };
+        let list = |items: &mut dyn Iterator<Item = &Expr>| {
+            items.map(|item| item.bare_source(parenthesize)).collect::<Vec<_>>().join(", ")
+        };
+        
+        match self {
+            Expr::Binary { left, operator, right } => {
+                // Everything is left-associative, so the right side needs parens at equal precedence
+                format!("{} {} {}", child(left, operator.precedence()), operator.symbol(), child(right, operator.precedence() + 1))
+            }
+            Expr::Unary { operator, right } => format!("{}{}", operator.symbol(), child(right, UNARY_PRECEDENCE)),
+            Expr::Literal(literal) => literal.to_source(),
+            Expr::Variable(name) => name.clone(),
+            Expr::Call { callee, arguments, .. } => format!("{}({})", child(callee, POSTFIX_PRECEDENCE), list(&mut arguments.iter())),
+            Expr::Assign { name, value } => format!("{} = {}", name, value.bare_source(parenthesize)),
+            Expr::Set { object, name, value } => format!("{}.{} = {}", child(object, POSTFIX_PRECEDENCE), name, value.bare_source(parenthesize)),
+            Expr::Array(items) => format!("[{}]", list(&mut items.iter())),
+            Expr::Object(fields) => {
+                let fields: Vec<String> = fields.iter()
+                    .map(|(key, value)| format!("{}: {}", key_source(key), value.bare_source(parenthesize)))
+                    .collect();
+                format!("{{{}}}", fields.join(", "))
+            }
+            Expr::Index { object, index, optional } => {
+                format!("{}{}[{}]", child(object, POSTFIX_PRECEDENCE), if *optional { "?" } else { "" }, index.bare_source(parenthesize))
+            }
+            Expr::Get { object, name, optional } => format!("{}{}.{}", child(object, POSTFIX_PRECEDENCE), if *optional { "?" } else { "" }, name),
+            Expr::Propagate { value, .. } => format!("{}?", child(value, POSTFIX_PRECEDENCE)),
+            // Nests to the right, `a ? b : c ? d : e` needs no parentheses
+            Expr::Conditional { condition, then_branch, else_branch } => format!(
+                "{} ? {} : {}",
+                child(condition, CONDITIONAL_PRECEDENCE + 1),
+                child(then_branch, CONDITIONAL_PRECEDENCE),
+                child(else_branch, CONDITIONAL_PRECEDENCE)
+            ),
+            // Written tight like in a case label, and `1..2..3` isn't a thing, so both sides bind tighter
+            Expr::Range { start, end, inclusive } => format!(
+                "{}{}{}",
+                child(start, RANGE_PRECEDENCE + 1),
+                if *inclusive { "..=" } else { ".." },
+                child(end, RANGE_PRECEDENCE + 1)
+            ),
+            // Statements have no source of their own, the body is just hinted at
+            Expr::Function { params, rest, body } => {
+                let body = if body.is_empty() { "" } else { " ... " };
+                format!("flex({}) {{{}}}", parameter_list(params, *rest), body)
+            }
+            Expr::Spread(value) => format!("...{}", child(value, UNARY_PRECEDENCE)),
+        }
+    }
+    
+    /// Bodies of the lambdas written in this expression, in source order. One nested in
+    /// another lambda's body is part of that body, it isn't listed on its own
+    pub fn lambda_bodies(&self) -> Vec<&[Stmt]> {
+        let mut bodies = Vec::new();
+        self.collect_lambda_bodies(&mut bodies);
+        bodies
+    }
+    
+    fn collect_lambda_bodies<'a>(&'a self, bodies: &mut Vec<&'a [Stmt]>) {
+        match self {
+            Expr::Function { body, .. } => bodies.push(body),
+            Expr::Binary { left, right, .. } | Expr::Index { object: left, index: right, .. } | Expr::Set { object: left, value: right, .. } |
+            Expr::Range { start: left, end: right, .. } => {
+                left.collect_lambda_bodies(bodies);
+                right.collect_lambda_bodies(bodies);
+            }
+            Expr::Unary { right: value, .. } | Expr::Assign { value, .. } | Expr::Propagate { value, .. } | Expr::Get { object: value, .. } |
+            Expr::Spread(value) => {
+                value.collect_lambda_bodies(bodies);
+            }
+            Expr::Call { callee, arguments, .. } => {
+                callee.collect_lambda_bodies(bodies);
+                for argument in arguments {
+                    argument.collect_lambda_bodies(bodies);
+                }
+            }
+            Expr::Array(items) => {
+                for item in items {
+                    item.collect_lambda_bodies(bodies);
+                }
+            }
+            Expr::Object(fields) => {
+                for (_, value) in fields {
+                    value.collect_lambda_bodies(bodies);
+                }
+            }
+            Expr::Conditional { condition, then_branch, else_branch } => {
+                condition.collect_lambda_bodies(bodies);
+                then_branch.collect_lambda_bodies(bodies);
+                else_branch.collect_lambda_bodies(bodies);
+            }
+            Expr::Literal(_) | Expr::Variable(_) => {}
+        }
+    }
+    
+    fn is_operation(&self) -> bool {
+        matches!(self, Expr::Binary { .. } | Expr::Unary { .. } | Expr::Assign { .. } | Expr::Set { .. } | Expr::Conditional { .. } | Expr::Range { .. })
+    }
+    
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Assign { .. } | Expr::Set { .. } => 0,
+            Expr::Conditional { .. } => CONDITIONAL_PRECEDENCE,
+            Expr::Binary { operator, .. } => operator.precedence(),
+            Expr::Range { .. } => RANGE_PRECEDENCE,
+            Expr::Unary { .. } => UNARY_PRECEDENCE,
+            _ => POSTFIX_PRECEDENCE,
+        }
+    }
+}
+
+const CONDITIONAL_PRECEDENCE: u8 = 1;
+// Just above comparisons, so `0..n - 1` and `1..=3 == list` mean what they look like
+const RANGE_PRECEDENCE: u8 = 6;
+const UNARY_PRECEDENCE: u8 = 13;
+const POSTFIX_PRECEDENCE: u8 = 14;
+
+/// `a, b, ...rest` - what goes between a function's parentheses
+fn parameter_list(params: &[String], rest: bool) -> String {
+    match params.split_last() {
+        Some((last, fixed)) if rest => fixed.iter().cloned().chain([format!("...{}", last)]).collect::<Vec<_>>().join(", "),
+        _ => params.join(", "),
+    }
+}
+
+/// Object keys that aren't plain identifiers need quotes
+fn key_source(key: &str) -> String {
+    let is_identifier = key.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_identifier { key.to_string() } else { json::string(key) }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +250,7 @@ pub enum Stmt {
     Expression(Expr),
     VarDeclaration {
         name: String,
+        type_annotation: Option<TypeAnnotation>,
         initializer: Option<Expr>,
     },
     Block(Vec<Stmt>),
@@ -44,35 +259,219 @@ pub enum Stmt {
         then_branch: Box<Stmt>,
         else_branch: Option<Box<Stmt>>,
     },
+    // A loop's `else_branch` is its `no sus`, which runs when it finishes without a `slay`
     While {
         condition: Expr,
         body: Box<Stmt>,
+        else_branch: Option<Box<Stmt>>,
     },
     For {
         variable: String,
         iterable: Expr,
         body: Box<Stmt>,
+        // `grind? (x in list)` - no loop at all when list is nil
+        optional: bool,
+        else_branch: Option<Box<Stmt>>,
+    },
+    // `grind (bet i = 0; i < 10; i = i + 1)` - any of the three clauses can be left empty
+    ClassicFor {
+        initializer: Option<Box<Stmt>>,
+        condition: Option<Expr>,
+        increment: Option<Expr>,
+        body: Box<Stmt>,
+        else_branch: Option<Box<Stmt>>,
     },
     Switch {
         expr: Expr,
-        cases: Vec<(Expr, Vec<Stmt>)>,
+        cases: Vec<SwitchCase>,
         default: Option<Vec<Stmt>>,
     },
     Try {
         try_block: Vec<Stmt>,
-        catch_block: Option<(String, Vec<Stmt>)>,
+        catch_clauses: Vec<CatchClause>,
         finally_block: Option<Vec<Stmt>>,
     },
     Throw(Expr),
+    // `drama type NotFoundDrama` or `drama type NotFoundDrama: LibraryDrama`
+    DramaType {
+        name: String,
+        parent: Option<String>,
+    },
     Function {
         name: String,
         params: Vec<String>,
+        param_types: Vec<Option<TypeAnnotation>>,
+        // `flex sum(...nums)` - the last parameter collects any extra arguments into an array
+        rest: bool,
+        return_type: Option<TypeAnnotation>,
         body: Vec<Stmt>,
     },
     Return(Option<Expr>),
     Break,
     Continue,
     Print(Expr),
+    Inspect(Expr),
+    Global(Vec<String>),
+    Outer(Vec<String>),
+    // `needs condition` at the top of a function body - a precondition checked on every call
+    Needs {
+        function: String,
+        condition: Expr,
+        line: usize,
+    },
+    // `pull "utils.zlang"` - runs another file (once) and keeps what it declares,
+    // `pull "utils.zlang" as u` keeps it all in one object called `u` instead
+    Pull {
+        path: String,
+        alias: Option<String>,
+        line: usize,
+    },
+    // `squad Point { bet x = 0  flex move(dx) { ... } }` - the body is its `bet` fields and
+    // `flex` methods, in the order they were written. `squad Dog from Animal { ... }` gets Animal's
+    // members too, with its own ones overriding them
+    Squad {
+        name: String,
+        parent: Option<String>,
+        body: Vec<Stmt>,
+    },
+}
+
+/// One `vibe check` case, picked when any of its patterns matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchCase {
+    pub patterns: Vec<CasePattern>,
+    pub body: Vec<Stmt>,
+}
+
+impl SwitchCase {
+    /// Every expression in the case's label, in source order
+    pub fn exprs(&self) -> impl Iterator<Item = &Expr> {
+        self.patterns.iter().flat_map(CasePattern::exprs)
+    }
+    
+    pub fn exprs_mut(&mut self) -> impl Iterator<Item = &mut Expr> {
+        self.patterns.iter_mut().flat_map(CasePattern::exprs_mut)
+    }
+}
+
+/// What a case label says about the switched value
+#[derive(Debug, Clone, PartialEq)]
+pub enum CasePattern {
+    // `"happy":` - equal to it
+    Value(Expr),
+    // `80..90:` leaves 90 out, `90..=100:` takes 100 in
+    Range {
+        low: Expr,
+        high: Expr,
+        inclusive: bool,
+    },
+    // `>= 50:` - the switched value goes on the comparison's left
+    Compare {
+        operator: BinaryOp,
+        value: Expr,
+    },
+}
+
+impl CasePattern {
+    pub fn exprs(&self) -> Vec<&Expr> {
+        match self {
+            CasePattern::Value(value) | CasePattern::Compare { value, .. } => vec![value],
+            CasePattern::Range { low, high, .. } => vec![low, high],
+        }
+    }
+    
+    pub fn exprs_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            CasePattern::Value(value) | CasePattern::Compare { value, .. } => vec![value],
+            CasePattern::Range { low, high, .. } => vec![low, high],
+        }
+    }
+}
+
+/// One `caught (e)` or `caught (e: SomeDrama)` clause - untyped clauses catch everything
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause {
+    pub variable: String,
+    pub error_type: Option<String>,
+    pub body: Vec<Stmt>,
+}
+
+/// Optional type annotations like `bet name: string` - only the type checker reads these
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAnnotation {
+    Number,
+    String,
+    Bool,
+    Array,
+    Object,
+    Nil,
+    Money,
+    Function,
+    Heap,
+    Queue,
+    Stack,
+    Any,
+}
+
+impl TypeAnnotation {
+    /// The runtime type of a value, never `Any`
+    pub fn of(value: &Literal) -> Self {
+        match value {
+            Literal::Number(_) => TypeAnnotation::Number,
+            Literal::String(_) => TypeAnnotation::String,
+            Literal::Boolean(_) => TypeAnnotation::Bool,
+            Literal::Nil => TypeAnnotation::Nil,
+            Literal::Money(_) => TypeAnnotation::Money,
+            Literal::Array(_) => TypeAnnotation::Array,
+            Literal::Object(_) => TypeAnnotation::Object,
+            // A squad is called to make an instance, and an instance's fields are read with dots
+            Literal::Function(_) | Literal::Squad(_) => TypeAnnotation::Function,
+            Literal::Instance(_) => TypeAnnotation::Object,
+            Literal::Heap(_) => TypeAnnotation::Heap,
+            Literal::Deque(deque) => match deque.kind() {
+                deque::Kind::Queue => TypeAnnotation::Queue,
+                deque::Kind::Stack => TypeAnnotation::Stack,
+            },
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "number" => Some(TypeAnnotation::Number),
+            "string" => Some(TypeAnnotation::String),
+            "bool" | "boolean" => Some(TypeAnnotation::Bool),
+            "array" => Some(TypeAnnotation::Array),
+            "object" => Some(TypeAnnotation::Object),
+            "nil" => Some(TypeAnnotation::Nil),
+            "money" => Some(TypeAnnotation::Money),
+            "function" => Some(TypeAnnotation::Function),
+            "heap" => Some(TypeAnnotation::Heap),
+            "queue" => Some(TypeAnnotation::Queue),
+            "stack" => Some(TypeAnnotation::Stack),
+            "any" => Some(TypeAnnotation::Any),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TypeAnnotation::Number => "number",
+            TypeAnnotation::String => "string",
+            TypeAnnotation::Bool => "bool",
+            TypeAnnotation::Array => "array",
+            TypeAnnotation::Object => "object",
+            TypeAnnotation::Nil => "nil",
+            TypeAnnotation::Money => "money",
+            TypeAnnotation::Function => "function",
+            TypeAnnotation::Heap => "heap",
+            TypeAnnotation::Queue => "queue",
+            TypeAnnotation::Stack => "stack",
+            TypeAnnotation::Any => "any",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -81,6 +480,8 @@ pub enum BinaryOp {
     Subtract,
     Multiply,
     Divide,
+    // `~/` - divide and round down, `//` was already taken by comments
+    IntDivide,
     Modulo,
     Equal,
     NotEqual,
@@ -90,12 +491,112 @@ pub enum BinaryOp {
     LessEqual,
     And,
     Or,
+    // Bitwise, on the numbers' whole parts as 64-bit integers
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::IntDivide => "~/",
+            BinaryOp::Modulo => "%",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::ShiftLeft => "<<",
+            BinaryOp::ShiftRight => ">>",
+        }
+    }
+    
+    /// The operator written `symbol`, the way `symbol()` spells it
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        let operator = match symbol {
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Subtract,
+            "*" => BinaryOp::Multiply,
+            "/" => BinaryOp::Divide,
+            "~/" => BinaryOp::IntDivide,
+            "%" => BinaryOp::Modulo,
+            "==" => BinaryOp::Equal,
+            "!=" => BinaryOp::NotEqual,
+            ">" => BinaryOp::Greater,
+            ">=" => BinaryOp::GreaterEqual,
+            "<" => BinaryOp::Less,
+            "<=" => BinaryOp::LessEqual,
+            "&&" => BinaryOp::And,
+            "||" => BinaryOp::Or,
+            "&" => BinaryOp::BitAnd,
+            "|" => BinaryOp::BitOr,
+            "^" => BinaryOp::BitXor,
+            "<<" => BinaryOp::ShiftLeft,
+            ">>" => BinaryOp::ShiftRight,
+            _ => return None,
+        };
+        Some(operator)
+    }
+    
+    /// How tightly the operator binds, matching the parser's levels - higher wins
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Or => 2,
+            BinaryOp::And => 3,
+            BinaryOp::Equal | BinaryOp::NotEqual => 4,
+            BinaryOp::Greater | BinaryOp::GreaterEqual | BinaryOp::Less | BinaryOp::LessEqual => 5,
+            // Above comparisons, so `flags & 1 == 0` means what it looks like
+            BinaryOp::BitOr => 7,
+            BinaryOp::BitXor => 8,
+            BinaryOp::BitAnd => 9,
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => 10,
+            BinaryOp::Add | BinaryOp::Subtract => 11,
+            BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::IntDivide | BinaryOp::Modulo => 12,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum UnaryOp {
     Minus,
+    // `+"42"` - whatever it's given as a number, same as num()
+    Plus,
     Not,
+    BitNot,
+}
+
+impl UnaryOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOp::Minus => "-",
+            UnaryOp::Plus => "+",
+            UnaryOp::Not => "!",
+            UnaryOp::BitNot => "~",
+        }
+    }
+    
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "-" => Some(UnaryOp::Minus),
+            "+" => Some(UnaryOp::Plus),
+            "!" => Some(UnaryOp::Not),
+            "~" => Some(UnaryOp::BitNot),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -104,8 +605,30 @@ pub enum Literal {
     String(String),
     Boolean(bool),
     Nil,
+    // Whole cents, made with `money(...)`
+    Money(i64),
     Array(Vec<Literal>),
     Object(std::collections::HashMap<String, Literal>),
+    Function(Function),
+    // Made with `heapNew()`, shared instead of copied
+    Heap(Heap),
+    // Made with `queue()` or `stack()`, shared the same way
+    Deque(Deque),
+    // What `squad Point { ... }` declares, and the instances `Point(...)` makes - shared too
+    Squad(Arc<Squad>),
+    Instance(Instance),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<String>,
+    // The last parameter collects any extra arguments into an array
+    pub rest: bool,
+    // Shared so calling a function doesn't copy its whole body
+    pub body: Arc<Vec<Stmt>>,
+    // Where it was made, when that's inside a function or block
+    pub closure: Option<Closure>,
 }
 
 impl std::fmt::Display for Literal {
@@ -116,6 +639,7 @@ impl std::fmt::Display for Literal {
             Literal::Boolean(true) => write!(f, "fr"),
             Literal::Boolean(false) => write!(f, "cap"),
             Literal::Nil => write!(f, "nil"),
+            Literal::Money(cents) => write!(f, "{}", money::format(*cents)),
             Literal::Array(arr) => {
                 write!(f, "[")?;
                 for (i, item) in arr.iter().enumerate() {
@@ -134,6 +658,82 @@ impl std::fmt::Display for Literal {
                 }
                 write!(f, "}}")
             }
+            Literal::Function(function) => write!(f, "<flex {}({})>", function.name, parameter_list(&function.params, function.rest)),
+            Literal::Heap(heap) => write!(f, "<heap of {}>", heap.len()),
+            Literal::Deque(deque) => write!(f, "<{} of {}>", TypeAnnotation::of(self), deque.len()),
+            Literal::Squad(squad) => write!(f, "<squad {}>", squad.name),
+            Literal::Instance(instance) => write!(f, "{}", instance),
         }
     }
 }
+
+impl Literal {
+    /// How the value would be written in ZLang code - strings quoted, booleans as fr/cap
+    pub fn to_source(&self) -> String {
+        match self {
+            Literal::String(s) => json::string(s),
+            Literal::Money(cents) => format!("money(\"{}\")", money::plain(*cents)),
+            Literal::Array(items) => {
+                let items: Vec<String> = items.iter().map(Literal::to_source).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Literal::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                let fields: Vec<String> = keys.iter().map(|key| format!("{}: {}", key_source(key), map[*key].to_source())).collect();
+                format!("{{{}}}", fields.join(", "))
+            }
+            other => other.to_string(),
+        }
+    }
+    
+    /// Debug view of a value: strings quoted, nested arrays and objects indented,
+    /// object keys sorted. Values are copied rather than shared, so they can't
+    /// contain cycles yet
+    pub fn inspect(&self) -> String {
+        let mut output = String::new();
+        self.write_inspect(&mut output, 0);
+        output
+    }
+    
+    fn write_inspect(&self, output: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        match self {
+            Literal::String(s) => output.push_str(&format!("{:?}", s)),
+            Literal::Array(items) if items.is_empty() => output.push_str("[]"),
+            Literal::Array(items) if items.iter().all(|item| !item.is_container()) => {
+                let items: Vec<String> = items.iter().map(Literal::inspect).collect();
+                output.push_str(&format!("[{}]", items.join(", ")));
+            }
+            Literal::Array(items) => {
+                output.push_str("[\n");
+                for item in items {
+                    output.push_str(&indent);
+                    item.write_inspect(output, depth + 1);
+                    output.push_str(",\n");
+                }
+                output.push_str(&"  ".repeat(depth));
+                output.push(']');
+            }
+            Literal::Object(map) if map.is_empty() => output.push_str("{}"),
+            Literal::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                
+                output.push_str("{\n");
+                for key in keys {
+                    output.push_str(&format!("{}{}: ", indent, key));
+                    map[key].write_inspect(output, depth + 1);
+                    output.push_str(",\n");
+                }
+                output.push_str(&"  ".repeat(depth));
+                output.push('}');
+            }
+            other => output.push_str(&other.to_string()),
+        }
+    }
+    
+    fn is_container(&self) -> bool {
+        matches!(self, Literal::Array(_) | Literal::Object(_))
+    }
+}
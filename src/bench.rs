@@ -0,0 +1,70 @@
+//! ZLang Benchmarks - Representative programs so optimization PRs come with receipts 🧾
+//! The same programs power `zlang bench` and the criterion suite in `benches/`
+
+use std::time::{Duration, Instant};
+
+use crate::error::ZLangError;
+use crate::interpreter::{ExecutionResult, Interpreter};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+pub struct BenchProgram {
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+pub const PROGRAMS: &[BenchProgram] = &[
+    BenchProgram { name: "fib", source: include_str!("../benches/programs/fib.zlang") },
+    BenchProgram { name: "string_building", source: include_str!("../benches/programs/string_building.zlang") },
+    BenchProgram { name: "array_churn", source: include_str!("../benches/programs/array_churn.zlang") },
+    BenchProgram { name: "deep_objects", source: include_str!("../benches/programs/deep_objects.zlang") },
+];
+
+pub struct BenchResult {
+    pub name: &'static str,
+    pub iterations: u32,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+/// Lex, parse and interpret a program from scratch with a fresh interpreter
+pub fn run_program(source: &str) -> Result<ExecutionResult, ZLangError> {
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize()?;
+    
+    let mut parser = Parser::new(tokens);
+    let statements = parser.parse()?;
+    
+    let mut interpreter = Interpreter::new();
+    interpreter.interpret(statements)
+}
+
+/// Time every program `iterations` times, bailing out on the first program that errors
+pub fn run_suite(iterations: u32) -> Result<Vec<BenchResult>, ZLangError> {
+    let iterations = iterations.max(1);
+    let mut results = Vec::new();
+    
+    for program in PROGRAMS {
+        let mut timings = Vec::new();
+        
+        for _ in 0..iterations {
+            let start = Instant::now();
+            run_program(program.source).map_err(|e| {
+                ZLangError::new(&format!("Benchmark '{}' flopped: {}", program.name, e))
+            })?;
+            timings.push(start.elapsed());
+        }
+        
+        let total: Duration = timings.iter().sum();
+        results.push(BenchResult {
+            name: program.name,
+            iterations,
+            min: timings.iter().min().copied().unwrap_or_default(),
+            mean: total / iterations,
+            max: timings.iter().max().copied().unwrap_or_default(),
+        });
+    }
+    
+    Ok(results)
+}
@@ -0,0 +1,97 @@
+//! ZLang Challenges - Code golf in the playground, shortest working answer wins ⛳
+//! Each challenge is an assignment file baked into the binary, graded by the same runner as `zlang grade`
+
+use crate::grade::{self, Assignment, CaseResult, Outcome};
+use crate::interpreter::Capabilities;
+use crate::json;
+
+pub struct Challenge {
+    /// Short name, the `{id}` in `/challenge/{id}`
+    pub id: &'static str,
+    source: &'static str,
+}
+
+/// In the order the playground lists them
+pub const CHALLENGES: &[Challenge] = &[
+    Challenge { id: "fizzbuzz", source: include_str!("../examples/challenges/fizzbuzz.toml") },
+    Challenge { id: "shout", source: include_str!("../examples/challenges/shout.toml") },
+    Challenge { id: "word_count", source: include_str!("../examples/challenges/word_count.toml") },
+];
+
+pub fn find(id: &str) -> Option<&'static Challenge> {
+    CHALLENGES.iter().find(|challenge| challenge.id == id)
+}
+
+impl Challenge {
+    pub fn assignment(&self) -> Assignment {
+        Assignment::parse(self.source).expect("built-in challenges are valid assignments")
+    }
+    
+    /// The problem statement and each case's name and points - inputs and expected output stay hidden
+    pub fn to_json(&self) -> String {
+        let assignment = self.assignment();
+        let cases: Vec<String> = assignment.cases.iter()
+            .map(|case| format!("{{\"name\": {}, \"points\": {}}}", json::string(&case.name), case.points))
+            .collect();
+        
+        format!(
+            "{{\"id\": {}, \"title\": {}, \"description\": {}, \"cases\": [{}], \"total_points\": {}}}",
+            json::string(self.id),
+            json::string(assignment.title.as_deref().unwrap_or(self.id)),
+            json::string(assignment.description.as_deref().unwrap_or("").trim_end()),
+            cases.join(", "),
+            assignment.total_points()
+        )
+    }
+    
+    /// Grade a submission, one sandboxed run per case - a wrong-output message would show off
+    /// whatever a pull read, so the server's files stay out of reach
+    pub fn submit(&self, source: &str) -> Vec<CaseResult> {
+        grade::grade(source, &self.assignment(), Capabilities { input: true, pull: false, fetch: false })
+    }
+}
+
+/// Every challenge's id and title, for the picker
+pub fn list_json() -> String {
+    let challenges: Vec<String> = CHALLENGES.iter()
+        .map(|challenge| format!(
+            "{{\"id\": {}, \"title\": {}}}",
+            json::string(challenge.id),
+            json::string(challenge.assignment().title.as_deref().unwrap_or(challenge.id))
+        ))
+        .collect();
+    format!("{{\"challenges\": [{}]}}", challenges.join(", "))
+}
+
+/// Per-case pass/fail for a submission of `size` characters. A wrong answer says where it went
+/// off the rails but never what the right line was, that would give the case away
+pub fn results_json(results: &[CaseResult], size: usize) -> String {
+    let cases: Vec<String> = results.iter().map(|result| {
+        let (outcome, detail) = match &result.outcome {
+            Outcome::Passed => ("passed", None),
+            Outcome::WrongOutput { line, got: Some(got), .. } => ("wrong_output", Some(format!("Line {} printed '{}', not what we wanted", line, got))),
+            Outcome::WrongOutput { line, got: None, .. } => ("wrong_output", Some(format!("Output stopped at line {}, there's more to print", line))),
+            Outcome::Failed(error) => ("failed", Some(error.clone())),
+            Outcome::TimedOut => ("timed_out", Some("Took too long, infinite loop maybe? ⏱️".to_string())),
+        };
+        format!(
+            "{{\"name\": {}, \"points\": {}, \"passed\": {}, \"outcome\": {}, \"detail\": {}}}",
+            json::string(&result.name),
+            result.points,
+            result.outcome == Outcome::Passed,
+            json::string(outcome),
+            detail.as_deref().map_or("null".to_string(), json::string)
+        )
+    }).collect();
+    
+    let passed = results.iter().filter(|result| result.outcome == Outcome::Passed).count();
+    format!(
+        "{{\"success\": true, \"passed\": {}, \"total\": {}, \"score\": {}, \"total_points\": {}, \"size\": {}, \"cases\": [{}]}}",
+        passed,
+        results.len(),
+        results.iter().map(CaseResult::earned).sum::<f64>(),
+        results.iter().map(|result| result.points).sum::<f64>(),
+        size,
+        cases.join(", ")
+    )
+}
@@ -0,0 +1,99 @@
+//! ZLang Collab - Live-coding rooms, everybody in the room sees the same code 👯
+//! The server just relays: the last edit wins and becomes the room's code, run results go to everyone
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::json;
+use crate::websocket::WebSocket;
+
+/// Rooms open at once, and people per room - a classroom, not a stadium
+const MAX_ROOMS: usize = 1_000;
+const MAX_MEMBERS: usize = 100;
+
+struct Room {
+    /// Whatever the last edit said
+    code: String,
+    members: Vec<(usize, WebSocket)>,
+}
+
+/// Every open room, shared between the server's connections
+#[derive(Default)]
+pub struct Rooms {
+    rooms: Mutex<HashMap<String, Room>>,
+    next_member: AtomicUsize,
+}
+
+impl Rooms {
+    /// Put `socket` in `room`, opening it if nobody's there yet, and catch it up on the room's code.
+    /// Returns the new member's id, or an error message when there's no space
+    pub fn join(&self, room: &str, socket: WebSocket) -> Result<usize, String> {
+        let member = self.next_member.fetch_add(1, Ordering::Relaxed);
+        let mut rooms = self.rooms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !rooms.contains_key(room) && rooms.len() >= MAX_ROOMS {
+            return Err("Every room is taken rn, try again in a bit 🚪".to_string());
+        }
+        let entry = rooms.entry(room.to_string()).or_insert_with(|| Room { code: String::new(), members: Vec::new() });
+        if entry.members.len() >= MAX_MEMBERS {
+            return Err(format!("Room '{}' is full ({} people), it's giving sold out 🎟️", room, MAX_MEMBERS));
+        }
+        
+        let _ = socket.send(&format!(
+            "{{\"type\": \"sync\", \"code\": {}, \"you\": {}}}",
+            json::string(&entry.code),
+            member
+        ));
+        entry.members.push((member, socket));
+        broadcast_members(entry);
+        Ok(member)
+    }
+    
+    /// Someone left or dropped - the room closes with its last member
+    pub fn leave(&self, room: &str, member: usize) {
+        let mut rooms = self.rooms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entry) = rooms.get_mut(room) {
+            entry.members.retain(|(id, _)| *id != member);
+            if entry.members.is_empty() {
+                rooms.remove(room);
+            } else {
+                broadcast_members(entry);
+            }
+        }
+    }
+    
+    /// `member` replaced the room's code, everybody else gets the new version
+    pub fn edit(&self, room: &str, member: usize, code: &str) {
+        let mut rooms = self.rooms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entry) = rooms.get_mut(room) {
+            entry.code = code.to_string();
+            let message = format!("{{\"type\": \"edit\", \"code\": {}, \"from\": {}}}", json::string(code), member);
+            for (_, socket) in entry.members.iter().filter(|(id, _)| *id != member) {
+                let _ = socket.send(&message);
+            }
+        }
+    }
+    
+    /// The room's current code, what a run runs
+    pub fn code(&self, room: &str) -> String {
+        let rooms = self.rooms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        rooms.get(room).map(|entry| entry.code.clone()).unwrap_or_default()
+    }
+    
+    /// Send `message` to the whole room, sender included
+    pub fn broadcast(&self, room: &str, message: &str) {
+        let rooms = self.rooms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entry) = rooms.get(room) {
+            for (_, socket) in &entry.members {
+                let _ = socket.send(message);
+            }
+        }
+    }
+}
+
+fn broadcast_members(room: &Room) {
+    let message = format!("{{\"type\": \"members\", \"count\": {}}}", room.members.len());
+    for (_, socket) in &room.members {
+        let _ = socket.send(&message);
+    }
+}
@@ -0,0 +1,350 @@
+//! ZLang Config - Your `.zlangrc`, so the REPL looks and feels the way you like ⚙️
+//! One `key = value` per line, `#` starts a comment, anything you leave out keeps its default
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::ZLangError;
+use crate::highlight::Theme;
+use crate::interpreter::{Capabilities, BUILTINS};
+use crate::messages::Lang;
+use crate::token::{TokenType, KEYWORDS, WORD_OPERATORS};
+
+pub const FILE_NAME: &str = ".zlangrc";
+
+pub const DEFAULT_PROMPT: &str = "zlang> ";
+pub const DEFAULT_GREETING: &str = "💬 Starting ZLang REPL... Type ':vars' to see your variables, ':undo' to take back the last change, 'exit' to bounce!";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// What the REPL asks for input with - quote it to keep a trailing space
+    pub prompt: String,
+    /// Syntax highlighting colors for the REPL
+    pub theme: Theme,
+    /// First thing the REPL says, empty for nothing
+    pub greeting: String,
+    /// Whether the ASCII art banner shows up at all
+    pub banner: bool,
+    /// What language diagnostics are written in (`lang = es`)
+    pub lang: Lang,
+    /// Spaces per indent level when the formatter rewrites code (`format.indent`)
+    pub format_indent: usize,
+    /// How loud `zlang lint` gets about each kind of finding (`lint.<rule>`)
+    pub lint: LintLevels,
+    /// Extra words for keywords and word operators when running, checking and formatting code, in the order they
+    /// were set (`alias.<word> = <keyword>`)
+    pub aliases: Vec<(String, String)>,
+    /// What programs run from the CLI are allowed to do (`allow.<capability>`)
+    pub capabilities: Capabilities,
+    /// Flags added to a command when you didn't pass them yourself (`flags.<command>`)
+    pub flags: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LintLevel {
+    Off,
+    Warn,
+    /// Shows up like a warning but makes `zlang lint` fail
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LintLevels {
+    pub unused_function: LintLevel,
+    pub unused_variable: LintLevel,
+}
+
+impl Default for LintLevels {
+    fn default() -> Self {
+        Self {
+            unused_function: LintLevel::Warn,
+            unused_variable: LintLevel::Warn,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            prompt: DEFAULT_PROMPT.to_string(),
+            theme: Theme::DEFAULT,
+            greeting: DEFAULT_GREETING.to_string(),
+            banner: true,
+            lang: Lang::English,
+            format_indent: 4,
+            lint: LintLevels::default(),
+            aliases: Vec::new(),
+            capabilities: Capabilities::default(),
+            flags: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// The `.zlangrc` in your home directory, overridden by the closest one in the current
+    /// directory or any folder above it. No file anywhere is fine, that's just the defaults
+    pub fn load() -> Result<Config, ZLangError> {
+        let mut config = Config::default();
+        for path in files() {
+            let source = fs::read_to_string(&path)
+                .map_err(|e| ZLangError::new(&format!("Couldn't read {}: {} 📁", path.display(), e)))?;
+            config.apply(&source).map_err(|e| ZLangError::new(&format!("{}: {}", path.display(), e)))?;
+        }
+        Ok(config)
+    }
+    
+    pub fn parse(source: &str) -> Result<Config, ZLangError> {
+        let mut config = Config::default();
+        config.apply(source)?;
+        Ok(config)
+    }
+    
+    /// Layer the settings in `source` over this config. Its aliases are checked together, so
+    /// every clash in the file is reported at once
+    pub fn apply(&mut self, source: &str) -> Result<(), ZLangError> {
+        let mut aliases = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            
+            let at_line = |e: ZLangError| ZLangError::new(&format!("Line {}: {}", index + 1, e.message));
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(at_line(ZLangError::new(&format!("expected `key = value`, got `{}` 🤨", line))));
+            };
+            let value = unquote(value.trim()).map_err(at_line)?;
+            match key.trim().strip_prefix("alias.") {
+                Some(word) => {
+                    check_alias(word, &value).map_err(at_line)?;
+                    aliases.push((word.to_string(), value));
+                }
+                None => self.set(key.trim(), &value).map_err(at_line)?,
+            }
+        }
+        
+        self.add_aliases(aliases)
+    }
+    
+    /// Change one setting, the way a `key = value` line or a `--key=value` flag does
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), ZLangError> {
+        if let Some(word) = key.strip_prefix("alias.") {
+            check_alias(word, value)?;
+            return self.add_aliases(vec![(word.to_string(), value.to_string())]);
+        }
+        if let Some(command) = key.strip_prefix("flags.") {
+            self.flags.insert(command.to_string(), value.split_whitespace().map(str::to_string).collect());
+            return Ok(());
+        }
+        
+        match key {
+            "prompt" => self.prompt = value.to_string(),
+            "greeting" => self.greeting = value.to_string(),
+            "banner" => self.banner = parse_bool(value)?,
+            "lang" => {
+                self.lang = Lang::named(value).ok_or_else(|| ZLangError::new(&format!(
+                    "we don't speak '{}' yet, pick one of {} 🌍",
+                    value,
+                    Lang::NAMES.join(", ")
+                )))?;
+            }
+            "theme" => {
+                self.theme = Theme::named(value).ok_or_else(|| ZLangError::new(&format!(
+                    "there's no '{}' theme, pick one of {} 🎨",
+                    value,
+                    Theme::NAMES.join(", ")
+                )))?;
+            }
+            "format.indent" => {
+                self.format_indent = value.parse()
+                    .map_err(|_| ZLangError::new(&format!("'{}' isn't a number of spaces 🔢", value)))?;
+            }
+            "lint.unused_function" => self.lint.unused_function = parse_lint_level(value)?,
+            "lint.unused_variable" => self.lint.unused_variable = parse_lint_level(value)?,
+            "allow.input" => self.capabilities.input = parse_bool(value)?,
+            "allow.pull" => self.capabilities.pull = parse_bool(value)?,
+            "allow.fetch" => self.capabilities.fetch = parse_bool(value)?,
+            _ => return Err(ZLangError::new(&format!("'{}' isn't a setting we know about 🤔", key))),
+        }
+        Ok(())
+    }
+    
+    /// Take on `aliases`, replacing any earlier alias for the same word - unless they clash
+    fn add_aliases(&mut self, aliases: Vec<(String, String)>) -> Result<(), ZLangError> {
+        let conflicts = alias_conflicts(&aliases);
+        if !conflicts.is_empty() {
+            let listed: Vec<String> = conflicts.iter().map(|conflict| format!("  - {}", conflict)).collect();
+            return Err(ZLangError::new(&format!(
+                "these aliases would quietly change what your code means, sort them out first 🏷️\n{}",
+                listed.join("\n")
+            )));
+        }
+        
+        for (word, keyword) in aliases {
+            self.aliases.retain(|(existing, _)| *existing != word);
+            self.aliases.push((word, keyword));
+        }
+        Ok(())
+    }
+    
+    /// The aliases as the lexer wants them
+    pub fn keyword_aliases(&self) -> HashMap<String, TokenType> {
+        self.aliases.iter()
+            .filter_map(|(word, keyword)| Some((word.clone(), aliased(keyword)?)))
+            .collect()
+    }
+    
+    /// `args` (program name first) plus this command's default flags that aren't already there
+    pub fn with_default_flags(&self, mut args: Vec<String>) -> Vec<String> {
+        let defaults = args.get(1).and_then(|command| self.flags.get(command)).cloned().unwrap_or_default();
+        for flag in defaults {
+            if !args.contains(&flag) {
+                args.push(flag);
+            }
+        }
+        args
+    }
+}
+
+/// Why an alias can't be used as written
+#[derive(Debug, Clone, PartialEq)]
+pub enum AliasConflict {
+    /// The word is (part of) a keyword already, and keywords always win
+    Keyword { word: String, keyword: &'static str },
+    /// The word names a builtin, every call to it would turn into the keyword
+    Builtin { word: String, keyword: String },
+    /// One file gives the word two different keywords
+    Twice { word: String, first: String, second: String },
+}
+
+impl fmt::Display for AliasConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AliasConflict::Keyword { word, keyword } if word == keyword => write!(f, "'{}' is already a keyword", word),
+            AliasConflict::Keyword { word, keyword } => write!(f, "'{}' is already part of the keyword '{}'", word, keyword),
+            AliasConflict::Builtin { word, keyword } => {
+                write!(f, "'{}' is a builtin, aliasing it to '{}' would hide it", word, keyword)
+            }
+            AliasConflict::Twice { word, first, second } => {
+                write!(f, "'{}' is aliased to both '{}' and '{}'", word, first, second)
+            }
+        }
+    }
+}
+
+/// Everything wrong with a set of `(word, keyword)` aliases taken together, in the order they were set
+pub fn alias_conflicts(aliases: &[(String, String)]) -> Vec<AliasConflict> {
+    let mut conflicts = Vec::new();
+    let mut firsts: HashMap<&str, &str> = HashMap::new();
+    for (word, keyword) in aliases {
+        let Some(first) = firsts.get(word.as_str()) else {
+            firsts.insert(word, keyword);
+            let reserved = KEYWORDS.iter()
+                .chain(&["vibecheck"])
+                .chain(WORD_OPERATORS.iter().map(|(operator, _)| operator))
+                .find(|reserved| reserved.split_whitespace().any(|part| part == word));
+            if let Some(reserved) = reserved {
+                conflicts.push(AliasConflict::Keyword { word: word.clone(), keyword: reserved });
+            } else if BUILTINS.contains(&word.as_str()) {
+                conflicts.push(AliasConflict::Builtin { word: word.clone(), keyword: keyword.clone() });
+            }
+            continue;
+        };
+        
+        // Listed once per word, however many more keywords it gets
+        let listed = conflicts.iter().any(|conflict| matches!(conflict, AliasConflict::Twice { word: other, .. } if other == word));
+        if first != keyword && !listed {
+            conflicts.push(AliasConflict::Twice { word: word.clone(), first: first.to_string(), second: keyword.clone() });
+        }
+    }
+    conflicts
+}
+
+/// The problems with a single alias that don't depend on any other
+fn check_alias(word: &str, keyword: &str) -> Result<(), ZLangError> {
+    let is_word = word.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !is_word {
+        return Err(ZLangError::new(&format!("'{}' can't be an alias, it has to look like a variable name 🏷️", word)));
+    }
+    if aliased(keyword).is_none() {
+        return Err(ZLangError::new(&format!("'{}' isn't a ZLang keyword or word operator, so '{}' can't stand in for it 🤔", keyword, word)));
+    }
+    Ok(())
+}
+
+/// The token an alias stands for - a keyword, or one of the operators that has a word (`is` or `==` alike)
+fn aliased(keyword: &str) -> Option<TokenType> {
+    TokenType::from_keyword(keyword).or_else(|| TokenType::from_operator(keyword))
+}
+
+/// The config files to read, weakest first
+fn files() -> Vec<PathBuf> {
+    // USERPROFILE is where Windows keeps it
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(FILE_NAME))
+        .filter(|path| path.is_file());
+    let project = env::current_dir().ok().and_then(|dir| find_upward(&dir));
+    
+    let mut files: Vec<PathBuf> = home.into_iter().collect();
+    // Working somewhere under your home directory, the home file is the project file
+    if let Some(project) = project.filter(|project| !files.contains(project)) {
+        files.push(project);
+    }
+    files
+}
+
+fn find_upward(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().map(|dir| dir.join(FILE_NAME)).find(|path| path.is_file())
+}
+
+/// A value is either bare (everything up to a `#` comment) or a quoted string with the usual escapes
+fn unquote(value: &str) -> Result<String, ZLangError> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return Ok(value.split('#').next().unwrap_or("").trim().to_string());
+    };
+    
+    let mut result = String::new();
+    let mut chars = quoted.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                let rest = chars.as_str().trim();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(ZLangError::new(&format!("unexpected `{}` after the closing quote 🤨", rest)));
+                }
+                return Ok(result);
+            }
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some(other) => result.push(other),
+                None => break,
+            },
+            other => result.push(other),
+        }
+    }
+    
+    Err(ZLangError::new("that string never got its closing quote 💀"))
+}
+
+fn parse_lint_level(value: &str) -> Result<LintLevel, ZLangError> {
+    match value {
+        "off" => Ok(LintLevel::Off),
+        "warn" => Ok(LintLevel::Warn),
+        "error" => Ok(LintLevel::Error),
+        _ => Err(ZLangError::new(&format!("'{}' isn't a lint level, use off, warn or error 🔍", value))),
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, ZLangError> {
+    match value {
+        "fr" | "true" | "on" | "yes" => Ok(true),
+        "cap" | "false" | "off" | "no" => Ok(false),
+        _ => Err(ZLangError::new(&format!("'{}' should be fr or cap 🤷", value))),
+    }
+}
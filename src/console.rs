@@ -0,0 +1,137 @@
+//! ZLang Console - Where a running program's prints go and `spill()` input comes from 🖥️
+//! Without one, prints are collected into the ExecutionResult and `spill()` reads stdin
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+
+use crate::ansi;
+use crate::draw::Shape;
+
+pub trait Console {
+    /// Show one printed line right away
+    fn print(&mut self, line: &str);
+    
+    /// Show `prompt` and wait for a line of input, None once input has run out
+    fn read_line(&mut self, prompt: &str) -> Option<String>;
+    
+    /// Show the canvas as it is right now - sent whenever a program pauses with `sleep()`,
+    /// which is what turns a drawing loop into an animation. Only the playground has a canvas
+    fn draw(&mut self, _shapes: &[Shape]) {}
+    
+    /// Keys pressed since the last time we asked, by name ("a", "ArrowUp", " "), oldest first.
+    /// Only the playground's output pane passes keys along
+    fn keys(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+    
+    /// Whether `colored()` and `bold()` show up as colors here. When they don't,
+    /// their escapes are stripped before a line reaches `print`
+    fn colors(&self) -> bool {
+        false
+    }
+}
+
+/// The terminal the interpreter is running in - prints stream to stdout as they happen
+pub struct StdConsole {
+    /// Put in front of every printed line (the REPL marks output with 📤)
+    pub prefix: &'static str,
+}
+
+impl Console for StdConsole {
+    fn print(&mut self, line: &str) {
+        println!("{}{}", self.prefix, line);
+    }
+    
+    fn read_line(&mut self, prompt: &str) -> Option<String> {
+        read_stdin_line(prompt)
+    }
+    
+    fn colors(&self) -> bool {
+        ansi::stdout_wants_colors()
+    }
+}
+
+/// Keeps printed lines instead of showing them, and keeps them even when the run fails.
+/// Clones share the same lines. `spill()` reads stdin without showing its prompt,
+/// unless the input was scripted up front
+#[derive(Debug, Clone, Default)]
+pub struct CaptureConsole {
+    lines: Rc<RefCell<Vec<String>>>,
+    input: Option<Rc<RefCell<VecDeque<String>>>>,
+}
+
+impl CaptureConsole {
+    /// Answer each `spill()` with the next line of `input`, then nil once it runs out
+    pub fn with_input(input: &str) -> Self {
+        Self {
+            lines: Rc::default(),
+            input: Some(Rc::new(RefCell::new(input.lines().map(str::to_string).collect()))),
+        }
+    }
+    
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.borrow().clone()
+    }
+}
+
+impl Console for CaptureConsole {
+    fn print(&mut self, line: &str) {
+        self.lines.borrow_mut().push(line.to_string());
+    }
+    
+    fn read_line(&mut self, _prompt: &str) -> Option<String> {
+        match &self.input {
+            Some(input) => input.borrow_mut().pop_front(),
+            None => read_stdin_line(""),
+        }
+    }
+}
+
+/// Read one line from stdin after showing `prompt`, without its line ending
+pub fn read_stdin_line(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+    
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_string()),
+    }
+}
+
+/// Get a Windows console ready for us: UTF-8 both ways so emoji survive printing and `spill()`,
+/// and ANSI escapes switched on for the REPL colors. Every other terminal already is
+#[cfg(windows)]
+pub fn prepare_terminal() {
+    use std::ffi::c_void;
+    
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetConsoleOutputCP(code_page: u32) -> i32;
+        fn SetConsoleCP(code_page: u32) -> i32;
+        fn GetStdHandle(std_handle: u32) -> *mut c_void;
+        fn GetConsoleMode(handle: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(handle: *mut c_void, mode: u32) -> i32;
+    }
+    
+    const UTF8: u32 = 65001;
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+    
+    // Best effort - output redirected to a file has no console and that's fine
+    unsafe {
+        SetConsoleOutputCP(UTF8);
+        SetConsoleCP(UTF8);
+        
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) != 0 {
+            SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub fn prepare_terminal() {}
@@ -0,0 +1,246 @@
+//! ZLang Dead Code - Finding the functions and variables nobody talks to 🪦
+//! Built on the call graph, so a function only counts if something reachable calls it
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use crate::ast::{Expr, Stmt};
+use crate::error::ZLangError;
+use crate::graph::{CallGraph, TOP_LEVEL};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::{Token, TokenType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnusedKind {
+    /// A `flex` function the top level never reaches
+    Function,
+    /// A `bet` variable that's set but never read
+    Variable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Unused {
+    pub kind: UnusedKind,
+    pub name: String,
+    pub line: usize,
+    /// Char range an editor can dim - the whole function, or just the variable's name
+    pub span: Range<usize>,
+}
+
+impl Unused {
+    pub fn message(&self) -> String {
+        match self.kind {
+            UnusedKind::Function => format!("Function '{}' is never called, it's giving dead code 🫥", self.name),
+            UnusedKind::Variable => format!("'{}' is set but never read, left on read fr 👻", self.name),
+        }
+    }
+}
+
+/// Everything in `source` that could be deleted without changing what the program does.
+/// Names starting with `_` are treated as unused on purpose and never reported.
+/// `aliases` are the keyword aliases the source was written with
+pub fn find(source: &str, aliases: HashMap<String, TokenType>) -> Result<Vec<Unused>, ZLangError> {
+    let tokens = Lexer::new(source).with_aliases(aliases).tokenize()?;
+    let statements = Parser::new(tokens.clone()).parse()?;
+    
+    let graph = CallGraph::build(&statements);
+    let mut reachable = HashSet::from([TOP_LEVEL]);
+    let mut pending = vec![TOP_LEVEL];
+    while let Some(caller) = pending.pop() {
+        for callee in graph.callees(caller) {
+            if reachable.insert(callee) {
+                pending.push(callee);
+            }
+        }
+    }
+    
+    let mut declared = Vec::new();
+    let mut read = HashSet::new();
+    for statement in &statements {
+        collect_stmt(statement, &mut declared, &mut read);
+    }
+    
+    let mut unused = Vec::new();
+    for function in &graph.functions {
+        if !reachable.contains(function.as_str()) && !function.starts_with('_') {
+            if let Some((line, span)) = locate(&tokens, TokenType::Flex, function, true) {
+                unused.push(Unused { kind: UnusedKind::Function, name: function.clone(), line, span });
+            }
+        }
+    }
+    for variable in declared {
+        if !read.contains(&variable) && !variable.starts_with('_') {
+            if let Some((line, span)) = locate(&tokens, TokenType::Bet, &variable, false) {
+                unused.push(Unused { kind: UnusedKind::Variable, name: variable, line, span });
+            }
+        }
+    }
+    
+    unused.sort_by_key(|entry| entry.span.start);
+    Ok(unused)
+}
+
+/// Where `keyword name` first shows up. With `whole_body` the span runs to the closing `}`
+fn locate(tokens: &[Token], keyword: TokenType, name: &str, whole_body: bool) -> Option<(usize, Range<usize>)> {
+    let index = tokens.windows(2).position(|pair| {
+        pair[0].token_type == keyword && pair[1].token_type == TokenType::Identifier(name.to_string())
+    })?;
+    
+    let start = &tokens[index];
+    if !whole_body {
+        return Some((start.line, tokens[index + 1].span.clone()));
+    }
+    
+    let mut depth = 0;
+    for token in &tokens[index..] {
+        match token.token_type {
+            TokenType::LeftBrace => depth += 1,
+            TokenType::RightBrace => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((start.line, start.span.start..token.span.end));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn collect_stmt(stmt: &Stmt, declared: &mut Vec<String>, read: &mut HashSet<String>) {
+    match stmt {
+        Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) => collect_expr(expr, declared, read),
+        Stmt::VarDeclaration { name, initializer, .. } => {
+            if !declared.contains(name) {
+                declared.push(name.clone());
+            }
+            if let Some(expr) = initializer {
+                collect_expr(expr, declared, read);
+            }
+        }
+        Stmt::Return(value) => {
+            if let Some(expr) = value {
+                collect_expr(expr, declared, read);
+            }
+        }
+        Stmt::Needs { condition, .. } => collect_expr(condition, declared, read),
+        Stmt::Block(body) | Stmt::Function { body, .. } => collect_all(body, declared, read),
+        Stmt::If { condition, then_branch, else_branch } => {
+            collect_expr(condition, declared, read);
+            collect_stmt(then_branch, declared, read);
+            if let Some(else_branch) = else_branch {
+                collect_stmt(else_branch, declared, read);
+            }
+        }
+        Stmt::While { condition, body, else_branch } => {
+            collect_expr(condition, declared, read);
+            collect_stmt(body, declared, read);
+            if let Some(else_branch) = else_branch {
+                collect_stmt(else_branch, declared, read);
+            }
+        }
+        Stmt::For { iterable, body, else_branch, .. } => {
+            collect_expr(iterable, declared, read);
+            collect_stmt(body, declared, read);
+            if let Some(else_branch) = else_branch {
+                collect_stmt(else_branch, declared, read);
+            }
+        }
+        Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+            if let Some(initializer) = initializer {
+                collect_stmt(initializer, declared, read);
+            }
+            for expr in condition.iter().chain(increment) {
+                collect_expr(expr, declared, read);
+            }
+            collect_stmt(body, declared, read);
+            if let Some(else_branch) = else_branch {
+                collect_stmt(else_branch, declared, read);
+            }
+        }
+        Stmt::Switch { expr, cases, default } => {
+            collect_expr(expr, declared, read);
+            for case in cases {
+                case.exprs().for_each(|value| collect_expr(value, declared, read));
+                collect_all(&case.body, declared, read);
+            }
+            if let Some(body) = default {
+                collect_all(body, declared, read);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            collect_all(try_block, declared, read);
+            for clause in catch_clauses {
+                collect_all(&clause.body, declared, read);
+            }
+            if let Some(body) = finally_block {
+                collect_all(body, declared, read);
+            }
+        }
+        Stmt::Pull { alias: Some(alias), .. } => {
+            if !declared.contains(alias) {
+                declared.push(alias.clone());
+            }
+        }
+        // A field is read through a dot, which could be any object's, so its `bet` isn't reported -
+        // only what the defaults and methods read counts
+        Stmt::Squad { body, .. } => {
+            for member in body {
+                match member {
+                    Stmt::VarDeclaration { initializer: Some(expr), .. } => collect_expr(expr, declared, read),
+                    Stmt::Function { body, .. } => collect_all(body, declared, read),
+                    _ => {}
+                }
+            }
+        }
+        Stmt::DramaType { .. } | Stmt::Break | Stmt::Continue | Stmt::Global(_) | Stmt::Outer(_) | Stmt::Pull { alias: None, .. } => {}
+    }
+}
+
+fn collect_all(statements: &[Stmt], declared: &mut Vec<String>, read: &mut HashSet<String>) {
+    for statement in statements {
+        collect_stmt(statement, declared, read);
+    }
+}
+
+fn collect_expr(expr: &Expr, declared: &mut Vec<String>, read: &mut HashSet<String>) {
+    match expr {
+        Expr::Variable(name) => {
+            read.insert(name.clone());
+        }
+        Expr::Call { callee, arguments, .. } => {
+            collect_expr(callee, declared, read);
+            for argument in arguments {
+                collect_expr(argument, declared, read);
+            }
+        }
+        Expr::Binary { left, right, .. } => {
+            collect_expr(left, declared, read);
+            collect_expr(right, declared, read);
+        }
+        Expr::Index { object, index, .. } | Expr::Set { object, value: index, .. } | Expr::Range { start: object, end: index, .. } => {
+            collect_expr(object, declared, read);
+            collect_expr(index, declared, read);
+        }
+        // Assigning isn't reading, only the new value is
+        Expr::Unary { right: value, .. } | Expr::Assign { value, .. } | Expr::Propagate { value, .. } => collect_expr(value, declared, read),
+        Expr::Get { object, .. } | Expr::Spread(object) => collect_expr(object, declared, read),
+        Expr::Conditional { condition, then_branch, else_branch } => {
+            collect_expr(condition, declared, read);
+            collect_expr(then_branch, declared, read);
+            collect_expr(else_branch, declared, read);
+        }
+        Expr::Array(items) => {
+            for item in items {
+                collect_expr(item, declared, read);
+            }
+        }
+        Expr::Object(fields) => {
+            for (_, value) in fields {
+                collect_expr(value, declared, read);
+            }
+        }
+        Expr::Function { body, .. } => collect_all(body, declared, read),
+        Expr::Literal(_) => {}
+    }
+}
@@ -0,0 +1,92 @@
+//! ZLang Deque - `queue()` and `stack()`, with push, pop and peek that never copy what's inside 🚏
+//! Shared like heaps are: a queue hands things back oldest first, a stack newest first
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::ast::Literal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Queue,
+    Stack,
+}
+
+#[derive(Clone)]
+pub struct Deque {
+    kind: Kind,
+    items: Arc<Mutex<VecDeque<Literal>>>,
+}
+
+// A deque can hold itself, so neither of these looks at what's inside
+impl std::fmt::Debug for Deque {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}({} items)", self.kind, self.len())
+    }
+}
+
+impl PartialEq for Deque {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl Deque {
+    /// A `kind` holding `items`, the first of them the first pushed
+    pub fn new(kind: Kind, items: Vec<Literal>) -> Self {
+        Self { kind, items: Arc::new(Mutex::new(items.into())) }
+    }
+    
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+    
+    pub fn push(&self, value: Literal) {
+        self.lock().push_back(value);
+    }
+    
+    /// The next value out, taken out - None once it's empty
+    pub fn pop(&self) -> Option<Literal> {
+        let mut items = self.lock();
+        match self.kind {
+            Kind::Queue => items.pop_front(),
+            Kind::Stack => items.pop_back(),
+        }
+    }
+    
+    /// The next value out, left where it is
+    pub fn peek(&self) -> Option<Literal> {
+        let items = self.lock();
+        match self.kind {
+            Kind::Queue => items.front().cloned(),
+            Kind::Stack => items.back().cloned(),
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Every value, first pushed first
+    pub fn values(&self) -> Vec<Literal> {
+        self.lock().iter().cloned().collect()
+    }
+    
+    /// Swap everything inside for `values`, first pushed first - how undoing puts a deque back
+    pub fn refill(&self, values: Vec<Literal>) {
+        *self.lock() = values.into();
+    }
+    
+    /// Tells deques apart without looking inside, for walks that could loop back to the same one
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.items) as usize
+    }
+    
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Literal>> {
+        self.items.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,87 @@
+//! ZLang Draw - Lines, circles and text for the playground canvas 🎨
+//! `draw.line(...)` and friends only queue up shapes, the browser does the actual drawing
+
+use crate::ast::Literal;
+use crate::error::ZLangError;
+use crate::json;
+
+/// The canvas the playground draws on, (0, 0) is the top left corner
+pub const WIDTH: f64 = 400.0;
+pub const HEIGHT: f64 = 300.0;
+/// Shapes one run can queue up, enough for art and not enough to freeze a browser tab
+pub const MAX_SHAPES: usize = 10_000;
+/// What a shape gets drawn in when no color is given
+pub const DEFAULT_COLOR: &str = "#222222";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Line { from: (f64, f64), to: (f64, f64), color: String },
+    Circle { center: (f64, f64), radius: f64, color: String },
+    Text { at: (f64, f64), text: String, color: String },
+}
+
+impl Shape {
+    /// The shape `draw.<name>(arguments)` asks for
+    pub fn from_call(name: &str, arguments: &[Literal]) -> Result<Shape, ZLangError> {
+        let (usage, count) = match name {
+            "line" => ("draw.line(x1, y1, x2, y2, color?)", 4),
+            "circle" => ("draw.circle(x, y, radius, color?)", 3),
+            "text" => ("draw.text(x, y, text, color?)", 3),
+            _ => return Err(ZLangError::new(&format!("draw.{} isn't a thing, try draw.line, draw.circle, draw.text or draw.clear 🎨", name))),
+        };
+        if arguments.len() != count && arguments.len() != count + 1 {
+            return Err(ZLangError::new(&format!("It's {} bestie! 🎨", usage)));
+        }
+        
+        let number = |index: usize| match &arguments[index] {
+            Literal::Number(n) if n.is_finite() => Ok(*n),
+            _ => Err(ZLangError::new(&format!("{} wants numbers for its coordinates and sizes! 🔢", usage))),
+        };
+        let color = match arguments.get(count) {
+            None => DEFAULT_COLOR.to_string(),
+            Some(Literal::String(color)) if is_color(color) => color.clone(),
+            Some(_) => return Err(ZLangError::new("Colors are strings like \"red\" or \"#ff6b6b\" 🖍️")),
+        };
+        
+        Ok(match name {
+            "line" => Shape::Line { from: (number(0)?, number(1)?), to: (number(2)?, number(3)?), color },
+            "circle" => {
+                let radius = number(2)?;
+                if radius < 0.0 {
+                    return Err(ZLangError::new("A circle's radius can't be negative 🔵"));
+                }
+                Shape::Circle { center: (number(0)?, number(1)?), radius, color }
+            }
+            _ => Shape::Text { at: (number(0)?, number(1)?), text: arguments[2].to_string(), color },
+        })
+    }
+    
+    pub fn to_json(&self) -> String {
+        match self {
+            Shape::Line { from, to, color } => format!(
+                "{{\"shape\": \"line\", \"x1\": {}, \"y1\": {}, \"x2\": {}, \"y2\": {}, \"color\": {}}}",
+                from.0, from.1, to.0, to.1, json::string(color)
+            ),
+            Shape::Circle { center, radius, color } => format!(
+                "{{\"shape\": \"circle\", \"x\": {}, \"y\": {}, \"radius\": {}, \"color\": {}}}",
+                center.0, center.1, radius, json::string(color)
+            ),
+            Shape::Text { at, text, color } => format!(
+                "{{\"shape\": \"text\", \"x\": {}, \"y\": {}, \"text\": {}, \"color\": {}}}",
+                at.0, at.1, json::string(text), json::string(color)
+            ),
+        }
+    }
+}
+
+/// A JSON array of every shape, in the order they were drawn
+pub fn to_json(shapes: &[Shape]) -> String {
+    let shapes: Vec<String> = shapes.iter().map(Shape::to_json).collect();
+    format!("[{}]", shapes.join(", "))
+}
+
+/// Names, hex codes and `rgb(...)`, nothing a canvas would choke on
+fn is_color(color: &str) -> bool {
+    !color.is_empty() && color.len() <= 32
+        && color.chars().all(|c| c.is_ascii_alphanumeric() || "#(),.% ".contains(c))
+}
@@ -1,24 +1,163 @@
 //! Environment for variable and function scoping in ZLang
 //! This is where we keep track of what variables exist and their values
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use crate::ast::Literal;
 use crate::error::ZLangError;
+use crate::messages;
+use crate::similar;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Scope {
+    values: HashMap<String, Literal>,
+    // Names declared `global`/`outer` here, pointing at the scope that really owns them
+    redirects: HashMap<String, usize>,
+}
+
+// Shared so a function made inside a scope can keep using it after the scope's block or call ends
+type SharedScope = Arc<Mutex<Scope>>;
+
+fn lock(scope: &SharedScope) -> MutexGuard<'_, Scope> {
+    scope.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The scopes a function was made in, global scope left out - calling the function runs its
+/// body inside them, so it keeps seeing (and updating) the variables it was made next to
+#[derive(Clone)]
+pub struct Closure {
+    scopes: Vec<SharedScope>,
+}
+
+// A closure can sit in one of its own scopes (`bet f = flex() { ... }` inside a function),
+// so neither of these looks inside the scopes
+impl std::fmt::Debug for Closure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Closure({} scopes)", self.scopes.len())
+    }
+}
+
+impl PartialEq for Closure {
+    fn eq(&self, other: &Self) -> bool {
+        self.scopes.len() == other.scopes.len() && self.scopes.iter().zip(&other.scopes).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Closure {
+    /// `closure`'s scopes with `fields` inside them - where a method runs, so the instance's
+    /// fields read and assign like any other variable
+    pub fn around(closure: Option<&Closure>, fields: &Fields) -> Self {
+        let mut scopes = closure.map_or_else(Vec::new, |closure| closure.scopes.clone());
+        scopes.push(Arc::clone(&fields.scope));
+        Closure { scopes }
+    }
+    
+    /// What this closure's scopes hold, leaving out any scope already in `seen` - a closure can
+    /// sit in one of its own scopes, so a walk over values has to keep track
+    pub fn values(&self, seen: &mut HashSet<usize>) -> Vec<Literal> {
+        self.scopes.iter()
+            .filter(|scope| seen.insert(Arc::as_ptr(scope) as usize))
+            .flat_map(|scope| lock(scope).values.values().cloned().collect::<Vec<_>>())
+            .collect()
+    }
+    
+    /// This closure with one more scope inside it, holding only `variables` - like `me` and `og` for a method
+    pub fn with_variables(mut self, variables: Vec<(String, Literal)>) -> Self {
+        let scope = Scope { values: variables.into_iter().collect(), ..Scope::default() };
+        self.scopes.push(Arc::new(Mutex::new(scope)));
+        self
+    }
+}
+
+/// A squad instance's fields - one scope, shared by every variable holding the instance
+#[derive(Clone)]
+pub struct Fields {
+    scope: SharedScope,
+}
+
+// An instance can hold itself, so neither of these looks inside
+impl std::fmt::Debug for Fields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fields({} values)", lock(&self.scope).values.len())
+    }
+}
+
+impl PartialEq for Fields {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.scope, &other.scope)
+    }
+}
+
+impl Fields {
+    pub fn get(&self, name: &str) -> Option<Literal> {
+        lock(&self.scope).values.get(name).cloned()
+    }
+    
+    /// Give the field `name` a new value - false when there's no such field, fields can't be added
+    pub fn set(&self, name: &str, value: Literal) -> bool {
+        match lock(&self.scope).values.get_mut(name) {
+            Some(slot) => {
+                *slot = value;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Tells instances apart without looking inside, for walks that could loop back to the same one
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.scope) as usize
+    }
+}
+
+#[derive(Debug)]
 pub struct Environment {
-    scopes: Vec<HashMap<String, Literal>>,
+    scopes: Vec<SharedScope>,
+    // Scopes of each call still waiting for a function it called to return, outermost first
+    callers: Vec<Vec<SharedScope>>,
+    // The program's scopes while a `pull "..." as name` file runs in a global scope of its own
+    outside: Vec<Vec<SharedScope>>,
+}
+
+// A copy gets scopes of its own, so restoring one really does rewind the variables
+impl Clone for Environment {
+    fn clone(&self) -> Self {
+        let copy = |scopes: &Vec<SharedScope>| scopes.iter().map(|scope| Arc::new(Mutex::new(lock(scope).clone()))).collect();
+        Self {
+            scopes: copy(&self.scopes),
+            callers: self.callers.iter().map(copy).collect(),
+            outside: self.outside.iter().map(copy).collect(),
+        }
+    }
+}
+
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        let same = |a: &Vec<SharedScope>, b: &Vec<SharedScope>| {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| Arc::ptr_eq(a, b) || *lock(a) == *lock(b))
+        };
+        let all_same = |a: &Vec<Vec<SharedScope>>, b: &Vec<Vec<SharedScope>>| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b));
+        same(&self.scopes, &other.scopes) && all_same(&self.callers, &other.callers) && all_same(&self.outside, &other.outside)
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Environment {
     pub fn new() -> Self {
         Self {
-            scopes: vec![HashMap::new()], // Global scope
+            scopes: vec![SharedScope::default()], // Global scope
+            callers: Vec::new(),
+            outside: Vec::new(),
         }
     }
     
     pub fn push_scope(&mut self) {
-        self.scopes.push(HashMap::new());
+        self.scopes.push(SharedScope::default());
     }
     
     pub fn pop_scope(&mut self) -> Result<(), ZLangError> {
@@ -29,32 +168,188 @@ impl Environment {
         Ok(())
     }
     
+    /// The scopes a function made right now would be made in, None at the top level
+    pub fn capture(&self) -> Option<Closure> {
+        // A pulled file's global scope is its own, the program calling its functions later won't have it
+        let from = if self.outside.is_empty() { 1 } else { 0 };
+        (self.scopes.len() > from).then(|| Closure { scopes: self.scopes[from..].to_vec() })
+    }
+    
+    /// Give a `pull "..." as name` file a fresh global scope to run in, away from the program's
+    /// variables. `leave_module` brings them back
+    pub fn enter_module(&mut self) {
+        let program = std::mem::replace(&mut self.scopes, vec![SharedScope::default()]);
+        self.outside.push(program);
+    }
+    
+    pub fn leave_module(&mut self) -> Result<(), ZLangError> {
+        self.scopes = self.outside.pop().ok_or_else(|| ZLangError::new("Can't leave a pulled file we never entered bestie! 🏗️"))?;
+        Ok(())
+    }
+    
+    /// The innermost scope, kept as an instance's fields once whatever filled it is done
+    pub fn current_fields(&self) -> Fields {
+        Fields { scope: Arc::clone(&self.scopes[self.current_index()]) }
+    }
+    
+    /// Swap in a call's scopes - the global scope, `closure`'s if the function has one, and a
+    /// fresh one for its parameters. The caller's own scopes wait until `leave_call`
+    pub fn enter_call(&mut self, closure: Option<&Closure>) {
+        let caller = self.scopes.split_off(1);
+        self.callers.push(caller);
+        if let Some(closure) = closure {
+            self.scopes.extend(closure.scopes.iter().cloned());
+        }
+        self.push_scope();
+    }
+    
+    /// Back to the scopes of whoever made the call `enter_call` started
+    pub fn leave_call(&mut self) -> Result<(), ZLangError> {
+        let caller = self.callers.pop().ok_or_else(|| ZLangError::new("Can't leave a call we never entered bestie! 🏗️"))?;
+        self.scopes.truncate(1);
+        self.scopes.extend(caller);
+        Ok(())
+    }
+    
     pub fn define(&mut self, name: String, value: Literal) {
-        if let Some(current_scope) = self.scopes.last_mut() {
-            current_scope.insert(name, value);
+        let target = self.current_index();
+        let target = lock(&self.scopes[target]).redirects.get(&name).copied().unwrap_or(target);
+        lock(&self.scopes[target]).values.insert(name, value);
+    }
+    
+    /// True when `name` lives in some scope other than the innermost one
+    pub fn defined_in_enclosing(&self, name: &str) -> bool {
+        if lock(&self.scopes[self.current_index()]).redirects.contains_key(name) {
+            return false; // `bet` writes through to the outer variable on purpose
+        }
+        
+        let enclosing = &self.scopes[..self.current_index()];
+        enclosing.iter().any(|scope| lock(scope).values.contains_key(name))
+    }
+    
+    /// `global name` - from now on this scope reads and writes the global `name`
+    pub fn bind_global(&mut self, name: &str) {
+        let current = self.current_index();
+        if current > 0 {
+            lock(&self.scopes[current]).redirects.insert(name.to_string(), 0);
+        }
+    }
+    
+    /// `outer name` - from now on this scope reads and writes the nearest enclosing `name`
+    pub fn bind_outer(&mut self, name: &str) -> Result<(), ZLangError> {
+        let current = self.current_index();
+        match self.resolve(name, current) {
+            Some(target) => {
+                lock(&self.scopes[current]).redirects.insert(name.to_string(), target);
+                Ok(())
+            }
+            None => Err(ZLangError::new(&format!(
+                "No outer variable '{}' to grab, declare it in an enclosing scope first 🔭",
+                name
+            ))),
         }
     }
     
     pub fn get(&self, name: &str) -> Result<Literal, ZLangError> {
-        // Search from the most recent scope backwards
-        for scope in self.scopes.iter().rev() {
-            if let Some(value) = scope.get(name) {
+        if let Some(index) = self.resolve(name, self.scopes.len()) {
+            if let Some(value) = lock(&self.scopes[index]).values.get(name) {
                 return Ok(value.clone());
             }
         }
         
-        Err(ZLangError::new(&format!("Undefined variable '{}', you haven't declared this bestie! 🤔", name)))
+        Err(messages::UNDEFINED_VARIABLE.error(&[&name, &self.did_you_mean(name)]))
     }
     
     pub fn assign(&mut self, name: &str, value: Literal) -> Result<(), ZLangError> {
-        // Search from the most recent scope backwards
-        for scope in self.scopes.iter_mut().rev() {
-            if scope.contains_key(name) {
-                scope.insert(name.to_string(), value);
+        if let Some(index) = self.resolve(name, self.scopes.len()) {
+            if let Some(slot) = lock(&self.scopes[index]).values.get_mut(name) {
+                *slot = value;
                 return Ok(());
             }
         }
         
-        Err(ZLangError::new(&format!("Undefined variable '{}', can't assign to something that doesn't exist! 🚫", name)))
+        Err(messages::UNDEFINED_ASSIGNMENT.error(&[&name, &self.did_you_mean(name)]))
+    }
+    
+    /// Every variable visible from the innermost scope, inner declarations winning
+    pub fn visible_variables(&self) -> HashMap<String, Literal> {
+        let mut visible = HashMap::new();
+        for scope in &self.scopes {
+            let redirects = {
+                let scope = lock(scope);
+                for (name, value) in &scope.values {
+                    visible.insert(name.clone(), value.clone());
+                }
+                scope.redirects.clone()
+            };
+            for (name, target) in redirects {
+                if let Some(value) = lock(&self.scopes[target]).values.get(&name) {
+                    visible.insert(name, value.clone());
+                }
+            }
+        }
+        visible
+    }
+    
+    /// Every value any scope holds, hidden ones and ones waiting on a call or a pulled file too
+    pub fn values(&self) -> Vec<Literal> {
+        self.scopes.iter()
+            .chain(self.callers.iter().flatten())
+            .chain(self.outside.iter().flatten())
+            .flat_map(|scope| lock(scope).values.values().cloned().collect::<Vec<_>>())
+            .collect()
+    }
+    
+    /// Each scope's own variables sorted by name, global scope first - `global`/`outer`
+    /// aliases are left out, their variable already shows up where it lives
+    pub fn scopes(&self) -> Vec<Vec<(String, Literal)>> {
+        self.scopes.iter().map(|scope| {
+            let mut variables: Vec<(String, Literal)> = lock(scope).values.iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            variables.sort_by(|a, b| a.0.cmp(&b.0));
+            variables
+        }).collect()
+    }
+    
+    /// Every value held in a scope that's in use - shadowed ones, and the locals of calls
+    /// still going, too. A scope shared between calls (or a `global`/`outer` alias) counts once
+    pub fn for_each_value(&self, mut f: impl FnMut(&Literal)) {
+        let mut visited = HashSet::new();
+        for scope in self.outside.iter().chain(&self.callers).flatten().chain(&self.scopes) {
+            if visited.insert(Arc::as_ptr(scope)) {
+                lock(scope).values.values().for_each(&mut f);
+            }
+        }
+    }
+    
+    /// Names of every variable declared at the global level
+    pub fn global_names(&self) -> Vec<String> {
+        lock(&self.scopes[0]).values.keys().cloned().collect()
+    }
+    
+    /// A hint naming the visible variable `name` was probably a typo of, if there is one
+    fn did_you_mean(&self, name: &str) -> String {
+        let visible = self.visible_variables();
+        similar::did_you_mean(name, visible.keys().map(String::as_str))
+    }
+    
+    fn current_index(&self) -> usize {
+        self.scopes.len() - 1
+    }
+    
+    /// Find the scope that owns `name`, searching scopes below `below` from the most
+    /// recent backwards and following any global/outer redirects on the way
+    fn resolve(&self, name: &str, below: usize) -> Option<usize> {
+        for index in (0..below).rev() {
+            let scope = lock(&self.scopes[index]);
+            if let Some(&target) = scope.redirects.get(name) {
+                return Some(target);
+            }
+            if scope.values.contains_key(name) {
+                return Some(index);
+            }
+        }
+        None
     }
 }
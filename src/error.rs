@@ -3,22 +3,64 @@
 
 use std::fmt;
 
+use crate::ast::Literal;
+
 #[derive(Debug, Clone)]
 pub struct ZLangError {
     pub message: String,
+    // Name of the user-declared drama type, None for built-in errors
+    pub kind: Option<String>,
+    // Where the message came from in the catalog (`Z0023`), None for ones that aren't in it yet
+    pub code: Option<&'static str>,
+    // The nah(...) a `?` is carrying back to its function - not drama, the call it's in returns it.
+    // Only when there's no function to catch it does it end the program with the message
+    pub propagating: Option<Box<Literal>>,
 }
 
 impl ZLangError {
     pub fn new(message: &str) -> Self {
         Self {
             message: message.to_string(),
+            kind: None,
+            code: None,
+            propagating: None,
+        }
+    }
+    
+    /// A diagnostic the `messages` catalog has a code for, `zlang explain <code>` says more
+    pub fn coded(code: &'static str, message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            kind: None,
+            code: Some(code),
+            propagating: None,
+        }
+    }
+    
+    pub fn typed(kind: &str, message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            kind: Some(kind.to_string()),
+            code: None,
+            propagating: None,
+        }
+    }
+    
+    /// The error a `?` unwinds with, until the function it's in hands `result` back as its return value
+    pub fn propagating(message: &str, result: Literal) -> Self {
+        Self {
+            propagating: Some(Box::new(result)),
+            ..Self::new(message)
         }
     }
 }
 
 impl fmt::Display for ZLangError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)
+        match &self.kind {
+            Some(kind) => write!(f, "{}: {}", kind, self.message),
+            None => write!(f, "{}", self.message),
+        }
     }
 }
 
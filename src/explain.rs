@@ -0,0 +1,121 @@
+//! ZLang Explain - Shows exactly how an expression gets evaluated 🧮
+//! Precedence spelled out with parentheses, then one operation at a time until a value drops out
+
+use crate::ast::{Expr, Literal, Stmt};
+use crate::error::ZLangError;
+use crate::interpreter::Interpreter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// The expression with every operation in its own parentheses
+    pub parenthesized: String,
+    /// The expression after each evaluation step, starting with the original and ending with the value
+    pub steps: Vec<String>,
+    /// What it all came out to - an error stops the trace at the step that blew up
+    pub value: Result<Literal, ZLangError>,
+}
+
+pub fn explain(source: &str) -> Result<Explanation, ZLangError> {
+    let statements = Parser::new(Lexer::new(source).tokenize()?).parse()?;
+    let mut expr = match statements.as_slice() {
+        [Stmt::Expression(expr)] => expr.clone(),
+        _ => return Err(ZLangError::new("explain only takes one expression bestie, like \"1 + 2 * 3\" 🧮")),
+    };
+    
+    let parenthesized = expr.to_parenthesized();
+    let mut steps = vec![expr.to_source()];
+    let mut interpreter = Interpreter::new();
+    
+    let value = loop {
+        if let Expr::Literal(value) = &expr {
+            break Ok(value.clone());
+        }
+        if let Err(error) = step(&mut expr, &mut interpreter) {
+            break Err(error);
+        }
+        
+        // `-5` reads the same whether it's been negated yet or not
+        let source = expr.to_source();
+        if steps.last() != Some(&source) {
+            steps.push(source);
+        }
+    };
+    
+    Ok(Explanation { parenthesized, steps, value })
+}
+
+/// Evaluate the leftmost operation whose operands are already values - the same order
+/// the interpreter goes in, so the trace hits any error exactly where a real run would
+fn step(expr: &mut Expr, interpreter: &mut Interpreter) -> Result<(), ZLangError> {
+    if let Some(operand) = operands(expr).into_iter().find(|operand| !settled(operand)) {
+        return step(operand, interpreter);
+    }
+    
+    // Picking a branch is a step of its own, then the branch gets worked out like anything else
+    if let Expr::Conditional { condition, then_branch, else_branch } = expr {
+        if let Expr::Literal(condition) = condition.as_ref() {
+            let branch = if interpreter.is_truthy(condition) { then_branch } else { else_branch };
+            *expr = std::mem::replace(branch.as_mut(), Expr::Literal(Literal::Nil));
+            return Ok(());
+        }
+    }
+    
+    let value = interpreter.evaluate(expr)?;
+    *expr = Expr::Literal(value);
+    Ok(())
+}
+
+/// Worked out as far as it goes on its own - a `...spread` array gets laid out by whatever holds it
+fn settled(expr: &Expr) -> bool {
+    match expr {
+        Expr::Spread(value) => matches!(value.as_ref(), Expr::Literal(_)),
+        other => matches!(other, Expr::Literal(_)),
+    }
+}
+
+/// Sub-expressions that get evaluated before `expr` itself, in evaluation order
+fn operands(expr: &mut Expr) -> Vec<&mut Expr> {
+    // A nil part way along `a?.b[i]` skips the rest, `i` included, so past where it starts the chain is one step
+    if optional_chain(expr) {
+        return vec![chain_start(expr)];
+    }
+    match expr {
+        Expr::Binary { left, right, .. } | Expr::Index { object: left, index: right, .. } | Expr::Set { object: left, value: right, .. } |
+        Expr::Range { start: left, end: right, .. } => {
+            vec![left.as_mut(), right.as_mut()]
+        }
+        Expr::Unary { right: value, .. } | Expr::Assign { value, .. } | Expr::Propagate { value, .. } => vec![value.as_mut()],
+        Expr::Get { object, .. } | Expr::Spread(object) => vec![object.as_mut()],
+        // Only the condition - the branch that doesn't get picked never runs
+        Expr::Conditional { condition, .. } => vec![condition.as_mut()],
+        // A builtin's name isn't a value, only its arguments are
+        Expr::Call { callee, arguments, .. } => {
+            let mut operands: Vec<&mut Expr> = Vec::new();
+            if !matches!(callee.as_ref(), Expr::Variable(_)) {
+                operands.push(callee.as_mut());
+            }
+            operands.extend(arguments.iter_mut());
+            operands
+        }
+        Expr::Array(items) => items.iter_mut().collect(),
+        Expr::Object(fields) => fields.iter_mut().map(|(_, value)| value).collect(),
+        // A lambda's body runs when it's called, not when it's written down
+        Expr::Literal(_) | Expr::Variable(_) | Expr::Function { .. } => Vec::new(),
+    }
+}
+
+fn optional_chain(expr: &Expr) -> bool {
+    match expr {
+        Expr::Get { object, optional, .. } | Expr::Index { object, optional, .. } => *optional || optional_chain(object),
+        _ => false,
+    }
+}
+
+fn chain_start(expr: &mut Expr) -> &mut Expr {
+    match expr {
+        Expr::Get { object, .. } | Expr::Index { object, .. } => chain_start(object),
+        start => start,
+    }
+}
@@ -0,0 +1,177 @@
+//! ZLang Fetch - `pull "https://example.com/lib.zlang"` pulls a file straight off a link 🌐
+//! What a link served is kept in `.zlang_cache` next to the program, and `zlang.lock` pins its hash the
+//! first time, so a link that starts serving something else later gets refused instead of quietly run
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::error::ZLangError;
+use crate::messages;
+
+/// One `link hash` line per link a program pulled, next to the program
+pub const LOCK_FILE: &str = "zlang.lock";
+/// Where the copies live, named by their hash
+pub const CACHE_DIR: &str = ".zlang_cache";
+/// Biggest file a link may serve
+pub const MAX_SIZE: usize = 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn is_link(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// The copy of `link` for the program in `root`, fetched when there's no copy that matches the lock file
+pub fn fetched(root: &Path, link: &str, line: usize) -> Result<PathBuf, ZLangError> {
+    if let Some(file) = cached(root, link) {
+        return Ok(file);
+    }
+    
+    let source = fetch(link).map_err(|why| messages::FETCH_FAILED.error(&[&link, &line, &why]))?;
+    let hash = sha256_hex(source.as_bytes());
+    let lock_file = root.join(LOCK_FILE);
+    let mut lock = read_lock(&lock_file);
+    match lock.iter().find(|(locked, _)| locked == link) {
+        Some((_, pinned)) if *pinned != hash => return Err(messages::FETCH_CHANGED.error(&[&link, &line, &LOCK_FILE])),
+        Some(_) => {}
+        None => {
+            lock.push((link.to_string(), hash.clone()));
+            let lines: String = lock.iter().map(|(link, hash)| format!("{} {}\n", link, hash)).collect();
+            fs::write(&lock_file, lines)
+                .map_err(|e| ZLangError::new(&format!("Couldn't write {}: {} 🔒", lock_file.display(), e)))?;
+        }
+    }
+    
+    let file = root.join(CACHE_DIR).join(format!("{}.zlang", hash));
+    fs::create_dir_all(root.join(CACHE_DIR))
+        .and_then(|_| fs::write(&file, source))
+        .map_err(|e| ZLangError::new(&format!("Couldn't keep a copy of {} in {}: {} 📁", link, CACHE_DIR, e)))?;
+    Ok(file)
+}
+
+/// The copy of `link` the lock file pins, when it's there and still hashes to what was pinned
+pub fn cached(root: &Path, link: &str) -> Option<PathBuf> {
+    let (_, hash) = read_lock(&root.join(LOCK_FILE)).into_iter().find(|(locked, _)| locked == link)?;
+    let file = root.join(CACHE_DIR).join(format!("{}.zlang", hash));
+    fs::read(&file).ok().filter(|bytes| sha256_hex(bytes) == hash).map(|_| file)
+}
+
+/// Every `link hash` line of a lock file, in order - none when there isn't one yet
+fn read_lock(file: &Path) -> Vec<(String, String)> {
+    let text = fs::read_to_string(file).unwrap_or_default();
+    text.lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(link, hash)| (link.to_string(), hash.trim().to_string()))
+        .collect()
+}
+
+/// What `link` serves, or why it couldn't be had
+fn fetch(link: &str) -> Result<String, String> {
+    let bytes = match link.strip_prefix("http://") {
+        Some(rest) => http_get(rest)?,
+        None => curl(link)?,
+    };
+    if bytes.len() > MAX_SIZE {
+        return Err(format!("it's over {} KB", MAX_SIZE / 1024));
+    }
+    String::from_utf8(bytes).map_err(|_| "what it served isn't text".to_string())
+}
+
+/// A plain `GET`, for `http://` links - `rest` is the link without its scheme
+fn http_get(rest: &str) -> Result<Vec<u8>, String> {
+    let rest = rest.split('#').next().unwrap_or(rest);
+    let (authority, path) = rest.split_at(rest.find(['/', '?']).unwrap_or(rest.len()));
+    let path = if path.is_empty() { "/" } else { path };
+    let address = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+    
+    let address = address.to_socket_addrs().ok().and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| format!("there's no server called {}", authority))?;
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: zlang\r\nConnection: close\r\n\r\n", path, authority)
+        .map_err(|e| e.to_string())?;
+    
+    let mut response = Vec::new();
+    stream.take((MAX_SIZE + 64 * 1024) as u64).read_to_end(&mut response).map_err(|e| e.to_string())?;
+    let split = response.windows(4).position(|window| window == b"\r\n\r\n")
+        .ok_or("the server's answer got cut off")?;
+    let status = String::from_utf8_lossy(&response[..split]).lines().next().unwrap_or_default().to_string();
+    match status.split_whitespace().nth(1) {
+        Some("200") => Ok(response[split + 4..].to_vec()),
+        _ => Err(format!("the server answered '{}'", status)),
+    }
+}
+
+/// `https://` links go through curl, which knows how to do TLS
+fn curl(link: &str) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--max-filesize", &MAX_SIZE.to_string(), link])
+        .output()
+        .map_err(|_| "https links need curl installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(output.stdout)
+}
+
+/// SHA-256 of `bytes` in lowercase hex, what the lock file pins
+fn sha256_hex(bytes: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+    
+    // The message, a 1 bit, zeros up to 8 short of a 64 byte block, then the length in bits
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+    
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, add) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(add);
+        }
+    }
+    
+    state.iter().map(|word| format!("{:08x}", word)).collect()
+}
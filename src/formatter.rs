@@ -1,6 +1,8 @@
 //! ZLang Code Formatter - Making your code look fresh! ✨
 //! Automatically formats ZLang code with proper indentation and spacing
 
+use std::collections::HashMap;
+
 use crate::lexer::Lexer;
 use crate::token::{Token, TokenType};
 use crate::error::ZLangError;
@@ -12,6 +14,18 @@ pub struct Formatter {
     output: String,
     indent_level: usize,
     indent_size: usize,
+    emoji_keywords: bool,
+    aliases: HashMap<String, TokenType>,
+    /// The source as chars, so literals can be written exactly as they were typed
+    source: Vec<char>,
+    /// A `//` comment was just written - anything more on this line would vanish into it
+    after_comment: bool,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[allow(dead_code)]
@@ -23,16 +37,43 @@ impl Formatter {
             output: String::new(),
             indent_level: 0,
             indent_size: 4, // 4 spaces per indent level
+            emoji_keywords: false,
+            aliases: HashMap::new(),
+            source: Vec::new(),
+            after_comment: false,
         }
     }
     
+    /// Write every keyword as its emoji alias instead of the word (`zlang emojify`)
+    pub fn with_emoji_keywords(mut self) -> Self {
+        self.emoji_keywords = true;
+        self
+    }
+    
+    /// Spaces per indent level, 4 unless your .zlangrc says otherwise
+    pub fn with_indent_size(mut self, indent_size: usize) -> Self {
+        self.indent_size = indent_size;
+        self
+    }
+    
+    /// Understand keyword aliases from .zlangrc - they come out as the real keyword
+    pub fn with_aliases(mut self, aliases: HashMap<String, TokenType>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+    
     pub fn format(&mut self, source: &str) -> Result<String, ZLangError> {
-        // Tokenize the source code
-        let mut lexer = Lexer::new(source);
-        self.tokens = lexer.tokenize()?;
+        // Tokenize the source code, keeping comments but not the old whitespace
+        let mut lexer = Lexer::new(source).with_trivia().with_aliases(self.aliases.clone());
+        self.tokens = lexer.tokenize()?
+            .into_iter()
+            .filter(|token| !matches!(token.token_type, TokenType::Whitespace(_)))
+            .collect();
+        self.source = source.chars().collect();
         self.current = 0;
         self.output.clear();
         self.indent_level = 0;
+        self.after_comment = false;
         
         self.format_tokens()?;
         
@@ -41,37 +82,55 @@ impl Formatter {
     
     fn format_tokens(&mut self) -> Result<(), ZLangError> {
         while !self.is_at_end() {
+            let before = self.current;
             self.format_statement()?;
+            
+            // A stray `}` at the top level - keep it and move on
+            if self.current == before {
+                self.add_token();
+            }
         }
         Ok(())
     }
     
     fn format_statement(&mut self) -> Result<(), ZLangError> {
-        // Skip multiple newlines
+        // Collapse runs of blank lines down to one, and none right after an opening brace
+        let mut newlines = 0;
         while self.match_token(&TokenType::Newline) {
-            // Only add one newline max
+            newlines += 1;
             if !self.output.ends_with('\n') {
-                self.output.push('\n');
+                self.newline();
             }
         }
+        if newlines > 1 && !self.output.is_empty() && !self.output.ends_with("{\n") && !self.output.ends_with("\n\n") {
+            self.newline();
+        }
         
-        if self.is_at_end() {
+        if self.is_at_end() || self.check(&TokenType::RightBrace) {
             return Ok(());
         }
         
         // Add proper indentation
         self.add_indent();
         
+        let lambda = matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::LeftParen));
         match &self.peek().token_type {
-            TokenType::Flex => self.format_function()?,
+            _ if self.at_squad() => self.format_squad()?,
+            _ if self.at_meme() => self.format_meme()?,
+            TokenType::Flex if !lambda => self.format_function()?,
             TokenType::Bet => self.format_variable_declaration()?,
             TokenType::Sus => self.format_if_statement()?,
             TokenType::Lowkey => self.format_while_statement()?,
-            TokenType::Highkey => self.format_for_statement()?,
-            TokenType::Bruh => self.format_print_statement()?,
+            TokenType::Highkey | TokenType::Grind => self.format_for_statement()?,
+            TokenType::VibeCheck => self.format_switch_statement()?,
+            TokenType::Manifest => self.format_try_statement()?,
+            TokenType::Drama => self.format_throw_statement()?,
+            TokenType::Bruh | TokenType::BruhBang | TokenType::Needs => self.format_print_statement()?,
+            TokenType::Pull => self.format_pull_statement()?,
             TokenType::LeftBrace => self.format_block()?,
             TokenType::Vibe => self.format_return_statement()?,
-            TokenType::Slay | TokenType::Ghost => {
+            TokenType::Global | TokenType::Outer => self.format_binding_statement()?,
+            TokenType::Slay | TokenType::Ghost | TokenType::NoChill | TokenType::Comment(_) => {
                 self.add_token();
                 self.consume_statement_end();
             }
@@ -89,21 +148,36 @@ impl Formatter {
         self.add_token(); // (
         self.format_parameter_list()?;
         self.add_token(); // )
+        self.format_type_annotation();
         self.add_space();
         
         self.format_block()?;
         Ok(())
     }
     
+    /// `flex(x) {` in the middle of an expression - the body gets laid out like any other block
+    fn format_lambda(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // flex
+        self.add_token(); // (
+        self.format_parameter_list()?;
+        self.add_token(); // )
+        self.add_space();
+        self.format_block()
+    }
+    
     fn format_parameter_list(&mut self) -> Result<(), ZLangError> {
         if !self.check(&TokenType::RightParen) {
             loop {
+                if self.check(&TokenType::Ellipsis) {
+                    self.add_token(); // ...
+                }
                 self.add_token(); // parameter name
+                self.format_type_annotation();
                 
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
-                self.output.push_str(", ");
+                self.write(", ");
             }
         }
         Ok(())
@@ -113,9 +187,10 @@ impl Formatter {
         self.add_token(); // bet
         self.add_space();
         self.add_token(); // variable name
+        self.format_type_annotation();
         
         if self.match_token(&TokenType::Equal) {
-            self.output.push_str(" = ");
+            self.write(" = ");
             self.format_expression()?;
         }
         
@@ -123,8 +198,15 @@ impl Formatter {
         Ok(())
     }
     
+    fn format_type_annotation(&mut self) {
+        if self.match_token(&TokenType::Colon) {
+            self.write(": ");
+            self.add_token(); // type name
+        }
+    }
+    
     fn format_if_statement(&mut self) -> Result<(), ZLangError> {
-        self.add_token(); // sus
+        self.add_token(); // sus or lowkey sus
         self.add_space();
         
         self.add_token(); // (
@@ -134,9 +216,23 @@ impl Formatter {
         
         self.format_statement_or_block()?;
         
-        if self.match_token(&TokenType::Bussin) {
-            self.output.push_str(" bussin ");
-            self.format_statement_or_block()?;
+        if !self.else_ahead(&[TokenType::Bussin, TokenType::NoSus, TokenType::LowkeySus]) {
+            return Ok(());
+        }
+        if self.check(&TokenType::LowkeySus) {
+            self.format_if_statement()?;
+        } else {
+            self.add_token(); // bussin or no sus
+            if self.check(&TokenType::Sus) {
+                // `bussin sus (...)` has to stay on one line
+                self.add_space();
+                self.format_if_statement()?;
+            } else {
+                if self.check(&TokenType::LeftBrace) {
+                    self.add_space();
+                }
+                self.format_statement_or_block()?;
+            }
         }
         
         Ok(())
@@ -151,39 +247,297 @@ impl Formatter {
         self.add_token(); // )
         self.add_space();
         
+        let braced = self.check(&TokenType::LeftBrace);
         self.format_statement_or_block()?;
-        Ok(())
+        self.format_loop_else(braced)
     }
     
     fn format_for_statement(&mut self) -> Result<(), ZLangError> {
-        self.add_token(); // highkey
+        self.add_token(); // highkey or grind
+        if self.check(&TokenType::Question) {
+            self.add_token(); // grind?
+        }
         self.add_space();
         
         self.add_token(); // (
-        self.add_token(); // variable
+        let for_in = matches!(self.peek().token_type, TokenType::Identifier(_))
+            && matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::In));
+        if for_in {
+            self.add_token(); // variable
+            self.add_space();
+            self.add_token(); // in
+            self.add_space();
+            self.format_expression()?;
+        } else {
+            self.format_for_clauses()?;
+        }
+        self.add_token(); // )
         self.add_space();
-        self.add_token(); // in
+        
+        let braced = self.check(&TokenType::LeftBrace);
+        self.format_statement_or_block()?;
+        self.format_loop_else(braced)
+    }
+    
+    /// Same as the parser, an else on a later line still belongs to what came before it - it goes right
+    /// after the closing brace, or lines up with the `sus` or loop when there's no brace to put it after.
+    /// Moves onto the else and says whether there was one of `keywords`
+    fn else_ahead(&mut self, keywords: &[TokenType]) -> bool {
+        let mut ahead = self.current;
+        while matches!(self.tokens.get(ahead).map(|token| &token.token_type), Some(TokenType::Newline)) {
+            ahead += 1;
+        }
+        if !self.tokens.get(ahead).is_some_and(|token| keywords.contains(&token.token_type)) {
+            return false;
+        }
+        self.current = ahead;
+        if self.output.ends_with('\n') {
+            self.add_indent();
+        } else {
+            self.add_space();
+        }
+        true
+    }
+    
+    /// A loop's `no sus`, for when it finishes without a `slay` - only a `{}` body has one, same as the parser
+    fn format_loop_else(&mut self, braced: bool) -> Result<(), ZLangError> {
+        if !braced || !self.else_ahead(&[TokenType::NoSus]) {
+            return Ok(());
+        }
+        self.add_token(); // no sus
+        if self.check(&TokenType::LeftBrace) {
+            self.add_space();
+        }
+        self.format_statement_or_block()
+    }
+    
+    /// `bet i = 0; i < 10; i = i + 1`, with `;;` kept tight when clauses are left out
+    fn format_for_clauses(&mut self) -> Result<(), ZLangError> {
+        if self.check(&TokenType::Bet) {
+            self.add_token(); // bet
+            self.add_space();
+            self.add_token(); // name
+            if self.check(&TokenType::Colon) {
+                self.add_token(); // :
+                self.add_space();
+                self.add_token(); // type
+            }
+        }
+        self.format_expression()?;
+        self.add_token(); // ;
+        
+        if !self.check(&TokenType::Semicolon) {
+            self.add_space();
+            self.format_expression()?;
+        }
+        self.add_token(); // ;
+        
+        if !self.check(&TokenType::RightParen) {
+            self.add_space();
+            self.format_expression()?;
+        }
+        Ok(())
+    }
+    
+    fn format_switch_statement(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // vibe check
         self.add_space();
+        
+        self.add_token(); // (
         self.format_expression()?;
         self.add_token(); // )
         self.add_space();
         
-        self.format_statement_or_block()?;
+        self.add_token(); // {
+        self.end_line();
+        self.indent_level += 1;
+        
+        loop {
+            while self.match_token(&TokenType::Newline) {}
+            if self.check(&TokenType::RightBrace) || self.is_at_end() {
+                break;
+            }
+            
+            if matches!(self.peek().token_type, TokenType::Comment(_)) {
+                self.add_indent();
+                self.add_token();
+                self.newline();
+                continue;
+            }
+            
+            // Case label: `default:`, `case value:` or just `value:`
+            self.add_indent();
+            if self.check_identifier("case") {
+                self.add_token();
+                self.add_space();
+            }
+            let mut pattern_start = true;
+            while !self.check(&TokenType::Colon) && !self.check(&TokenType::Newline) && !self.is_at_end() {
+                if self.match_token(&TokenType::Comma) {
+                    self.write(", ");
+                    pattern_start = true;
+                    continue;
+                }
+                // `>= 50:` has nothing on the comparison's left to space it from
+                if pattern_start && self.at_comparison() {
+                    self.add_token();
+                    self.write(" ");
+                } else {
+                    self.format_expression_token()?;
+                }
+                pattern_start = false;
+            }
+            self.add_token(); // :
+            
+            if self.check(&TokenType::LeftBrace) {
+                self.add_space();
+                self.format_block()?;
+                self.end_line();
+            } else {
+                self.end_line();
+                self.indent_level += 1;
+                loop {
+                    while self.match_token(&TokenType::Newline) {}
+                    if self.check(&TokenType::RightBrace) || self.at_case_label() || self.is_at_end() {
+                        break;
+                    }
+                    self.format_statement()?;
+                }
+                self.indent_level -= 1;
+            }
+        }
+        
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_token(); // }
+        Ok(())
+    }
+    
+    fn format_try_statement(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // manifest
+        self.add_space();
+        self.format_block()?;
+        
+        while self.check(&TokenType::Caught) {
+            self.add_space();
+            self.add_token(); // caught
+            self.add_space();
+            self.add_token(); // (
+            self.add_token(); // error variable
+            self.format_type_annotation(); // optional drama type
+            self.add_token(); // )
+            self.add_space();
+            self.format_block()?;
+        }
+        
+        if self.check(&TokenType::Frfr) {
+            self.add_space();
+            self.add_token(); // frfr
+            self.add_space();
+            self.format_block()?;
+        }
+        
+        Ok(())
+    }
+    
+    fn format_throw_statement(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // drama
+        self.add_space();
+        
+        // `drama type Name` or `drama type Name: Parent`
+        let declares_type = self.check_identifier("type")
+            && matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::Identifier(_)));
+        if declares_type {
+            self.add_token(); // type
+            self.add_space();
+            self.add_token(); // name
+            self.format_type_annotation(); // optional parent
+        } else {
+            self.format_expression()?;
+        }
+        
+        self.consume_statement_end();
         Ok(())
     }
     
     fn format_print_statement(&mut self) -> Result<(), ZLangError> {
-        self.add_token(); // bruh
+        self.add_token(); // bruh, bruh! or needs
         self.add_space();
         self.format_expression()?;
         self.consume_statement_end();
         Ok(())
     }
     
+    fn format_pull_statement(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // pull
+        self.add_space();
+        self.add_token(); // the file
+        if self.check_identifier("as") {
+            self.add_space();
+            self.add_token(); // as
+            self.add_space();
+            self.add_token(); // the name it goes by
+        }
+        self.consume_statement_end();
+        Ok(())
+    }
+    
+    /// Same test as the parser's - `squad` is a keyword only with a name, maybe `from` and a
+    /// parent's name, and a `{` after it
+    fn at_squad(&self) -> bool {
+        let ahead = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        let from = matches!(ahead(2), Some(TokenType::Identifier(word)) if word == "from") && matches!(ahead(3), Some(TokenType::Identifier(_)));
+        self.check_identifier("squad") && matches!(ahead(1), Some(TokenType::Identifier(_)))
+            && matches!(ahead(if from { 4 } else { 2 }), Some(TokenType::LeftBrace))
+    }
+    
+    fn format_squad(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // squad
+        self.add_space();
+        self.add_token(); // its name
+        self.add_space();
+        if self.check_identifier("from") {
+            self.add_token(); // from
+            self.add_space();
+            self.add_token(); // the parent's name
+            self.add_space();
+        }
+        self.format_block()
+    }
+    
+    /// Same test as the parser's - `meme` is a keyword only with a name and a `(` after it
+    fn at_meme(&self) -> bool {
+        let ahead = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        self.check_identifier("meme") && matches!(ahead(1), Some(TokenType::Identifier(_))) && matches!(ahead(2), Some(TokenType::LeftParen))
+    }
+    
+    /// `meme double(x) => x * 2` - past the `meme` it reads like an expression, `=>` spaced like an operator
+    fn format_meme(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // meme
+        self.add_space();
+        self.format_expression_statement()
+    }
+    
+    fn format_binding_statement(&mut self) -> Result<(), ZLangError> {
+        self.add_token(); // global or outer
+        self.add_space();
+        
+        loop {
+            self.add_token(); // name
+            if !self.match_token(&TokenType::Comma) {
+                break;
+            }
+            self.write(", ");
+        }
+        
+        self.consume_statement_end();
+        Ok(())
+    }
+    
     fn format_return_statement(&mut self) -> Result<(), ZLangError> {
         self.add_token(); // vibe
         
-        if !self.check(&TokenType::Semicolon) && !self.check(&TokenType::Newline) {
+        if !self.is_statement_end() && !self.is_at_end() {
             self.add_space();
             self.format_expression()?;
         }
@@ -202,7 +556,7 @@ impl Formatter {
         if self.check(&TokenType::LeftBrace) {
             self.format_block()
         } else {
-            self.output.push('\n');
+            self.newline();
             self.indent_level += 1;
             self.format_statement()?;
             self.indent_level -= 1;
@@ -212,7 +566,7 @@ impl Formatter {
     
     fn format_block(&mut self) -> Result<(), ZLangError> {
         self.add_token(); // {
-        self.output.push('\n');
+        self.end_line();
         self.indent_level += 1;
         
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
@@ -220,6 +574,9 @@ impl Formatter {
         }
         
         self.indent_level -= 1;
+        if !self.output.ends_with('\n') {
+            self.newline();
+        }
         self.add_indent();
         self.add_token(); // }
         
@@ -227,34 +584,53 @@ impl Formatter {
     }
     
     fn format_expression(&mut self) -> Result<(), ZLangError> {
-        // Simple expression formatting - could be enhanced further
         while !self.is_statement_end() && !self.is_at_end() {
-            match &self.peek().token_type {
-                TokenType::LeftParen => {
-                    self.add_token();
-                    self.format_expression_until(&TokenType::RightParen)?;
-                    self.add_token();
-                }
-                TokenType::LeftBracket => {
-                    self.add_token();
-                    self.format_array_elements()?;
-                    self.add_token();
-                }
-                TokenType::LeftBrace => {
-                    self.add_token();
-                    self.format_object_elements()?;
-                    self.add_token();
-                }
-                TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash |
-                TokenType::Equal | TokenType::EqualEqual | TokenType::BangEqual |
-                TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual |
-                TokenType::And | TokenType::Or => {
-                    self.output.push(' ');
-                    self.add_token();
-                    self.output.push(' ');
-                }
-                _ => self.add_token(),
+            self.format_expression_token()?;
+        }
+        Ok(())
+    }
+    
+    fn format_expression_token(&mut self) -> Result<(), ZLangError> {
+        match &self.peek().token_type {
+            TokenType::LeftParen => {
+                self.add_token();
+                self.format_expression_until(&TokenType::RightParen)?;
+                self.add_token();
+            }
+            TokenType::LeftBracket | TokenType::QuestionBracket => {
+                self.add_token();
+                self.format_array_elements()?;
+                self.add_token();
+            }
+            TokenType::LeftBrace => {
+                self.add_token();
+                self.format_object_elements()?;
+                self.add_token();
+            }
+            TokenType::Flex => self.format_lambda()?,
+            // A minus or plus with nothing in front of it works on one value, it isn't subtraction or addition
+            TokenType::Minus | TokenType::Plus if !self.follows_operand() => self.add_token(),
+            TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash | TokenType::Percent | TokenType::TildeSlash |
+            TokenType::Ampersand | TokenType::Pipe | TokenType::Caret | TokenType::LessLess | TokenType::GreaterGreater |
+            TokenType::Equal | TokenType::EqualEqual | TokenType::BangEqual |
+            TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual |
+            TokenType::And | TokenType::Or | TokenType::In | TokenType::FatArrow => {
+                self.write(" ");
+                self.add_token();
+                self.write(" ");
+            }
+            // `cond ? a : b` gets spaced out, a postfix `result?` stays stuck to its value
+            TokenType::Question if !self.question_is_postfix() => {
+                self.write(" ");
+                self.add_token();
+                self.write(" ");
             }
+            TokenType::Colon => {
+                self.write(" ");
+                self.add_token();
+                self.write(" ");
+            }
+            _ => self.add_token(),
         }
         Ok(())
     }
@@ -263,53 +639,128 @@ impl Formatter {
         while !self.check(end_token) && !self.is_at_end() {
             self.format_expression()?;
             if self.match_token(&TokenType::Comma) {
-                self.output.push_str(", ");
+                self.write(", ");
+            } else if !self.check(end_token) {
+                // Stray newline or brace inside the parens - keep it rather than loop forever
+                self.add_token();
             }
         }
         Ok(())
     }
     
     fn format_array_elements(&mut self) -> Result<(), ZLangError> {
+        self.skip_layout();
         if !self.check(&TokenType::RightBracket) {
             loop {
                 self.format_expression()?;
+                self.skip_layout();
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
-                self.output.push_str(", ");
+                self.write(", ");
+                self.skip_layout();
             }
         }
         Ok(())
     }
     
     fn format_object_elements(&mut self) -> Result<(), ZLangError> {
+        self.skip_layout();
         if !self.check(&TokenType::RightBrace) {
             loop {
                 self.add_token(); // key
                 self.add_token(); // :
-                self.output.push(' ');
+                self.write(" ");
                 self.format_expression()?;
+                self.skip_layout();
                 
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
-                self.output.push_str(", ");
+                self.write(", ");
+                self.skip_layout();
             }
         }
         Ok(())
     }
     
+    /// Newlines inside brackets don't matter, comments in there still get written
+    fn skip_layout(&mut self) {
+        loop {
+            if self.match_token(&TokenType::Newline) {
+                continue;
+            }
+            if matches!(self.peek().token_type, TokenType::Comment(_)) && !self.is_at_end() {
+                self.add_token();
+                continue;
+            }
+            break;
+        }
+    }
+    
     fn consume_statement_end(&mut self) {
         self.match_token(&TokenType::Semicolon);
+        self.end_line();
+    }
+    
+    /// Finish the line, keeping a trailing `// comment` on it
+    fn end_line(&mut self) {
+        if matches!(self.peek().token_type, TokenType::Comment(_)) && !self.is_at_end() {
+            self.add_token();
+        }
         if !self.output.ends_with('\n') {
-            self.output.push('\n');
+            self.newline();
         }
     }
     
     fn is_statement_end(&self) -> bool {
-        self.check(&TokenType::Semicolon) || self.check(&TokenType::Newline) || 
+        self.check(&TokenType::Semicolon) || self.check(&TokenType::Newline) ||
         self.check(&TokenType::RightParen) || self.check(&TokenType::RightBrace) ||
-        self.check(&TokenType::RightBracket) || self.check(&TokenType::Comma)
+        self.check(&TokenType::RightBracket) || self.check(&TokenType::Comma) ||
+        self.check(&TokenType::Bussin) || self.check(&TokenType::NoSus) || self.check(&TokenType::LowkeySus)
+    }
+    
+    /// Same lookahead the parser uses to end a case body
+    fn at_case_label(&self) -> bool {
+        if self.check_identifier("case") || self.check_identifier("default") || self.at_comparison() {
+            return true;
+        }
+        
+        let start = self.current + usize::from(self.check(&TokenType::Minus));
+        let simple_value = matches!(
+            self.tokens.get(start).map(|t| &t.token_type),
+            Some(TokenType::Number(_) | TokenType::String(_) | TokenType::Fr | TokenType::Cap | TokenType::Identifier(_))
+        );
+        simple_value && matches!(
+            self.tokens.get(start + 1).map(|t| &t.token_type),
+            Some(TokenType::Colon | TokenType::Comma | TokenType::DotDot | TokenType::DotDotEqual)
+        )
+    }
+    
+    fn at_comparison(&self) -> bool {
+        !self.is_at_end() && matches!(
+            self.peek().token_type,
+            TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual | TokenType::EqualEqual | TokenType::BangEqual
+        )
+    }
+    
+    /// Same lookahead the parser uses to tell `result?` from `cond ? a : b`
+    fn question_is_postfix(&self) -> bool {
+        matches!(
+            self.tokens.get(self.current + 1).map(|t| &t.token_type),
+            None | Some(TokenType::Newline) | Some(TokenType::Semicolon) | Some(TokenType::Eof)
+                | Some(TokenType::RightParen) | Some(TokenType::RightBracket) | Some(TokenType::RightBrace)
+                | Some(TokenType::Comma) | Some(TokenType::Question)
+        )
+    }
+    
+    /// Was the token just written something a binary operator could follow?
+    fn follows_operand(&self) -> bool {
+        self.current > 0 && matches!(
+            self.previous().token_type,
+            TokenType::Number(_) | TokenType::String(_) | TokenType::Identifier(_) | TokenType::Fr | TokenType::Cap |
+            TokenType::RightParen | TokenType::RightBracket | TokenType::RightBrace | TokenType::Question
+        )
     }
     
     fn add_indent(&mut self) {
@@ -319,54 +770,102 @@ impl Formatter {
     }
     
     fn add_space(&mut self) {
-        self.output.push(' ');
+        self.write(" ");
+    }
+    
+    /// Everything but indentation and line breaks goes through here
+    fn write(&mut self, text: &str) {
+        if self.after_comment {
+            self.newline();
+            self.add_indent();
+        }
+        self.output.push_str(text);
+    }
+    
+    /// End the current line, dropping any spaces left dangling at the end of it
+    fn newline(&mut self) {
+        let trimmed = self.output.trim_end_matches([' ', '\t']).len();
+        self.output.truncate(trimmed);
+        self.output.push('\n');
+        self.after_comment = false;
     }
     
     fn add_token(&mut self) {
-        let token_type = self.advance().token_type.clone();
-        match &token_type {
-            TokenType::Number(n) => self.output.push_str(&n.to_string()),
-            TokenType::String(s) => self.output.push_str(&format!("\"{}\"", s)),
-            TokenType::Identifier(name) => self.output.push_str(name),
-            TokenType::Fr => self.output.push_str("fr"),
-            TokenType::Cap => self.output.push_str("cap"),
-            TokenType::Bet => self.output.push_str("bet"),
-            TokenType::Sus => self.output.push_str("sus"),
-            TokenType::Bussin => self.output.push_str("bussin"),
-            TokenType::Flex => self.output.push_str("flex"),
-            TokenType::Vibe => self.output.push_str("vibe"),
-            TokenType::Lowkey => self.output.push_str("lowkey"),
-            TokenType::Highkey => self.output.push_str("highkey"),
-            TokenType::Bruh => self.output.push_str("bruh"),
-            TokenType::Slay => self.output.push_str("slay"),
-            TokenType::Ghost => self.output.push_str("ghost"),
-            TokenType::In => self.output.push_str("in"),
-            TokenType::Plus => self.output.push('+'),
-            TokenType::Minus => self.output.push('-'),
-            TokenType::Star => self.output.push('*'),
-            TokenType::Slash => self.output.push('/'),
-            TokenType::Percent => self.output.push('%'),
-            TokenType::Equal => self.output.push('='),
-            TokenType::EqualEqual => self.output.push_str("=="),
-            TokenType::BangEqual => self.output.push_str("!="),
-            TokenType::Greater => self.output.push('>'),
-            TokenType::GreaterEqual => self.output.push_str(">="),
-            TokenType::Less => self.output.push('<'),
-            TokenType::LessEqual => self.output.push_str("<="),
-            TokenType::And => self.output.push_str("&&"),
-            TokenType::Or => self.output.push_str("||"),
-            TokenType::Bang => self.output.push('!'),
-            TokenType::LeftParen => self.output.push('('),
-            TokenType::RightParen => self.output.push(')'),
-            TokenType::LeftBrace => self.output.push('{'),
-            TokenType::RightBrace => self.output.push('}'),
-            TokenType::LeftBracket => self.output.push('['),
-            TokenType::RightBracket => self.output.push(']'),
-            TokenType::Comma => self.output.push(','),
-            TokenType::Semicolon => self.output.push(';'),
-            TokenType::Colon => self.output.push(':'),
-            _ => {} // Skip newlines and EOF
+        let token = self.advance().clone();
+        let token_type = &token.token_type;
+        
+        if let Some(keyword) = token_type.keyword() {
+            match token_type.emoji() {
+                Some(emoji) if self.emoji_keywords => self.write(&emoji.to_string()),
+                _ => self.write(keyword),
+            }
+            return;
+        }
+        // `is` stays a word and `==` a symbol, whatever alias the word came in as
+        if let Some(word) = token_type.operator_word() {
+            let written_as_word = token.lexeme(&self.source).starts_with(|c: char| c.is_alphabetic() || c == '_');
+            if written_as_word {
+                self.write(word);
+                return;
+            }
         }
+        
+        let text = match token_type {
+            // Literals go out exactly as they came in, `1.50` stays `1.50`
+            TokenType::Number(_) => token.lexeme(&self.source),
+            // ...except a string spanning lines, which gets the same line endings as everything else
+            TokenType::String(_) => token.lexeme(&self.source).replace("\r\n", "\n"),
+            TokenType::Identifier(name) => name.clone(),
+            TokenType::Comment(comment) => {
+                if !self.output.is_empty() && !self.output.ends_with([' ', '\n']) {
+                    self.write(" ");
+                }
+                self.write(comment);
+                self.after_comment = true;
+                return;
+            }
+            TokenType::Plus => "+".to_string(),
+            TokenType::Minus => "-".to_string(),
+            TokenType::Star => "*".to_string(),
+            TokenType::Slash => "/".to_string(),
+            TokenType::Percent => "%".to_string(),
+            TokenType::TildeSlash => "~/".to_string(),
+            TokenType::Ampersand => "&".to_string(),
+            TokenType::Pipe => "|".to_string(),
+            TokenType::Caret => "^".to_string(),
+            TokenType::Tilde => "~".to_string(),
+            TokenType::LessLess => "<<".to_string(),
+            TokenType::GreaterGreater => ">>".to_string(),
+            TokenType::Equal => "=".to_string(),
+            TokenType::EqualEqual => "==".to_string(),
+            TokenType::BangEqual => "!=".to_string(),
+            TokenType::Greater => ">".to_string(),
+            TokenType::GreaterEqual => ">=".to_string(),
+            TokenType::Less => "<".to_string(),
+            TokenType::LessEqual => "<=".to_string(),
+            TokenType::And => "&&".to_string(),
+            TokenType::Or => "||".to_string(),
+            TokenType::Bang => "!".to_string(),
+            TokenType::FatArrow => "=>".to_string(),
+            TokenType::LeftParen => "(".to_string(),
+            TokenType::RightParen => ")".to_string(),
+            TokenType::LeftBrace => "{".to_string(),
+            TokenType::RightBrace => "}".to_string(),
+            TokenType::LeftBracket => "[".to_string(),
+            TokenType::RightBracket => "]".to_string(),
+            TokenType::Comma => ",".to_string(),
+            TokenType::Dot => ".".to_string(),
+            TokenType::QuestionDot => "?.".to_string(),
+            TokenType::QuestionBracket => "?[".to_string(),
+            TokenType::Ellipsis => "...".to_string(),
+            TokenType::DotDot => "..".to_string(),
+            TokenType::DotDotEqual => "..=".to_string(),
+            TokenType::Semicolon => ";".to_string(),
+            TokenType::Colon => ":".to_string(),
+            TokenType::Question => "?".to_string(),
+            _ => return, // Skip newlines and EOF
+        };
+        self.write(&text);
     }
     
     // Helper methods
@@ -387,6 +886,10 @@ impl Formatter {
         }
     }
     
+    fn check_identifier(&self, name: &str) -> bool {
+        !self.is_at_end() && matches!(&self.peek().token_type, TokenType::Identifier(ident) if ident == name)
+    }
+    
     fn advance(&mut self) -> &Token {
         if !self.is_at_end() {
             self.current += 1;
@@ -405,4 +908,4 @@ impl Formatter {
     fn previous(&self) -> &Token {
         &self.tokens[self.current - 1]
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,26 @@
+//! ZLang Gallery - The playground's example snippets, straight from examples/playground 🖼️
+//! Baked into the binary so the web server and `zlang examples` work from any directory
+
+pub struct Example {
+    /// Short name, used by `zlang examples <name>` and the playground's buttons
+    pub name: &'static str,
+    pub title: &'static str,
+    pub source: &'static str,
+}
+
+/// In the order the playground shows them
+pub const EXAMPLES: &[Example] = &[
+    Example { name: "hello", title: "Hello World", source: include_str!("../examples/playground/hello.zlang") },
+    Example { name: "variables", title: "Variables", source: include_str!("../examples/playground/variables.zlang") },
+    Example { name: "functions", title: "Functions", source: include_str!("../examples/playground/functions.zlang") },
+    Example { name: "loops", title: "Loops", source: include_str!("../examples/playground/loops.zlang") },
+    Example { name: "conditions", title: "If/Else", source: include_str!("../examples/playground/conditions.zlang") },
+    Example { name: "errors", title: "Error Handling", source: include_str!("../examples/playground/errors.zlang") },
+    Example { name: "drawing", title: "Drawing", source: include_str!("../examples/playground/drawing.zlang") },
+    Example { name: "animation", title: "Animation", source: include_str!("../examples/playground/animation.zlang") },
+    Example { name: "comprehensive", title: "All Features", source: include_str!("../examples/playground/comprehensive.zlang") },
+];
+
+pub fn find(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.name == name)
+}
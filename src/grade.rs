@@ -0,0 +1,437 @@
+//! ZLang Grader - Autograding homework so the teacher can touch grass 📝
+//! Runs a student's file once per case in the assignment, feeding `spill()` and checking what got printed
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::console::CaptureConsole;
+use crate::error::ZLangError;
+use crate::interpreter::{Capabilities, Interpreter, INTERPRETER_STACK_SIZE};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+
+/// Seconds a case gets when neither it nor the assignment says otherwise
+pub const DEFAULT_TIMEOUT: f64 = 5.0;
+
+/// An assignment file, a small slice of TOML:
+///
+/// ```toml
+/// title = "FizzBuzz"
+/// description = "Print 1 to 15, fizz for threes and buzz for fives"
+/// file = "fizzbuzz.zlang"   # the student's file, relative to this one
+/// timeout = 2               # seconds per case
+///
+/// [[case]]
+/// name = "three"
+/// input = "3"               # one line per spill()
+/// expected = """
+/// fizz
+/// """
+/// points = 2
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub title: Option<String>,
+    /// The problem statement, for assignments that get handed out as challenges
+    pub description: Option<String>,
+    pub file: Option<String>,
+    pub cases: Vec<Case>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub input: String,
+    pub expected: String,
+    pub points: f64,
+    pub timeout: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed,
+    /// First line that differs, 1-based, with what was printed and what should have been
+    WrongOutput { line: usize, got: Option<String>, expected: Option<String> },
+    Failed(String),
+    TimedOut,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseResult {
+    pub name: String,
+    pub points: f64,
+    pub outcome: Outcome,
+}
+
+impl CaseResult {
+    pub fn earned(&self) -> f64 {
+        if self.outcome == Outcome::Passed { self.points } else { 0.0 }
+    }
+}
+
+impl Assignment {
+    pub fn parse(source: &str) -> Result<Assignment, ZLangError> {
+        let tables = toml::parse(source)?;
+        let (top, cases) = tables.split_first().expect("there's always a top-level table");
+        
+        let mut assignment = Assignment { title: None, description: None, file: None, cases: Vec::new() };
+        let mut timeout = DEFAULT_TIMEOUT;
+        for (key, value, line) in top {
+            match key.as_str() {
+                "title" => assignment.title = Some(value.string(key, *line)?),
+                "description" => assignment.description = Some(value.string(key, *line)?),
+                "file" => assignment.file = Some(value.string(key, *line)?),
+                "timeout" => timeout = value.seconds(key, *line)?,
+                _ => return Err(unknown_key(key, *line)),
+            }
+        }
+        
+        for (index, table) in cases.iter().enumerate() {
+            let mut case = Case {
+                name: format!("case {}", index + 1),
+                input: String::new(),
+                expected: String::new(),
+                points: 1.0,
+                timeout,
+            };
+            let mut has_expected = false;
+            
+            for (key, value, line) in table {
+                match key.as_str() {
+                    "name" => case.name = value.string(key, *line)?,
+                    "input" => case.input = value.string(key, *line)?,
+                    "expected" => {
+                        case.expected = value.string(key, *line)?;
+                        has_expected = true;
+                    }
+                    "points" => case.points = value.number(key, *line)?,
+                    "timeout" => case.timeout = value.seconds(key, *line)?,
+                    _ => return Err(unknown_key(key, *line)),
+                }
+            }
+            if !has_expected {
+                return Err(ZLangError::new(&format!("'{}' has no `expected` output to check against 🤷", case.name)));
+            }
+            assignment.cases.push(case);
+        }
+        
+        if assignment.cases.is_empty() {
+            return Err(ZLangError::new("No [[case]] tables, there's nothing to grade 📭"));
+        }
+        Ok(assignment)
+    }
+    
+    /// The student's file named by `file`, which is relative to the assignment at `path`
+    pub fn student_file(&self, path: &Path) -> Option<PathBuf> {
+        let file = self.file.as_ref()?;
+        Some(path.parent().unwrap_or(Path::new("")).join(file))
+    }
+    
+    pub fn total_points(&self) -> f64 {
+        self.cases.iter().map(|case| case.points).sum()
+    }
+}
+
+fn unknown_key(key: &str, line: usize) -> ZLangError {
+    ZLangError::new(&format!("Line {}: '{}' isn't something an assignment can have 🤔", line, key))
+}
+
+/// Run `source` once per case, each on its own fresh interpreter that may only reach for `capabilities`
+pub fn grade(source: &str, assignment: &Assignment, capabilities: Capabilities) -> Vec<CaseResult> {
+    assignment.cases.iter()
+        .map(|case| CaseResult {
+            name: case.name.clone(),
+            points: case.points,
+            outcome: run_case(source, case, capabilities),
+        })
+        .collect()
+}
+
+fn run_case(source: &str, case: &Case, capabilities: Capabilities) -> Outcome {
+    let interrupt = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+    
+    let worker = {
+        let (source, input, interrupt) = (source.to_string(), case.input.clone(), Arc::clone(&interrupt));
+        thread::Builder::new()
+            .stack_size(INTERPRETER_STACK_SIZE)
+            .spawn(move || {
+                let output = CaptureConsole::with_input(&input);
+                let mut interpreter = Interpreter::new()
+                    .with_console(Box::new(output.clone()))
+                    .with_capabilities(capabilities)
+                    .with_interrupt(interrupt);
+                let result = execute(&mut interpreter, &source).map(|_| output.lines());
+                let _ = sender.send(result);
+            })
+    };
+    if let Err(e) = worker {
+        return Outcome::Failed(format!("Couldn't start the case: {}", e));
+    }
+    
+    // Out of time: flip the interrupt so the run stops at its next statement, then collect it
+    let result = match receiver.recv_timeout(Duration::from_secs_f64(case.timeout)) {
+        Ok(result) => result,
+        // The worker hung up without an answer, the interpreter panicked
+        Err(RecvTimeoutError::Disconnected) => {
+            return Outcome::Failed("The interpreter had a moment 💀 This case broke something on our end, not yours.".to_string());
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            interrupt.store(true, Ordering::Relaxed);
+            let _ = receiver.recv();
+            return Outcome::TimedOut;
+        }
+    };
+    
+    match result {
+        Err(e) => Outcome::Failed(e.to_string()),
+        Ok(output) => compare(&output, &case.expected),
+    }
+}
+
+fn execute(interpreter: &mut Interpreter, source: &str) -> Result<(), ZLangError> {
+    let statements = Parser::new(Lexer::new(source).tokenize()?).parse()?;
+    let diagnostics = Resolver::new().resolve(&statements);
+    if let Some(error) = diagnostics.error() {
+        return Err(error);
+    }
+    interpreter.interpret(statements).map(|_| ())
+}
+
+/// Line by line, ignoring trailing spaces and blank lines at the end
+fn compare(output: &[String], expected: &str) -> Outcome {
+    let got: Vec<&str> = output.iter().flat_map(|line| line.lines()).map(str::trim_end).collect();
+    let wanted: Vec<&str> = expected.lines().map(str::trim_end).collect();
+    let trim = |lines: &[&str]| lines.len() - lines.iter().rev().take_while(|line| line.is_empty()).count();
+    let (got, wanted) = (&got[..trim(&got)], &wanted[..trim(&wanted)]);
+    
+    match (0..got.len().max(wanted.len())).find(|&i| got.get(i) != wanted.get(i)) {
+        None => Outcome::Passed,
+        Some(line) => Outcome::WrongOutput {
+            line: line + 1,
+            got: got.get(line).map(|line| line.to_string()),
+            expected: wanted.get(line).map(|line| line.to_string()),
+        },
+    }
+}
+
+/// Just enough TOML for assignment files: `key = value` pairs, `[[case]]` tables, comments,
+/// and strings (basic, literal and their multi-line forms), numbers and booleans as values
+mod toml {
+    use crate::error::ZLangError;
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        String(String),
+        Number(f64),
+        Bool(bool),
+    }
+    
+    impl Value {
+        pub fn string(&self, key: &str, line: usize) -> Result<String, ZLangError> {
+            match self {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(ZLangError::new(&format!("Line {}: '{}' should be a string 🧵", line, key))),
+            }
+        }
+        
+        pub fn number(&self, key: &str, line: usize) -> Result<f64, ZLangError> {
+            match self {
+                Value::Number(n) => Ok(*n),
+                _ => Err(ZLangError::new(&format!("Line {}: '{}' should be a number 🔢", line, key))),
+            }
+        }
+        
+        pub fn seconds(&self, key: &str, line: usize) -> Result<f64, ZLangError> {
+            match self.number(key, line)? {
+                n if n > 0.0 && n.is_finite() => Ok(n),
+                _ => Err(ZLangError::new(&format!("Line {}: '{}' has to be some positive number of seconds ⏱️", line, key))),
+            }
+        }
+    }
+    
+    /// Every (key, value, line) of the top-level table, then of each `[[case]]` in order
+    pub type Table = Vec<(String, Value, usize)>;
+    
+    pub fn parse(source: &str) -> Result<Vec<Table>, ZLangError> {
+        let mut reader = Reader { chars: source.chars().collect(), current: 0, line: 1 };
+        let mut tables = vec![Table::new()];
+        
+        loop {
+            reader.skip_blank();
+            if reader.is_at_end() {
+                return Ok(tables);
+            }
+            
+            if reader.peek() == Some('[') {
+                let header = reader.take_while(|c| c != '\n' && c != '#');
+                if header.trim() != "[[case]]" {
+                    return Err(reader.error(&format!("`{}` - [[case]] is the only kind of table here", header.trim())));
+                }
+                tables.push(Table::new());
+            } else {
+                let line = reader.line;
+                let key = reader.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+                reader.skip_spaces();
+                if key.is_empty() || !reader.eat('=') {
+                    return Err(reader.error("expected `key = value`"));
+                }
+                reader.skip_spaces();
+                let value = reader.value()?;
+                
+                let table = tables.last_mut().expect("there's always a table");
+                if table.iter().any(|(existing, _, _)| *existing == key) {
+                    return Err(reader.error(&format!("'{}' is set twice", key)));
+                }
+                table.push((key, value, line));
+            }
+            reader.end_of_line()?;
+        }
+    }
+    
+    struct Reader {
+        chars: Vec<char>,
+        current: usize,
+        line: usize,
+    }
+    
+    impl Reader {
+        fn value(&mut self) -> Result<Value, ZLangError> {
+            if self.eat_str("\"\"\"") {
+                return self.string("\"\"\"", true).map(Value::String);
+            }
+            if self.eat_str("'''") {
+                return self.string("'''", false).map(Value::String);
+            }
+            if self.eat('"') {
+                return self.string("\"", true).map(Value::String);
+            }
+            if self.eat('\'') {
+                return self.string("'", false).map(Value::String);
+            }
+            
+            let word = self.take_while(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '+' | '_'));
+            match word.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                _ => word.replace('_', "").parse().map(Value::Number)
+                    .map_err(|_| self.error(&format!("'{}' isn't a string, number or boolean", word))),
+            }
+        }
+        
+        /// The rest of a string after its opening `quote`. Multi-line ones drop a newline right after the quotes
+        fn string(&mut self, quote: &str, escapes: bool) -> Result<String, ZLangError> {
+            let multi_line = quote.len() == 3;
+            if multi_line {
+                self.eat('\r');
+                self.eat('\n');
+            }
+            
+            let mut value = String::new();
+            loop {
+                if self.eat_str(quote) {
+                    return Ok(value);
+                }
+                if self.is_at_end() || (!multi_line && self.peek() == Some('\n')) {
+                    return Err(self.error("this string never got its closing quote"));
+                }
+                match self.advance() {
+                    None => unreachable!("checked just above"),
+                    Some('\\') if escapes => match self.advance() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some('\\') => value.push('\\'),
+                        Some('"') => value.push('"'),
+                        // A backslash at the end of a line glues it to the next one
+                        Some('\n') if multi_line => {
+                            self.take_while(char::is_whitespace);
+                        }
+                        other => return Err(self.error(&format!("'\\{}' isn't an escape we know", other.unwrap_or(' ')))),
+                    },
+                    Some('\r') if self.peek() == Some('\n') => {}
+                    Some(c) => value.push(c),
+                }
+            }
+        }
+        
+        fn end_of_line(&mut self) -> Result<(), ZLangError> {
+            self.skip_spaces();
+            if self.peek() == Some('#') {
+                self.take_while(|c| c != '\n');
+            }
+            match self.peek() {
+                None | Some('\n') => Ok(()),
+                Some(_) => Err(self.error("unexpected stuff after the value")),
+            }
+        }
+        
+        /// Whitespace, newlines and comment lines
+        fn skip_blank(&mut self) {
+            loop {
+                self.take_while(char::is_whitespace);
+                if self.peek() != Some('#') {
+                    return;
+                }
+                self.take_while(|c| c != '\n');
+            }
+        }
+        
+        fn skip_spaces(&mut self) {
+            self.take_while(|c| c == ' ' || c == '\t' || c == '\r');
+        }
+        
+        fn take_while(&mut self, keep: impl Fn(char) -> bool) -> String {
+            let mut taken = String::new();
+            while let Some(c) = self.peek().filter(|c| keep(*c)) {
+                taken.push(c);
+                self.advance();
+            }
+            taken
+        }
+        
+        fn eat(&mut self, expected: char) -> bool {
+            if self.peek() == Some(expected) {
+                self.advance();
+                return true;
+            }
+            false
+        }
+        
+        fn eat_str(&mut self, expected: &str) -> bool {
+            let expected: Vec<char> = expected.chars().collect();
+            if self.chars[self.current..].starts_with(&expected) {
+                for _ in 0..expected.len() {
+                    self.advance();
+                }
+                return true;
+            }
+            false
+        }
+        
+        fn advance(&mut self) -> Option<char> {
+            let c = self.peek()?;
+            self.current += 1;
+            if c == '\n' {
+                self.line += 1;
+            }
+            Some(c)
+        }
+        
+        fn peek(&self) -> Option<char> {
+            self.chars.get(self.current).copied()
+        }
+        
+        fn is_at_end(&self) -> bool {
+            self.current >= self.chars.len()
+        }
+        
+        fn error(&self, message: &str) -> ZLangError {
+            ZLangError::new(&format!("Line {}: {} 📝", self.line, message))
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! ZLang Call Graph - Who's calling who in your code 🕸️
+//! Comes out as DOT for graphviz or JSON for everything else
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::ast::{Expr, Stmt};
+use crate::fetch;
+use crate::json;
+use crate::modules::{self, FileProvider};
+
+/// Stand-in caller for code that isn't inside any function
+pub const TOP_LEVEL: &str = "<top level>";
+
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Every function, in declaration order
+    pub functions: Vec<String>,
+    /// (caller, callee) pairs, each listed once, in the order they first show up. Passing a
+    /// function by name (like `attempt(3, flaky)`) counts as calling it
+    pub calls: Vec<(String, String)>,
+    /// Each file of the program with the functions it declares, the one it starts from first.
+    /// Empty when there's only the one file
+    pub modules: Vec<(String, Vec<String>)>,
+    /// (puller, pulled) file pairs, each listed once. A pulled file's own top-level code calls
+    /// things as the file
+    pub pulls: Vec<(String, String)>,
+    // What a `pull "..." as name` file's functions are reached through while it's being walked
+    namespace: String,
+}
+
+/// One file of a program, as one of its pulls ran it
+struct Module {
+    name: String,
+    namespace: String,
+    statements: Vec<Stmt>,
+}
+
+/// Where a program's files come from, for walking its pulls
+struct Program<'a> {
+    files: &'a dyn FileProvider,
+    root: &'a Path,
+    // The root's one real path, what every file is named relative to
+    real_root: Option<PathBuf>,
+}
+
+impl Program<'_> {
+    /// `file` relative to the program's folder, like `lib/utils.zlang`
+    fn name(&self, file: &Path) -> String {
+        let relative = self.real_root.as_ref().and_then(|root| file.strip_prefix(root).ok());
+        relative.unwrap_or(file).display().to_string()
+    }
+}
+
+impl CallGraph {
+    pub fn build(statements: &[Stmt]) -> Self {
+        let mut graph = CallGraph::default();
+        collect_functions(statements, "", &mut graph.functions);
+        
+        for statement in statements {
+            graph.visit_stmt(statement, TOP_LEVEL);
+        }
+        graph
+    }
+    
+    /// The graph of the program in `file` and every file it pulls out of `files`. A function from
+    /// a `pull "..." as name` file goes by `name.function`, the way it gets called
+    pub fn build_program(file: &Path, statements: &[Stmt], files: &dyn FileProvider) -> Self {
+        let root = file.parent().unwrap_or(Path::new(""));
+        let real_root = files.canonicalize(if root.as_os_str().is_empty() { Path::new(".") } else { root }).ok();
+        let program = Program { files, root, real_root };
+        
+        let mut graph = CallGraph::default();
+        let real_file = files.canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        let main = Module { name: program.name(&real_file), namespace: String::new(), statements: statements.to_vec() };
+        let mut modules = Vec::new();
+        graph.collect_pulls(&program, &main, root, &mut HashSet::new(), &mut modules);
+        if graph.pulls.is_empty() {
+            return CallGraph::build(statements);
+        }
+        modules.insert(0, main);
+        
+        // Every file's functions first, so a call into a file that's pulled later still lands
+        for module in &modules {
+            let mut functions = Vec::new();
+            collect_functions(&module.statements, &module.namespace, &mut functions);
+            for function in &functions {
+                if !graph.functions.contains(function) {
+                    graph.functions.push(function.clone());
+                }
+            }
+            match graph.modules.iter_mut().find(|(name, _)| *name == module.name) {
+                Some((_, listed)) => listed.extend(functions),
+                None => graph.modules.push((module.name.clone(), functions)),
+            }
+        }
+        for (i, module) in modules.iter().enumerate() {
+            graph.namespace.clone_from(&module.namespace);
+            graph.visit_all(&module.statements, if i == 0 { TOP_LEVEL } else { &module.name });
+        }
+        graph.namespace.clear();
+        graph
+    }
+    
+    /// Every file `module` in `dir` pulls and the files those pull, in the order they're pulled -
+    /// each once for every namespace it's pulled into
+    fn collect_pulls(&mut self, program: &Program, module: &Module, dir: &Path, seen: &mut HashSet<(PathBuf, String)>, modules: &mut Vec<Module>) {
+        for statement in &module.statements {
+            let Stmt::Pull { path, alias, .. } = statement else {
+                continue;
+            };
+            let Some(file) = modules::find_pulled(path, dir, program.root, program.files) else {
+                continue;
+            };
+            // A link goes by the link, not by its copy in the cache
+            let name = if fetch::is_link(path) { path.clone() } else { program.name(&file) };
+            let pull = (module.name.clone(), name.clone());
+            if !self.pulls.contains(&pull) {
+                self.pulls.push(pull);
+            }
+            let namespace = match alias {
+                Some(alias) => qualified(&module.namespace, alias),
+                None => module.namespace.clone(),
+            };
+            if !seen.insert((file.clone(), namespace.clone())) {
+                continue;
+            }
+            
+            // A file that can't be read or parsed is left out, running the program says why
+            if let Ok((statements, _)) = modules::load(program.files, &file) {
+                let pulled = Module { name, namespace, statements };
+                let index = modules.len();
+                self.collect_pulls(program, &pulled, file.parent().unwrap_or(dir), seen, modules);
+                modules.insert(index, pulled);
+            }
+        }
+    }
+    
+    /// Functions `caller` calls directly
+    pub fn callees(&self, caller: &str) -> Vec<&str> {
+        self.calls.iter()
+            .filter(|(from, _)| from == caller)
+            .map(|(_, to)| to.as_str())
+            .collect()
+    }
+    
+    /// Each file is a box of its own functions when the graph has files, and dashed arrows are pulls
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph zlang {\n");
+        let top_level = format!("{} [shape=box];\n", json::string(TOP_LEVEL));
+        if self.modules.is_empty() {
+            dot.push_str(&format!("    {}", top_level));
+            for function in &self.functions {
+                dot.push_str(&format!("    {};\n", json::string(function)));
+            }
+        }
+        for (i, (module, functions)) in self.modules.iter().enumerate() {
+            dot.push_str(&format!("    subgraph \"cluster_{}\" {{\n", i));
+            dot.push_str(&format!("        label = {};\n", json::string(module)));
+            dot.push_str(&format!("        {} [shape=folder];\n", json::string(module)));
+            if i == 0 {
+                dot.push_str(&format!("        {}", top_level));
+            }
+            for function in functions {
+                dot.push_str(&format!("        {};\n", json::string(function)));
+            }
+            dot.push_str("    }\n");
+        }
+        for (puller, pulled) in &self.pulls {
+            dot.push_str(&format!("    {} -> {} [style=dashed];\n", json::string(puller), json::string(pulled)));
+        }
+        for (caller, callee) in &self.calls {
+            dot.push_str(&format!("    {} -> {};\n", json::string(caller), json::string(callee)));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+    
+    pub fn to_json(&self) -> String {
+        let strings = |names: &[String]| names.iter().map(|name| json::string(name)).collect::<Vec<_>>().join(", ");
+        let edges = |edges: &[(String, String)]| edges.iter()
+            .map(|(from, to)| format!("{{\"from\": {}, \"to\": {}}}", json::string(from), json::string(to)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let modules: Vec<String> = self.modules.iter()
+            .map(|(name, functions)| format!("{{\"name\": {}, \"functions\": [{}]}}", json::string(name), strings(functions)))
+            .collect();
+        format!(
+            "{{\"functions\": [{}], \"calls\": [{}], \"modules\": [{}], \"pulls\": [{}]}}\n",
+            strings(&self.functions),
+            edges(&self.calls),
+            modules.join(", "),
+            edges(&self.pulls)
+        )
+    }
+    
+    fn visit_stmt(&mut self, stmt: &Stmt, caller: &str) {
+        match stmt {
+            Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) => self.visit_expr(expr, caller),
+            Stmt::VarDeclaration { initializer: Some(expr), .. } | Stmt::Return(Some(expr)) => self.visit_expr(expr, caller),
+            Stmt::Needs { condition, .. } => self.visit_expr(condition, caller),
+            Stmt::Block(statements) => self.visit_all(statements, caller),
+            Stmt::If { condition, then_branch, else_branch } => {
+                self.visit_expr(condition, caller);
+                self.visit_stmt(then_branch, caller);
+                if let Some(else_branch) = else_branch {
+                    self.visit_stmt(else_branch, caller);
+                }
+            }
+            Stmt::While { condition, body, else_branch } => {
+                self.visit_expr(condition, caller);
+                self.visit_stmt(body, caller);
+                if let Some(else_branch) = else_branch {
+                    self.visit_stmt(else_branch, caller);
+                }
+            }
+            Stmt::For { iterable, body, else_branch, .. } => {
+                self.visit_expr(iterable, caller);
+                self.visit_stmt(body, caller);
+                if let Some(else_branch) = else_branch {
+                    self.visit_stmt(else_branch, caller);
+                }
+            }
+            Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+                if let Some(initializer) = initializer {
+                    self.visit_stmt(initializer, caller);
+                }
+                for expr in condition.iter().chain(increment) {
+                    self.visit_expr(expr, caller);
+                }
+                self.visit_stmt(body, caller);
+                if let Some(else_branch) = else_branch {
+                    self.visit_stmt(else_branch, caller);
+                }
+            }
+            Stmt::Switch { expr, cases, default } => {
+                self.visit_expr(expr, caller);
+                for case in cases {
+                    case.exprs().for_each(|value| self.visit_expr(value, caller));
+                    self.visit_all(&case.body, caller);
+                }
+                if let Some(body) = default {
+                    self.visit_all(body, caller);
+                }
+            }
+            Stmt::Try { try_block, catch_clauses, finally_block } => {
+                self.visit_all(try_block, caller);
+                for clause in catch_clauses {
+                    self.visit_all(&clause.body, caller);
+                }
+                if let Some(body) = finally_block {
+                    self.visit_all(body, caller);
+                }
+            }
+            // Nested functions are their own callers
+            Stmt::Function { name, body, .. } => self.visit_all(body, &qualified(&self.namespace, name)),
+            // Methods aren't nodes, they run whenever the code holding an instance wants - so
+            // their calls count for whoever declared the squad
+            Stmt::Squad { body, .. } => {
+                for member in body {
+                    match member {
+                        Stmt::Function { body, .. } => self.visit_all(body, caller),
+                        field => self.visit_stmt(field, caller),
+                    }
+                }
+            }
+            Stmt::VarDeclaration { initializer: None, .. } | Stmt::Return(None) | Stmt::DramaType { .. } |
+            Stmt::Break | Stmt::Continue | Stmt::Global(_) | Stmt::Outer(_) | Stmt::Pull { .. } => {}
+        }
+    }
+    
+    fn visit_all(&mut self, statements: &[Stmt], caller: &str) {
+        for statement in statements {
+            self.visit_stmt(statement, caller);
+        }
+    }
+    
+    fn visit_expr(&mut self, expr: &Expr, caller: &str) {
+        match expr {
+            // Calls and functions passed by name both land here through the callee
+            Expr::Variable(name) => self.call(caller, qualified(&self.namespace, name)),
+            // `utils.shout(...)` calls into a file pulled `as utils`
+            Expr::Get { object, name, .. } => {
+                let callee = match object.as_ref() {
+                    Expr::Variable(namespace) => Some(qualified(&qualified(&self.namespace, namespace), name)),
+                    _ => None,
+                };
+                match callee {
+                    Some(callee) if self.functions.contains(&callee) => self.call(caller, callee),
+                    _ => self.visit_expr(object, caller),
+                }
+            }
+            Expr::Call { callee, arguments, .. } => {
+                self.visit_expr(callee, caller);
+                for argument in arguments {
+                    self.visit_expr(argument, caller);
+                }
+            }
+            Expr::Binary { left, right, .. } => {
+                self.visit_expr(left, caller);
+                self.visit_expr(right, caller);
+            }
+            Expr::Index { object, index, .. } | Expr::Set { object, value: index, .. } | Expr::Range { start: object, end: index, .. } => {
+                self.visit_expr(object, caller);
+                self.visit_expr(index, caller);
+            }
+            Expr::Unary { right: value, .. } | Expr::Assign { value, .. } | Expr::Propagate { value, .. } => self.visit_expr(value, caller),
+            Expr::Spread(object) => self.visit_expr(object, caller),
+            Expr::Conditional { condition, then_branch, else_branch } => {
+                self.visit_expr(condition, caller);
+                self.visit_expr(then_branch, caller);
+                self.visit_expr(else_branch, caller);
+            }
+            Expr::Array(items) => {
+                for item in items {
+                    self.visit_expr(item, caller);
+                }
+            }
+            Expr::Object(fields) => {
+                for (_, value) in fields {
+                    self.visit_expr(value, caller);
+                }
+            }
+            // Calls a lambda makes count for the function it's written in
+            Expr::Function { body, .. } => self.visit_all(body, caller),
+            Expr::Literal(_) => {}
+        }
+    }
+    
+    fn call(&mut self, caller: &str, callee: String) {
+        if self.functions.contains(&callee) && !self.calls.iter().any(|(from, to)| from == caller && *to == callee) {
+            self.calls.push((caller.to_string(), callee));
+        }
+    }
+}
+
+/// `name` as it's reached from outside a `pull "..." as namespace` file
+fn qualified(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn collect_functions(statements: &[Stmt], namespace: &str, functions: &mut Vec<String>) {
+    for statement in statements {
+        if let Stmt::Function { name, body, .. } = statement {
+            let name = qualified(namespace, name);
+            if !functions.contains(&name) {
+                functions.push(name);
+            }
+            collect_functions(body, namespace, functions);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! ZLang Graphs - `graphNew()`, `addEdge()`, `bfs()` and friends, for the classic graph exercises 🕸️
+//! A graph is a plain object - {directed, edges} with each node's neighbors in the order they were added.
+//! Not to be confused with graph.rs, which draws who-calls-whom for `zlang graph`
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::ast::{Literal, TypeAnnotation};
+use crate::error::ZLangError;
+
+/// A graph taken out of its object, neighbors by node
+struct Graph {
+    directed: bool,
+    edges: HashMap<String, Vec<String>>,
+}
+
+impl Graph {
+    fn read(builtin: &str, value: &Literal) -> Result<Self, ZLangError> {
+        let not_a_graph = || ZLangError::new(&format!("{} wants a graph from graphNew(), not a {} 🕸️", builtin, TypeAnnotation::of(value)));
+        let Literal::Object(fields) = value else {
+            return Err(not_a_graph());
+        };
+        let (Some(Literal::Boolean(directed)), Some(Literal::Object(edges))) = (fields.get("directed"), fields.get("edges")) else {
+            return Err(not_a_graph());
+        };
+        
+        let mut neighbors = HashMap::new();
+        for (node, next) in edges {
+            let Literal::Array(next) = next else {
+                return Err(not_a_graph());
+            };
+            let next = next.iter()
+                .map(|item| match item {
+                    Literal::String(name) => Ok(name.clone()),
+                    _ => Err(not_a_graph()),
+                })
+                .collect::<Result<Vec<String>, ZLangError>>()?;
+            neighbors.insert(node.clone(), next);
+        }
+        Ok(Self { directed: *directed, edges: neighbors })
+    }
+    
+    fn into_literal(self) -> Literal {
+        let edges = self.edges.into_iter()
+            .map(|(node, next)| (node, names(next)))
+            .collect();
+        Literal::Object(HashMap::from([
+            ("directed".to_string(), Literal::Boolean(self.directed)),
+            ("edges".to_string(), Literal::Object(edges)),
+        ]))
+    }
+    
+    /// Where edges from `node` lead - nothing for a node only a hand-made graph could leave out
+    fn next(&self, node: &str) -> &[String] {
+        self.edges.get(node).map_or(&[], Vec::as_slice)
+    }
+    
+    fn neighbors(&self, builtin: &str, node: &str) -> Result<&[String], ZLangError> {
+        self.edges.get(node)
+            .map(Vec::as_slice)
+            .ok_or_else(|| ZLangError::new(&format!("{} can't find '{}', it isn't in the graph 🕸️", builtin, node)))
+    }
+}
+
+/// An empty graph - edges only go one way in a directed one
+pub fn new(directed: bool) -> Literal {
+    Graph { directed, edges: HashMap::new() }.into_literal()
+}
+
+/// `graph` with an edge from `from` to `to` (and back, unless it's directed). Adding an edge
+/// that's already there changes nothing
+pub fn add_edge(graph: &Literal, from: &str, to: &str) -> Result<Literal, ZLangError> {
+    let mut graph = Graph::read("addEdge", graph)?;
+    let mut link = |a: &str, b: &str| {
+        let next = graph.edges.entry(a.to_string()).or_default();
+        if !next.iter().any(|node| node == b) {
+            next.push(b.to_string());
+        }
+    };
+    link(from, to);
+    if graph.directed {
+        graph.edges.entry(to.to_string()).or_default();
+    } else {
+        link(to, from);
+    }
+    Ok(graph.into_literal())
+}
+
+/// The nodes an edge leads to from `node`
+pub fn neighbors(graph: &Literal, node: &str) -> Result<Literal, ZLangError> {
+    let graph = Graph::read("neighbors", graph)?;
+    Ok(names(graph.neighbors("neighbors", node)?.to_vec()))
+}
+
+/// Every node reachable from `start`, closest first
+pub fn bfs(graph: &Literal, start: &str) -> Result<Literal, ZLangError> {
+    let graph = Graph::read("bfs", graph)?;
+    graph.neighbors("bfs", start)?;
+    
+    let mut visited = vec![start.to_string()];
+    let mut seen: HashSet<&str> = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.next(node) {
+            if seen.insert(next) {
+                visited.push(next.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(names(visited))
+}
+
+/// Every node reachable from `start`, following each path as deep as it goes before backing up -
+/// the order a recursive search trying neighbors oldest first would visit them in
+pub fn dfs(graph: &Literal, start: &str) -> Result<Literal, ZLangError> {
+    let graph = Graph::read("dfs", graph)?;
+    graph.neighbors("dfs", start)?;
+    
+    let mut visited = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node) {
+            continue;
+        }
+        visited.push(node.to_string());
+        // Pushed backwards so the oldest neighbor comes off the stack first
+        stack.extend(graph.next(node).iter().rev().map(String::as_str).filter(|next| !seen.contains(next)));
+    }
+    Ok(names(visited))
+}
+
+/// The fewest-edges path from `from` to `to`, both ends included - nil when there's no way there
+pub fn shortest_path(graph: &Literal, from: &str, to: &str) -> Result<Literal, ZLangError> {
+    let graph = Graph::read("shortestPath", graph)?;
+    graph.neighbors("shortestPath", from)?;
+    graph.neighbors("shortestPath", to)?;
+    
+    let mut came_from: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to.to_string()];
+            let mut step = to;
+            while let Some(&previous) = came_from.get(step) {
+                path.push(previous.to_string());
+                step = previous;
+            }
+            path.reverse();
+            return Ok(names(path));
+        }
+        for next in graph.next(node) {
+            if next != from && !came_from.contains_key(next.as_str()) {
+                came_from.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(Literal::Nil)
+}
+
+fn names(nodes: Vec<String>) -> Literal {
+    Literal::Array(nodes.into_iter().map(Literal::String).collect())
+}
@@ -0,0 +1,120 @@
+//! ZLang Heap - `heapNew()`, `heapPush()` and `heapPop()`, the smallest priority always comes out first 🏔️
+//! Unlike an array a heap is shared rather than copied - every variable holding one sees the same
+//! items, so a push or pop costs log n instead of copying the whole thing
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::ast::Literal;
+
+#[derive(Clone, Default)]
+pub struct Heap {
+    items: Arc<Mutex<Items>>,
+}
+
+#[derive(Default)]
+struct Items {
+    entries: BinaryHeap<Entry>,
+    // Counts every push, so equal priorities come out in the order they went in
+    pushed: u64,
+}
+
+struct Entry {
+    priority: f64,
+    order: u64,
+    value: Literal,
+}
+
+// BinaryHeap hands back its biggest entry, so the smallest priority (then the oldest) counts as biggest
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority).then(other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+/// Everything a heap held at one moment, for `Heap::refill` to put back
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contents {
+    // (priority, order, value), in the order they went in
+    entries: Vec<(f64, u64, Literal)>,
+    pushed: u64,
+}
+
+// A heap can hold itself, so neither of these looks at what's inside
+impl std::fmt::Debug for Heap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Heap({} items)", self.len())
+    }
+}
+
+impl PartialEq for Heap {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl Heap {
+    pub fn push(&self, value: Literal, priority: f64) {
+        let mut items = self.lock();
+        let order = items.pushed;
+        items.pushed += 1;
+        items.entries.push(Entry { priority, order, value });
+    }
+    
+    /// The value with the smallest priority, taken out - None once the heap's empty
+    pub fn pop(&self) -> Option<Literal> {
+        self.lock().entries.pop().map(|entry| entry.value)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Every value in the heap, in no particular order
+    pub fn values(&self) -> Vec<Literal> {
+        self.lock().entries.iter().map(|entry| entry.value.clone()).collect()
+    }
+    
+    /// What's in the heap right now, the order things went in included
+    pub fn contents(&self) -> Contents {
+        let items = self.lock();
+        let mut entries: Vec<_> = items.entries.iter().map(|entry| (entry.priority, entry.order, entry.value.clone())).collect();
+        entries.sort_by_key(|(_, order, _)| *order);
+        Contents { entries, pushed: items.pushed }
+    }
+    
+    /// Swap everything inside for what `contents` took - how undoing puts a heap back
+    pub fn refill(&self, contents: Contents) {
+        let mut items = self.lock();
+        items.entries = contents.entries.into_iter().map(|(priority, order, value)| Entry { priority, order, value }).collect();
+        items.pushed = contents.pushed;
+    }
+    
+    /// Tells heaps apart without looking inside, for walks that could loop back to the same one
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.items) as usize
+    }
+    
+    fn lock(&self) -> MutexGuard<'_, Items> {
+        self.items.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,174 @@
+//! ZLang Highlighter - Giving the REPL some color 🎨
+//! Driven by the real lexer, so the colors never disagree with how ZLang reads your code
+
+use crate::lexer::Lexer;
+use crate::token::{Token, TokenType};
+
+const RESET: &str = "\x1b[0m";
+
+/// The ANSI color for each kind of token, an empty string leaves it uncolored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub keyword: &'static str,
+    pub boolean: &'static str,
+    pub string: &'static str,
+    pub number: &'static str,
+    pub comment: &'static str,
+    pub error: &'static str,
+    pub matching_bracket: &'static str,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        keyword: "\x1b[1;35m",
+        boolean: "\x1b[36m",
+        string: "\x1b[32m",
+        number: "\x1b[33m",
+        comment: "\x1b[90m",
+        error: "\x1b[4;31m",
+        matching_bracket: "\x1b[1;34m",
+    };
+    
+    /// Cool tones only, easier on dark blue terminals
+    pub const OCEAN: Theme = Theme {
+        keyword: "\x1b[1;34m",
+        boolean: "\x1b[96m",
+        string: "\x1b[36m",
+        number: "\x1b[94m",
+        comment: "\x1b[2;37m",
+        error: "\x1b[4;91m",
+        matching_bracket: "\x1b[1;7m",
+    };
+    
+    /// No colors at all, brackets still flash so you can find your partner
+    pub const MONO: Theme = Theme {
+        keyword: "",
+        boolean: "",
+        string: "",
+        number: "",
+        comment: "",
+        error: "\x1b[4m",
+        matching_bracket: "\x1b[7m",
+    };
+    
+    pub const NAMES: [&'static str; 3] = ["default", "ocean", "mono"];
+    
+    pub fn named(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::DEFAULT),
+            "ocean" => Some(Theme::OCEAN),
+            "mono" => Some(Theme::MONO),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DEFAULT
+    }
+}
+
+/// Color a line of ZLang with ANSI escapes. `flash` is the byte offset of a bracket
+/// to light up, usually whatever `matching_bracket` found for the cursor
+pub fn highlight(line: &str, flash: Option<usize>, theme: &Theme) -> String {
+    let offsets = byte_offsets(line);
+    let mut output = String::new();
+    
+    // With trivia and recovery every char of the line belongs to some token, comments and typos included
+    for token in Lexer::new(line).with_trivia().with_recovery().tokenize_partial() {
+        let start = offsets[token.span.start];
+        let end = offsets[token.span.end];
+        
+        let text = &line[start..end];
+        match &token.token_type {
+            _ if flash == Some(start) => paint(&mut output, theme.matching_bracket, text),
+            // A string you're still typing should still look like one
+            TokenType::Error(_) if end == line.len() && text.starts_with(['"', 'r']) => paint(&mut output, theme.string, text),
+            token_type => match color_of(token_type, theme) {
+                Some(color) => paint(&mut output, color, text),
+                None => output.push_str(text),
+            },
+        }
+    }
+    
+    output
+}
+
+/// Byte offset of the bracket matching the one under (or just before) the cursor.
+/// Brackets inside strings and comments never count, the lexer doesn't see them, and a typo
+/// earlier on the line doesn't stop the search
+pub fn matching_bracket(line: &str, cursor: usize) -> Option<usize> {
+    let offsets = byte_offsets(line);
+    let tokens = Lexer::new(line).with_recovery().tokenize_partial();
+    let is_bracket = |token: &Token| partner_of(&token.token_type).is_some();
+    
+    let index = tokens.iter()
+        .position(|token| offsets[token.span.start] == cursor && is_bracket(token))
+        .or_else(|| tokens.iter().position(|token| offsets[token.span.end] == cursor && is_bracket(token)))?;
+    
+    let bracket = &tokens[index].token_type;
+    let (partner, forward) = partner_of(bracket)?;
+    let candidates: Box<dyn Iterator<Item = &Token>> = if forward {
+        Box::new(tokens[index..].iter())
+    } else {
+        Box::new(tokens[..=index].iter().rev())
+    };
+    
+    // `?[` opens like `[` does
+    let opening = |token_type: &TokenType| if *token_type == TokenType::QuestionBracket { TokenType::LeftBracket } else { token_type.clone() };
+    let (bracket, partner) = (opening(bracket), opening(&partner));
+    let mut depth = 0;
+    for token in candidates {
+        let token_type = opening(&token.token_type);
+        if token_type == bracket {
+            depth += 1;
+        } else if token_type == partner {
+            depth -= 1;
+            if depth == 0 {
+                return Some(offsets[token.span.start]);
+            }
+        }
+    }
+    None
+}
+
+fn color_of(token_type: &TokenType, theme: &Theme) -> Option<&'static str> {
+    match token_type {
+        TokenType::Fr | TokenType::Cap => Some(theme.boolean),
+        TokenType::String(_) => Some(theme.string),
+        TokenType::Number(_) => Some(theme.number),
+        TokenType::Comment(_) => Some(theme.comment),
+        TokenType::Error(_) => Some(theme.error),
+        other if other.keyword().is_some() => Some(theme.keyword),
+        _ => None,
+    }
+}
+
+/// The other half of a bracket, and whether it comes after this one
+fn partner_of(token_type: &TokenType) -> Option<(TokenType, bool)> {
+    match token_type {
+        TokenType::LeftParen => Some((TokenType::RightParen, true)),
+        TokenType::LeftBrace => Some((TokenType::RightBrace, true)),
+        TokenType::LeftBracket | TokenType::QuestionBracket => Some((TokenType::RightBracket, true)),
+        TokenType::RightParen => Some((TokenType::LeftParen, false)),
+        TokenType::RightBrace => Some((TokenType::LeftBrace, false)),
+        TokenType::RightBracket => Some((TokenType::LeftBracket, false)),
+        _ => None,
+    }
+}
+
+fn paint(output: &mut String, color: &str, text: &str) {
+    if color.is_empty() {
+        output.push_str(text);
+        return;
+    }
+    output.push_str(color);
+    output.push_str(text);
+    output.push_str(RESET);
+}
+
+/// Token spans count chars, the line editor counts bytes - index by char, get the byte
+fn byte_offsets(line: &str) -> Vec<usize> {
+    line.char_indices().map(|(offset, _)| offset).chain(std::iter::once(line.len())).collect()
+}
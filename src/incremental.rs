@@ -0,0 +1,280 @@
+//! ZLang Incremental Parsing - Only reparse the bit you actually touched ✏️
+//! Coarse on purpose: top-level statements the edit can't reach are kept, everything else gets parsed again
+
+use std::ops::Range;
+use std::sync::Arc;
+use crate::ast::{Expr, Stmt};
+use crate::error::ZLangError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+/// A parsed file that remembers where each top-level statement came from
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub source: String,
+    pub statements: Vec<Stmt>,
+    /// Char range of each top-level statement in `source`
+    pub spans: Vec<Range<usize>>,
+    /// Statements parsed fresh by the last parse - the only ones an editor needs to re-check
+    pub fresh: Range<usize>,
+}
+
+/// Replace the chars in `range` of the old source with `text`
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+pub fn parse(source: &str) -> Result<Program, ZLangError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let (statements, spans): (Vec<Stmt>, Vec<Range<usize>>) =
+        Parser::new(tokens).parse_with_spans()?.into_iter().unzip();
+    
+    Ok(Program {
+        source: source.to_string(),
+        fresh: 0..statements.len(),
+        statements,
+        spans,
+    })
+}
+
+/// Apply `edit` to `old` and parse only the top-level statements it could have changed.
+/// Anything that might parse differently in context falls back to a full `parse`,
+/// so the result (and any error) always matches parsing the edited source from scratch
+pub fn reparse(old: &Program, edit: &Edit) -> Result<Program, ZLangError> {
+    let old_chars: Vec<char> = old.source.chars().collect();
+    if edit.range.start > edit.range.end || edit.range.end > old_chars.len() {
+        return Err(ZLangError::new(&format!(
+            "Edit {}..{} doesn't fit in a {} char file bestie 📏",
+            edit.range.start, edit.range.end, old_chars.len()
+        )));
+    }
+    
+    let chars: Vec<char> = old_chars[..edit.range.start].iter()
+        .copied()
+        .chain(edit.text.chars())
+        .chain(old_chars[edit.range.end..].iter().copied())
+        .collect();
+    let source: String = chars.iter().collect();
+    // A meme changes how everything below it parses, so no part of the file parses on its own
+    if old.source.contains("meme") || source.contains("meme") {
+        return parse(&source);
+    }
+    
+    let removed = &old_chars[edit.range.clone()];
+    let char_delta = edit.text.chars().count() as isize - removed.len() as isize;
+    let line_delta = edit.text.matches('\n').count() as isize - removed.iter().filter(|c| **c == '\n').count() as isize;
+    let moved = |offset: usize| (offset as isize + char_delta) as usize;
+    
+    // Statements that end before the edit stay exactly as they are...
+    let mut kept_before = old.spans.iter().take_while(|span| span.end <= edit.range.start).count();
+    while kept_before > 0 && !is_boundary(&chars, old.spans[kept_before - 1].end) {
+        kept_before -= 1;
+    }
+    
+    // ...and statements that start after it just move
+    let mut kept_after = old.spans.iter()
+        .position(|span| span.start > edit.range.end)
+        .unwrap_or(old.spans.len())
+        .max(kept_before);
+    while kept_after < old.spans.len() && !is_boundary(&chars, moved(old.spans[kept_after].start)) {
+        kept_after += 1;
+    }
+    
+    let start = if kept_before > 0 { old.spans[kept_before - 1].end } else { 0 };
+    let end = if kept_after < old.spans.len() { moved(old.spans[kept_after].start) } else { chars.len() };
+    
+    let middle: String = chars[start..end].iter().collect();
+    let first_line = 1 + chars[..start].iter().filter(|c| **c == '\n').count();
+    let reparsed = Lexer::new(&middle)
+        .starting_at_line(first_line)
+        .tokenize()
+        .and_then(|tokens| Parser::new(tokens).parse_with_spans());
+    
+    // The middle doesn't parse on its own - let a full parse find the real problem
+    let Ok(reparsed) = reparsed else {
+        return parse(&source);
+    };
+    
+    let mut statements = old.statements[..kept_before].to_vec();
+    let mut spans = old.spans[..kept_before].to_vec();
+    
+    for (statement, span) in reparsed {
+        statements.push(statement);
+        spans.push(span.start + start..span.end + start);
+    }
+    let fresh = kept_before..statements.len();
+    
+    for (statement, span) in old.statements[kept_after..].iter().zip(&old.spans[kept_after..]) {
+        let mut statement = statement.clone();
+        shift_lines(&mut statement, line_delta);
+        statements.push(statement);
+        spans.push(moved(span.start)..moved(span.end));
+    }
+    
+    Ok(Program { source, statements, spans, fresh })
+}
+
+/// Can parsing stop and start again at `at` without changing what either side means?
+/// True after a newline, or a `;` or `}` with no comment before it on the line, unless
+/// the words around a newline would lex as one keyword (`vibe` + `check`, `lowkey` + `sus`)
+fn is_boundary(chars: &[char], at: usize) -> bool {
+    let mut before = at;
+    while before > 0 && matches!(chars[before - 1], ' ' | '\t' | '\r') {
+        before -= 1;
+    }
+    if before == 0 {
+        return true;
+    }
+    
+    match chars[before - 1] {
+        // Unless the edit just turned the rest of the line into a comment
+        ';' | '}' => {
+            let line_start = chars[..before].iter().rposition(|c| *c == '\n').map_or(0, |newline| newline + 1);
+            !chars[line_start..before].windows(2).any(|pair| pair == ['/', '/'])
+        }
+        '\n' => {
+            let mut word_end = before - 1;
+            while word_end > 0 && chars[word_end - 1].is_whitespace() {
+                word_end -= 1;
+            }
+            let mut word_start = word_end;
+            while word_start > 0 && (chars[word_start - 1].is_alphanumeric() || chars[word_start - 1] == '_') {
+                word_start -= 1;
+            }
+            let word: String = chars[word_start..word_end].iter().collect();
+            !matches!(word.as_str(), "lowkey" | "no" | "vibe")
+        }
+        _ => false,
+    }
+}
+
+/// Kept statements after the edit moved up or down by `delta` lines
+fn shift_lines(statement: &mut Stmt, delta: isize) {
+    if delta == 0 {
+        return;
+    }
+    
+    match statement {
+        Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) => shift_expr_lines(expr, delta),
+        Stmt::VarDeclaration { initializer, .. } => {
+            if let Some(expr) = initializer {
+                shift_expr_lines(expr, delta);
+            }
+        }
+        Stmt::Return(value) => {
+            if let Some(expr) = value {
+                shift_expr_lines(expr, delta);
+            }
+        }
+        Stmt::Block(body) | Stmt::Function { body, .. } | Stmt::Squad { body, .. } => shift_all(body, delta),
+        Stmt::If { condition, then_branch, else_branch } => {
+            shift_expr_lines(condition, delta);
+            shift_lines(then_branch, delta);
+            if let Some(else_branch) = else_branch {
+                shift_lines(else_branch, delta);
+            }
+        }
+        Stmt::While { condition, body, else_branch } => {
+            shift_expr_lines(condition, delta);
+            shift_lines(body, delta);
+            if let Some(else_branch) = else_branch {
+                shift_lines(else_branch, delta);
+            }
+        }
+        Stmt::For { iterable, body, else_branch, .. } => {
+            shift_expr_lines(iterable, delta);
+            shift_lines(body, delta);
+            if let Some(else_branch) = else_branch {
+                shift_lines(else_branch, delta);
+            }
+        }
+        Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+            if let Some(initializer) = initializer {
+                shift_lines(initializer, delta);
+            }
+            for expr in condition.iter_mut().chain(increment) {
+                shift_expr_lines(expr, delta);
+            }
+            shift_lines(body, delta);
+            if let Some(else_branch) = else_branch {
+                shift_lines(else_branch, delta);
+            }
+        }
+        Stmt::Switch { expr, cases, default } => {
+            shift_expr_lines(expr, delta);
+            for case in cases {
+                case.exprs_mut().for_each(|value| shift_expr_lines(value, delta));
+                shift_all(&mut case.body, delta);
+            }
+            if let Some(body) = default {
+                shift_all(body, delta);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            shift_all(try_block, delta);
+            for clause in catch_clauses {
+                shift_all(&mut clause.body, delta);
+            }
+            if let Some(body) = finally_block {
+                shift_all(body, delta);
+            }
+        }
+        Stmt::Needs { condition, line, .. } => {
+            shift_expr_lines(condition, delta);
+            *line = (*line as isize + delta) as usize;
+        }
+        Stmt::Pull { line, .. } => *line = (*line as isize + delta) as usize,
+        Stmt::DramaType { .. } | Stmt::Break | Stmt::Continue | Stmt::Global(_) | Stmt::Outer(_) => {}
+    }
+}
+
+fn shift_all(statements: &mut [Stmt], delta: isize) {
+    for statement in statements {
+        shift_lines(statement, delta);
+    }
+}
+
+fn shift_expr_lines(expr: &mut Expr, delta: isize) {
+    match expr {
+        Expr::Call { callee, arguments, line } => {
+            *line = (*line as isize + delta) as usize;
+            shift_expr_lines(callee, delta);
+            for argument in arguments {
+                shift_expr_lines(argument, delta);
+            }
+        }
+        Expr::Propagate { value, line } => {
+            *line = (*line as isize + delta) as usize;
+            shift_expr_lines(value, delta);
+        }
+        Expr::Binary { left, right, .. } => {
+            shift_expr_lines(left, delta);
+            shift_expr_lines(right, delta);
+        }
+        Expr::Index { object, index, .. } | Expr::Set { object, value: index, .. } | Expr::Range { start: object, end: index, .. } => {
+            shift_expr_lines(object, delta);
+            shift_expr_lines(index, delta);
+        }
+        Expr::Unary { right: value, .. } | Expr::Assign { value, .. } => shift_expr_lines(value, delta),
+        Expr::Get { object, .. } | Expr::Spread(object) => shift_expr_lines(object, delta),
+        Expr::Conditional { condition, then_branch, else_branch } => {
+            shift_expr_lines(condition, delta);
+            shift_expr_lines(then_branch, delta);
+            shift_expr_lines(else_branch, delta);
+        }
+        Expr::Function { body, .. } => shift_all(Arc::make_mut(body).as_mut_slice(), delta),
+        Expr::Array(items) => {
+            for item in items {
+                shift_expr_lines(item, delta);
+            }
+        }
+        Expr::Object(fields) => {
+            for (_, value) in fields {
+                shift_expr_lines(value, delta);
+            }
+        }
+        Expr::Literal(_) | Expr::Variable(_) => {}
+    }
+}
@@ -1,24 +1,230 @@
 //! ZLang Interpreter - Executes the Abstract Syntax Tree
 //! This is where the magic happens and code actually runs! ✨
 
-use std::collections::HashMap;
-use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Literal};
-use crate::environment::Environment;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::ansi;
+use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Literal, TypeAnnotation, CasePattern};
+pub use crate::ast::Function;
+use crate::environment::{Closure, Environment};
+use crate::console::{self, Console};
+use crate::draw::{self, Shape};
+use crate::graphs;
+use crate::deque::{self, Deque};
+use crate::heap::{self, Heap};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::TokenType;
 use crate::error::ZLangError;
+use crate::markdown;
+use crate::memes::Meme;
+use crate::memory::MemStats;
+use crate::messages;
+use crate::fetch;
+use crate::modules::{self, DiskFiles, FileProvider, Modules, Pulled};
+use crate::money;
+use crate::pragmas::{Pragmas, Rule};
+use crate::similar;
+use crate::squad::{Instance, Squad};
+use crate::syntax;
+use crate::stash::{self, MemoryStash, Stash};
+use crate::steps::{SpanIndex, Tracer};
+use crate::timers::Timers;
+use crate::replay::Tape;
+use crate::rng::Rng;
+use crate::url;
 
-#[derive(Debug, Clone)]
-pub struct Function {
-    pub name: String,
-    pub params: Vec<String>,
-    pub body: Vec<Stmt>,
+/// Every builtin function's name, for "did you mean" hints - keep in step with the match in evaluate_expr
+pub const BUILTINS: &[&str] = &[
+    "sqrt", "abs", "num", "random", "spill", "length", "uppercase", "split", "inspect", "ok", "nah", "attempt",
+    "sum", "avg", "median", "stddev", "minOf", "maxOf", "money", "divmod", "sortBy", "sortDesc", "reversed", "minBy",
+    "maxBy", "groupBy", "countBy", "unique", "levenshtein", "similarity", "markdown", "parseUrl", "buildUrl",
+    "colored", "bold", "stash", "unstash", "draw.line", "draw.circle", "draw.text", "draw.clear", "sleep", "scope",
+    "expect", "after", "every", "cancelTimer", "runLoop", "gameLoop", "onKey", "memStats", "opsSoFar", "benchmark", "seed", "simulate", "graphNew", "addEdge",
+    "neighbors", "bfs", "dfs", "shortestPath", "heapNew", "heapPush", "heapPop", "heapSize",
+    "queue", "stack", "push", "pop", "peek", "size", "tokenize", "parseCode", "runAst",
+];
+
+/// How many nested function calls we allow before calling it infinite recursion
+pub const MAX_CALL_DEPTH: usize = 1000;
+
+/// Longest one `sleep()` may nap, in milliseconds
+pub const MAX_SLEEP_MS: f64 = 10_000.0;
+
+/// Fastest `gameLoop()` runs, in frames per second
+pub const MAX_FPS: f64 = 120.0;
+
+/// Most times one `benchmark()` calls its function
+pub const MAX_BENCHMARK_ITERATIONS: f64 = 100_000.0;
+
+/// Most runs one `simulate()` does
+pub const MAX_SIMULATIONS: f64 = 100_000.0;
+
+/// Most numbers one range counts through
+pub const MAX_RANGE: f64 = 1_000_000.0;
+
+/// Stack size for threads that run the interpreter, roomy enough for MAX_CALL_DEPTH calls
+pub const INTERPRETER_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// What a statement tells the code around it to do next
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlFlow {
+    Normal,
+    Break,
+    Continue,
+    Return(Literal),
+}
+
+/// What running a program produced, kept apart so each front end can show it its own way
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionResult {
+    /// Lines printed with `bruh`/`bruh!`, in order
+    pub output: Vec<String>,
+    /// Value of the program's last statement when that's a bare expression (not an
+    /// assignment) - what a REPL echoes back. None if it came out nil
+    pub value: Option<Literal>,
+    /// Runtime warnings like shadowing
+    pub warnings: Vec<String>,
+    /// Shapes queued with `draw.line(...)` and friends, in order, for the playground canvas
+    pub drawing: Vec<Shape>,
+}
+
+/// Everything a program can change: variables, functions, drama types, memes and the files it
+/// pulled, and what's inside its queues, stacks, heaps and squad instances. Restoring one rewinds
+/// the interpreter to when it was taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    environment: Environment,
+    functions: HashMap<String, Function>,
+    drama_types: HashMap<String, Option<String>>,
+    modules: Pulled,
+    memes: HashMap<String, Meme>,
+    // Copying the variables doesn't copy a queue, stack, heap or instance, they're shared - so what each
+    // one held is kept here, by id, and goes back into the very same one on restore
+    held: HashMap<usize, Held>,
+}
+
+/// What one shared value held when a snapshot was taken
+#[derive(Debug, Clone, PartialEq)]
+enum Held {
+    Deque(Deque, Vec<Literal>),
+    Heap(Heap, heap::Contents),
+    Instance(Instance, Vec<(String, Literal)>),
+}
+
+impl Held {
+    fn put_back(self) {
+        match self {
+            Held::Deque(deque, values) => deque.refill(values),
+            Held::Heap(heap, contents) => heap.refill(contents),
+            Held::Instance(instance, fields) => {
+                for (name, value) in fields {
+                    instance.set_field(&name, value);
+                }
+            }
+        }
+    }
+}
+
+/// Note what every queue, stack, heap and instance inside `value` holds, each one once.
+/// `seen` has the ones already noted, and the closure scopes already looked in
+fn hold(value: &Literal, held: &mut HashMap<usize, Held>, seen: &mut HashSet<usize>) {
+    match value {
+        Literal::Array(items) => items.iter().for_each(|item| hold(item, held, seen)),
+        Literal::Object(fields) => fields.values().for_each(|field| hold(field, held, seen)),
+        Literal::Function(function) => {
+            if let Some(closure) = &function.closure {
+                closure.values(seen).iter().for_each(|value| hold(value, held, seen));
+            }
+        }
+        Literal::Deque(deque) if seen.insert(deque.id()) => {
+            let values = deque.values();
+            values.iter().for_each(|value| hold(value, held, seen));
+            held.insert(deque.id(), Held::Deque(deque.clone(), values));
+        }
+        Literal::Heap(heap) if seen.insert(heap.id()) => {
+            heap.values().iter().for_each(|value| hold(value, held, seen));
+            held.insert(heap.id(), Held::Heap(heap.clone(), heap.contents()));
+        }
+        Literal::Instance(instance) if seen.insert(instance.id()) => {
+            let fields: Vec<(String, Literal)> = instance.squad().fields.iter()
+                .filter_map(|(name, _)| Some((name.clone(), instance.field(name)?)))
+                .collect();
+            fields.iter().for_each(|(_, value)| hold(value, held, seen));
+            held.insert(instance.id(), Held::Instance(instance.clone(), fields));
+        }
+        _ => {}
+    }
 }
 
 pub struct Interpreter {
     environment: Environment,
     functions: HashMap<String, Function>,
-    return_value: Option<Literal>,
-    should_break: bool,
-    should_continue: bool,
+    // Declared drama types and their parent (None means it extends the root `Drama`)
+    drama_types: HashMap<String, Option<String>>,
+    output: Vec<String>,
+    call_depth: usize,
+    warnings: Vec<String>,
+    tracer: Option<Tracer>,
+    // Value of the most recent expression statement, for ExecutionResult::value
+    last_value: Option<Literal>,
+    console: Option<Box<dyn Console>>,
+    capabilities: Capabilities,
+    // Set from outside (Ctrl+C) to stop the run before its next statement
+    interrupt: Option<Arc<AtomicBool>>,
+    spans: Option<SpanIndex>,
+    // Span of the statement an interrupt stopped the run at
+    stopped_at: Option<Range<usize>>,
+    stash: Box<dyn Stash>,
+    drawing: Vec<Shape>,
+    // The canvas changed since the console last saw it
+    drawing_changed: bool,
+    timers: Timers,
+    // runLoop() is driving the timers, a callback can't start a second one
+    running_loop: bool,
+    // Set by onKey(), gets each key the console passes along while the program pauses
+    key_handler: Option<Function>,
+    // Records or replays random(), spill() and the game clock
+    tape: Option<Tape>,
+    // Statements run plus expressions evaluated, what opsSoFar() reports
+    ops: u64,
+    // Where random() gets its numbers, seed() restarts it
+    rng: Rng,
+    // The files `pull` has run and the ones it's in the middle of
+    modules: Modules,
+    // `// zlang-ignore` comments in the program, whose warnings stay quiet
+    pragmas: Pragmas,
+    // Memes earlier source declared, for the next REPL line to parse with
+    memes: HashMap<String, Meme>,
+}
+
+/// What a program may reach outside of itself for. Everything's allowed unless a host switches it off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    /// `spill()` reading a line of input
+    pub input: bool,
+    /// `pull "file"` reading another file
+    pub pull: bool,
+    /// `pull "https://..."` fetching a file off a link
+    pub fetch: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self { input: true, pull: true, fetch: true }
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
@@ -26,64 +232,277 @@ impl Interpreter {
         Self {
             environment: Environment::new(),
             functions: HashMap::new(),
-            return_value: None,
-            should_break: false,
-            should_continue: false,
+            drama_types: HashMap::new(),
+            output: Vec::new(),
+            call_depth: 0,
+            warnings: Vec::new(),
+            tracer: None,
+            last_value: None,
+            console: None,
+            capabilities: Capabilities::default(),
+            interrupt: None,
+            spans: None,
+            stopped_at: None,
+            stash: Box::new(MemoryStash::default()),
+            drawing: Vec::new(),
+            drawing_changed: false,
+            timers: Timers::default(),
+            running_loop: false,
+            key_handler: None,
+            tape: None,
+            ops: 0,
+            rng: Rng::default(),
+            modules: Modules::default(),
+            pragmas: Pragmas::default(),
+            memes: HashMap::new(),
+        }
+    }
+    
+    /// Record every statement this interpreter runs, for step-through mode
+    pub fn with_tracer(mut self, tracer: Tracer) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+    
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.tracer.take()
+    }
+    
+    /// Stream prints to `console` as they happen (they no longer show up in
+    /// ExecutionResult::output) and read `spill()` input from it
+    pub fn with_console(mut self, console: Box<dyn Console>) -> Self {
+        self.console = Some(console);
+        self
+    }
+    
+    /// Keep `stash()` values in `stash` instead of memory that goes away with the interpreter
+    pub fn with_stash(mut self, stash: Box<dyn Stash>) -> Self {
+        self.stash = stash;
+        self
+    }
+    
+    /// The program being run is `file`, so `pull "file"` finds files next to it
+    pub fn with_program_file(mut self, file: &Path) -> Self {
+        self.modules = Modules::for_program(file, Arc::new(DiskFiles));
+        self
+    }
+    
+    /// Like `with_program_file`, with `file` and everything it pulls read from `files` instead of the disk
+    pub fn with_program_files(mut self, file: &Path, files: Arc<dyn FileProvider>) -> Self {
+        self.modules = Modules::for_program(file, files);
+        self
+    }
+    
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+    
+    /// Stop with an error before the next statement once `flag` is set. The host clears it again
+    pub fn with_interrupt(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.interrupt = Some(flag);
+        self
+    }
+    
+    /// Write every random(), spill() and clock reading down on `tape`, or take them off it
+    /// when it's replaying a recorded run
+    pub fn with_tape(mut self, tape: Tape) -> Self {
+        self.tape = Some(tape);
+        self
+    }
+    
+    /// Remember where the statements about to run came from, so an interrupt can say
+    /// where it landed. Replaces whatever was tracked for the previous run
+    pub fn track_spans(&mut self, spans: SpanIndex) {
+        self.spans = Some(spans);
+        self.stopped_at = None;
+    }
+    
+    /// Keep quiet about the warnings the source about to run asks to with `// zlang-ignore`.
+    /// Replaces whatever the previous run's source asked for
+    pub fn follow_pragmas(&mut self, pragmas: Pragmas) {
+        self.pragmas = pragmas;
+    }
+    
+    /// Char range of the statement the last interrupt stopped at, in the source given to `track_spans`
+    pub fn stopped_at(&self) -> Option<Range<usize>> {
+        self.stopped_at.clone()
+    }
+    
+    fn interrupted(&self) -> bool {
+        self.interrupt.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+    
+    fn print(&mut self, line: String) {
+        match &mut self.console {
+            Some(console) if console.colors() => console.print(&line),
+            Some(console) => console.print(&ansi::strip(&line)),
+            None => self.output.push(ansi::strip(&line).into_owned()),
+        }
+    }
+    
+    /// Drain the warnings collected so far (shadowing and friends). A successful
+    /// `interpret` already hands them back, this is for runs that failed
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+    
+    /// Global variables and functions that exist right now, so static checks
+    /// on later REPL input know about them
+    pub fn known_names(&self) -> Vec<String> {
+        let mut names = self.environment.global_names();
+        names.extend(self.functions.keys().cloned());
+        names.extend(self.drama_types.keys().cloned());
+        names
+    }
+    
+    /// Every meme the source run so far declared, so a REPL line can call one an earlier line made
+    pub fn memes(&self) -> &HashMap<String, Meme> {
+        &self.memes
+    }
+    
+    pub fn remember_memes(&mut self, memes: &HashMap<String, Meme>) {
+        self.memes.clone_from(memes);
+    }
+    
+    /// Every name the main program's `statements` get from their pulls, for the resolver. Links
+    /// get fetched for it when they're allowed to be, they'd be fetched to run anyway
+    pub fn pulled_names(&self, statements: &[Stmt]) -> HashSet<String> {
+        self.modules.pulled_names(statements, self.capabilities.fetch)
+    }
+    
+    pub fn snapshot(&self) -> Snapshot {
+        let mut held = HashMap::new();
+        let mut seen = HashSet::new();
+        let modules = self.modules.pulled();
+        let functions = self.functions.values().map(|function| Literal::Function(function.clone()));
+        for value in self.environment.values().into_iter().chain(functions).chain(modules.namespaces().cloned()) {
+            hold(&value, &mut held, &mut seen);
+        }
+        
+        Snapshot {
+            environment: self.environment.clone(),
+            functions: self.functions.clone(),
+            drama_types: self.drama_types.clone(),
+            modules,
+            memes: self.memes.clone(),
+            held,
+        }
+    }
+    
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.environment = snapshot.environment;
+        self.functions = snapshot.functions;
+        self.drama_types = snapshot.drama_types;
+        self.modules.restore(snapshot.modules);
+        self.memes = snapshot.memes;
+        snapshot.held.into_values().for_each(Held::put_back);
+    }
+    
+    /// Variables visible right now, sorted by name - what `scope()` and `:vars` show
+    pub fn variables(&self) -> Vec<(String, Literal)> {
+        let mut variables: Vec<_> = self.environment.visible_variables().into_iter().collect();
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+        variables
+    }
+    
+    fn warn(&mut self, message: String) {
+        // Loops would otherwise repeat the same warning every iteration
+        if !self.warnings.contains(&message) {
+            self.warnings.push(message);
         }
     }
     
-    pub fn interpret(&mut self, statements: Vec<Stmt>) -> Result<String, ZLangError> {
-        let mut output = Vec::new();
+    pub fn interpret(&mut self, statements: Vec<Stmt>) -> Result<ExecutionResult, ZLangError> {
+        self.last_value = None;
+        let result = self.execute_program(&statements);
+        
+        // Drain the output either way so a failed run can't leak prints into the next one
+        let output = std::mem::take(&mut self.output);
+        let drawing = std::mem::take(&mut self.drawing);
+        self.drawing_changed = false;
+        let value = match (statements.last(), self.last_value.take()) {
+            (Some(Stmt::Expression(expr)), Some(value)) if !matches!(expr, Expr::Assign { .. } | Expr::Set { .. }) && value != Literal::Nil => Some(value),
+            _ => None,
+        };
         
+        result?;
+        Ok(ExecutionResult { output, value, warnings: self.take_warnings(), drawing })
+    }
+    
+    /// Evaluate a single expression against whatever is defined right now
+    pub fn evaluate(&mut self, expr: &Expr) -> Result<Literal, ZLangError> {
+        self.evaluate_expr(expr)
+    }
+    
+    fn execute_program(&mut self, statements: &[Stmt]) -> Result<(), ZLangError> {
         for stmt in statements {
-            if let Some(result) = self.execute_stmt(&stmt)? {
-                output.push(result);
+            match self.execute_stmt(stmt)? {
+                ControlFlow::Normal => {}
+                // A top-level `vibe` just ends the program early
+                ControlFlow::Return(_) => break,
+                flow => return Err(stray_control_flow(&flow)),
             }
-            
-            // Handle early returns from functions
-            if self.return_value.is_some() {
-                break;
+        }
+        Ok(())
+    }
+    
+    pub fn execute_stmt(&mut self, stmt: &Stmt) -> Result<ControlFlow, ZLangError> {
+        // Every loop iteration and function body comes through here, so nothing runs away from Ctrl+C
+        if self.interrupted() {
+            // Only the first stop counts, a frfr on the way out trips over the same flag.
+            // A loop body's block has no span of its own, its first statement does
+            if self.stopped_at.is_none() {
+                let located = match stmt {
+                    Stmt::Block(body) => body.first().unwrap_or(stmt),
+                    _ => stmt,
+                };
+                self.stopped_at = self.spans.as_ref().and_then(|spans| spans.get(located));
             }
+            return Err(messages::INTERRUPTED.error(&[]));
         }
+        self.ops += 1;
+        let flow = self.run_stmt(stmt)?;
         
-        Ok(output.join("\n"))
+        if let Some(tracer) = &mut self.tracer {
+            tracer.record(stmt, &self.environment, &self.output)?;
+        }
+        Ok(flow)
     }
     
-    pub fn execute_stmt(&mut self, stmt: &Stmt) -> Result<Option<String>, ZLangError> {
+    fn run_stmt(&mut self, stmt: &Stmt) -> Result<ControlFlow, ZLangError> {
         match stmt {
             Stmt::Expression(expr) => {
-                self.evaluate_expr(expr)?;
-                Ok(None)
+                self.last_value = Some(self.evaluate_expr(expr)?);
+                Ok(ControlFlow::Normal)
             }
-            Stmt::VarDeclaration { name, initializer } => {
-                let value = if let Some(init) = initializer {
+            Stmt::VarDeclaration { name, initializer, .. } => {
+                let mut value = if let Some(init) = initializer {
                     self.evaluate_expr(init)?
                 } else {
                     Literal::Nil
                 };
+                // `bet double = flex(x) { ... }` - the lambda goes by its variable's name in errors
+                if let (Some(Expr::Function { .. }), Literal::Function(function)) = (initializer, &mut value) {
+                    function.name = name.clone();
+                }
                 
-                // Try to assign to existing variable first, if that fails, define new one
-                if self.environment.assign(name, value.clone()).is_err() {
-                    self.environment.define(name.clone(), value);
+                // `bet` always declares in the current scope - updating an outer
+                // variable is what plain assignment is for
+                if self.environment.defined_in_enclosing(name) && !self.pragmas.ignores(Rule::Shadow, name) {
+                    self.warn(format!(
+                        "'{}' shadows a variable from an outer scope, use `{} = ...` if you meant to update it 👀",
+                        name, name
+                    ));
                 }
-                Ok(None)
+                self.environment.define(name.clone(), value);
+                Ok(ControlFlow::Normal)
             }
             Stmt::Block(statements) => {
                 self.environment.push_scope();
-                let mut result = None;
-                
-                for stmt in statements {
-                    if let Some(output) = self.execute_stmt(stmt)? {
-                        result = Some(output);
-                    }
-                    
-                    if self.return_value.is_some() || self.should_break || self.should_continue {
-                        break;
-                    }
-                }
-                
+                let flow = self.execute_block(statements);
                 self.environment.pop_scope()?;
-                Ok(result)
+                flow
             }
             Stmt::If { condition, then_branch, else_branch } => {
                 let condition_value = self.evaluate_expr(condition)?;
@@ -93,304 +512,1427 @@ impl Interpreter {
                 } else if let Some(else_stmt) = else_branch {
                     self.execute_stmt(else_stmt)
                 } else {
-                    Ok(None)
+                    Ok(ControlFlow::Normal)
                 }
             }
-            Stmt::While { condition, body } => {
-                loop {
+            Stmt::While { condition, body, else_branch } => {
+                let flow = loop {
+                    self.charge_loop()?;
                     let condition_value = self.evaluate_expr(condition)?;
                     if !self.is_truthy(&condition_value) {
-                        break;
-                    }
-                    
-                    self.execute_stmt(body)?;
-                    
-                    if self.should_break {
-                        self.should_break = false;
-                        break;
-                    }
-                    
-                    if self.should_continue {
-                        self.should_continue = false;
-                        continue;
+                        break ControlFlow::Normal;
                     }
                     
-                    if self.return_value.is_some() {
-                        break;
+                    match self.execute_stmt(body)? {
+                        ControlFlow::Normal | ControlFlow::Continue => {}
+                        flow => break flow,
                     }
-                }
-                Ok(None)
+                };
+                self.finish_loop(flow, else_branch.as_deref())
             }
-            Stmt::For { variable, iterable, body } => {
+            Stmt::For { variable, iterable, body, optional, else_branch } => {
                 let iterable_value = self.evaluate_expr(iterable)?;
                 
                 match iterable_value {
                     Literal::Array(arr) => {
                         self.environment.push_scope();
-                        
-                        for item in arr {
-                            self.environment.define(variable.clone(), item);
-                            self.execute_stmt(body)?;
-                            
-                            if self.should_break {
-                                self.should_break = false;
-                                break;
+                        let flow = self.execute_for(variable, arr, body);
+                        self.environment.pop_scope()?;
+                        self.finish_loop(flow?, else_branch.as_deref())
+                    }
+                    // Skipping a nil list is a loop that ran out of items without a `slay`
+                    Literal::Nil if *optional => self.finish_loop(ControlFlow::Normal, else_branch.as_deref()),
+                    other => {
+                        let hint = if other == Literal::Nil { " - if it's allowed to be nil, `grind?` skips the loop then" } else { "" };
+                        Err(messages::NOT_ITERABLE.error(&[&iterable.to_source(), &TypeAnnotation::of(&other), &hint]))
+                    }
+                }
+            }
+            Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+                // The loop variable lives just as long as the loop, its `no sus` comes after
+                self.environment.push_scope();
+                let flow = self.execute_classic_for(initializer.as_deref(), condition.as_ref(), increment.as_ref(), body);
+                self.environment.pop_scope()?;
+                self.finish_loop(flow?, else_branch.as_deref())
+            }
+            Stmt::Function { name, params, rest, body, .. } => {
+                let function = self.declare_function(name.clone(), params, *rest, body);
+                self.functions.insert(name.clone(), function);
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Squad { name, parent, body } => {
+                let parent = match parent {
+                    Some(parent) => match self.evaluate_expr(&Expr::Variable(parent.clone()))? {
+                        Literal::Squad(squad) => Some(squad),
+                        other => return Err(messages::NOT_A_SQUAD.error(&[name, parent, &TypeAnnotation::of(&other)])),
+                    },
+                    None => None,
+                };
+                
+                // The parent's fields come first, in its order - one declared again here only gets a new default
+                let mut fields = parent.as_ref().map_or_else(Vec::new, |parent| parent.fields.clone());
+                let mut methods = HashMap::new();
+                for member in body {
+                    match member {
+                        Stmt::VarDeclaration { name: field, initializer, .. } => {
+                            if parent.as_ref().is_some_and(|parent| parent.method(field).is_some()) {
+                                return Err(messages::MEMBER_KIND_CHANGED.error(&[name, field]));
+                            }
+                            match fields.iter_mut().find(|(inherited, _)| inherited == field) {
+                                Some(inherited) => inherited.1 = initializer.clone(),
+                                None => fields.push((field.clone(), initializer.clone())),
+                            }
+                        }
+                        Stmt::Function { name: method, params, rest, body, .. } => {
+                            if parent.as_ref().is_some_and(|parent| parent.fields.iter().any(|(field, _)| field == method)) {
+                                return Err(messages::MEMBER_KIND_CHANGED.error(&[name, method]));
+                            }
+                            let function = self.declare_function(format!("{}.{}", name, method), params, *rest, body);
+                            methods.insert(method.clone(), function);
+                        }
+                        _ => {}
+                    }
+                }
+                
+                let squad = Squad { name: name.clone(), parent, fields, methods, closure: self.environment.capture() };
+                self.environment.define(name.clone(), Literal::Squad(Arc::new(squad)));
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Return(expr) => {
+                let value = if let Some(expr) = expr {
+                    self.evaluate_expr(expr)?
+                } else {
+                    Literal::Nil
+                };
+                
+                Ok(ControlFlow::Return(value))
+            }
+            Stmt::Break => Ok(ControlFlow::Break),
+            Stmt::Continue => Ok(ControlFlow::Continue),
+            Stmt::Pull { path, alias, line } => {
+                if !self.capabilities.pull {
+                    return Err(messages::PULL_NOT_ALLOWED.error(&[line]));
+                }
+                let file = if fetch::is_link(path) {
+                    if !self.capabilities.fetch {
+                        return Err(messages::FETCH_NOT_ALLOWED.error(&[path, line]));
+                    }
+                    self.modules.fetched(path, *line)?
+                } else {
+                    self.modules.locate(path, *line)?
+                };
+                match alias {
+                    None if self.modules.is_loaded(&file) => {}
+                    // The pulled file runs in the global scope, so what it declares is there afterwards
+                    None => {
+                        self.pulling(&file, |interpreter, statements| interpreter.execute_program(statements))?;
+                        self.modules.mark_loaded(file);
+                    }
+                    Some(alias) => {
+                        let namespace = match self.modules.namespace(&file) {
+                            Some(namespace) => namespace.clone(),
+                            None => {
+                                let namespace = self.pulling(&file, Self::run_namespaced)?;
+                                self.modules.keep_namespace(file, namespace.clone());
+                                namespace
+                            }
+                        };
+                        self.environment.define(alias.clone(), namespace);
+                    }
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Print(expr) => {
+                let value = self.evaluate_expr(expr)?;
+                self.print(value.to_string());
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Inspect(expr) => {
+                let value = self.evaluate_expr(expr)?;
+                self.print(value.inspect());
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Global(names) => {
+                for name in names {
+                    self.environment.bind_global(name);
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Outer(names) => {
+                for name in names {
+                    self.environment.bind_outer(name)?;
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Needs { function, condition, line } => {
+                let holds = self.evaluate_expr(condition)?;
+                if !self.is_truthy(&holds) {
+                    return Err(messages::CONTRACT_BROKEN.error(&[line, function]));
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Stmt::Switch { expr, cases, default } => {
+                let switch_value = self.evaluate_expr(expr)?;
+                let mut matched = None;
+                
+                'cases: for case in cases {
+                    for pattern in &case.patterns {
+                        if self.case_matches(&switch_value, pattern)? {
+                            matched = Some(&case.body);
+                            break 'cases;
+                        }
+                    }
+                }
+                
+                let statements = match matched.or(default.as_ref()) {
+                    Some(statements) => statements,
+                    None => return Ok(ControlFlow::Normal),
+                };
+                
+                // `slay` only leaves the vibecheck, `ghost` and `vibe` belong to whatever surrounds it
+                match self.execute_block(statements)? {
+                    ControlFlow::Break => Ok(ControlFlow::Normal),
+                    flow => Ok(flow),
+                }
+            }
+            Stmt::Try { try_block, catch_clauses, finally_block } => {
+                // Whatever happens in manifest/caught (vibe, slay, ghost or drama) is held
+                // here until frfr has run, then handed back unchanged
+                let mut result = self.execute_block(try_block);
+                
+                // A `?` unwinding to its function isn't drama, so it skips the caught clauses,
+                // and neither does running out of steps or being interrupted
+                let catchable = result.as_ref().is_err_and(|error| error.propagating.is_none())
+                    && !self.tracer.as_ref().is_some_and(Tracer::is_full)
+                    && !self.interrupted();
+                if let (Err(error), true) = (&result, catchable) {
+                    // First clause whose type fits wins, untyped clauses catch everything
+                    let clause = catch_clauses.iter().find(|clause| match &clause.error_type {
+                        Some(wanted) => self.drama_is_a(error.kind.as_deref(), wanted),
+                        None => true,
+                    });
+                    
+                    if let Some(clause) = clause {
+                        self.environment.push_scope();
+                        self.environment.define(clause.variable.clone(), Literal::String(error.message.clone()));
+                        result = self.execute_block(&clause.body);
+                        self.environment.pop_scope()?;
+                    }
+                }
+                
+                // frfr always runs - only its own drama or control flow can replace the outcome
+                if let Some(finally_stmts) = finally_block {
+                    match self.execute_block(finally_stmts)? {
+                        ControlFlow::Normal => {}
+                        flow => return Ok(flow),
+                    }
+                }
+                
+                result
+            }
+            Stmt::Throw(expr) => {
+                let error_value = self.evaluate_expr(expr)?;
+                match error_value {
+                    Literal::String(s) => Err(ZLangError::new(&s)),
+                    Literal::Object(fields) => match (fields.get("type"), fields.get("message")) {
+                        (Some(Literal::String(kind)), Some(message)) if self.drama_types.contains_key(kind) => {
+                            Err(ZLangError::typed(kind, &message.to_string()))
+                        }
+                        _ => Err(messages::THROWN_VALUE.error(&[])),
+                    },
+                    _ => Err(messages::THROWN_VALUE.error(&[])),
+                }
+            }
+            Stmt::DramaType { name, parent } => {
+                let parent = match parent.as_deref() {
+                    None | Some("Drama") => None,
+                    Some(parent) if self.drama_types.contains_key(parent) => Some(parent.to_string()),
+                    Some(parent) => {
+                        return Err(messages::UNDECLARED_DRAMA_TYPE.error(&[&parent]));
+                    }
+                };
+                self.drama_types.insert(name.clone(), parent);
+                Ok(ControlFlow::Normal)
+            }
+        }
+    }
+    
+    /// Wait until `deadline`, showing the frame drawn so far first - that's what makes a loop animate.
+    /// Naps in slices so Ctrl+C (or a closed browser tab) doesn't wait out the whole thing,
+    /// and so key presses reach the onKey() handler while it waits
+    fn nap_until(&mut self, deadline: Instant) -> Result<(), ZLangError> {
+        if let (Some(console), true) = (&mut self.console, self.drawing_changed) {
+            console.draw(&self.drawing);
+            self.drawing_changed = false;
+        }
+        while !self.interrupted() {
+            self.handle_keys()?;
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            thread::sleep(left.min(Duration::from_millis(10)));
+        }
+        Ok(())
+    }
+    
+    /// A value from outside the program, `fresh` unless there's a tape recording or replaying it
+    fn outside_input(&mut self, kind: &str, fresh: impl FnOnce(&mut Self) -> Literal) -> Result<Literal, ZLangError> {
+        match self.tape.clone() {
+            Some(tape) => tape.take(kind, || fresh(self)),
+            None => Ok(fresh(self)),
+        }
+    }
+    
+    /// Restart random() from `seed`, so the numbers after this come out the same every run
+    fn reseed(&mut self, builtin: &str, seed: &Literal) -> Result<(), ZLangError> {
+        match seed {
+            Literal::Number(n) if n.fract() == 0.0 => {
+                self.rng = Rng::seeded(*n as i64 as u64);
+                Ok(())
+            }
+            _ => Err(messages::SEED_NOT_WHOLE.error(&[&builtin])),
+        }
+    }
+    
+    /// Hand every key pressed since the last nap to the onKey() handler, oldest first
+    fn handle_keys(&mut self) -> Result<(), ZLangError> {
+        let Some(handler) = self.key_handler.clone() else {
+            return Ok(());
+        };
+        let keys = self.console.as_mut().map(|console| console.keys()).unwrap_or_default();
+        for key in keys {
+            self.invoke(&handler, vec![Literal::String(key)])?;
+        }
+        Ok(())
+    }
+    
+    /// Call `update` once a frame until it hands back cap, or the run gets interrupted. Each frame
+    /// gets the seconds since the last one, so movement stays smooth when a frame runs late
+    fn run_game_loop(&mut self, fps: f64, update: &Function) -> Result<(), ZLangError> {
+        let frame = Duration::from_secs_f64(1.0 / fps);
+        let mut last = Instant::now();
+        let mut next = last + frame;
+        loop {
+            // Shows whatever the last frame drew, then waits out the rest of this one
+            self.nap_until(next)?;
+            if self.interrupted() {
+                break;
+            }
+            
+            let now = Instant::now();
+            let delta = self.outside_input("clock", |_| Literal::Number((now - last).as_secs_f64()))?;
+            last = now;
+            next += frame;
+            if next < now {
+                next = now + frame;
+            }
+            
+            let arguments = if update.params.is_empty() { Vec::new() } else { vec![delta] };
+            if self.invoke(update, arguments)? == Literal::Boolean(false) {
+                break;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Fire timers as they come due until none are left, or the run gets interrupted
+    fn run_timers(&mut self) -> Result<(), ZLangError> {
+        while let Some(due) = self.timers.next_due() {
+            self.nap_until(due)?;
+            if self.interrupted() {
+                break;
+            }
+            if let Some(function) = self.timers.take_due(Instant::now()) {
+                self.invoke(&function, Vec::new())?;
+            }
+        }
+        Ok(())
+    }
+    
+    fn stash_key(&mut self, argument: &Expr) -> Result<String, ZLangError> {
+        match self.evaluate_expr(argument)? {
+            Literal::String(key) if !key.is_empty() => Ok(key),
+            _ => Err(messages::STASH_KEY.error(&[])),
+        }
+    }
+    
+    /// The function a builtin argument refers to, by bare name, by string or as a lambda
+    fn function_named(&mut self, expr: &Expr) -> Result<Function, ZLangError> {
+        let name = match expr {
+            Expr::Variable(name) if self.functions.contains_key(name) => name.clone(),
+            other => match self.evaluate_expr(other)? {
+                Literal::String(name) => name,
+                Literal::Function(function) => return Ok(function),
+                _ => return Err(messages::FUNCTION_EXPECTED.error(&[])),
+            },
+        };
+        
+        self.functions.get(&name).cloned().ok_or_else(|| self.undefined_function(&name))
+    }
+    
+    fn array_argument(&mut self, builtin: &str, argument: &Expr) -> Result<Vec<Literal>, ZLangError> {
+        match self.evaluate_expr(argument)? {
+            Literal::Array(items) => Ok(items),
+            other => Err(messages::NOT_AN_ARRAY.error(&[&builtin, &TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    /// Whether the switched `value` fits a case's pattern. A range or comparison against another
+    /// type just doesn't match, so `"none":` and `1..5:` can share a vibe check
+    fn case_matches(&mut self, value: &Literal, pattern: &CasePattern) -> Result<bool, ZLangError> {
+        match pattern {
+            CasePattern::Value(expected) | CasePattern::Compare { operator: BinaryOp::Equal, value: expected } => {
+                let expected = self.evaluate_expr(expected)?;
+                Ok(self.values_equal(value, &expected))
+            }
+            CasePattern::Compare { operator: BinaryOp::NotEqual, value: unwanted } => {
+                let unwanted = self.evaluate_expr(unwanted)?;
+                Ok(!self.values_equal(value, &unwanted))
+            }
+            CasePattern::Compare { operator, value: bound } => {
+                let bound = self.evaluate_expr(bound)?;
+                self.compares(value, operator, &bound)
+            }
+            CasePattern::Range { low, high, inclusive } => {
+                let low = self.evaluate_expr(low)?;
+                let high = self.evaluate_expr(high)?;
+                let below_high = if *inclusive { BinaryOp::LessEqual } else { BinaryOp::Less };
+                Ok(self.compares(value, &BinaryOp::GreaterEqual, &low)? && self.compares(value, &below_high, &high)?)
+            }
+        }
+    }
+    
+    fn compares(&mut self, left: &Literal, operator: &BinaryOp, right: &Literal) -> Result<bool, ZLangError> {
+        if TypeAnnotation::of(left) != TypeAnnotation::of(right) {
+            return Ok(false);
+        }
+        let result = self.apply_binary_op(left, operator, right)?;
+        Ok(self.is_truthy(&result))
+    }
+    
+    /// A `flex` declaration as a value, made in the scopes it's declared in. Its body is copied, so the
+    /// tracer and span index learn where the copy's statements came from
+    fn declare_function(&mut self, name: String, params: &[String], rest: bool, body: &[Stmt]) -> Function {
+        let function = Function {
+            name,
+            params: params.to_vec(),
+            rest,
+            body: Arc::new(body.to_vec()),
+            closure: self.environment.capture(),
+        };
+        if let Some(tracer) = &mut self.tracer {
+            tracer.alias(body, &function.body);
+        }
+        if let Some(spans) = &mut self.spans {
+            spans.alias(body, &function.body);
+        }
+        function
+    }
+    
+    /// Calls whatever the script called `name`
+    fn call_named(&mut self, name: &str, arguments: &[Expr]) -> Result<Literal, ZLangError> {
+        // A variable holding a lambda wins over a user-defined function of the same name
+        match self.environment.get(name) {
+            Ok(Literal::Function(function)) => self.call_function(function, arguments),
+            Ok(Literal::Squad(squad)) => self.instantiate(squad, arguments),
+            _ => match self.functions.get(name).cloned() {
+                Some(function) => self.call_function(function, arguments),
+                None => match self.environment.get(name) {
+                    Ok(other) => Err(messages::NOT_CALLABLE.error(&[&name, &TypeAnnotation::of(&other)])),
+                    Err(_) => Err(self.undefined_function(name)),
+                },
+            },
+        }
+    }
+    
+    fn deque_argument(&mut self, builtin: &str, argument: &Expr) -> Result<Deque, ZLangError> {
+        match self.evaluate_expr(argument)? {
+            Literal::Deque(deque) => Ok(deque),
+            other => Err(messages::NOT_A_DEQUE.error(&[&builtin, &TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    fn heap_argument(&mut self, builtin: &str, argument: &Expr) -> Result<Heap, ZLangError> {
+        match self.evaluate_expr(argument)? {
+            Literal::Heap(heap) => Ok(heap),
+            other => Err(messages::NOT_A_HEAP.error(&[&builtin, &TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    /// A graph node's name - strings only, they double as the keys of the graph's object
+    fn node_argument(&mut self, builtin: &str, argument: &Expr) -> Result<String, ZLangError> {
+        match self.evaluate_expr(argument)? {
+            Literal::String(node) => Ok(node),
+            other => Err(messages::NODE_NOT_STRING.error(&[&builtin, &TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    /// What `function` says each item's key is, one call per item
+    fn keys_of(&mut self, builtin: &str, items: &[Literal], function: &Function) -> Result<Vec<Literal>, ZLangError> {
+        if function.params.len() != 1 {
+            return Err(messages::KEY_FUNCTION_PARAMETERS.error(&[&builtin, &function.name, &function.params.len()]));
+        }
+        items.iter().map(|item| self.invoke(function, vec![item.clone()])).collect()
+    }
+    
+    /// Sort for sortBy() and sortDesc(). A 1-parameter function gives each item's key, a 2-parameter
+    /// one compares two items (negative puts the first one first). Equal items keep their order
+    fn sort_values(&mut self, builtin: &str, items: Vec<Literal>, function: Option<&Function>, descending: bool) -> Result<Vec<Literal>, ZLangError> {
+        let flip = |ordering: cmp::Ordering| if descending { ordering.reverse() } else { ordering };
+        match function {
+            Some(function) if function.params.len() == 2 => merge_sort(items, &mut |a, b| {
+                match self.invoke(function, vec![a.clone(), b.clone()])? {
+                    Literal::Number(n) if !n.is_nan() => Ok(flip(n.total_cmp(&0.0))),
+                    other => Err(messages::COMPARE_NOT_NUMBER.error(&[&function.name, &TypeAnnotation::of(&other)])),
+                }
+            }),
+            Some(function) => {
+                let keys = self.keys_of(builtin, &items, function)?;
+                let sorted = merge_sort(keys.into_iter().zip(items).collect(), &mut |(a, _), (b, _)| compare_values(a, b).map(flip))?;
+                Ok(sorted.into_iter().map(|(_, item)| item).collect())
+            }
+            None => merge_sort(items, &mut |a, b| compare_values(a, b).map(flip)),
+        }
+    }
+    
+    fn undefined_function(&self, name: &str) -> ZLangError {
+        let candidates = self.functions.keys().chain(self.drama_types.keys()).map(String::as_str).chain(BUILTINS.iter().copied());
+        messages::UNDEFINED_FUNCTION.error(&[&name, &similar::did_you_mean(name, candidates)])
+    }
+    
+    /// Does an error of drama type `kind` (None for built-in errors) count as a `wanted`?
+    fn drama_is_a(&self, kind: Option<&str>, wanted: &str) -> bool {
+        if wanted == "Drama" {
+            return true;
+        }
+        
+        let mut current = kind.map(str::to_string);
+        while let Some(name) = current {
+            if name == wanted {
+                return true;
+            }
+            current = self.drama_types.get(&name).cloned().flatten();
+        }
+        false
+    }
+    
+    /// Load the pulled `file` and `run` it, with the file's name on anything that goes wrong. The
+    /// file's own `// zlang-ignore` comments are the ones that count while it runs
+    fn pulling<T>(&mut self, file: &Path, run: impl FnOnce(&mut Self, &[Stmt]) -> Result<T, ZLangError>) -> Result<T, ZLangError> {
+        self.modules.enter(file)?;
+        let ran = modules::load(self.modules.files(), file).and_then(|(statements, pragmas)| {
+            let outside = std::mem::replace(&mut self.pragmas, pragmas);
+            let ran = run(self, &statements);
+            self.pragmas = outside;
+            ran
+        });
+        self.modules.leave();
+        ran.map_err(|e| modules::in_file(file, e))
+    }
+    
+    /// Run a pulled file with globals of its own and hand back everything it declared as one object,
+    /// so its names can't clash with the program's
+    fn run_namespaced(&mut self, statements: &[Stmt]) -> Result<Literal, ZLangError> {
+        let outside = std::mem::take(&mut self.functions);
+        self.environment.enter_module();
+        let ran = self.execute_program(statements);
+        
+        // Its functions become variables in its global scope, which they close over, so they
+        // still find each other once they're only reachable through the object
+        let functions = std::mem::replace(&mut self.functions, outside);
+        for (name, function) in functions {
+            self.environment.define(name, Literal::Function(function));
+        }
+        let declared = self.environment.scopes().pop().unwrap_or_default();
+        self.environment.leave_module()?;
+        
+        ran?;
+        Ok(Literal::Object(declared.into_iter().collect()))
+    }
+    
+    /// Run statements in order until one of them breaks, continues or returns
+    fn execute_block(&mut self, statements: &[Stmt]) -> Result<ControlFlow, ZLangError> {
+        for stmt in statements {
+            match self.execute_stmt(stmt)? {
+                ControlFlow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+    
+    /// What's left once a loop stops - a `slay` ends it right there, running out the normal way
+    /// runs its `no sus` if it has one. `flow` is Break for a `slay`, Normal for running out
+    fn finish_loop(&mut self, flow: ControlFlow, else_branch: Option<&Stmt>) -> Result<ControlFlow, ZLangError> {
+        match (flow, else_branch) {
+            (ControlFlow::Break, _) => Ok(ControlFlow::Normal),
+            (ControlFlow::Normal, Some(else_stmt)) => self.execute_stmt(else_stmt),
+            (flow, _) => Ok(flow),
+        }
+    }
+    
+    fn execute_for(&mut self, variable: &str, items: Vec<Literal>, body: &Stmt) -> Result<ControlFlow, ZLangError> {
+        for item in items {
+            self.charge_loop()?;
+            self.environment.define(variable.to_string(), item);
+            match self.execute_stmt(body)? {
+                ControlFlow::Normal | ControlFlow::Continue => {}
+                flow => return Ok(flow),
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+    
+    fn execute_classic_for(&mut self, initializer: Option<&Stmt>, condition: Option<&Expr>, increment: Option<&Expr>, body: &Stmt) -> Result<ControlFlow, ZLangError> {
+        // Part of the loop's own line, so step-through doesn't show it as a step of its own.
+        // A counter is the loop's like `highkey`'s variable is, shadowing an outer one is the point
+        match initializer {
+            Some(Stmt::VarDeclaration { name, initializer: Some(value), .. }) => {
+                let value = self.evaluate_expr(value)?;
+                self.environment.define(name.clone(), value);
+            }
+            Some(initializer) => {
+                self.run_stmt(initializer)?;
+            }
+            None => {}
+        }
+        loop {
+            self.charge_loop()?;
+            if let Some(condition) = condition {
+                let condition_value = self.evaluate_expr(condition)?;
+                if !self.is_truthy(&condition_value) {
+                    break;
+                }
+            }
+            
+            match self.execute_stmt(body)? {
+                // `skip` still takes the step, or the loop would never get anywhere
+                ControlFlow::Normal | ControlFlow::Continue => {}
+                flow => return Ok(flow),
+            }
+            if let Some(increment) = increment {
+                self.evaluate_expr(increment)?;
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+    
+    /// Every pass round a loop costs a step-through run something, so one with an empty body
+    /// still runs out of steps
+    fn charge_loop(&mut self) -> Result<(), ZLangError> {
+        match &mut self.tracer {
+            Some(tracer) => tracer.charge(),
+            None => Ok(()),
+        }
+    }
+    
+    fn values_equal(&self, left: &Literal, right: &Literal) -> bool {
+        match (left, right) {
+            (Literal::Number(a), Literal::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Literal::String(a), Literal::String(b)) => a == b,
+            (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
+            (Literal::Nil, Literal::Nil) => true,
+            _ => false,
+        }
+    }
+    
+    fn evaluate_expr(&mut self, expr: &Expr) -> Result<Literal, ZLangError> {
+        self.ops += 1;
+        match expr {
+            Expr::Literal(literal) => Ok(literal.clone()),
+            // A function's name on its own is the function, ready to store, pass or return
+            Expr::Variable(name) => match self.environment.get(name) {
+                Err(_) if self.functions.contains_key(name) => Ok(Literal::Function(self.functions[name].clone())),
+                result => result,
+            },
+            Expr::Assign { name, value } => {
+                let val = self.evaluate_expr(value)?;
+                self.environment.assign(name, val.clone())?;
+                Ok(val)
+            }
+            Expr::Binary { left, operator, right } => {
+                let left_val = self.evaluate_expr(left)?;
+                let right_val = self.evaluate_expr(right)?;
+                self.apply_binary_op(&left_val, operator, &right_val)
+            }
+            Expr::Unary { operator, right } => {
+                let right_val = self.evaluate_expr(right)?;
+                self.apply_unary_op(operator, &right_val)
+            }
+            Expr::Call { callee, arguments, line } => {
+                // A ...spread's items take its place up front, so everything below sees plain arguments
+                let spread: Vec<Expr>;
+                let arguments = if arguments.iter().any(|argument| matches!(argument, Expr::Spread(_))) {
+                    spread = self.evaluate_items(arguments)?.into_iter().map(Expr::Literal).collect();
+                    &spread
+                } else {
+                    arguments
+                };
+                if let Expr::Variable(name) = callee.as_ref() {
+                    // A function the script named itself wins over a builtin of the same name
+                    let shadowed = self.functions.contains_key(name) || matches!(self.environment.get(name), Ok(Literal::Function(_) | Literal::Squad(_)));
+                    // Built-in functions
+                    match name.as_str() {
+                        _ if shadowed => self.call_named(name, arguments),
+                        "sqrt" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::SQRT_ARGUMENTS.error(&[]));
+                            }
+                            let arg = self.evaluate_expr(&arguments[0])?;
+                            if let Literal::Number(n) = arg {
+                                if n < 0.0 {
+                                    return Err(messages::SQRT_NEGATIVE.error(&[]));
+                                }
+                                Ok(Literal::Number(n.sqrt()))
+                            } else {
+                                Err(messages::SQRT_NOT_NUMBER.error(&[]))
+                            }
+                        }
+                        "abs" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::ABS_ARGUMENTS.error(&[]));
+                            }
+                            let arg = self.evaluate_expr(&arguments[0])?;
+                            if let Literal::Number(n) = arg {
+                                Ok(Literal::Number(n.abs()))
+                            } else {
+                                Err(messages::ABS_NOT_NUMBER.error(&[]))
+                            }
+                        }
+                        "num" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::NUM_ARGUMENTS.error(&[]));
+                            }
+                            let arg = self.evaluate_expr(&arguments[0])?;
+                            to_number(&arg).map(Literal::Number)
+                        }
+                        "random" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::RANDOM_ARGUMENTS.error(&[]));
+                            }
+                            self.outside_input("random", |interpreter| Literal::Number(interpreter.rng.next_f64()))
+                        }
+                        "spill" => {
+                            if arguments.len() > 1 {
+                                return Err(messages::SPILL_ARGUMENTS.error(&[]));
+                            }
+                            if !self.capabilities.input {
+                                return Err(messages::INPUT_NOT_ALLOWED.error(&[]));
+                            }
+                            let prompt = match arguments.first() {
+                                Some(argument) => self.evaluate_expr(argument)?.to_string(),
+                                None => String::new(),
+                            };
+                            
+                            // Out of input (Ctrl+D, closed terminal) comes back as nil
+                            let answer = self.outside_input("input", |interpreter| {
+                                let line = match &mut interpreter.console {
+                                    Some(console) => console.read_line(&prompt),
+                                    None => console::read_stdin_line(&prompt),
+                                };
+                                line.map_or(Literal::Nil, Literal::String)
+                            })?;
+                            // Nobody's typing during a replay, show what was typed back then
+                            let replaying = self.tape.as_ref().is_some_and(Tape::is_replaying);
+                            if let (true, Literal::String(typed)) = (replaying, &answer) {
+                                self.print(format!("{}{}", prompt, typed));
+                            }
+                            Ok(answer)
+                        }
+                        "length" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::LENGTH_ARGUMENTS.error(&[]));
+                            }
+                            let arg = self.evaluate_expr(&arguments[0])?;
+                            match arg {
+                                Literal::String(s) => Ok(Literal::Number(s.len() as f64)),
+                                Literal::Array(arr) => Ok(Literal::Number(arr.len() as f64)),
+                                _ => Err(messages::LENGTH_NOT_SIZED.error(&[])),
+                            }
+                        }
+                        "uppercase" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::UPPERCASE_ARGUMENTS.error(&[]));
+                            }
+                            let arg = self.evaluate_expr(&arguments[0])?;
+                            if let Literal::String(s) = arg {
+                                Ok(Literal::String(ansi::uppercase(&s)))
+                            } else {
+                                Err(messages::UPPERCASE_NOT_STRING.error(&[]))
+                            }
+                        }
+                        "split" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::SPLIT_ARGUMENTS.error(&[]));
+                            }
+                            let string_arg = self.evaluate_expr(&arguments[0])?;
+                            let delimiter_arg = self.evaluate_expr(&arguments[1])?;
+                            
+                            if let (Literal::String(s), Literal::String(delim)) = (string_arg, delimiter_arg) {
+                                let parts: Vec<Literal> = s.split(&delim)
+                                    .map(|part| Literal::String(part.to_string()))
+                                    .collect();
+                                Ok(Literal::Array(parts))
+                            } else {
+                                Err(messages::SPLIT_NOT_STRINGS.error(&[]))
+                            }
+                        }
+                        "inspect" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::INSPECT_ARGUMENTS.error(&[]));
+                            }
+                            let value = self.evaluate_expr(&arguments[0])?;
+                            Ok(Literal::String(value.inspect()))
+                        }
+                        "ok" | "nah" => {
+                            if arguments.len() > 1 {
+                                return Err(messages::RESULT_ARGUMENTS.error(&[&name]));
+                            }
+                            let payload = match arguments.first() {
+                                Some(argument) => self.evaluate_expr(argument)?,
+                                None => Literal::Nil,
+                            };
+                            
+                            if name == "ok" {
+                                Ok(result_object(true, payload, Literal::Nil))
+                            } else {
+                                Ok(result_object(false, Literal::Nil, payload))
+                            }
+                        }
+                        "attempt" => {
+                            if arguments.len() < 2 {
+                                return Err(messages::ATTEMPT_ARGUMENTS.error(&[]));
+                            }
+                            let tries = match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(n) if n >= 1.0 => n as usize,
+                                _ => return Err(messages::ATTEMPT_TRIES.error(&[])),
+                            };
+                            let function = self.function_named(&arguments[1])?;
+                            
+                            let mut arg_values = Vec::new();
+                            for arg in &arguments[2..] {
+                                arg_values.push(self.evaluate_expr(arg)?);
+                            }
+                            if arg_values.len() != function.params.len() {
+                                return Err(messages::ATTEMPT_PASSES.error(&[&function.name, &function.params.len(), &arg_values.len()]));
+                            }
+                            
+                            let mut last_error = Literal::Nil;
+                            for _ in 0..tries {
+                                match self.invoke(&function, arg_values.clone()) {
+                                    Ok(value) => return Ok(result_object(true, value, Literal::Nil)),
+                                    Err(error) => last_error = Literal::String(error.message),
+                                }
+                            }
+                            Ok(result_object(false, Literal::Nil, last_error))
+                        }
+                        "sum" | "avg" | "median" | "stddev" | "minOf" | "maxOf" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::STATS_ARGUMENTS.error(&[&name]));
+                            }
+                            let items = self.array_argument(name, &arguments[0])?;
+                            let mut numbers = Vec::with_capacity(items.len());
+                            for (index, item) in items.iter().enumerate() {
+                                match item {
+                                    Literal::Number(n) => numbers.push(*n),
+                                    other => return Err(messages::STATS_NOT_NUMBER.error(&[&name, &index, &TypeAnnotation::of(other), &other.to_source()])),
+                                }
+                            }
+                            if numbers.is_empty() && name != "sum" {
+                                return Err(messages::STATS_EMPTY.error(&[&name]));
+                            }
+                            Ok(Literal::Number(summarize(name, numbers)))
+                        }
+                        "money" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::MONEY_ARGUMENTS.error(&[]));
+                            }
+                            match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(amount) => Ok(Literal::Money(money::from_number(amount)?)),
+                                Literal::Money(cents) => Ok(Literal::Money(cents)),
+                                Literal::String(text) => money::parse(&text).map(Literal::Money).ok_or_else(|| messages::NOT_MONEY.error(&[&text])),
+                                other => Err(messages::MONEY_NOT_CONVERTIBLE.error(&[&TypeAnnotation::of(&other)])),
+                            }
+                        }
+                        "divmod" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::DIVMOD_ARGUMENTS.error(&[]));
+                            }
+                            match (self.evaluate_expr(&arguments[0])?, self.evaluate_expr(&arguments[1])?) {
+                                (Literal::Number(_), Literal::Number(0.0)) => {
+                                    Err(messages::DIVISION_BY_ZERO.error(&[]))
+                                }
+                                (Literal::Number(a), Literal::Number(b)) => {
+                                    let (quotient, remainder) = floor_divmod(a, b);
+                                    Ok(Literal::Array(vec![Literal::Number(quotient), Literal::Number(remainder)]))
+                                }
+                                _ => Err(messages::DIVMOD_NOT_NUMBERS.error(&[])),
+                            }
+                        }
+                        "sortBy" | "sortDesc" => {
+                            let descending = name == "sortDesc";
+                            let fits = if descending { (1..=2).contains(&arguments.len()) } else { arguments.len() == 2 };
+                            if !fits {
+                                let usage = if descending { "sortDesc(array, function?)" } else { "sortBy(array, function)" };
+                                return Err(messages::SORT_ARGUMENTS.error(&[&usage]));
+                            }
+                            let items = self.array_argument(name, &arguments[0])?;
+                            let function = match arguments.get(1) {
+                                Some(argument) => Some(self.function_named(argument)?),
+                                None => None,
+                            };
+                            Ok(Literal::Array(self.sort_values(name, items, function.as_ref(), descending)?))
+                        }
+                        "minBy" | "maxBy" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::MIN_MAX_BY_ARGUMENTS.error(&[&name]));
+                            }
+                            let items = self.array_argument(name, &arguments[0])?;
+                            let function = self.function_named(&arguments[1])?;
+                            let keys = self.keys_of(name, &items, &function)?;
+                            
+                            // Ties go to whoever came first
+                            let wanted = if name == "minBy" { cmp::Ordering::Less } else { cmp::Ordering::Greater };
+                            let mut best: Option<usize> = None;
+                            for index in 0..keys.len() {
+                                let better = match best {
+                                    None => true,
+                                    Some(best) => compare_values(&keys[index], &keys[best])? == wanted,
+                                };
+                                if better {
+                                    best = Some(index);
+                                }
+                            }
+                            Ok(best.map_or(Literal::Nil, |best| items[best].clone()))
+                        }
+                        "groupBy" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::GROUP_BY_ARGUMENTS.error(&[]));
+                            }
+                            let items = self.array_argument(name, &arguments[0])?;
+                            let function = self.function_named(&arguments[1])?;
+                            let keys = self.keys_of(name, &items, &function)?;
+                            
+                            // Each group keeps its items in their original order
+                            let mut groups: HashMap<String, Literal> = HashMap::new();
+                            for (key, item) in keys.into_iter().zip(items) {
+                                if let Literal::Array(group) = groups.entry(key.to_string()).or_insert_with(|| Literal::Array(Vec::new())) {
+                                    group.push(item);
+                                }
+                            }
+                            Ok(Literal::Object(groups))
+                        }
+                        "countBy" => {
+                            if !(1..=2).contains(&arguments.len()) {
+                                return Err(messages::COUNT_BY_ARGUMENTS.error(&[]));
+                            }
+                            let items = self.array_argument(name, &arguments[0])?;
+                            // Without a function, the items count themselves
+                            let keys = match arguments.get(1) {
+                                Some(argument) => {
+                                    let function = self.function_named(argument)?;
+                                    self.keys_of(name, &items, &function)?
+                                }
+                                None => items,
+                            };
+                            
+                            let mut counts: HashMap<String, Literal> = HashMap::new();
+                            for key in keys {
+                                if let Literal::Number(count) = counts.entry(key.to_string()).or_insert(Literal::Number(0.0)) {
+                                    *count += 1.0;
+                                }
+                            }
+                            Ok(Literal::Object(counts))
+                        }
+                        "reversed" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::REVERSED_ARGUMENTS.error(&[]));
+                            }
+                            let mut items = self.array_argument(name, &arguments[0])?;
+                            items.reverse();
+                            Ok(Literal::Array(items))
+                        }
+                        "unique" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::UNIQUE_ARGUMENTS.error(&[]));
+                            }
+                            // Values that read the same as source are the same, the first one stays
+                            let mut seen = HashSet::new();
+                            let items = self.array_argument(name, &arguments[0])?;
+                            Ok(Literal::Array(items.into_iter().filter(|item| seen.insert(item.to_source())).collect()))
+                        }
+                        "levenshtein" | "similarity" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::SIMILARITY_ARGUMENTS.error(&[&name]));
+                            }
+                            match (self.evaluate_expr(&arguments[0])?, self.evaluate_expr(&arguments[1])?) {
+                                (Literal::String(a), Literal::String(b)) if name == "levenshtein" => Ok(Literal::Number(similar::levenshtein(&a, &b) as f64)),
+                                (Literal::String(a), Literal::String(b)) => Ok(Literal::Number(similar::similarity(&a, &b))),
+                                _ => Err(messages::SIMILARITY_NOT_STRINGS.error(&[&name])),
+                            }
+                        }
+                        "tokenize" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::TOKENIZE_ARGUMENTS.error(&[]));
+                            }
+                            let Literal::String(code) = self.evaluate_expr(&arguments[0])? else {
+                                return Err(messages::TOKENIZE_NOT_STRING.error(&[]));
+                            };
+                            let source: Vec<char> = code.chars().collect();
+                            // The trailing Eof has nothing to show, the end of the array already says it
+                            let tokens = Lexer::new(&code).tokenize()?.into_iter()
+                                .filter(|token| token.token_type != TokenType::Eof)
+                                .map(|token| Literal::Object(HashMap::from([
+                                    ("type".to_string(), Literal::String(token.token_type.kind().to_string())),
+                                    ("text".to_string(), Literal::String(token.lexeme(&source))),
+                                    ("line".to_string(), Literal::Number(token.line as f64)),
+                                ])))
+                                .collect();
+                            Ok(Literal::Array(tokens))
+                        }
+                        "parseCode" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::PARSE_CODE_ARGUMENTS.error(&[]));
+                            }
+                            let Literal::String(code) = self.evaluate_expr(&arguments[0])? else {
+                                return Err(messages::PARSE_CODE_NOT_STRING.error(&[]));
+                            };
+                            let statements = Parser::new(Lexer::new(&code).tokenize()?).parse()?;
+                            Ok(syntax::from_statements(&statements))
+                        }
+                        "runAst" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::RUN_AST_ARGUMENTS.error(&[]));
+                            }
+                            let ast = self.evaluate_expr(&arguments[0])?;
+                            let statements = syntax::to_statements(&ast)?;
+                            // Its own scope like a call's, so what it declares stays in there and a
+                            // `vibe` at its top level hands back what runAst returns
+                            match self.execute_stmt(&Stmt::Block(statements))? {
+                                ControlFlow::Return(value) => Ok(value),
+                                ControlFlow::Normal => Ok(Literal::Nil),
+                                flow => Err(stray_control_flow(&flow)),
+                            }
+                        }
+                        "markdown" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::MARKDOWN_ARGUMENTS.error(&[]));
+                            }
+                            match self.evaluate_expr(&arguments[0])? {
+                                Literal::String(text) => Ok(Literal::String(markdown::to_html(&text))),
+                                _ => Err(messages::MARKDOWN_NOT_STRING.error(&[])),
+                            }
+                        }
+                        "parseUrl" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::PARSE_URL_ARGUMENTS.error(&[]));
+                            }
+                            match self.evaluate_expr(&arguments[0])? {
+                                Literal::String(text) => Ok(Literal::Object(url::parse(&text)?)),
+                                other => Err(messages::PARSE_URL_NOT_STRING.error(&[&TypeAnnotation::of(&other)])),
+                            }
+                        }
+                        "buildUrl" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::BUILD_URL_ARGUMENTS.error(&[]));
+                            }
+                            match self.evaluate_expr(&arguments[0])? {
+                                Literal::Object(fields) => Ok(Literal::String(url::build(&fields)?)),
+                                other => Err(messages::BUILD_URL_NOT_OBJECT.error(&[&TypeAnnotation::of(&other)])),
+                            }
+                        }
+                        "colored" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::COLORED_ARGUMENTS.error(&[]));
+                            }
+                            let text = self.evaluate_expr(&arguments[0])?.to_string();
+                            let color = match self.evaluate_expr(&arguments[1])? {
+                                Literal::String(color) => color,
+                                _ => return Err(messages::COLOR_NOT_STRING.error(&[])),
+                            };
+                            let names: Vec<&str> = ansi::COLORS.iter().map(|(name, _)| *name).collect();
+                            ansi::colored(&text, &color).map(Literal::String).ok_or_else(|| messages::UNKNOWN_COLOR.error(&[&color, &names.join(", ")]))
+                        }
+                        "bold" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::BOLD_ARGUMENTS.error(&[]));
+                            }
+                            let text = self.evaluate_expr(&arguments[0])?.to_string();
+                            Ok(Literal::String(ansi::bold(&text)))
+                        }
+                        "stash" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::STASH_ARGUMENTS.error(&[]));
+                            }
+                            let key = self.stash_key(&arguments[0])?;
+                            let value = self.evaluate_expr(&arguments[1])?;
+                            // Written out it'd be something the next run can't read back, and that breaks every key
+                            if let Some(part) = stash::unstashable(&value) {
+                                let kind = match part {
+                                    Literal::Instance(instance) => format!("{} instance", instance.squad().name),
+                                    Literal::Squad(_) => "squad".to_string(),
+                                    other => TypeAnnotation::of(other).to_string(),
+                                };
+                                return Err(messages::NOT_STASHABLE.error(&[&kind]));
+                            }
+                            if value.to_source().len() > stash::MAX_VALUE {
+                                return Err(messages::STASH_TOO_BIG.error(&[&(stash::MAX_VALUE / 1024)]));
+                            }
+                            
+                            // Stashing nil clears the key, unstash would hand back nil either way
+                            if value == Literal::Nil {
+                                self.stash.remove(&key)?;
+                            } else {
+                                self.stash.set(&key, value.clone())?;
+                            }
+                            Ok(value)
+                        }
+                        "unstash" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::UNSTASH_ARGUMENTS.error(&[]));
+                            }
+                            let key = self.stash_key(&arguments[0])?;
+                            Ok(self.stash.get(&key)?.unwrap_or(Literal::Nil))
+                        }
+                        "draw.clear" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::CLEAR_ARGUMENTS.error(&[]));
+                            }
+                            self.drawing.clear();
+                            self.drawing_changed = true;
+                            Ok(Literal::Nil)
+                        }
+                        name if name.starts_with("draw.") => {
+                            let arguments = arguments.iter()
+                                .map(|argument| self.evaluate_expr(argument))
+                                .collect::<Result<Vec<_>, _>>()?;
+                            let shape = Shape::from_call(&name["draw.".len()..], &arguments)?;
+                            if self.drawing.len() >= draw::MAX_SHAPES {
+                                return Err(messages::CANVAS_FULL.error(&[&draw::MAX_SHAPES]));
+                            }
+                            self.drawing.push(shape);
+                            self.drawing_changed = true;
+                            Ok(Literal::Nil)
+                        }
+                        "sleep" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::SLEEP_ARGUMENTS.error(&[]));
+                            }
+                            let ms = match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(ms) if (0.0..=MAX_SLEEP_MS).contains(&ms) => ms,
+                                _ => return Err(messages::SLEEP_RANGE.error(&[&MAX_SLEEP_MS])),
+                            };
+                            self.nap_until(Instant::now() + Duration::from_secs_f64(ms / 1000.0))?;
+                            Ok(Literal::Nil)
+                        }
+                        "after" | "every" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::TIMER_ARGUMENTS.error(&[&name]));
+                            }
+                            // every(0, ...) would never let anything else have a turn
+                            let shortest = if name == "every" { 1.0 } else { 0.0 };
+                            let ms = match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(ms) if ms.is_finite() && ms >= shortest => ms,
+                                _ => return Err(messages::TIMER_TOO_SOON.error(&[&name, &shortest])),
+                            };
+                            let function = self.function_named(&arguments[1])?;
+                            if !function.params.is_empty() {
+                                return Err(messages::TIMER_PARAMETERS.error(&[&name, &function.name]));
+                            }
+                            let id = self.timers.schedule(function, Duration::from_secs_f64(ms / 1000.0), name == "every")?;
+                            Ok(Literal::Number(id as f64))
+                        }
+                        "cancelTimer" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::CANCEL_TIMER_ARGUMENTS.error(&[]));
+                            }
+                            match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(id) if id >= 0.0 && id.fract() == 0.0 => Ok(Literal::Boolean(self.timers.cancel(id as usize))),
+                                _ => Err(messages::NOT_A_TIMER.error(&[])),
+                            }
+                        }
+                        "gameLoop" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::GAME_LOOP_ARGUMENTS.error(&[]));
+                            }
+                            let fps = match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(fps) if (1.0..=MAX_FPS).contains(&fps) => fps,
+                                _ => return Err(messages::GAME_LOOP_FPS.error(&[&MAX_FPS])),
+                            };
+                            let update = self.function_named(&arguments[1])?;
+                            if update.params.len() > 1 {
+                                return Err(messages::GAME_LOOP_PARAMETERS.error(&[&update.name, &update.params.len()]));
+                            }
+                            self.run_game_loop(fps, &update).map(|_| Literal::Nil)
+                        }
+                        "seed" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::SEED_ARGUMENTS.error(&[]));
+                            }
+                            let seed = self.evaluate_expr(&arguments[0])?;
+                            self.reseed("seed", &seed)?;
+                            Ok(Literal::Nil)
+                        }
+                        "simulate" => {
+                            if !(2..=3).contains(&arguments.len()) {
+                                return Err(messages::SIMULATE_ARGUMENTS.error(&[]));
+                            }
+                            let runs = match self.evaluate_expr(&arguments[0])? {
+                                Literal::Number(n) if n.fract() == 0.0 && (1.0..=MAX_SIMULATIONS).contains(&n) => n as usize,
+                                _ => return Err(messages::SIMULATE_RUNS.error(&[&MAX_SIMULATIONS])),
+                            };
+                            let trial = self.function_named(&arguments[1])?;
+                            if trial.params.len() > 1 {
+                                return Err(messages::SIMULATE_PARAMETERS.error(&[&trial.name, &trial.params.len()]));
+                            }
+                            if let Some(seed) = arguments.get(2) {
+                                let seed = self.evaluate_expr(seed)?;
+                                self.reseed("simulate", &seed)?;
                             }
                             
-                            if self.should_continue {
-                                self.should_continue = false;
-                                continue;
+                            let mut results = Vec::with_capacity(runs);
+                            for run in 0..runs {
+                                let arguments = trial.params.iter().map(|_| Literal::Number(run as f64)).collect();
+                                results.push(self.invoke(&trial, arguments)?);
                             }
-                            
-                            if self.return_value.is_some() {
-                                break;
+                            Ok(Literal::Array(results))
+                        }
+                        "graphNew" => {
+                            let directed = match arguments.as_slice() {
+                                [] => false,
+                                [directed] => match self.evaluate_expr(directed)? {
+                                    Literal::Boolean(directed) => directed,
+                                    _ => return Err(messages::GRAPH_DIRECTED.error(&[])),
+                                },
+                                _ => return Err(messages::GRAPH_NEW_ARGUMENTS.error(&[])),
+                            };
+                            Ok(graphs::new(directed))
+                        }
+                        "addEdge" | "shortestPath" => {
+                            if arguments.len() != 3 {
+                                return Err(messages::EDGE_ARGUMENTS.error(&[&name]));
+                            }
+                            let graph = self.evaluate_expr(&arguments[0])?;
+                            let from = self.node_argument(name, &arguments[1])?;
+                            let to = self.node_argument(name, &arguments[2])?;
+                            match name.as_str() {
+                                "addEdge" => graphs::add_edge(&graph, &from, &to),
+                                _ => graphs::shortest_path(&graph, &from, &to),
                             }
                         }
-                        
-                        self.environment.pop_scope()?;
-                    }
-                    _ => return Err(ZLangError::new("Can only iterate over arrays bestie! 📚")),
-                }
-                Ok(None)
-            }
-            Stmt::Function { name, params, body } => {
-                let function = Function {
-                    name: name.clone(),
-                    params: params.clone(),
-                    body: body.clone(),
-                };
-                
-                self.functions.insert(name.clone(), function);
-                Ok(None)
-            }
-            Stmt::Return(expr) => {
-                let value = if let Some(expr) = expr {
-                    self.evaluate_expr(expr)?
-                } else {
-                    Literal::Nil
-                };
-                
-                self.return_value = Some(value);
-                Ok(None)
-            }
-            Stmt::Break => {
-                self.should_break = true;
-                Ok(None)
-            }
-            Stmt::Continue => {
-                self.should_continue = true;
-                Ok(None)
-            }
-            Stmt::Print(expr) => {
-                let value = self.evaluate_expr(expr)?;
-                Ok(Some(format!("{}", value)))
-            }
-            Stmt::Switch { expr, cases, default } => {
-                let switch_value = self.evaluate_expr(expr)?;
-                let mut executed = false;
-                
-                for (case_expr, statements) in cases {
-                    let case_value = self.evaluate_expr(case_expr)?;
-                    if self.values_equal(&switch_value, &case_value) {
-                        for stmt in statements {
-                            match self.execute_stmt(stmt) {
-                                Ok(_) => {},
-                                Err(e) if e.message.contains("break") => return Ok(None),
-                                Err(e) => return Err(e),
-                            }
-                        }
-                        executed = true;
-                        break;
-                    }
-                }
-                
-                if !executed {
-                    if let Some(default_stmts) = default {
-                        for stmt in default_stmts {
-                            self.execute_stmt(stmt)?;
+                        "neighbors" | "bfs" | "dfs" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::NODE_ARGUMENTS.error(&[&name]));
+                            }
+                            let graph = self.evaluate_expr(&arguments[0])?;
+                            let node = self.node_argument(name, &arguments[1])?;
+                            match name.as_str() {
+                                "neighbors" => graphs::neighbors(&graph, &node),
+                                "bfs" => graphs::bfs(&graph, &node),
+                                _ => graphs::dfs(&graph, &node),
+                            }
                         }
-                    }
-                }
-                
-                Ok(None)
-            }
-            Stmt::Try { try_block, catch_block, finally_block } => {
-                let mut try_result = Ok(None);
-                
-                // Execute try block
-                for stmt in try_block {
-                    match self.execute_stmt(stmt) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            try_result = Err(e);
-                            break;
+                        "heapNew" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::HEAP_NEW_ARGUMENTS.error(&[]));
+                            }
+                            Ok(Literal::Heap(Heap::default()))
                         }
-                    }
-                }
-                
-                // Execute catch block if there was an error
-                if try_result.is_err() {
-                    if let Some((error_var, catch_stmts)) = catch_block {
-                        if let Err(error) = &try_result {
-                            self.environment.define(error_var.clone(), Literal::String(error.to_string()));
+                        "heapPush" => {
+                            if arguments.len() != 3 {
+                                return Err(messages::HEAP_PUSH_ARGUMENTS.error(&[]));
+                            }
+                            let heap = self.heap_argument(name, &arguments[0])?;
+                            let value = self.evaluate_expr(&arguments[1])?;
+                            let priority = match self.evaluate_expr(&arguments[2])? {
+                                Literal::Number(priority) if !priority.is_nan() => priority,
+                                other => return Err(messages::PRIORITY_NOT_NUMBER.error(&[&TypeAnnotation::of(&other)])),
+                            };
+                            heap.push(value, priority);
+                            Ok(Literal::Nil)
                         }
-                        for stmt in catch_stmts {
-                            self.execute_stmt(stmt)?;
+                        "heapPop" | "heapSize" => {
+                            if arguments.len() != 1 {
+                                return Err(messages::HEAP_ARGUMENTS.error(&[&name]));
+                            }
+                            let heap = self.heap_argument(name, &arguments[0])?;
+                            match name.as_str() {
+                                // An empty heap pops nil, heapSize tells that apart from a nil that went in
+                                "heapPop" => Ok(heap.pop().unwrap_or(Literal::Nil)),
+                                _ => Ok(Literal::Number(heap.len() as f64)),
+                            }
                         }
-                        try_result = Ok(None); // Error was handled
-                    }
-                }
-                
-                // Always execute finally block
-                if let Some(finally_stmts) = finally_block {
-                    for stmt in finally_stmts {
-                        self.execute_stmt(stmt)?;
-                    }
-                }
-                
-                try_result
-            }
-            Stmt::Throw(expr) => {
-                let error_value = self.evaluate_expr(expr)?;
-                let error_message = match error_value {
-                    Literal::String(s) => s,
-                    _ => "Thrown error".to_string(),
-                };
-                Err(ZLangError::new(&error_message))
-            }
-        }
-    }
-    
-    fn values_equal(&self, left: &Literal, right: &Literal) -> bool {
-        match (left, right) {
-            (Literal::Number(a), Literal::Number(b)) => (a - b).abs() < f64::EPSILON,
-            (Literal::String(a), Literal::String(b)) => a == b,
-            (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
-            (Literal::Nil, Literal::Nil) => true,
-            _ => false,
-        }
-    }
-    
-    fn evaluate_expr(&mut self, expr: &Expr) -> Result<Literal, ZLangError> {
-        match expr {
-            Expr::Literal(literal) => Ok(literal.clone()),
-            Expr::Variable(name) => self.environment.get(name),
-            Expr::Assign { name, value } => {
-                let val = self.evaluate_expr(value)?;
-                self.environment.assign(name, val.clone())?;
-                Ok(val)
-            }
-            Expr::Binary { left, operator, right } => {
-                let left_val = self.evaluate_expr(left)?;
-                let right_val = self.evaluate_expr(right)?;
-                self.apply_binary_op(&left_val, operator, &right_val)
-            }
-            Expr::Unary { operator, right } => {
-                let right_val = self.evaluate_expr(right)?;
-                self.apply_unary_op(operator, &right_val)
-            }
-            Expr::Call { callee, arguments } => {
-                if let Expr::Variable(name) = callee.as_ref() {
-                    // Built-in functions
-                    match name.as_str() {
-                        "sqrt" => {
+                        "queue" | "stack" => {
+                            let items = match arguments.as_slice() {
+                                [] => Vec::new(),
+                                [items] => self.array_argument(name, items)?,
+                                _ => return Err(messages::DEQUE_NEW_ARGUMENTS.error(&[&name])),
+                            };
+                            let kind = if name == "queue" { deque::Kind::Queue } else { deque::Kind::Stack };
+                            Ok(Literal::Deque(Deque::new(kind, items)))
+                        }
+                        "push" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::PUSH_ARGUMENTS.error(&[]));
+                            }
+                            let deque = self.deque_argument(name, &arguments[0])?;
+                            let value = self.evaluate_expr(&arguments[1])?;
+                            deque.push(value);
+                            Ok(Literal::Nil)
+                        }
+                        "pop" | "peek" | "size" => {
                             if arguments.len() != 1 {
-                                return Err(ZLangError::new("sqrt expects 1 argument bestie! 📊"));
+                                return Err(messages::DEQUE_ARGUMENTS.error(&[&name]));
                             }
-                            let arg = self.evaluate_expr(&arguments[0])?;
-                            if let Literal::Number(n) = arg {
-                                if n < 0.0 {
-                                    return Err(ZLangError::new("Can't sqrt negative numbers, that's imaginary! 🤔"));
-                                }
-                                Ok(Literal::Number(n.sqrt()))
-                            } else {
-                                Err(ZLangError::new("sqrt only works with numbers! 🔢"))
+                            let deque = self.deque_argument(name, &arguments[0])?;
+                            // Nothing left pops and peeks nil, size tells that apart from a nil that went in
+                            match name.as_str() {
+                                "pop" => Ok(deque.pop().unwrap_or(Literal::Nil)),
+                                "peek" => Ok(deque.peek().unwrap_or(Literal::Nil)),
+                                _ => Ok(Literal::Number(deque.len() as f64)),
                             }
                         }
-                        "abs" => {
+                        "onKey" => {
                             if arguments.len() != 1 {
-                                return Err(ZLangError::new("abs expects 1 argument bestie! 📊"));
+                                return Err(messages::ON_KEY_ARGUMENTS.error(&[]));
                             }
-                            let arg = self.evaluate_expr(&arguments[0])?;
-                            if let Literal::Number(n) = arg {
-                                Ok(Literal::Number(n.abs()))
-                            } else {
-                                Err(ZLangError::new("abs only works with numbers! 🔢"))
+                            let handler = self.function_named(&arguments[0])?;
+                            if handler.params.len() != 1 {
+                                return Err(messages::ON_KEY_PARAMETERS.error(&[&handler.name]));
                             }
+                            // One handler at a time, a second onKey() takes over from the first
+                            self.key_handler = Some(handler);
+                            Ok(Literal::Nil)
                         }
-                        "random" => {
-                            if arguments.len() != 0 {
-                                return Err(ZLangError::new("random takes no arguments bestie! 🎲"));
+                        "runLoop" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::RUN_LOOP_ARGUMENTS.error(&[]));
+                            }
+                            if self.running_loop {
+                                return Err(messages::LOOP_ALREADY_RUNNING.error(&[]));
                             }
-                            // Simple pseudo-random number (0.0 to 1.0)
-                            use std::collections::hash_map::DefaultHasher;
-                            use std::hash::{Hash, Hasher};
-                            use std::time::{SystemTime, UNIX_EPOCH};
                             
-                            let mut hasher = DefaultHasher::new();
-                            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos().hash(&mut hasher);
-                            let hash = hasher.finish();
-                            let random_val = (hash % 1000) as f64 / 1000.0;
-                            Ok(Literal::Number(random_val))
+                            self.running_loop = true;
+                            let result = self.run_timers();
+                            self.running_loop = false;
+                            result.map(|_| Literal::Nil)
                         }
-                        "length" => {
-                            if arguments.len() != 1 {
-                                return Err(ZLangError::new("length expects 1 argument bestie! 📏"));
+                        "scope" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::SCOPE_ARGUMENTS.error(&[]));
                             }
-                            let arg = self.evaluate_expr(&arguments[0])?;
-                            match arg {
-                                Literal::String(s) => Ok(Literal::Number(s.len() as f64)),
-                                Literal::Array(arr) => Ok(Literal::Number(arr.len() as f64)),
-                                _ => Err(ZLangError::new("length only works with strings and arrays! 📝")),
+                            // A snapshot, so writing to it never touches the real variables
+                            Ok(Literal::Object(self.environment.visible_variables()))
+                        }
+                        "benchmark" => {
+                            if arguments.len() != 2 {
+                                return Err(messages::BENCHMARK_ARGUMENTS.error(&[]));
+                            }
+                            let function = self.function_named(&arguments[0])?;
+                            if !function.params.is_empty() {
+                                return Err(messages::BENCHMARK_PARAMETERS.error(&[&function.name]));
+                            }
+                            let iterations = match self.evaluate_expr(&arguments[1])? {
+                                Literal::Number(n) if n.fract() == 0.0 && (1.0..=MAX_BENCHMARK_ITERATIONS).contains(&n) => n as usize,
+                                _ => return Err(messages::BENCHMARK_ITERATIONS.error(&[&MAX_BENCHMARK_ITERATIONS])),
+                            };
+                            
+                            let mut times = Vec::with_capacity(iterations);
+                            for _ in 0..iterations {
+                                let start = Instant::now();
+                                self.invoke(&function, Vec::new())?;
+                                times.push(start.elapsed().as_secs_f64() * 1000.0);
+                            }
+                            let average = times.iter().sum::<f64>() / iterations as f64;
+                            let fastest = times.iter().copied().fold(f64::INFINITY, f64::min);
+                            let slowest = times.iter().copied().fold(0.0, f64::max);
+                            
+                            // Timings are clock readings, so a replay hands back the recorded ones
+                            let mut fields = HashMap::new();
+                            for (key, ms) in [("avgMs", average), ("minMs", fastest), ("maxMs", slowest)] {
+                                fields.insert(key.to_string(), self.outside_input("clock", |_| Literal::Number(ms))?);
                             }
+                            Ok(Literal::Object(fields))
                         }
-                        "uppercase" => {
-                            if arguments.len() != 1 {
-                                return Err(ZLangError::new("uppercase expects 1 argument bestie! 📝"));
+                        "opsSoFar" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::OPS_SO_FAR_ARGUMENTS.error(&[]));
                             }
-                            let arg = self.evaluate_expr(&arguments[0])?;
-                            if let Literal::String(s) = arg {
-                                Ok(Literal::String(s.to_uppercase()))
-                            } else {
-                                Err(ZLangError::new("uppercase only works with strings! 📝"))
+                            Ok(Literal::Number(self.ops as f64))
+                        }
+                        "memStats" => {
+                            if !arguments.is_empty() {
+                                return Err(messages::MEM_STATS_ARGUMENTS.error(&[]));
                             }
+                            let mut stats = MemStats::default();
+                            self.environment.for_each_value(|value| stats.add(value));
+                            Ok(Literal::Object(stats.to_object()))
                         }
-                        "split" => {
+                        "expect" => {
                             if arguments.len() != 2 {
-                                return Err(ZLangError::new("split expects 2 arguments (string, delimiter) bestie! ✂️"));
+                                return Err(messages::EXPECT_ARGUMENTS.error(&[]));
                             }
-                            let string_arg = self.evaluate_expr(&arguments[0])?;
-                            let delimiter_arg = self.evaluate_expr(&arguments[1])?;
+                            let value = self.evaluate_expr(&arguments[0])?;
+                            let type_name = match self.evaluate_expr(&arguments[1])? {
+                                Literal::String(s) => s,
+                                _ => return Err(messages::TYPE_NAME_NOT_STRING.error(&[])),
+                            };
+                            let expected = TypeAnnotation::from_name(&type_name).ok_or_else(|| messages::UNKNOWN_TYPE_NAME.error(&[&type_name]))?;
                             
-                            if let (Literal::String(s), Literal::String(delim)) = (string_arg, delimiter_arg) {
-                                let parts: Vec<Literal> = s.split(&delim)
-                                    .map(|part| Literal::String(part.to_string()))
-                                    .collect();
-                                Ok(Literal::Array(parts))
-                            } else {
-                                Err(ZLangError::new("split needs two strings (text, delimiter)! ✂️"))
+                            let actual = TypeAnnotation::of(&value);
+                            if expected != TypeAnnotation::Any && expected != actual {
+                                return Err(messages::TYPE_CHECK_FAILED.error(&[&line, &expected, &actual]));
                             }
+                            Ok(value)
                         }
-                        _ => {
-                            // User-defined function
-                            if let Some(function) = self.functions.get(name).cloned() {
-                                self.call_function(function, arguments)
-                            } else {
-                                Err(ZLangError::new(&format!("Undefined function '{}', that function doesn't exist bestie! 📞", name)))
-                            }
+                        _ if self.drama_types.contains_key(name) => {
+                            // Calling a drama type builds the value `drama` throws
+                            let message = match arguments.as_slice() {
+                                [] => Literal::String(format!("{} happened", name)),
+                                [message] => self.evaluate_expr(message)?,
+                                _ => return Err(messages::DRAMA_ARGUMENTS.error(&[&name])),
+                            };
+                            
+                            let mut fields = HashMap::new();
+                            fields.insert("type".to_string(), Literal::String(name.clone()));
+                            fields.insert("message".to_string(), message);
+                            Ok(Literal::Object(fields))
+                        }
+                        _ => self.call_named(name, arguments),
+                    }
+                } else if let Expr::Get { object, name, optional } = callee.as_ref() {
+                    // `p.move(3)` runs one of p's methods, any other dot is a field holding something to call.
+                    // `p?.move(3)` is nil when p is, and the method never hears about it
+                    let Some(object) = self.link(object, *optional)? else {
+                        return Ok(Literal::Nil);
+                    };
+                    let method = match &object {
+                        Literal::Instance(instance) => instance.method(name).map(|(method, parent)| (method.clone(), parent.cloned())),
+                        _ => None,
+                    };
+                    match (object, method) {
+                        (Literal::Instance(instance), Some((method, parent))) => self.call_method(&instance, method, parent, arguments),
+                        (object, _) => {
+                            let callee = self.field_of(object, name)?;
+                            self.call_value(callee, arguments)
                         }
                     }
                 } else {
-                    Err(ZLangError::new("Can only call functions, not other expressions! 🤙"))
-                }
-            }
-            Expr::Array(elements) => {
-                let mut values = Vec::new();
-                for element in elements {
-                    values.push(self.evaluate_expr(element)?);
+                    let callee = self.evaluate_expr(callee)?;
+                    self.call_value(callee, arguments)
                 }
-                Ok(Literal::Array(values))
             }
+            Expr::Function { params, rest, body } => Ok(Literal::Function(Function {
+                name: "lambda".to_string(),
+                params: params.clone(),
+                rest: *rest,
+                body: Arc::clone(body),
+                closure: self.environment.capture(),
+            })),
+            Expr::Array(elements) => Ok(Literal::Array(self.evaluate_items(elements)?)),
+            // The parser only writes these inside [ ] or a call's ( ), which lay them out themselves
+            Expr::Spread(_) => Err(messages::SPREAD_OUTSIDE.error(&[])),
             Expr::Object(pairs) => {
                 let mut map = std::collections::HashMap::new();
                 for (key, value_expr) in pairs {
@@ -399,34 +1941,190 @@ impl Interpreter {
                 }
                 Ok(Literal::Object(map))
             }
-            Expr::Index { object, index } => {
-                let obj_value = self.evaluate_expr(object)?;
-                let index_value = self.evaluate_expr(index)?;
+            Expr::Range { start, end, inclusive } => {
+                match (self.evaluate_expr(start)?, self.evaluate_expr(end)?) {
+                    (Literal::Number(start), Literal::Number(end)) => count_through(start, end, *inclusive).map(Literal::Array),
+                    (start, end) => Err(messages::RANGE_NOT_NUMBERS.error(&[&TypeAnnotation::of(&start), &TypeAnnotation::of(&end)])),
+                }
+            }
+            Expr::Conditional { condition, then_branch, else_branch } => {
+                let condition = self.evaluate_expr(condition)?;
+                if self.is_truthy(&condition) {
+                    self.evaluate_expr(then_branch)
+                } else {
+                    self.evaluate_expr(else_branch)
+                }
+            }
+            Expr::Propagate { value, line } => {
+                let result = self.evaluate_expr(value)?;
+                let fields = match &result {
+                    Literal::Object(fields) => fields,
+                    _ => return Err(not_a_result(*line)),
+                };
                 
-                match (obj_value, index_value) {
-                    (Literal::Array(arr), Literal::Number(idx)) => {
-                        let idx = idx as usize;
-                        if idx < arr.len() {
-                            Ok(arr[idx].clone())
-                        } else {
-                            Err(ZLangError::new("Array index out of bounds bestie! 📚"))
-                        }
+                match (fields.get("ok"), fields.get("value"), fields.get("error")) {
+                    (Some(Literal::Boolean(true)), Some(value), _) => Ok(value.clone()),
+                    (Some(Literal::Boolean(false)), _, Some(error)) => {
+                        // Only surfaces as an error when there's no function to return from
+                        let message = format!("'?' at line {} got nah({}) with no function to hand it back to 🎁", line, error);
+                        Err(ZLangError::propagating(&message, result.clone()))
                     }
-                    (Literal::Object(obj), Literal::String(key)) => {
-                        Ok(obj.get(&key).cloned().unwrap_or(Literal::Nil))
+                    _ => Err(not_a_result(*line)),
+                }
+            }
+            Expr::Index { .. } | Expr::Get { .. } => Ok(self.chain(expr)?.unwrap_or(Literal::Nil)),
+            Expr::Set { object, name, value } => {
+                let object = self.evaluate_expr(object)?;
+                let value = self.evaluate_expr(value)?;
+                match object {
+                    Literal::Instance(instance) if instance.set_field(name, value.clone()) => Ok(value),
+                    Literal::Instance(instance) if instance.squad().method(name).is_some() => {
+                        Err(messages::METHOD_NOT_SETTABLE.error(&[&instance.squad().name, name]))
                     }
-                    _ => Err(ZLangError::new("Invalid indexing operation, check your types! 🎯")),
+                    Literal::Instance(instance) => Err(no_such_member(&instance, name)),
+                    other => Err(messages::NOT_SETTABLE.error(&[name, &TypeAnnotation::of(&other)])),
+                }
+            }
+        }
+    }
+    
+    /// A run of `.name` and `[index]` links, None when a `?.` or `?[` in it met a nil - which
+    /// skips the rest of the run, so `user?.address.city` is nil when user is
+    fn chain(&mut self, expr: &Expr) -> Result<Option<Literal>, ZLangError> {
+        match expr {
+            Expr::Get { object, name, optional } => {
+                let Some(object) = self.link(object, *optional)? else {
+                    return Ok(None);
+                };
+                self.field_of(object, name).map(Some)
+            }
+            Expr::Index { object, index, optional } => {
+                let Some(object) = self.link(object, *optional)? else {
+                    return Ok(None);
+                };
+                let index = self.evaluate_expr(index)?;
+                self.index_of(object, index).map(Some)
+            }
+            other => self.evaluate_expr(other).map(Some),
+        }
+    }
+    
+    /// What a link of a chain looks inside - None to stop, when the chain already has or this is a `?` link and it's nil
+    fn link(&mut self, object: &Expr, optional: bool) -> Result<Option<Literal>, ZLangError> {
+        match self.chain(object)? {
+            Some(Literal::Nil) if optional => Ok(None),
+            object => Ok(object),
+        }
+    }
+    
+    /// `value[index]` - an array's item or an object's key (nil when it isn't there)
+    fn index_of(&self, value: Literal, index: Literal) -> Result<Literal, ZLangError> {
+        match (value, index) {
+            (Literal::Array(arr), Literal::Number(idx)) => {
+                let idx = idx as usize;
+                if idx < arr.len() {
+                    Ok(arr[idx].clone())
+                } else {
+                    Err(messages::INDEX_OUT_OF_BOUNDS.error(&[]))
                 }
             }
+            (Literal::Object(obj), Literal::String(key)) => {
+                Ok(obj.get(&key).cloned().unwrap_or(Literal::Nil))
+            }
+            _ => Err(messages::INVALID_INDEXING.error(&[])),
+        }
+    }
+    
+    /// `value.name` - an object's key (nil when it isn't there) or one of an instance's fields
+    fn field_of(&self, value: Literal, name: &str) -> Result<Literal, ZLangError> {
+        match value {
+            Literal::Object(fields) => Ok(fields.get(name).cloned().unwrap_or(Literal::Nil)),
+            Literal::Instance(instance) => match instance.field(name) {
+                Some(value) => Ok(value),
+                None if instance.squad().method(name).is_some() => Err(messages::METHOD_NOT_CALLED.error(&[&instance.squad().name, &name])),
+                None => Err(no_such_member(&instance, name)),
+            },
+            other => Err(messages::NOT_AN_OBJECT.error(&[&name, &TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    /// Call something that isn't called by name - a lambda, a function out of an object, or a squad
+    fn call_value(&mut self, callee: Literal, arguments: &[Expr]) -> Result<Literal, ZLangError> {
+        match callee {
+            Literal::Function(function) => self.call_function(function, arguments),
+            Literal::Squad(squad) => self.instantiate(squad, arguments),
+            other => Err(messages::CALLING_NON_FUNCTION.error(&[&TypeAnnotation::of(&other)])),
+        }
+    }
+    
+    /// `Point(1, 2)` - the values fill the fields in the order they're declared, the rest start at their defaults
+    fn instantiate(&mut self, squad: Arc<Squad>, arguments: &[Expr]) -> Result<Literal, ZLangError> {
+        if arguments.len() > squad.fields.len() {
+            return Err(messages::TOO_MANY_FIELD_VALUES.error(&[&squad.name, &squad.fields.len(), &arguments.len()]));
+        }
+        let mut values = Vec::new();
+        for argument in arguments {
+            values.push(self.evaluate_expr(argument)?);
+        }
+        
+        // Defaults are worked out where the squad was declared, in the scope that becomes the
+        // instance's fields - so each one sees the fields before it
+        self.environment.enter_call(squad.closure.as_ref());
+        let filled = self.fill_fields(&squad, values);
+        let fields = self.environment.current_fields();
+        self.environment.leave_call()?;
+        filled?;
+        Ok(Literal::Instance(Instance::new(squad, fields)))
+    }
+    
+    fn fill_fields(&mut self, squad: &Squad, values: Vec<Literal>) -> Result<(), ZLangError> {
+        let mut values = values.into_iter();
+        for (name, default) in &squad.fields {
+            let value = match (values.next(), default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => self.evaluate_expr(default)?,
+                (None, None) => Literal::Nil,
+            };
+            self.environment.define(name.clone(), value);
+        }
+        Ok(())
+    }
+    
+    /// `p.move(3)` - the method runs with p's fields as the scope just outside its own, so it reads
+    /// and assigns them like variables and every holder of p sees the change. p itself is `me`
+    /// (and `this`) in a scope between the two, so a parameter can hide either. When the squad
+    /// that declared the method comes from a `parent`, `og` is p looking for methods from there
+    fn call_method(&mut self, instance: &Instance, method: Function, parent: Option<Arc<Squad>>, arguments: &[Expr]) -> Result<Literal, ZLangError> {
+        let me = Literal::Instance(instance.itself());
+        let mut receivers = vec![("me".to_string(), me.clone()), ("this".to_string(), me)];
+        if let Some(parent) = parent {
+            receivers.push(("og".to_string(), Literal::Instance(instance.as_parent(parent))));
+        }
+        let closure = Closure::around(method.closure.as_ref(), instance.fields()).with_variables(receivers);
+        self.call_function(Function { closure: Some(closure), ..method }, arguments)
+    }
+    
+    /// Array items or call arguments, in order, with each `...spread` array's items laid out in its place
+    fn evaluate_items(&mut self, items: &[Expr]) -> Result<Vec<Literal>, ZLangError> {
+        let mut values = Vec::new();
+        for item in items {
+            match item {
+                Expr::Spread(spread) => match self.evaluate_expr(spread)? {
+                    Literal::Array(spread) => values.extend(spread),
+                    other => return Err(messages::SPREAD_NOT_ARRAY.error(&[&TypeAnnotation::of(&other)])),
+                },
+                item => values.push(self.evaluate_expr(item)?),
+            }
         }
+        Ok(values)
     }
     
     fn call_function(&mut self, function: Function, arguments: &[Expr]) -> Result<Literal, ZLangError> {
-        if arguments.len() != function.params.len() {
-            return Err(ZLangError::new(&format!(
-                "Function '{}' expects {} arguments but got {}, check your parameters bestie! 📊",
-                function.name, function.params.len(), arguments.len()
-            )));
+        if function.rest && arguments.len() + 1 < function.params.len() {
+            return Err(messages::TOO_FEW_ARGUMENTS.error(&[&function.name, &(function.params.len() - 1), &arguments.len()]));
+        }
+        if !function.rest && arguments.len() != function.params.len() {
+            return Err(messages::WRONG_ARGUMENT_COUNT.error(&[&function.name, &function.params.len(), &arguments.len()]));
         }
         
         // Evaluate arguments
@@ -435,30 +2133,49 @@ impl Interpreter {
             arg_values.push(self.evaluate_expr(arg)?);
         }
         
-        // Create new scope for function
-        self.environment.push_scope();
-        
-        // Bind parameters
-        for (param, value) in function.params.iter().zip(arg_values.iter()) {
-            self.environment.define(param.clone(), value.clone());
+        self.invoke(&function, arg_values)
+    }
+    
+    /// Run a function with already-evaluated arguments (arity is checked by the caller)
+    fn invoke(&mut self, function: &Function, arg_values: Vec<Literal>) -> Result<Literal, ZLangError> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(messages::TOO_MUCH_RECURSION.error(&[&function.name, &MAX_CALL_DEPTH]));
         }
         
-        // Execute function body
-        let mut result = Literal::Nil;
-        for stmt in &function.body {
-            self.execute_stmt(stmt)?;
-            
-            if let Some(return_val) = &self.return_value {
-                result = return_val.clone();
-                self.return_value = None;
-                break;
-            }
+        // The function runs in the scopes it was made in, not its caller's
+        self.environment.enter_call(function.closure.as_ref());
+        self.call_depth += 1;
+        
+        // Bind parameters, whatever's left over going to a ...rest one as an array
+        let mut arg_values = arg_values;
+        if function.rest {
+            let fixed = function.params.len() - 1;
+            let extra = arg_values.split_off(fixed.min(arg_values.len()));
+            arg_values.push(Literal::Array(extra));
         }
+        for (param, value) in function.params.iter().zip(arg_values) {
+            self.environment.define(param.clone(), value);
+        }
+        
+        // Execute function body, turning a `?` that bailed out into the function's return value
+        let result = match self.execute_function_body(&function.body) {
+            Err(ZLangError { propagating: Some(result), .. }) => Ok(*result),
+            result => result,
+        };
         
-        // Clean up scope
-        self.environment.pop_scope()?;
+        // Clean up scope, even when the body errored
+        self.call_depth -= 1;
+        self.environment.leave_call()?;
         
-        Ok(result)
+        result
+    }
+    
+    fn execute_function_body(&mut self, body: &[Stmt]) -> Result<Literal, ZLangError> {
+        match self.execute_block(body)? {
+            ControlFlow::Normal => Ok(Literal::Nil),
+            ControlFlow::Return(value) => Ok(value),
+            flow => Err(stray_control_flow(&flow)),
+        }
     }
     
     fn apply_binary_op(&self, left: &Literal, op: &BinaryOp, right: &Literal) -> Result<Literal, ZLangError> {
@@ -470,14 +2187,21 @@ impl Interpreter {
                     BinaryOp::Multiply => Ok(Literal::Number(l * r)),
                     BinaryOp::Divide => {
                         if *r == 0.0 {
-                            Err(ZLangError::new("Division by zero bestie, that's undefined! ➗"))
+                            Err(messages::DIVISION_BY_ZERO.error(&[]))
                         } else {
                             Ok(Literal::Number(l / r))
                         }
                     }
+                    BinaryOp::IntDivide => {
+                        if *r == 0.0 {
+                            Err(messages::DIVISION_BY_ZERO.error(&[]))
+                        } else {
+                            Ok(Literal::Number(floor_divmod(*l, *r).0))
+                        }
+                    }
                     BinaryOp::Modulo => {
                         if *r == 0.0 {
-                            Err(ZLangError::new("Modulo by zero, that's not how math works! 🤓"))
+                            Err(messages::MODULO_BY_ZERO.error(&[]))
                         } else {
                             Ok(Literal::Number(l % r))
                         }
@@ -488,7 +2212,10 @@ impl Interpreter {
                     BinaryOp::LessEqual => Ok(Literal::Boolean(l <= r)),
                     BinaryOp::Equal => Ok(Literal::Boolean((l - r).abs() < f64::EPSILON)),
                     BinaryOp::NotEqual => Ok(Literal::Boolean((l - r).abs() >= f64::EPSILON)),
-                    _ => Err(ZLangError::new("Invalid operation for numbers, that's not it! 🔢")),
+                    BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor | BinaryOp::ShiftLeft | BinaryOp::ShiftRight => {
+                        bitwise(*l, op, *r).map(Literal::Number)
+                    }
+                    _ => Err(messages::NUMBER_OPERATION.error(&[])),
                 }
             }
             (Literal::String(l), Literal::String(r)) => {
@@ -496,38 +2223,70 @@ impl Interpreter {
                     BinaryOp::Add => Ok(Literal::String(format!("{}{}", l, r))),
                     BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
                     BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
-                    _ => Err(ZLangError::new("Invalid operation for strings, strings don't do that! 📝")),
+                    _ => Err(messages::STRING_OPERATION.error(&[])),
                 }
             }
             // String + other types (concatenation)
             (Literal::String(l), other) => {
                 match op {
                     BinaryOp::Add => Ok(Literal::String(format!("{}{}", l, other))),
-                    _ => Err(ZLangError::new("Can only concatenate with strings using +, that's the vibe! 🔗")),
+                    _ => Err(messages::CONCATENATION.error(&[])),
                 }
             }
             // Other types + String (concatenation)
             (other, Literal::String(r)) => {
                 match op {
                     BinaryOp::Add => Ok(Literal::String(format!("{}{}", other, r))),
-                    _ => Err(ZLangError::new("Can only concatenate with strings using +, that's the vibe! 🔗")),
+                    _ => Err(messages::CONCATENATION.error(&[])),
                 }
             }
+            (Literal::Money(_), _) | (_, Literal::Money(_)) => money::apply(left, op, right),
             (Literal::Boolean(l), Literal::Boolean(r)) => {
                 match op {
                     BinaryOp::And => Ok(Literal::Boolean(*l && *r)),
                     BinaryOp::Or => Ok(Literal::Boolean(*l || *r)),
                     BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
                     BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
-                    _ => Err(ZLangError::new("Invalid operation for booleans, booleans are limited bestie! ❌")),
+                    _ => Err(messages::BOOLEAN_OPERATION.error(&[])),
                 }
             }
+            // The same function, passed around - not just one that happens to look alike
+            (Literal::Function(l), Literal::Function(r)) => {
+                let same = Arc::ptr_eq(&l.body, &r.body) && l.closure == r.closure;
+                match op {
+                    BinaryOp::Equal => Ok(Literal::Boolean(same)),
+                    BinaryOp::NotEqual => Ok(Literal::Boolean(!same)),
+                    _ => Err(messages::FUNCTION_COMPARISON.error(&[])),
+                }
+            }
+            // The same heap, not one holding the same things
+            (Literal::Heap(l), Literal::Heap(r)) => match op {
+                BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
+                BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
+                _ => Err(messages::HEAP_COMPARISON.error(&[])),
+            },
+            (Literal::Deque(l), Literal::Deque(r)) => match op {
+                BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
+                BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
+                _ => Err(messages::DEQUE_COMPARISON.error(&[])),
+            },
+            // The same squad or instance, not one with the same fields
+            (Literal::Squad(l), Literal::Squad(r)) => match op {
+                BinaryOp::Equal => Ok(Literal::Boolean(Arc::ptr_eq(l, r))),
+                BinaryOp::NotEqual => Ok(Literal::Boolean(!Arc::ptr_eq(l, r))),
+                _ => Err(messages::SQUAD_COMPARISON.error(&[])),
+            },
+            (Literal::Instance(l), Literal::Instance(r)) => match op {
+                BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
+                BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
+                _ => Err(messages::INSTANCE_COMPARISON.error(&[])),
+            },
             _ => {
                 // Mixed types or unsupported operations
                 match op {
                     BinaryOp::Equal => Ok(Literal::Boolean(false)),
                     BinaryOp::NotEqual => Ok(Literal::Boolean(true)),
-                    _ => Err(ZLangError::new("Type mismatch in operation, these types don't work together! 🔀")),
+                    _ => Err(messages::TYPE_MISMATCH.error(&[])),
                 }
             }
         }
@@ -535,25 +2294,183 @@ impl Interpreter {
     
     fn apply_unary_op(&self, op: &UnaryOp, operand: &Literal) -> Result<Literal, ZLangError> {
         match op {
-            UnaryOp::Minus => {
-                if let Literal::Number(n) = operand {
-                    Ok(Literal::Number(-n))
-                } else {
-                    Err(ZLangError::new("Can only negate numbers, that's basic math! ➖"))
-                }
-            }
+            UnaryOp::Minus => match operand {
+                Literal::Number(n) => Ok(Literal::Number(-n)),
+                Literal::Money(cents) => Ok(Literal::Money(-cents)),
+                _ => Err(messages::NEGATE_NON_NUMBER.error(&[])),
+            },
+            UnaryOp::Plus => to_number(operand).map(Literal::Number),
             UnaryOp::Not => Ok(Literal::Boolean(!self.is_truthy(operand))),
+            UnaryOp::BitNot => match operand {
+                Literal::Number(n) => Ok(Literal::Number(!whole_bits(*n)? as f64)),
+                _ => Err(messages::FLIP_NOT_NUMBER.error(&[])),
+            },
         }
     }
     
-    fn is_truthy(&self, literal: &Literal) -> bool {
+    /// What `sus`, `while` and `? :` count as true - everything but cap, nil, zero and empties
+    pub fn is_truthy(&self, literal: &Literal) -> bool {
         match literal {
             Literal::Boolean(b) => *b,
             Literal::Nil => false,
             Literal::Number(n) => *n != 0.0,
+            Literal::Money(cents) => *cents != 0,
             Literal::String(s) => !s.is_empty(),
             Literal::Array(arr) => !arr.is_empty(),
             Literal::Object(obj) => !obj.is_empty(),
+            Literal::Function(_) | Literal::Squad(_) | Literal::Instance(_) => true,
+            Literal::Heap(heap) => !heap.is_empty(),
+            Literal::Deque(deque) => !deque.is_empty(),
+        }
+    }
+}
+
+/// The `{ok, value, error}` shape shared by ok(), nah() and attempt()
+fn result_object(ok: bool, value: Literal, error: Literal) -> Literal {
+    let mut fields = HashMap::new();
+    fields.insert("ok".to_string(), Literal::Boolean(ok));
+    fields.insert("value".to_string(), value);
+    fields.insert("error".to_string(), error);
+    Literal::Object(fields)
+}
+
+/// `a ~/ b` and what's left over. The quotient rounds down, so the remainder takes b's sign:
+/// -7 minutes is -1 hour and 53 minutes, same as a clock would say
+fn floor_divmod(a: f64, b: f64) -> (f64, f64) {
+    let quotient = (a / b).floor();
+    (quotient, a - b * quotient)
+}
+
+/// `&`, `|`, `^`, `<<` and `>>` on the whole parts of two numbers. `>>` keeps the sign, so -8 >> 1 is -4
+fn bitwise(l: f64, op: &BinaryOp, r: f64) -> Result<f64, ZLangError> {
+    let (l, r) = (whole_bits(l)?, whole_bits(r)?);
+    if matches!(op, BinaryOp::ShiftLeft | BinaryOp::ShiftRight) && !(0..64).contains(&r) {
+        return Err(messages::SHIFT_RANGE.error(&[&r]));
+    }
+    let bits = match op {
+        BinaryOp::BitAnd => l & r,
+        BinaryOp::BitOr => l | r,
+        BinaryOp::BitXor => l ^ r,
+        BinaryOp::ShiftLeft => l << r,
+        BinaryOp::ShiftRight => l >> r,
+        _ => unreachable!("only bitwise operators get here"),
+    };
+    Ok(bits as f64)
+}
+
+/// What num() and `+x` make of a value: numbers as they are, strings written like a number
+/// (`" -3.5 "` and `"1e3"` too), fr and cap as 1 and 0, and money as its amount. Anything
+/// else, a string like "12abc" included, is an error rather than a guess
+fn to_number(value: &Literal) -> Result<f64, ZLangError> {
+    match value {
+        Literal::Number(n) => Ok(*n),
+        Literal::Boolean(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        Literal::Money(cents) => Ok(*cents as f64 / 100.0),
+        Literal::String(text) => {
+            let trimmed = text.trim();
+            // Leaves out what Rust would also read, like "inf" and "NaN"
+            let looks_numeric = trimmed.chars().any(|c| c.is_ascii_digit())
+                && trimmed.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+            match trimmed.parse::<f64>() {
+                Ok(n) if looks_numeric && n.is_finite() => Ok(n),
+                _ => Err(messages::NOT_A_NUMBER.error(&[text])),
+            }
+        }
+        other => Err(messages::CANT_BE_NUMBER.error(&[&TypeAnnotation::of(other)])),
+    }
+}
+
+/// The numbers `start..end` counts through in steps of 1, down when end is the smaller one.
+/// `..=` takes end too when the steps land on it, `0.5..=2` stops at 1.5
+fn count_through(start: f64, end: f64, inclusive: bool) -> Result<Vec<Literal>, ZLangError> {
+    let step = if end < start { -1.0 } else { 1.0 };
+    let distance = (end - start).abs();
+    let count = if inclusive { distance.floor() + 1.0 } else { distance.ceil() };
+    // A NaN or infinite end lands here too
+    if count.is_nan() || count > MAX_RANGE {
+        return Err(messages::RANGE_TOO_BIG.error(&[&Literal::Number(start), &Literal::Number(end), &MAX_RANGE]));
+    }
+    Ok((0..count as usize).map(|i| Literal::Number(start + step * i as f64)).collect())
+}
+
+/// A number's whole part as the 64-bit integer bitwise operators work on, 5.9 is 5
+fn whole_bits(n: f64) -> Result<i64, ZLangError> {
+    if !n.is_finite() || n.abs() >= i64::MAX as f64 {
+        return Err(messages::NOT_64_BITS.error(&[&n]));
+    }
+    Ok(n.trunc() as i64)
+}
+
+/// The statistic a builtin like avg() or stddev() asks for, over at least one number (sum() takes none too).
+/// stddev is the population one, every number counts as the whole class
+fn summarize(statistic: &str, mut numbers: Vec<f64>) -> f64 {
+    // Folded from 0.0, summing nothing with .sum() comes out as -0
+    let total = numbers.iter().fold(0.0, |total, n| total + n);
+    let count = numbers.len() as f64;
+    let mean = total / count;
+    match statistic {
+        "sum" => total,
+        "avg" => mean,
+        "median" => {
+            numbers.sort_by(f64::total_cmp);
+            let middle = numbers.len() / 2;
+            if numbers.len().is_multiple_of(2) {
+                (numbers[middle - 1] + numbers[middle]) / 2.0
+            } else {
+                numbers[middle]
+            }
+        }
+        "stddev" => (numbers.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count).sqrt(),
+        "minOf" => numbers.into_iter().fold(f64::INFINITY, f64::min),
+        _ => numbers.into_iter().fold(f64::NEG_INFINITY, f64::max),
+    }
+}
+
+/// How two sort keys line up - numbers, strings and booleans each only compare with their own kind
+fn compare_values(a: &Literal, b: &Literal) -> Result<cmp::Ordering, ZLangError> {
+    match (a, b) {
+        (Literal::Number(a), Literal::Number(b)) => Ok(a.total_cmp(b)),
+        (Literal::String(a), Literal::String(b)) => Ok(a.cmp(b)),
+        (Literal::Boolean(a), Literal::Boolean(b)) => Ok(a.cmp(b)),
+        _ => Err(messages::UNSORTABLE.error(&[&TypeAnnotation::of(a), &TypeAnnotation::of(b)])),
+    }
+}
+
+/// A stable merge sort. `compare` runs ZLang code that can fail or contradict itself,
+/// and the standard library's sort is allowed to panic on a comparator like that
+fn merge_sort<T>(mut items: Vec<T>, compare: &mut impl FnMut(&T, &T) -> Result<cmp::Ordering, ZLangError>) -> Result<Vec<T>, ZLangError> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let mut left = merge_sort(items, compare)?.into_iter().peekable();
+    let mut right = merge_sort(right, compare)?.into_iter().peekable();
+    
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // Only a strictly smaller right side jumps ahead, ties stay in order
+        if compare(r, l)? == cmp::Ordering::Less {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
         }
     }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+fn no_such_member(instance: &Instance, name: &str) -> ZLangError {
+    let squad = instance.squad();
+    messages::NO_SUCH_MEMBER.error(&[&squad.name, &name, &similar::did_you_mean(name, squad.member_names())])
+}
+
+fn not_a_result(line: usize) -> ZLangError {
+    messages::NOT_A_RESULT.error(&[&line])
+}
+
+/// `slay` or `ghost` that escaped every loop around it
+fn stray_control_flow(flow: &ControlFlow) -> ZLangError {
+    let keyword = if *flow == ControlFlow::Break { "slay" } else { "ghost" };
+    messages::STRAY_CONTROL_FLOW.error(&[&keyword])
 }
@@ -0,0 +1,79 @@
+//! ZLang JSON helpers - Just enough JSON to talk to browsers and tools
+//! No serde here, we keep it dependency-light bestie 📦
+
+use crate::ast::Literal;
+use crate::money;
+
+/// Escape `s` for use inside a JSON string literal. Other control characters (the ANSI escapes
+/// from `colored()`, say) aren't allowed raw in JSON either, they go out as `\u00XX`
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `s` as a quoted JSON string
+pub fn string(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
+
+/// A ZLang value as the JSON value it maps to - nil is null, object keys come out sorted.
+/// JSON has no NaN or infinity, so those turn into null too, and a function, heap, queue, stack, squad or
+/// instance is its `<flex ...>`/`<heap of n>`/`Point {x: 1}` label
+pub fn value(value: &Literal) -> String {
+    match value {
+        Literal::Number(n) if n.is_finite() => n.to_string(),
+        Literal::Number(_) | Literal::Nil => "null".to_string(),
+        Literal::Money(cents) => money::plain(*cents),
+        Literal::String(s) => string(s),
+        Literal::Boolean(b) => b.to_string(),
+        Literal::Function(_) | Literal::Heap(_) | Literal::Deque(_) | Literal::Squad(_) | Literal::Instance(_) => string(&value.to_string()),
+        Literal::Array(items) => {
+            let items: Vec<String> = items.iter().map(self::value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Literal::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys.iter().map(|key| format!("{}: {}", string(key), self::value(&fields[*key]))).collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+/// The text inside a quoted JSON string, and whatever follows its closing quote.
+/// None when `text` doesn't start with a complete string
+pub fn parse_string(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.strip_prefix('"')?.char_indices();
+    let mut parsed = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((parsed, &text[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'n' => parsed.push('\n'),
+                'r' => parsed.push('\r'),
+                't' => parsed.push('\t'),
+                'b' => parsed.push('\u{8}'),
+                'f' => parsed.push('\u{c}'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                    parsed.push(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)?);
+                }
+                escaped @ ('"' | '\\' | '/') => parsed.push(escaped),
+                _ => return None,
+            },
+            c => parsed.push(c),
+        }
+    }
+    None
+}
@@ -1,49 +1,112 @@
 //! ZLang Lexer - Turns source code into tokens
 //! This is where we break down the code into bite-sized pieces
 
+use std::collections::HashMap;
+
 use crate::token::{Token, TokenType};
 use crate::error::ZLangError;
+use crate::messages;
 
 pub struct Lexer {
     source: Vec<char>,
+    start: usize,
     current: usize,
     line: usize,
     column: usize,
+    trivia: bool,
+    recovery: bool,
+    aliases: HashMap<String, TokenType>,
 }
 
 impl Lexer {
     pub fn new(source: &str) -> Self {
         Self {
             source: source.chars().collect(),
+            start: 0,
             current: 0,
             line: 1,
             column: 1,
+            trivia: false,
+            recovery: false,
+            aliases: HashMap::new(),
         }
     }
     
+    /// Also produce Whitespace and Comment tokens, so every char of the source lands in exactly
+    /// one token and joining their lexemes gives the source back. The parser doesn't want these
+    pub fn with_trivia(mut self) -> Self {
+        self.trivia = true;
+        self
+    }
+    
+    /// Turn whatever can't be lexed into an Error token and carry on after it, instead of giving
+    /// up on the whole source - one stray `@` shouldn't leave an editor with nothing to color
+    pub fn with_recovery(mut self) -> Self {
+        self.recovery = true;
+        self
+    }
+    
+    /// Extra words that lex as keywords, like `make` for `bet` (the `alias.` settings in .zlangrc)
+    pub fn with_aliases(mut self, aliases: HashMap<String, TokenType>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+    
+    /// Count lines from `line` instead of 1, for lexing a slice out of the middle of a file
+    pub fn starting_at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+    
     pub fn tokenize(&mut self) -> Result<Vec<Token>, ZLangError> {
         let mut tokens = Vec::new();
         
-        while !self.is_at_end() {
+        while let Some(token) = self.next_token()? {
+            tokens.push(token);
+        }
+        
+        let end = self.source.len();
+        tokens.push(Token::new(TokenType::Eof, self.line, self.column, end..end));
+        Ok(tokens)
+    }
+    
+    /// Every token up to the first error instead of nothing at all - for highlighting
+    /// half-typed lines like `bruh "hel`. No trailing Eof, and no first error with recovery on
+    pub fn tokenize_partial(&mut self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        while let Ok(Some(token)) = self.next_token() {
+            tokens.push(token);
+        }
+        tokens
+    }
+    
+    fn next_token(&mut self) -> Result<Option<Token>, ZLangError> {
+        loop {
+            let (whitespace_start, line, column) = (self.current, self.line, self.column);
             self.skip_whitespace();
             
+            if self.trivia && self.current > whitespace_start {
+                let whitespace = self.source[whitespace_start..self.current].iter().collect();
+                return Ok(Some(Token::new(TokenType::Whitespace(whitespace), line, column, whitespace_start..self.current)));
+            }
+            
             if self.is_at_end() {
-                break;
+                return Ok(None);
             }
             
+            self.start = self.current;
             let start_line = self.line;
             let start_column = self.column;
             
-            match self.scan_token()? {
-                Some(token_type) => {
-                    tokens.push(Token::new(token_type, start_line, start_column));
-                }
-                None => {} // Skip whitespace and comments
-            }
+            // None means whitespace or a comment, keep going
+            let token_type = match self.scan_token() {
+                Ok(Some(token_type)) => token_type,
+                Ok(None) => continue,
+                Err(error) if self.recovery => TokenType::Error(error.message),
+                Err(error) => return Err(error),
+            };
+            return Ok(Some(Token::new(token_type, start_line, start_column, self.start..self.current)));
         }
-        
-        tokens.push(Token::new(TokenType::Eof, self.line, self.column));
-        Ok(tokens)
     }
     
     fn scan_token(&mut self) -> Result<Option<TokenType>, ZLangError> {
@@ -58,23 +121,53 @@ impl Lexer {
             '[' => Ok(Some(TokenType::LeftBracket)),
             ']' => Ok(Some(TokenType::RightBracket)),
             ',' => Ok(Some(TokenType::Comma)),
+            '.' => {
+                if self.peek() == '.' && self.peek_next() == '.' {
+                    self.advance();
+                    self.advance();
+                    Ok(Some(TokenType::Ellipsis))
+                } else if self.match_char('.') {
+                    if self.match_char('=') { Ok(Some(TokenType::DotDotEqual)) } else { Ok(Some(TokenType::DotDot)) }
+                } else {
+                    Ok(Some(TokenType::Dot))
+                }
+            }
             ';' => Ok(Some(TokenType::Semicolon)),
             ':' => Ok(Some(TokenType::Colon)),
+            // Written together `?.` and `?[` look inside only what isn't nil - so `cond ?[1] : [2]` needs its space
+            '?' if self.peek() == '.' && self.peek_next() != '.' => {
+                self.advance();
+                Ok(Some(TokenType::QuestionDot))
+            }
+            '?' if self.match_char('[') => Ok(Some(TokenType::QuestionBracket)),
+            '?' => Ok(Some(TokenType::Question)),
             '+' => Ok(Some(TokenType::Plus)),
             '-' => Ok(Some(TokenType::Minus)),
             '*' => Ok(Some(TokenType::Star)),
             '/' => {
                 if self.match_char('/') {
-                    // Single line comment - skip to end of line
-                    while self.peek() != '\n' && !self.is_at_end() {
+                    // Single line comment - skip to end of line, leaving a CRLF's \r out of it
+                    while self.peek() != '\n' && !self.at_crlf() && !self.is_at_end() {
                         self.advance();
                     }
-                    Ok(None)
+                    if self.trivia {
+                        Ok(Some(TokenType::Comment(self.current_lexeme())))
+                    } else {
+                        Ok(None)
+                    }
                 } else {
                     Ok(Some(TokenType::Slash))
                 }
             }
             '%' => Ok(Some(TokenType::Percent)),
+            '~' => {
+                if self.match_char('/') {
+                    Ok(Some(TokenType::TildeSlash))
+                } else {
+                    Ok(Some(TokenType::Tilde))
+                }
+            }
+            '^' => Ok(Some(TokenType::Caret)),
             '!' => {
                 if self.match_char('=') {
                     Ok(Some(TokenType::BangEqual))
@@ -85,6 +178,8 @@ impl Lexer {
             '=' => {
                 if self.match_char('=') {
                     Ok(Some(TokenType::EqualEqual))
+                } else if self.match_char('>') {
+                    Ok(Some(TokenType::FatArrow))
                 } else {
                     Ok(Some(TokenType::Equal))
                 }
@@ -92,6 +187,8 @@ impl Lexer {
             '>' => {
                 if self.match_char('=') {
                     Ok(Some(TokenType::GreaterEqual))
+                } else if self.match_char('>') {
+                    Ok(Some(TokenType::GreaterGreater))
                 } else {
                     Ok(Some(TokenType::Greater))
                 }
@@ -99,6 +196,8 @@ impl Lexer {
             '<' => {
                 if self.match_char('=') {
                     Ok(Some(TokenType::LessEqual))
+                } else if self.match_char('<') {
+                    Ok(Some(TokenType::LessLess))
                 } else {
                     Ok(Some(TokenType::Less))
                 }
@@ -107,14 +206,14 @@ impl Lexer {
                 if self.match_char('&') {
                     Ok(Some(TokenType::And))
                 } else {
-                    Err(ZLangError::new(&format!("Unexpected character '&' at line {}, that ain't it", self.line)))
+                    Ok(Some(TokenType::Ampersand))
                 }
             }
             '|' => {
                 if self.match_char('|') {
                     Ok(Some(TokenType::Or))
                 } else {
-                    Err(ZLangError::new(&format!("Unexpected character '|' at line {}, not the vibe", self.line)))
+                    Ok(Some(TokenType::Pipe))
                 }
             }
             '\n' => {
@@ -122,52 +221,86 @@ impl Lexer {
                 self.column = 1;
                 Ok(Some(TokenType::Newline))
             }
-            '"' => self.string(),
+            '"' => self.string(false),
+            'r' if self.peek() == '"' => {
+                self.advance();
+                self.string(true)
+            }
             _ => {
                 if c.is_ascii_digit() {
                     self.number()
                 } else if c.is_alphabetic() || c == '_' {
                     self.identifier()
+                } else if let Some(keyword) = TokenType::from_emoji(c) {
+                    // Some editors tack a variation selector onto emoji, it's not part of the keyword
+                    if self.peek() == '\u{FE0F}' {
+                        self.advance();
+                    }
+                    Ok(Some(keyword))
                 } else {
-                    Err(ZLangError::new(&format!("Unexpected character '{}' at line {}, this ain't valid bestie", c, self.line)))
+                    Err(messages::UNEXPECTED_CHARACTER.error(&[&c, &self.line]))
                 }
             }
         }
     }
     
-    fn string(&mut self) -> Result<Option<TokenType>, ZLangError> {
-        let mut value = String::new();
+    /// A string after its opening quote. `"""` opens a multi-line one, and `r` in front
+    /// (`raw`) keeps backslashes as they are
+    fn string(&mut self, raw: bool) -> Result<Option<TokenType>, ZLangError> {
+        let triple = self.peek() == '"' && self.peek_next() == '"';
+        if triple {
+            self.advance();
+            self.advance();
+        }
         
-        while self.peek() != '"' && !self.is_at_end() {
+        let mut text = String::new();
+        // Reported once the whole string's been read, so recovery picks up after it and not halfway in
+        let mut bad_escape = None;
+        while !self.is_at_end() && !self.at_closing_quote(triple) {
+            // A string spanning lines means the same thing whatever line endings the file has
+            if self.at_crlf() {
+                self.advance();
+                continue;
+            }
             if self.peek() == '\n' {
                 self.line += 1;
                 self.column = 1;
             }
-            if self.peek() == '\\' {
-                self.advance(); // consume backslash
-                match self.advance() {
-                    'n' => value.push('\n'),
-                    't' => value.push('\t'),
-                    'r' => value.push('\r'),
-                    '\\' => value.push('\\'),
-                    '"' => value.push('"'),
-                    c => {
-                        return Err(ZLangError::new(&format!("Invalid escape sequence '\\{}' at line {}, that's sus", c, self.line)));
-                    }
+            // Escapes get checked now and turned into characters once the string is complete
+            if self.peek() == '\\' && !raw {
+                text.push(self.advance());
+                if self.is_at_end() {
+                    break; // Reported as unterminated below
+                }
+                let c = self.advance();
+                if !matches!(c, 'n' | 't' | 'r' | '\\' | '"') && bad_escape.is_none() {
+                    bad_escape = Some(messages::INVALID_ESCAPE.error(&[&c, &self.line]));
                 }
+                text.push(c);
             } else {
-                value.push(self.advance());
+                text.push(self.advance());
             }
         }
         
         if self.is_at_end() {
-            return Err(ZLangError::new(&format!("Unterminated string at line {}, where's the closing quote bestie?", self.line)));
+            let unterminated = if triple { messages::UNTERMINATED_TRIPLE_STRING } else { messages::UNTERMINATED_STRING };
+            return Err(bad_escape.unwrap_or_else(|| unterminated.error(&[&self.line])));
         }
         
-        // Consume closing quote
-        self.advance();
+        // Consume closing quote(s)
+        for _ in 0..if triple { 3 } else { 1 } {
+            self.advance();
+        }
+        if let Some(error) = bad_escape {
+            return Err(error);
+        }
         
-        Ok(Some(TokenType::String(value)))
+        let text = if triple { dedent(&text) } else { text };
+        Ok(Some(TokenType::String(if raw { text } else { unescape(&text) })))
+    }
+    
+    fn at_closing_quote(&self, triple: bool) -> bool {
+        self.peek() == '"' && (!triple || (self.peek_next() == '"' && self.source.get(self.current + 2) == Some(&'"')))
     }
     
     fn number(&mut self) -> Result<Option<TokenType>, ZLangError> {
@@ -183,9 +316,18 @@ impl Lexer {
             }
         }
         
-        let value: String = self.source[self.current - self.get_current_token_length()..self.current].iter().collect();
+        // A second point or letters stuck on the end, like `1.2.3` or `3px`, make it something that isn't a number
+        let second_point = self.peek() == '.' && self.peek_next().is_ascii_digit();
+        if second_point || self.peek().is_alphanumeric() || self.peek() == '_' {
+            while self.peek().is_alphanumeric() || self.peek() == '_' || (self.peek() == '.' && self.peek_next().is_alphanumeric()) {
+                self.advance();
+            }
+            return Err(messages::INVALID_NUMBER.error(&[&self.current_lexeme(), &self.line]));
+        }
+        
+        let value = self.current_lexeme();
         let number = value.parse::<f64>().map_err(|_| {
-            ZLangError::new(&format!("Invalid number '{}' at line {}, that's not how numbers work chief", value, self.line))
+            messages::INVALID_NUMBER.error(&[&value, &self.line])
         })?;
         
         Ok(Some(TokenType::Number(number)))
@@ -196,7 +338,7 @@ impl Lexer {
             self.advance();
         }
         
-        let text: String = self.source[self.current - self.get_current_token_length()..self.current].iter().collect();
+        let text = self.current_lexeme();
         
         // Check for multi-word keywords
         let multi_word_token = self.check_multi_word_keyword(&text)?;
@@ -216,6 +358,10 @@ impl Lexer {
             "lowkey" => TokenType::Lowkey,
             "grind" => TokenType::Grind,
             "highkey" => TokenType::Highkey,
+            "bruh" if self.peek() == '!' && self.peek_next() != '=' => {
+                self.advance();
+                TokenType::BruhBang
+            }
             "bruh" => TokenType::Bruh,
             "slay" => TokenType::Slay,
             "ghost" => TokenType::Ghost,
@@ -224,7 +370,20 @@ impl Lexer {
             "drama" => TokenType::Drama,
             "frfr" => TokenType::Frfr,
             "in" => TokenType::In,
-            _ => TokenType::Identifier(text),
+            "global" => TokenType::Global,
+            "outer" => TokenType::Outer,
+            "needs" => TokenType::Needs,
+            "pull" => TokenType::Pull,
+            "vibecheck" => TokenType::VibeCheck,
+            // Operators spelled as words, the same tokens as their symbols
+            "is" => TokenType::EqualEqual,
+            "aint" => TokenType::BangEqual,
+            "and" => TokenType::And,
+            "or" => TokenType::Or,
+            _ => match self.aliases.get(&text) {
+                Some(keyword) => keyword.clone(),
+                None => TokenType::Identifier(text),
+            },
         };
         
         Ok(Some(token_type))
@@ -309,25 +468,11 @@ impl Lexer {
         }
     }
     
-    fn get_current_token_length(&self) -> usize {
-        // This is a simple implementation - in a real lexer you'd track this better
-        let mut length = 1;
-        let mut pos = self.current - 1;
-        
-        while pos > 0 {
-            let c = self.source[pos - 1];
-            if c.is_whitespace() || self.is_operator_char(c) {
-                break;
-            }
-            length += 1;
-            pos -= 1;
-        }
-        
-        length
-    }
-    
-    fn is_operator_char(&self, c: char) -> bool {
-        matches!(c, '(' | ')' | '{' | '}' | '[' | ']' | ',' | ';' | ':' | '+' | '-' | '*' | '/' | '%' | '!' | '=' | '>' | '<' | '&' | '|')
+    fn current_lexeme(&self) -> String {
+        // Everything scanned since the token started - never walks outside the source
+        let end = self.current.min(self.source.len());
+        let start = self.start.min(end);
+        self.source[start..end].iter().collect()
     }
     
     fn skip_whitespace(&mut self) {
@@ -342,12 +487,22 @@ impl Lexer {
     }
     
     fn advance(&mut self) -> char {
+        if self.is_at_end() {
+            return '\0';
+        }
         let c = self.source[self.current];
         self.current += 1;
-        self.column += 1;
+        // The \r of a CRLF is part of the line ending, not a column
+        if !(c == '\r' && self.peek() == '\n') {
+            self.column += 1;
+        }
         c
     }
     
+    fn at_crlf(&self) -> bool {
+        self.peek() == '\r' && self.peek_next() == '\n'
+    }
+    
     fn match_char(&mut self, expected: char) -> bool {
         if self.is_at_end() || self.source[self.current] != expected {
             false
@@ -378,3 +533,44 @@ impl Lexer {
         self.current >= self.source.len()
     }
 }
+
+/// Backslash escapes into the characters they stand for - `string` already turned away bad ones
+fn unescape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some(other) => value.push(other),
+            None => {}
+        }
+    }
+    value
+}
+
+/// A `"""` string without the line break right after the opening quotes, the line the closing
+/// quotes sit on, or the indentation every line shares - so it can be indented with the code around it
+fn dedent(text: &str) -> String {
+    let spans_lines = text.contains('\n');
+    let text = text.strip_prefix('\n').unwrap_or(text);
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if spans_lines && lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let indent = lines.iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indent_of(line))
+        .min()
+        .unwrap_or(0);
+    
+    let lines: Vec<&str> = lines.iter().map(|line| &line[indent.min(indent_of(line))..]).collect();
+    lines.join("\n")
+}
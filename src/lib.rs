@@ -0,0 +1,54 @@
+//! ZLang - A Gen Z Programming Language Interpreter
+//! The library half of ZLang, so the CLI, benches and fuzzers all run the same code 💯
+
+pub mod lexer;
+pub mod parser;
+pub mod interpreter;
+pub mod resolver;
+pub mod typechecker;
+pub mod token;
+pub mod ast;
+pub mod environment;
+pub mod error;
+pub mod formatter;
+pub mod web_server;
+pub mod bench;
+pub mod stats;
+pub mod highlight;
+pub mod incremental;
+pub mod rename;
+pub mod json;
+pub mod graph;
+pub mod deadcode;
+pub mod explain;
+pub mod steps;
+pub mod gallery;
+pub mod console;
+pub mod websocket;
+pub mod config;
+pub mod grade;
+pub mod achievements;
+pub mod challenges;
+pub mod collab;
+pub mod stash;
+pub mod draw;
+pub mod ansi;
+pub mod money;
+pub mod similar;
+pub mod markdown;
+pub mod url;
+pub mod timers;
+pub mod replay;
+pub mod memory;
+pub mod rng;
+pub mod graphs;
+pub mod heap;
+pub mod deque;
+pub mod syntax;
+pub mod tree;
+pub mod messages;
+pub mod modules;
+pub mod fetch;
+pub mod squad;
+pub mod pragmas;
+pub mod memes;
@@ -1,63 +1,240 @@
 //! ZLang - A Gen Z Programming Language Interpreter
 //! Built with pure Rust, no cap! 💯
 
-mod lexer;
-mod parser;
-mod interpreter;
-mod token;
-mod ast;
-mod environment;
-mod error;
-mod formatter;
-mod web_server;
-
 use std::env;
 use std::fs;
-use std::io::{self, Write};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use zlang::bench;
+use zlang::stats;
+use zlang::rename;
+use zlang::graph::CallGraph;
+use zlang::deadcode::{self, UnusedKind};
+use zlang::explain;
+use zlang::gallery;
+use zlang::grade::{self, Assignment, Outcome};
+use zlang::ansi;
+use zlang::json;
+use zlang::syntax;
+use zlang::tree;
+use zlang::messages;
+use zlang::modules::{self, DiskFiles};
+use zlang::pragmas::{Pragmas, Rule};
+use zlang::console::{self, CaptureConsole, StdConsole};
+use zlang::config::{Config, LintLevel};
+use zlang::lexer::Lexer;
+use zlang::parser::Parser;
+use zlang::interpreter::{ExecutionResult, Interpreter, Snapshot, INTERPRETER_STACK_SIZE};
+use zlang::resolver::Resolver;
+use zlang::replay::Tape;
+use zlang::stash::FileStash;
+use zlang::steps::{self, SpanIndex};
+use zlang::token::TokenType;
+use zlang::typechecker::TypeChecker;
+use zlang::error::ZLangError;
+use zlang::formatter::Formatter;
+use zlang::web_server;
+use zlang::highlight::{self, Theme};
 
-use lexer::Lexer;
-use parser::Parser;
-use interpreter::Interpreter;
-use error::ZLangError;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    // Run everything on a thread with a roomy stack so deep (but legit) recursion in
+    // ZLang code hits our call depth limit instead of overflowing the native stack
+    let cli = thread::Builder::new()
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(run_cli);
+    
+    match cli.map(|handle| handle.join()) {
+        Ok(Ok(())) => {}
+        _ => process::exit(101),
+    }
+}
+
+fn run_cli() {
+    console::prepare_terminal();
     
-    // Print the sick ZLang banner
-    print_banner();
+    // A broken .zlangrc shouldn't lock you out of ZLang, complain and carry on with the defaults
+    let mut config = Config::load().unwrap_or_else(|e| {
+        eprintln!("⚠️  {}", e);
+        Config::default()
+    });
     
-    match args.len() {
-        1 => {
+    // `--key=value` beats whatever the .zlangrc said
+    let (overrides, args): (Vec<String>, Vec<String>) = env::args()
+        .partition(|arg| arg.starts_with("--") && arg.contains('='));
+    for arg in &overrides {
+        let (key, value) = arg[2..].split_once('=').unwrap_or_default();
+        if let Err(e) = config.set(key, value) {
+            eprintln!("❌ {}: {}", arg, e);
+            process::exit(1);
+        }
+    }
+    let args = config.with_default_flags(args);
+    messages::set_lang(config.lang);
+    let (args, tape_flag) = take_tape_flag(args).unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        process::exit(1);
+    });
+    
+    // Print the sick ZLang banner - except when the output is meant to be piped into a file
+    let piped = matches!(args.get(1).map(String::as_str), Some("emojify") | Some("de-emojify") | Some("graph") | Some("ast"))
+        || args.iter().any(|arg| arg == "--json");
+    if config.banner && !piped {
+        print_banner();
+    }
+    
+    let runs_a_script = match args.get(1).map(String::as_str) {
+        Some("run") => args.len() == 3,
+        Some("--web" | "-w" | "bench" | "examples") | None => false,
+        Some(_) => args.len() == 2,
+    };
+    if tape_flag.is_some() && !runs_a_script {
+        eprintln!("❌ --record and --replay go with running a script, like zlang run game.zlang --record run.json 📼");
+        process::exit(1);
+    }
+    
+    match args.get(1).map(String::as_str) {
+        None => {
             // No file provided, start REPL
-            println!("💬 Starting ZLang REPL... Type 'exit' to bounce!");
-            run_repl();
-        }
-        2 => {
-            let arg = &args[1];
-            if arg == "--web" || arg == "-w" {
-                // Start web server for interactive coding
-                println!("🌐 Starting ZLang Web Server for interactive coding...");
-                if let Err(e) = web_server::start_web_server() {
-                    eprintln!("❌ Web server failed: {}", e);
-                    process::exit(1);
-                }
-            } else {
-                // File provided, execute it
-                let filename = arg;
-                if let Err(e) = run_file(filename) {
-                    eprintln!("❌ That's not it chief: {}", e);
-                    process::exit(1);
-                }
+            if !config.greeting.is_empty() {
+                println!("{}", config.greeting);
+            }
+            run_repl(&config);
+        }
+        Some("--web") | Some("-w") if args.len() == 2 => {
+            // Start web server for interactive coding
+            println!("🌐 Starting ZLang Web Server for interactive coding...");
+            if let Err(e) = web_server::start_web_server() {
+                eprintln!("❌ Web server failed: {}", e);
+                process::exit(1);
+            }
+        }
+        Some("bench") if args.len() <= 3 => {
+            if let Err(e) = run_bench(args.get(2).map(String::as_str)) {
+                eprintln!("❌ Benchmarks took an L: {}", e);
+                process::exit(1);
+            }
+        }
+        Some("typecheck") if args.len() == 3 => {
+            if let Err(e) = run_typecheck(&args[2], &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("stats") if args.len() == 3 => {
+            if let Err(e) = run_stats(&args[2]) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some(command @ ("emojify" | "de-emojify")) if args.len() == 3 => {
+            if let Err(e) = run_emojify(&args[2], command == "emojify", &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("rename") if args.len() == 5 || args.len() == 6 => {
+            if let Err(e) = run_rename(&args[2], &args[3], &args[4], args.get(5).map(String::as_str)) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("lint") if args.len() == 3 => {
+            if let Err(e) = run_lint(&args[2], &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("explain") if args.len() == 3 => {
+            let explained = if is_error_code(&args[2]) { run_explain_code(&args[2], &config) } else { run_explain(&args[2]) };
+            if let Err(e) = explained {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("examples") if args.len() <= 3 => {
+            if let Err(e) = run_examples(args.get(2).map(String::as_str)) {
+                report(&e);
+                process::exit(1);
+            }
+        }
+        Some("test") if args.len() <= 4 && args.get(2).map(String::as_str) == Some("--examples") => {
+            if let Err(e) = run_example_tests(args.get(3).map(String::as_str).unwrap_or("examples")) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("graph") if args.len() == 3 || (args.len() == 4 && args[3] == "--json") => {
+            if let Err(e) = run_graph(&args[2], args.len() == 4, &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("ast") if args.len() == 3 || (args.len() == 4 && args[3] == "--pretty") => {
+            if let Err(e) = run_ast(&args[2], args.len() == 4, &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("grade") if args.len() == 3 || args.len() == 4 => {
+            if let Err(e) = run_grade(&args[2], args.get(3).map(String::as_str), &config) {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        }
+        Some("run") if args.len() == 4 && args[3] == "--json" => {
+            // Always answers with JSON, the exit code says whether the program made it
+            if !run_json(&args[2], &config) {
+                process::exit(1);
+            }
+        }
+        Some("run") if args.len() == 3 => {
+            if let Err(e) = run_file(&args[2], &config, tape_flag.as_ref()) {
+                report(&e);
+                process::exit(1);
+            }
+        }
+        Some(filename) if args.len() == 2 => {
+            // File provided, execute it
+            if let Err(e) = run_file(filename, &config, tape_flag.as_ref()) {
+                report(&e);
+                process::exit(1);
             }
         }
         _ => {
-            eprintln!("💀 Usage: zlang [script.zlang] or zlang --web");
+            eprintln!("💀 Usage: zlang [script.zlang] | zlang run <script.zlang> [--json | --record <run.json> | --replay <run.json>] | zlang --web | zlang bench [iterations] | zlang typecheck <script.zlang> | zlang stats <script.zlang> | zlang emojify|de-emojify <script.zlang> | zlang rename <script.zlang> <old> <new> [line] | zlang graph <script.zlang> [--json] | zlang ast <script.zlang> [--pretty] | zlang lint <script.zlang> | zlang explain \"<expression>\"|<code like Z0023> | zlang examples [name] | zlang test --examples [dir] | zlang grade <assignment.toml> [student.zlang]");
             process::exit(1);
         }
     }
 }
 
+/// A run that went wrong, with where to read more about it
+fn report(e: &ZLangError) {
+    eprintln!("❌ {}", messages::NOT_IT.render(&[e]));
+    explain_hint(e);
+}
+
+fn explain_hint(e: &ZLangError) {
+    if let Some(code) = e.code {
+        eprintln!("💡 {}", messages::EXPLAIN_HINT.render(&[&code]));
+    }
+}
+
 fn print_banner() {
     println!(r#"
  ______ _                        
@@ -74,16 +251,64 @@ Built by Gen Z, for Gen Z. No cap! 💯
 "#);
 }
 
-fn run_repl() {
-    let mut interpreter = Interpreter::new();
+/// How many changes `:undo` can walk back
+const UNDO_LIMIT: usize = 50;
+
+/// The REPL's inputs that changed something, and what each one replaced - most recent last
+#[derive(Default)]
+struct Timeline {
+    undo_stack: VecDeque<(String, Snapshot)>,
+}
+
+impl Timeline {
+    fn run(&mut self, interpreter: &mut Interpreter, input: &str, aliases: &HashMap<String, TokenType>) -> Result<ExecutionResult, ZLangError> {
+        let before = interpreter.snapshot();
+        let result = execute_code(interpreter, input, aliases);
+        
+        // Failed inputs count too, they can get halfway before blowing up
+        if interpreter.snapshot() != before {
+            if self.undo_stack.len() == UNDO_LIMIT {
+                self.undo_stack.pop_front();
+            }
+            self.undo_stack.push_back((input.to_string(), before));
+        }
+        result
+    }
+    
+    /// `:undo` - rewind to before the last input that changed something, and say which one that was
+    fn undo(&mut self, interpreter: &mut Interpreter) -> String {
+        match self.undo_stack.pop_back() {
+            Some((undone, snapshot)) => {
+                interpreter.restore(snapshot);
+                format!("⏪ Undid `{}`, it never happened bestie", undone)
+            }
+            None => "🫥 Nothing to undo, you're at the start of the timeline".to_string(),
+        }
+    }
+}
+
+fn run_repl(config: &Config) {
+    let interrupt = interrupt_flag();
+    let mut interpreter = Interpreter::new()
+        .with_console(Box::new(StdConsole { prefix: "📤 " }))
+        .with_capabilities(config.capabilities)
+        .with_stash(Box::new(FileStash::in_home()))
+        .with_interrupt(Arc::clone(&interrupt));
+    let aliases = config.keyword_aliases();
+    let mut timeline = Timeline::default();
+    
+    let mut editor = match Editor::<ReplHelper, DefaultHistory>::new() {
+        Ok(editor) => editor,
+        Err(e) => {
+            eprintln!("💀 Couldn't start the line editor: {}", e);
+            return;
+        }
+    };
+    editor.set_helper(Some(ReplHelper { theme: config.theme, matching_bracket: Cell::new(None) }));
     
     loop {
-        print!("zlang> ");
-        io::stdout().flush().unwrap();
-        
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
+        match editor.readline(&config.prompt) {
+            Ok(input) => {
                 let input = input.trim();
                 
                 if input == "exit" || input == "quit" {
@@ -94,16 +319,39 @@ fn run_repl() {
                 if input.is_empty() {
                     continue;
                 }
+                let _ = editor.add_history_entry(input);
+                
+                if input == ":vars" {
+                    print_variables(&interpreter);
+                    continue;
+                }
+                
+                if input == ":undo" {
+                    println!("{}", timeline.undo(&mut interpreter));
+                    continue;
+                }
                 
-                match execute_code(&mut interpreter, input) {
+                interrupt.store(false, Ordering::Relaxed);
+                match timeline.run(&mut interpreter, input, &aliases) {
                     Ok(result) => {
-                        if !result.is_empty() {
-                            println!("📤 {}", result);
+                        if let Some(value) = &result.value {
+                            println!("👉 {}", value.inspect());
                         }
                     }
-                    Err(e) => eprintln!("❌ {}", e),
+                    Err(e) => {
+                        eprintln!("❌ {}", e);
+                        explain_hint(&e);
+                    }
                 }
             }
+            // Ctrl+C while typing throws the line away, like any shell
+            Err(ReadlineError::Interrupted) => {
+                println!("🫥 Cancelled (Ctrl+D or 'exit' to bounce)");
+            }
+            Err(ReadlineError::Eof) => {
+                println!("👋 Peace out! Catch you later!");
+                break;
+            }
             Err(e) => {
                 eprintln!("💀 Failed to read input: {}", e);
                 break;
@@ -112,33 +360,617 @@ fn run_repl() {
     }
 }
 
-fn run_file(filename: &str) -> Result<(), ZLangError> {
+/// A flag that Ctrl+C sets instead of killing the process, for `Interpreter::with_interrupt`
+fn interrupt_flag() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let handler_flag = Arc::clone(&flag);
+    if let Err(e) = ctrlc::set_handler(move || handler_flag.store(true, Ordering::Relaxed)) {
+        eprintln!("⚠️  Couldn't catch Ctrl+C, it'll quit ZLang instead of stopping the code: {}", e);
+    }
+    flag
+}
+
+/// Line editor hooks for the REPL - colors and bracket matching come from `zlang::highlight`
+struct ReplHelper {
+    theme: Theme,
+    /// Byte offset of the bracket matching the one at the cursor, if any
+    matching_bracket: Cell<Option<usize>>,
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        Cow::Owned(highlight::highlight(line, self.matching_bracket.get(), &self.theme))
+    }
+    
+    fn highlight_char(&self, line: &str, pos: usize, forced: bool) -> bool {
+        if forced {
+            // Enter was pressed - only redraw if there's a flash to clear
+            return self.matching_bracket.replace(None).is_some();
+        }
+        self.matching_bracket.set(highlight::matching_bracket(line, pos));
+        true
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+fn print_variables(interpreter: &Interpreter) {
+    let variables = interpreter.variables();
+    if variables.is_empty() {
+        println!("🫥 No variables yet, `bet` something first!");
+        return;
+    }
+    
+    for (name, value) in variables {
+        println!("   {} = {}", name, value);
+    }
+}
+
+fn run_file(filename: &str, config: &Config, tape_flag: Option<&TapeFlag>) -> Result<(), ZLangError> {
     let source = fs::read_to_string(filename)
         .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    let tape = match tape_flag {
+        None => None,
+        Some(TapeFlag::Record(_)) => Some(Tape::recording()),
+        Some(TapeFlag::Replay(path)) => {
+            let recording = fs::read_to_string(path)
+                .map_err(|_| ZLangError::new(&format!("Can't find the recording '{}' bestie 📼", path)))?;
+            Some(Tape::replaying(&recording)?)
+        }
+    };
+    
+    println!("🚀 {}", messages::RUNNING.render(&[&filename]));
+    let mut interpreter = Interpreter::new()
+        .with_console(Box::new(StdConsole { prefix: "" }))
+        .with_capabilities(config.capabilities)
+        .with_program_file(Path::new(filename))
+        .with_stash(Box::new(FileStash::in_home()))
+        .with_interrupt(interrupt_flag());
+    if let Some(tape) = &tape {
+        interpreter = interpreter.with_tape(tape.clone());
+    }
+    
+    // Whatever printed before Ctrl+C already streamed out, just say where it stopped
+    let outcome = execute_code(&mut interpreter, &source, &config.keyword_aliases()).map(|result| {
+        if !result.drawing.is_empty() {
+            println!("🎨 Drew {} shape(s), run it in the playground (zlang --web) to see them", result.drawing.len());
+        }
+    }).map_err(|e| {
+        match interpreter.stopped_at() {
+            Some(span) => {
+                let line = source.chars().take(span.start).filter(|c| *c == '\n').count() + 1;
+                let text = source.lines().nth(line - 1).unwrap_or("").trim();
+                ZLangError { message: format!("{}\n📍 Stopped at line {}: {}", e, line, text), kind: None, code: e.code, propagating: None }
+            }
+            None => e,
+        }
+    });
+    
+    // A run that failed is the one worth keeping, so the recording gets written either way
+    match (tape_flag, &tape) {
+        (Some(TapeFlag::Record(path)), Some(tape)) => {
+            fs::write(path, tape.to_json())
+                .map_err(|e| ZLangError::new(&format!("Couldn't write the recording to '{}': {} 📼", path, e)))?;
+            println!("📼 Recorded {} input(s) to {}, --replay {} runs it the same way again", tape.len(), path, path);
+        }
+        (Some(TapeFlag::Replay(_)), Some(tape)) if outcome.is_ok() && !tape.is_empty() => {
+            eprintln!("⚠️  The run finished with {} recorded input(s) left over, did the script change?", tape.len());
+        }
+        _ => {}
+    }
+    outcome
+}
+
+/// `--record <file>` writes down a run's random(), spill() and clock values, `--replay <file>` plays them back
+enum TapeFlag {
+    Record(String),
+    Replay(String),
+}
+
+/// Pull `--record <file>` or `--replay <file>` out of the arguments, wherever it sits
+fn take_tape_flag(mut args: Vec<String>) -> Result<(Vec<String>, Option<TapeFlag>), String> {
+    let Some(index) = args.iter().position(|arg| arg == "--record" || arg == "--replay") else {
+        return Ok((args, None));
+    };
+    if index + 1 >= args.len() {
+        return Err(format!("{} needs a file, like {} run.json 📼", args[index], args[index]));
+    }
+    let path = args.remove(index + 1);
+    let flag = match args.remove(index).as_str() {
+        "--record" => TapeFlag::Record(path),
+        _ => TapeFlag::Replay(path),
+    };
+    if args.iter().any(|arg| arg == "--record" || arg == "--replay") {
+        return Err("Pick one of --record and --replay, a run can't do both 📼".to_string());
+    }
+    Ok((args, Some(flag)))
+}
+
+/// Run a script for a program to read: `{output, errors, warnings, duration}` on stdout, nothing else.
+/// `duration` is in seconds, output printed before an error is kept. Returns whether the run succeeded
+fn run_json(filename: &str, config: &Config) -> bool {
+    let output = CaptureConsole::default();
+    let mut interpreter = Interpreter::new()
+        .with_console(Box::new(output.clone()))
+        .with_capabilities(config.capabilities)
+        .with_program_file(Path::new(filename))
+        .with_stash(Box::new(FileStash::in_home()))
+        .with_interrupt(interrupt_flag());
+    let mut warnings = Vec::new();
+    let started = Instant::now();
+    
+    let errors = match fs::read_to_string(filename) {
+        Err(_) => vec![format!("Can't find that file '{}' bestie 📁", filename)],
+        Ok(source) => {
+            let statements = Lexer::new(&source)
+                .with_aliases(config.keyword_aliases())
+                .tokenize()
+                .and_then(|tokens| Parser::new(tokens).parse());
+            match statements {
+                Err(e) => vec![e.to_string()],
+                Ok(statements) => {
+                    let pragmas = Pragmas::scan(&source, config.keyword_aliases());
+                    let diagnostics = Resolver::with_known_names(interpreter.pulled_names(&statements))
+                        .with_pragmas(pragmas.clone())
+                        .resolve(&statements);
+                    interpreter.follow_pragmas(pragmas);
+                    warnings.extend(diagnostics.warnings);
+                    if !diagnostics.errors.is_empty() {
+                        diagnostics.errors.iter().map(ToString::to_string).collect()
+                    } else {
+                        match interpreter.interpret(statements) {
+                            Ok(result) => {
+                                warnings.extend(result.warnings);
+                                Vec::new()
+                            }
+                            Err(e) => {
+                                warnings.extend(interpreter.take_warnings());
+                                vec![e.to_string()]
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    
+    let strings = |lines: &[String]| lines.iter().map(|line| json::string(line)).collect::<Vec<_>>().join(", ");
+    println!(
+        "{{\"output\": [{}], \"errors\": [{}], \"warnings\": [{}], \"duration\": {}}}",
+        strings(&output.lines()),
+        strings(&errors),
+        strings(&warnings),
+        started.elapsed().as_secs_f64()
+    );
+    errors.is_empty()
+}
+
+/// Grade a student's file against every case in the assignment, rubric style
+fn run_grade(assignment_path: &str, student: Option<&str>, config: &Config) -> Result<(), ZLangError> {
+    let assignment_source = fs::read_to_string(assignment_path)
+        .map_err(|_| ZLangError::new(&format!("Can't find that assignment '{}' bestie 📁", assignment_path)))?;
+    let assignment = Assignment::parse(&assignment_source)
+        .map_err(|e| ZLangError::new(&format!("{}: {}", assignment_path, e)))?;
+    
+    let student = match student {
+        Some(student) => PathBuf::from(student),
+        None => assignment.student_file(Path::new(assignment_path))
+            .ok_or_else(|| ZLangError::new("Which file am I grading? Pass it after the assignment or set `file` in it 🤷"))?,
+    };
+    let source = fs::read_to_string(&student)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", student.display())))?;
+    
+    println!("📝 Grading {}{}", student.display(), assignment.title.as_ref().map_or(String::new(), |title| format!(" for {}", title)));
+    println!();
+    
+    let results = grade::grade(&source, &assignment, config.capabilities);
+    let width = results.iter().map(|result| result.name.chars().count()).max().unwrap_or(0);
+    for result in &results {
+        let (icon, note) = match &result.outcome {
+            Outcome::Passed => ("✅", String::new()),
+            Outcome::WrongOutput { line, got, expected } => ("❌", format!(
+                "line {} was '{}', expected '{}'",
+                line,
+                got.as_deref().unwrap_or("<nothing>"),
+                expected.as_deref().unwrap_or("<nothing>")
+            )),
+            Outcome::Failed(error) => ("💥", error.clone()),
+            Outcome::TimedOut => ("⏱️", "ran out of time".to_string()),
+        };
+        let row = format!("{} {:<width$}  {:>5}/{:<5} {}", icon, result.name, result.earned(), result.points, note, width = width);
+        println!("{}", row.trim_end());
+    }
+    
+    let earned: f64 = results.iter().map(|result| result.earned()).sum();
+    let total = assignment.total_points();
+    println!();
+    println!("🏆 Score: {}/{} ({:.0}%)", earned, total, if total > 0.0 { earned / total * 100.0 } else { 0.0 });
+    Ok(())
+}
+
+/// List the gallery, or run one example from it
+fn run_examples(name: Option<&str>) -> Result<(), ZLangError> {
+    let Some(name) = name else {
+        println!("🖼️  Examples (run one with `zlang examples <name>`):");
+        for example in gallery::EXAMPLES {
+            println!("   {:<14} {}", example.name, example.title);
+        }
+        return Ok(());
+    };
+    
+    let example = gallery::find(name)
+        .ok_or_else(|| ZLangError::new(&format!("No example called '{}', run `zlang examples` to see them all 🖼️", name)))?;
     
-    println!("🚀 Running {}...", filename);
-    let mut interpreter = Interpreter::new();
+    println!("🚀 Running the {} example...", example.title);
+    let mut interpreter = Interpreter::new().with_console(Box::new(StdConsole { prefix: "" }));
+    execute_code(&mut interpreter, example.source, &HashMap::new()).map(|_| ())
+}
+
+/// Run every .zlang file under `dir`. Each has to finish without an error, and match
+/// the `.expected` file next to it when there is one. The ones in a `steps` folder run
+/// the way the playground's step-through slider runs them, the ones in a `repl` folder
+/// get typed into the REPL a line at a time
+fn run_example_tests(dir: &str) -> Result<(), ZLangError> {
+    let mut files = Vec::new();
+    collect_examples(Path::new(dir), &mut files)
+        .map_err(|e| ZLangError::new(&format!("Can't read '{}' bestie: {} 📁", dir, e)))?;
+    files.sort();
+    
+    let mut failures = 0;
+    for file in &files {
+        let source = fs::read_to_string(file)
+            .map_err(|e| ZLangError::new(&format!("Can't read '{}' bestie: {} 📁", file.display(), e)))?;
+        let expected = fs::read_to_string(file.with_extension("expected")).ok();
+        
+        let folder = file.parent().and_then(Path::file_name).and_then(|folder| folder.to_str());
+        let run = match folder {
+            Some("steps") => step_through(&source),
+            Some("repl") => Ok(type_into_repl(file, &source)),
+            _ => execute_code(&mut Interpreter::new().with_program_file(file), &source, &HashMap::new()).map(|result| result.output),
+        };
+        let problem = match (run, expected) {
+            (Err(e), _) => Some(e.to_string()),
+            (Ok(output), Some(expected)) => {
+                // Line by line, so an .expected file checked out with CRLF endings still matches
+                let output = output.join("\n");
+                let got: Vec<&str> = output.trim_end().lines().collect();
+                let wanted: Vec<&str> = expected.trim_end().lines().collect();
+                (0..got.len().max(wanted.len())).find(|&i| got.get(i) != wanted.get(i)).map(|line| format!(
+                    "output line {} was '{}', expected '{}'",
+                    line + 1,
+                    got.get(line).unwrap_or(&"<nothing>"),
+                    wanted.get(line).unwrap_or(&"<nothing>")
+                ))
+            }
+            (Ok(_), None) => None,
+        };
+        
+        match problem {
+            None => println!("✅ {}", file.display()),
+            Some(problem) => {
+                failures += 1;
+                println!("❌ {} - {}", file.display(), problem);
+            }
+        }
+    }
+    
+    println!();
+    if failures > 0 {
+        return Err(ZLangError::new(&format!("{} of {} examples took an L 💀", failures, files.len())));
+    }
+    println!("🏆 All {} examples passed, the docs aren't lying 💯", files.len());
+    Ok(())
+}
+
+/// What a step-through run printed, with a last line saying so when it hit the step limit
+fn step_through(source: &str) -> Result<Vec<String>, ZLangError> {
+    let (statements, spans) = Parser::new(Lexer::new(source).tokenize()?).parse_with_statement_spans()?;
+    let trace = steps::run(statements, &spans, steps::LIMIT);
+    if let Some(error) = trace.error {
+        return Err(ZLangError::new(&error));
+    }
     
-    match execute_code(&mut interpreter, &source) {
-        Ok(result) => {
-            if !result.is_empty() {
-                println!("{}", result);
+    let mut output: Vec<String> = trace.steps.into_iter().flat_map(|step| step.output).collect();
+    if trace.truncated {
+        output.push(format!("🛑 cut off at the {} step limit", steps::LIMIT));
+    }
+    Ok(output)
+}
+
+/// What the REPL shows for each line of `source` typed in, pulling files next to `file` -
+/// comment lines are left out, and an input that fails shows its error and the session carries on
+fn type_into_repl(file: &Path, source: &str) -> Vec<String> {
+    let mut interpreter = Interpreter::new().with_program_file(file);
+    let mut timeline = Timeline::default();
+    let mut shown = Vec::new();
+    for input in source.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with("//")) {
+        shown.push(format!("> {}", input));
+        if input == ":undo" {
+            shown.push(timeline.undo(&mut interpreter));
+            continue;
+        }
+        match timeline.run(&mut interpreter, input, &HashMap::new()) {
+            Ok(result) => {
+                shown.extend(result.output);
+                shown.extend(result.value.map(|value| format!("👉 {}", value.inspect())));
             }
-            Ok(())
+            Err(e) => shown.push(format!("❌ {}", e)),
         }
-        Err(e) => Err(e),
     }
+    shown
+}
+
+fn collect_examples(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        // `.zlang_cache` holds what pulled links served, those aren't examples of their own
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_dir() && !hidden {
+            collect_examples(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "zlang") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn run_typecheck(filename: &str, config: &Config) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    
+    let tokens = Lexer::new(&source).with_aliases(config.keyword_aliases()).tokenize()?;
+    let statements = Parser::new(tokens).parse()?;
+    
+    let errors = TypeChecker::new().check(&statements);
+    if errors.is_empty() {
+        println!("✅ {} has no type drama, we're so back 🏷️", filename);
+        return Ok(());
+    }
+    
+    for error in &errors {
+        eprintln!("🏷️  {}", error);
+    }
+    Err(ZLangError::new(&format!("Found {} type error(s) in {} 💀", errors.len(), filename)))
 }
 
-fn execute_code(interpreter: &mut Interpreter, source: &str) -> Result<String, ZLangError> {
+/// Static checks without running anything: the resolver's findings plus dead code,
+/// as loud as the `lint.` settings say
+fn run_lint(filename: &str, config: &Config) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    
+    let statements = Parser::new(Lexer::new(&source).with_aliases(config.keyword_aliases()).tokenize()?).parse()?;
+    let pulled = modules::pulled_names(&statements, Path::new(filename).parent().unwrap_or(Path::new("")), &DiskFiles);
+    let pragmas = Pragmas::scan(&source, config.keyword_aliases());
+    let diagnostics = Resolver::with_known_names(pulled).with_pragmas(pragmas.clone()).resolve(&statements);
+    let unused: Vec<_> = deadcode::find(&source, config.keyword_aliases())?
+        .into_iter()
+        .filter(|entry| !pragmas.covers(Rule::Unused, entry.line))
+        .map(|entry| {
+            let level = match entry.kind {
+                UnusedKind::Function => config.lint.unused_function,
+                UnusedKind::Variable => config.lint.unused_variable,
+            };
+            (entry, level)
+        })
+        .filter(|(_, level)| *level != LintLevel::Off)
+        .collect();
+    let unused_errors = unused.iter().filter(|(_, level)| *level == LintLevel::Error).count();
+    
+    for error in &diagnostics.errors {
+        eprintln!("🔍 {}", error);
+    }
+    let warnings: Vec<String> = diagnostics.warnings.into_iter().chain(pragmas.unknown_warnings()).collect();
+    for warning in &warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    for (entry, _) in &unused {
+        eprintln!("🪦 Line {}: {}", entry.line, entry.message());
+    }
+    
+    let errors = diagnostics.errors.len() + unused_errors;
+    if errors > 0 {
+        return Err(ZLangError::new(&format!("Found {} error(s) in {} 💀", errors, filename)));
+    }
+    if warnings.is_empty() && unused.is_empty() {
+        println!("✅ {} is squeaky clean, no notes 🧼", filename);
+    }
+    Ok(())
+}
+
+fn run_explain(source: &str) -> Result<(), ZLangError> {
+    let explanation = explain::explain(source)?;
+    
+    println!("🧮 With the precedence spelled out:");
+    println!("   {}", explanation.parenthesized);
+    println!();
+    println!("👣 Step by step:");
+    for (i, step) in explanation.steps.iter().enumerate() {
+        println!("   {} {}", if i == 0 { " " } else { "=" }, step);
+    }
+    explanation.value.map(|_| ())
+}
+
+/// `Z0023` rather than an expression to work through
+fn is_error_code(arg: &str) -> bool {
+    arg.len() == 5 && arg.starts_with(['Z', 'z']) && arg[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// What an error code means, then the mistake that causes it and the fix
+fn run_explain_code(code: &str, config: &Config) -> Result<(), ZLangError> {
+    let message = messages::find(code)
+        .ok_or_else(|| ZLangError::new(&format!("There's no error {}, copy the code from the error message - it looks like Z0023 🔎", code)))?;
+    let colors = ansi::stdout_wants_colors();
+    
+    println!("📖 {} - {}", message.code, message.title());
+    println!();
+    for line in messages::explanation(message.code).unwrap_or_default().lines() {
+        match line.strip_prefix("    ") {
+            Some(code) if colors => println!("    {}", highlight::highlight(code, None, &config.theme)),
+            _ => println!("{}", line),
+        }
+    }
+    Ok(())
+}
+
+fn run_stats(filename: &str) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    let stats = stats::analyze(&source)?;
+    
+    println!("📊 Stats for {}", filename);
+    println!();
+    println!("🔑 Keywords:");
+    if stats.keyword_counts.is_empty() {
+        println!("   none at all?? 😶");
+    }
+    for (keyword, count) in &stats.keyword_counts {
+        println!("   {:<12} {}", keyword, count);
+    }
+    
+    println!();
+    println!("🧩 Functions (statements per body):");
+    if stats.function_lengths.is_empty() {
+        println!("   no functions yet");
+    }
+    for (name, length) in &stats.function_lengths {
+        println!("   {:<20} {:>4}  {}", name, length, "▇".repeat((*length).min(40)));
+    }
+    if let Some(longest) = stats.function_lengths.iter().map(|(_, length)| *length).max() {
+        let total: usize = stats.function_lengths.iter().map(|(_, length)| length).sum();
+        println!(
+            "   average {:.1}, longest {}",
+            total as f64 / stats.function_lengths.len() as f64,
+            longest
+        );
+    }
+    
+    println!();
+    println!("🪆 Max nesting depth: {}", stats.max_nesting);
+    println!("🔥 Slang score: {}/100 - {}", stats.slang_score, stats::slang_rating(stats.slang_score));
+    Ok(())
+}
+
+/// Reformat a script with every keyword as its emoji alias, or back to plain words
+fn run_emojify(filename: &str, emoji: bool, config: &Config) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    
+    let formatter = Formatter::new()
+        .with_indent_size(config.format_indent)
+        .with_aliases(config.keyword_aliases());
+    let mut formatter = if emoji { formatter.with_emoji_keywords() } else { formatter };
+    print!("{}", formatter.format(&source)?);
+    Ok(())
+}
+
+/// Rename a variable or function in place, following scopes instead of matching text
+fn run_rename(filename: &str, old: &str, new: &str, line: Option<&str>) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    let line = match line {
+        Some(line) => Some(line.parse::<usize>()
+            .map_err(|_| ZLangError::new(&format!("'{}' isn't a line number bestie 🔢", line)))?),
+        None => None,
+    };
+    
+    let renamed = rename::rename(&source, old, new, line)?;
+    fs::write(filename, &renamed.source)
+        .map_err(|e| ZLangError::new(&format!("Couldn't write {}: {} 💾", filename, e)))?;
+    
+    println!("✏️  Renamed {} occurrence(s) of '{}' to '{}' in {}", renamed.occurrences, old, new, filename);
+    Ok(())
+}
+
+/// Print the call graph of a program and the files it pulls as DOT (pipe it into graphviz) or JSON
+fn run_graph(filename: &str, as_json: bool, config: &Config) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    let statements = Parser::new(Lexer::new(&source).with_aliases(config.keyword_aliases()).tokenize()?).parse()?;
+    
+    let graph = CallGraph::build_program(Path::new(filename), &statements, &DiskFiles);
+    print!("{}", if as_json { graph.to_json() } else { graph.to_dot() });
+    Ok(())
+}
+
+/// The syntax tree as JSON (the same nodes `parseCode()` gives scripts), or drawn as a tree for people
+fn run_ast(filename: &str, pretty: bool, config: &Config) -> Result<(), ZLangError> {
+    let source = fs::read_to_string(filename)
+        .map_err(|_| ZLangError::new(&format!("Can't find that file '{}' bestie 📁", filename)))?;
+    let statements = Parser::new(Lexer::new(&source).with_aliases(config.keyword_aliases()).tokenize()?).parse()?;
+    
+    if pretty {
+        print!("{}", tree::pretty(&syntax::trees(&statements), ansi::stdout_wants_colors()));
+    } else {
+        println!("{}", json::value(&syntax::from_statements(&statements)));
+    }
+    Ok(())
+}
+
+fn run_bench(iterations: Option<&str>) -> Result<(), ZLangError> {
+    let iterations = match iterations {
+        Some(n) => n.parse::<u32>()
+            .map_err(|_| ZLangError::new(&format!("'{}' isn't a valid iteration count bestie 🔢", n)))?,
+        None => 10,
+    };
+    
+    println!("⏱️  Running {} benchmark programs x{} iterations...", bench::PROGRAMS.len(), iterations);
+    println!("{:<18} {:>12} {:>12} {:>12}", "program", "min", "mean", "max");
+    
+    for result in bench::run_suite(iterations)? {
+        println!(
+            "{:<18} {:>12.3?} {:>12.3?} {:>12.3?}",
+            result.name, result.min, result.mean, result.max
+        );
+    }
+    
+    Ok(())
+}
+
+fn execute_code(interpreter: &mut Interpreter, source: &str, aliases: &HashMap<String, TokenType>) -> Result<ExecutionResult, ZLangError> {
     // Lexical analysis - turn source into tokens
-    let mut lexer = Lexer::new(source);
+    let mut lexer = Lexer::new(source).with_aliases(aliases.clone());
     let tokens = lexer.tokenize()?;
     
-    // Parsing - turn tokens into AST
-    let mut parser = Parser::new(tokens);
-    let statements = parser.parse()?;
+    // Parsing - turn tokens into AST, remembering where each statement came from
+    let mut parser = Parser::new(tokens).with_memes(interpreter.memes().clone());
+    let (statements, spans) = parser.parse_with_statement_spans()?;
+    interpreter.remember_memes(parser.memes());
+    
+    // Static checks - catch undeclared variables before anything runs
+    let mut known = interpreter.known_names();
+    known.extend(interpreter.pulled_names(&statements));
+    let pragmas = Pragmas::scan(source, aliases.clone());
+    let diagnostics = Resolver::with_known_names(known).with_pragmas(pragmas.clone()).resolve(&statements);
+    for warning in &diagnostics.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    if let Some(error) = diagnostics.error() {
+        return Err(error);
+    }
     
     // Interpretation - execute the AST
-    interpreter.interpret(statements)
+    interpreter.track_spans(SpanIndex::new(&statements, &spans));
+    interpreter.follow_pragmas(pragmas);
+    let result = interpreter.interpret(statements);
+    
+    let warnings = match &result {
+        Ok(result) => result.warnings.clone(),
+        Err(_) => interpreter.take_warnings(),
+    };
+    for warning in warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    
+    result
 }
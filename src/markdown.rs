@@ -0,0 +1,210 @@
+//! ZLang Markdown - `markdown()` turns notes into HTML, no extra crates needed 📝
+//! Headings, paragraphs, lists, quotes, code blocks and the usual inline bits - HTML in the text gets escaped
+
+/// The HTML for `text`, one block per line
+pub fn to_html(text: &str) -> String {
+    let mut html = Vec::new();
+    let mut block: Option<Block> = None;
+    
+    for line in text.lines() {
+        // Inside a fence nothing counts as markdown until the closing ```
+        if let Some(Block::Code { lines, .. }) = &mut block {
+            if line.trim_start().starts_with("```") {
+                html.extend(block.take().map(Block::render));
+            } else {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        
+        let trimmed = line.trim();
+        if let Some(language) = trimmed.strip_prefix("```") {
+            html.extend(block.take().map(Block::render));
+            block = Some(Block::Code { language: language.trim().to_string(), lines: Vec::new() });
+        } else if trimmed.is_empty() {
+            html.extend(block.take().map(Block::render));
+        } else if let Some((level, title)) = heading(trimmed) {
+            html.extend(block.take().map(Block::render));
+            html.push(format!("<h{}>{}</h{}>", level, inline(title), level));
+        } else if is_rule(trimmed) {
+            html.extend(block.take().map(Block::render));
+            html.push("<hr>".to_string());
+        } else if let Some(quoted) = trimmed.strip_prefix('>') {
+            let quoted = quoted.strip_prefix(' ').unwrap_or(quoted).to_string();
+            match &mut block {
+                Some(Block::Quote(lines)) => lines.push(quoted),
+                _ => {
+                    html.extend(block.take().map(Block::render));
+                    block = Some(Block::Quote(vec![quoted]));
+                }
+            }
+        } else if let Some((ordered, item)) = list_item(trimmed) {
+            match &mut block {
+                Some(Block::List { ordered: same, items }) if *same == ordered => items.push(item.to_string()),
+                _ => {
+                    html.extend(block.take().map(Block::render));
+                    block = Some(Block::List { ordered, items: vec![item.to_string()] });
+                }
+            }
+        } else {
+            match &mut block {
+                Some(Block::Paragraph(lines)) => lines.push(trimmed.to_string()),
+                // A line right under a list item keeps going with that item
+                Some(Block::List { items, .. }) => {
+                    if let Some(last) = items.last_mut() {
+                        last.push(' ');
+                        last.push_str(trimmed);
+                    }
+                }
+                _ => {
+                    html.extend(block.take().map(Block::render));
+                    block = Some(Block::Paragraph(vec![trimmed.to_string()]));
+                }
+            }
+        }
+    }
+    
+    // An unclosed fence runs to the end of the text
+    html.extend(block.map(Block::render));
+    html.join("\n")
+}
+
+/// A block that's still collecting lines
+enum Block {
+    Paragraph(Vec<String>),
+    List { ordered: bool, items: Vec<String> },
+    Quote(Vec<String>),
+    Code { language: String, lines: Vec<String> },
+}
+
+impl Block {
+    fn render(self) -> String {
+        match self {
+            Block::Paragraph(lines) => format!("<p>{}</p>", inline(&lines.join("\n"))),
+            Block::List { ordered, items } => {
+                let tag = if ordered { "ol" } else { "ul" };
+                let items: Vec<String> = items.iter().map(|item| format!("<li>{}</li>", inline(item))).collect();
+                format!("<{}>\n{}\n</{}>", tag, items.join("\n"), tag)
+            }
+            // Quotes hold markdown of their own
+            Block::Quote(lines) => format!("<blockquote>\n{}\n</blockquote>", to_html(&lines.join("\n"))),
+            Block::Code { language, lines } => {
+                let class = if language.is_empty() { String::new() } else { format!(" class=\"language-{}\"", escape(&language)) };
+                format!("<pre><code{}>{}</code></pre>", class, escape(&lines.join("\n")))
+            }
+        }
+    }
+}
+
+/// `## Title` as (2, "Title") - up to six #s, and a space before the title
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let title = &line[level..];
+    if !(1..=6).contains(&level) || !(title.is_empty() || title.starts_with(' ')) {
+        return None;
+    }
+    Some((level, title.trim().trim_end_matches('#').trim_end()))
+}
+
+/// `---`, `***` or `___`, spaces allowed in between
+fn is_rule(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && matches!(marks[0], '-' | '*' | '_') && marks.iter().all(|&c| c == marks[0])
+}
+
+/// `- item`, `* item` or `+ item` as unordered, `1. item` as ordered
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")).or_else(|| line.strip_prefix("+ ")) {
+        return Some((false, item.trim()));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let item = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| (true, item.trim()))
+}
+
+/// Emphasis, code, links and images inside a block
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    let mut after_word = false;
+    
+    while let Some(c) = rest.chars().next() {
+        if let Some((rendered, after)) = span(rest, after_word) {
+            html.push_str(&rendered);
+            rest = after;
+            after_word = false;
+        } else {
+            html.push_str(&escape(&c.to_string()));
+            rest = &rest[c.len_utf8()..];
+            after_word = c.is_alphanumeric();
+        }
+    }
+    html
+}
+
+/// The inline element `rest` starts with, and whatever comes after it
+fn span(rest: &str, after_word: bool) -> Option<(String, &str)> {
+    let c = rest.chars().next()?;
+    match c {
+        '\\' => {
+            let escaped = rest[1..].chars().next().filter(char::is_ascii_punctuation)?;
+            Some((escape(&escaped.to_string()), &rest[1 + escaped.len_utf8()..]))
+        }
+        '`' => {
+            let end = rest[1..].find('`')?;
+            Some((format!("<code>{}</code>", escape(&rest[1..1 + end])), &rest[end + 2..]))
+        }
+        // `snake_case_names` stay as they are, an underscore only starts emphasis at a word's edge
+        '_' if after_word => None,
+        '*' | '_' => {
+            let delimiter = if rest[1..].starts_with(c) { &rest[..2] } else { &rest[..1] };
+            let inner = &rest[delimiter.len()..];
+            let end = inner.find(delimiter).filter(|&end| end > 0)?;
+            let after = &inner[end + delimiter.len()..];
+            if c == '_' && after.chars().next().is_some_and(char::is_alphanumeric) {
+                return None;
+            }
+            let tag = if delimiter.len() == 2 { "strong" } else { "em" };
+            Some((format!("<{}>{}</{}>", tag, inline(&inner[..end]), tag), after))
+        }
+        '!' => {
+            let (alt, url, after) = link(&rest[1..])?;
+            Some((format!("<img src=\"{}\" alt=\"{}\">", escape(url), escape(alt)), after))
+        }
+        '[' => {
+            let (label, url, after) = link(rest)?;
+            Some((format!("<a href=\"{}\">{}</a>", escape(url), inline(label)), after))
+        }
+        _ => None,
+    }
+}
+
+/// `[label](url)` split up - None for anything that isn't one, or points at a script
+fn link(rest: &str) -> Option<(&str, &str, &str)> {
+    let rest = rest.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let target = &rest[label_end + 2..];
+    let url_end = target.find(')')?;
+    let url = target[..url_end].trim();
+    
+    let scheme: String = url.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_lowercase();
+    if ["javascript:", "vbscript:", "data:"].iter().any(|bad| scheme.starts_with(bad)) {
+        return None;
+    }
+    Some((&rest[..label_end], url, &target[url_end + 1..]))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,405 @@
+//! ZLang Memes - `meme double(x) => x * 2` makes your own slang, swapped in wherever it's called 🎭
+//! The parser does the swapping, so `double(n + 1)` is `(n + 1) * 2` before anything runs. Names a
+//! meme's own lambdas declare get renamed first, so they can't grab a variable the call passes in.
+//! Any other name it reads means the top-level one, where the meme was declared, even when it's
+//! called from somewhere with a variable of its own by that name
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::ast::{Expr, Literal, Stmt};
+use crate::error::ZLangError;
+use crate::messages;
+
+/// What `meme name(params) => expansion` declared
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meme {
+    pub name: String,
+    pub params: Vec<String>,
+    expansion: Expr,
+}
+
+impl Meme {
+    pub fn new(name: String, params: Vec<String>, mut expansion: Expr) -> Self {
+        rewrite_expr(&mut expansion, &mut Hygiene { suffix: format!("@{}", name) });
+        rewrite_expr(&mut expansion, &mut TopLevel { params: &params });
+        Self { name, params, expansion }
+    }
+    
+    /// The expansion with each parameter swapped for its argument, for the call on `line`
+    pub fn expand(&self, arguments: Vec<Expr>, line: usize) -> Result<Expr, ZLangError> {
+        if arguments.iter().any(|argument| matches!(argument, Expr::Spread(_))) {
+            return Err(messages::MEME_SPREAD.error(&[&self.name, &line]));
+        }
+        if arguments.len() != self.params.len() {
+            return Err(messages::MEME_ARGUMENTS.error(&[&self.name, &self.params.len(), &line, &arguments.len()]));
+        }
+        
+        let mut expanded = self.expansion.clone();
+        let mut substitute = Substitute { meme: self, arguments: &arguments, line, error: None };
+        rewrite_expr(&mut expanded, &mut substitute);
+        match substitute.error {
+            Some(error) => Err(error),
+            None => Ok(expanded),
+        }
+    }
+}
+
+/// Something done to a tree on the way down. Each hook says whether it took care of the node
+/// itself, in which case the walk doesn't look inside it
+trait Rewrite {
+    fn expr(&mut self, _expr: &mut Expr) -> bool {
+        false
+    }
+    
+    fn stmt(&mut self, _stmt: &mut Stmt) -> bool {
+        false
+    }
+    
+    /// A name that's declared or read - variables, parameters and the functions and squads a body declares
+    fn name(&mut self, _name: &mut String) {}
+    
+    fn line(&mut self, _line: &mut usize) {}
+}
+
+/// Renames whatever each function in the expansion declares for itself, so `meme add(v) => (flex(n) { vibe n + v })(1)`
+/// still means the caller's `n` when it's called as `add(n)`
+struct Hygiene {
+    suffix: String,
+}
+
+impl Rewrite for Hygiene {
+    fn expr(&mut self, expr: &mut Expr) -> bool {
+        if let Expr::Function { params, body, .. } = expr {
+            rename_own(params, Arc::make_mut(body).as_mut_slice(), &self.suffix);
+        }
+        false
+    }
+    
+    fn stmt(&mut self, stmt: &mut Stmt) -> bool {
+        if let Stmt::Function { params, body, .. } = stmt {
+            rename_own(params, body, &self.suffix);
+        }
+        false
+    }
+}
+
+/// Rename the names a function declares for itself everywhere in it, nested functions included
+fn rename_own(params: &mut [String], body: &mut [Stmt], suffix: &str) {
+    let mut declared: HashSet<String> = params.iter().cloned().collect();
+    body.iter().for_each(|stmt| declared_in(stmt, &mut declared));
+    // Already renamed by the function around this one
+    declared.retain(|name| !name.contains('@'));
+    
+    let renamer = &mut Rename { names: declared, suffix };
+    params.iter_mut().for_each(|param| renamer.name(param));
+    rewrite_all(body, renamer);
+}
+
+/// Every name `stmt` declares in the function it sits in - not the ones nested functions declare, those get their own turn
+fn declared_in(stmt: &Stmt, declared: &mut HashSet<String>) {
+    match stmt {
+        Stmt::VarDeclaration { name, .. } | Stmt::Function { name, .. } | Stmt::Squad { name, .. } => {
+            declared.insert(name.clone());
+        }
+        Stmt::For { variable, body, else_branch, .. } => {
+            declared.insert(variable.clone());
+            declared_in(body, declared);
+            if let Some(else_branch) = else_branch {
+                declared_in(else_branch, declared);
+            }
+        }
+        Stmt::Block(body) => body.iter().for_each(|stmt| declared_in(stmt, declared)),
+        Stmt::If { then_branch, else_branch, .. } => {
+            declared_in(then_branch, declared);
+            if let Some(else_branch) = else_branch {
+                declared_in(else_branch, declared);
+            }
+        }
+        Stmt::While { body, else_branch, .. } => {
+            declared_in(body, declared);
+            if let Some(else_branch) = else_branch {
+                declared_in(else_branch, declared);
+            }
+        }
+        Stmt::ClassicFor { initializer, body, else_branch, .. } => {
+            if let Some(initializer) = initializer {
+                declared_in(initializer, declared);
+            }
+            declared_in(body, declared);
+            if let Some(else_branch) = else_branch {
+                declared_in(else_branch, declared);
+            }
+        }
+        Stmt::Switch { cases, default, .. } => {
+            for body in cases.iter().map(|case| &case.body).chain(default) {
+                body.iter().for_each(|stmt| declared_in(stmt, declared));
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            for clause in catch_clauses {
+                declared.insert(clause.variable.clone());
+            }
+            for body in [try_block].into_iter().chain(catch_clauses.iter().map(|clause| &clause.body)).chain(finally_block) {
+                body.iter().for_each(|stmt| declared_in(stmt, declared));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `n` to `n@add` everywhere it's declared or read - `@` can't be typed in a name, so nothing a call passes in is called that
+struct Rename<'a> {
+    names: HashSet<String>,
+    suffix: &'a str,
+}
+
+impl Rewrite for Rename<'_> {
+    fn name(&mut self, name: &mut String) {
+        if self.names.contains(name.as_str()) {
+            name.push_str(self.suffix);
+        }
+    }
+}
+
+/// Points each name the expansion reads that isn't a parameter at the top-level one, where the meme
+/// was declared. `v + bonus` becomes `v + (flex() { global bonus; vibe bonus })()`, which reads the
+/// top-level `bonus` wherever it's swapped in - a function with a `bonus` of its own included
+struct TopLevel<'a> {
+    params: &'a [String],
+}
+
+impl TopLevel<'_> {
+    /// Names the expansion's lambdas declare have an `@` by now, they're the only other names it has
+    fn is_free(&self, name: &str) -> bool {
+        !name.contains('@') && !self.params.iter().any(|param| param == name)
+    }
+}
+
+impl Rewrite for TopLevel<'_> {
+    fn expr(&mut self, expr: &mut Expr) -> bool {
+        let replacement = match expr {
+            Expr::Variable(name) if self.is_free(name) => at_top_level(name, None, Expr::Variable(name.clone()), Vec::new()),
+            // A builtin has no value to read, so the whole call happens up there - its arguments
+            // are worked out where the meme is called, and handed up
+            Expr::Call { callee, arguments, line } if matches!(callee.as_ref(), Expr::Variable(name) if self.is_free(name)) => {
+                arguments.iter_mut().for_each(|argument| rewrite_expr(argument, self));
+                let Expr::Variable(name) = callee.as_ref() else { unreachable!("matched a variable above") };
+                let spread = Expr::Spread(Box::new(Expr::Variable("@arguments".to_string())));
+                let call = Expr::Call { callee: callee.clone(), arguments: vec![spread], line: *line };
+                at_top_level(name, Some("@arguments"), call, std::mem::take(arguments))
+            }
+            Expr::Assign { name, value } if self.is_free(name) => {
+                rewrite_expr(value, self);
+                let assign = Expr::Assign { name: name.clone(), value: Box::new(Expr::Variable("@value".to_string())) };
+                let value = std::mem::replace(value.as_mut(), Expr::Literal(Literal::Nil));
+                at_top_level(name, Some("@value"), assign, vec![value])
+            }
+            _ => return false,
+        };
+        *expr = replacement;
+        true
+    }
+}
+
+/// `(flex(param) { global name; vibe inner })(arguments)` - `@arguments` collects every argument
+fn at_top_level(name: &str, param: Option<&str>, inner: Expr, arguments: Vec<Expr>) -> Expr {
+    let lambda = Expr::Function {
+        params: param.into_iter().map(str::to_string).collect(),
+        rest: param == Some("@arguments"),
+        body: Arc::new(vec![Stmt::Global(vec![name.to_string()]), Stmt::Return(Some(inner))]),
+    };
+    // The line becomes the call's when the meme is swapped in
+    Expr::Call { callee: Box::new(lambda), arguments, line: 0 }
+}
+
+/// Swaps parameters for the arguments a call passed, and gives the rest of the expansion the call's line
+struct Substitute<'a> {
+    meme: &'a Meme,
+    arguments: &'a [Expr],
+    line: usize,
+    error: Option<ZLangError>,
+}
+
+impl Substitute<'_> {
+    fn argument(&self, name: &str) -> Option<&Expr> {
+        self.meme.params.iter().position(|param| param == name).map(|index| &self.arguments[index])
+    }
+}
+
+impl Rewrite for Substitute<'_> {
+    fn expr(&mut self, expr: &mut Expr) -> bool {
+        match expr {
+            Expr::Variable(name) => match self.argument(name) {
+                Some(argument) => {
+                    *expr = argument.clone();
+                    true
+                }
+                None => false,
+            },
+            // `meme reset(x) => x = 0` needs a variable to set, `reset(5)` has nothing to assign to
+            Expr::Assign { name, .. } => {
+                let error = match self.argument(name) {
+                    Some(Expr::Variable(variable)) => {
+                        *name = variable.clone();
+                        None
+                    }
+                    Some(other) => Some(messages::MEME_ASSIGN_TARGET.error(&[&self.meme.name, name, &self.line, &other.to_source()])),
+                    None => None,
+                };
+                self.error = self.error.take().or(error);
+                false
+            }
+            _ => false,
+        }
+    }
+    
+    fn line(&mut self, line: &mut usize) {
+        *line = self.line;
+    }
+}
+
+fn rewrite_expr(expr: &mut Expr, rewrite: &mut impl Rewrite) {
+    if rewrite.expr(expr) {
+        return;
+    }
+    match expr {
+        Expr::Variable(name) => rewrite.name(name),
+        Expr::Assign { name, value } => {
+            rewrite.name(name);
+            rewrite_expr(value, rewrite);
+        }
+        Expr::Call { callee, arguments, line } => {
+            rewrite.line(line);
+            rewrite_expr(callee, rewrite);
+            arguments.iter_mut().for_each(|argument| rewrite_expr(argument, rewrite));
+        }
+        Expr::Propagate { value, line } => {
+            rewrite.line(line);
+            rewrite_expr(value, rewrite);
+        }
+        Expr::Binary { left, right, .. } | Expr::Index { object: left, index: right, .. } | Expr::Set { object: left, value: right, .. } |
+        Expr::Range { start: left, end: right, .. } => {
+            rewrite_expr(left, rewrite);
+            rewrite_expr(right, rewrite);
+        }
+        Expr::Unary { right: value, .. } | Expr::Get { object: value, .. } | Expr::Spread(value) => rewrite_expr(value, rewrite),
+        Expr::Conditional { condition, then_branch, else_branch } => {
+            rewrite_expr(condition, rewrite);
+            rewrite_expr(then_branch, rewrite);
+            rewrite_expr(else_branch, rewrite);
+        }
+        Expr::Function { params, body, .. } => {
+            params.iter_mut().for_each(|param| rewrite.name(param));
+            Arc::make_mut(body).iter_mut().for_each(|stmt| rewrite_stmt(stmt, rewrite));
+        }
+        Expr::Array(items) => items.iter_mut().for_each(|item| rewrite_expr(item, rewrite)),
+        Expr::Object(fields) => fields.iter_mut().for_each(|(_, value)| rewrite_expr(value, rewrite)),
+        Expr::Literal(_) => {}
+    }
+}
+
+fn rewrite_all(statements: &mut [Stmt], rewrite: &mut impl Rewrite) {
+    statements.iter_mut().for_each(|stmt| rewrite_stmt(stmt, rewrite));
+}
+
+fn rewrite_stmt(stmt: &mut Stmt, rewrite: &mut impl Rewrite) {
+    if rewrite.stmt(stmt) {
+        return;
+    }
+    match stmt {
+        Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) | Stmt::Return(Some(expr)) => {
+            rewrite_expr(expr, rewrite);
+        }
+        Stmt::VarDeclaration { name, initializer, .. } => {
+            rewrite.name(name);
+            if let Some(initializer) = initializer {
+                rewrite_expr(initializer, rewrite);
+            }
+        }
+        Stmt::Function { name, params, body, .. } => {
+            rewrite.name(name);
+            params.iter_mut().for_each(|param| rewrite.name(param));
+            rewrite_all(body, rewrite);
+        }
+        // Fields and methods are read with a `.`, only the squads' own names are variables
+        Stmt::Squad { name, parent, body } => {
+            rewrite.name(name);
+            if let Some(parent) = parent {
+                rewrite.name(parent);
+            }
+            for member in body {
+                match member {
+                    Stmt::VarDeclaration { initializer: Some(initializer), .. } => rewrite_expr(initializer, rewrite),
+                    Stmt::Function { params, body, .. } => {
+                        params.iter_mut().for_each(|param| rewrite.name(param));
+                        rewrite_all(body, rewrite);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Stmt::Block(body) => rewrite_all(body, rewrite),
+        Stmt::If { condition, then_branch, else_branch } => {
+            rewrite_expr(condition, rewrite);
+            rewrite_stmt(then_branch, rewrite);
+            if let Some(else_branch) = else_branch {
+                rewrite_stmt(else_branch, rewrite);
+            }
+        }
+        Stmt::While { condition, body, else_branch } => {
+            rewrite_expr(condition, rewrite);
+            rewrite_stmt(body, rewrite);
+            if let Some(else_branch) = else_branch {
+                rewrite_stmt(else_branch, rewrite);
+            }
+        }
+        Stmt::For { variable, iterable, body, else_branch, .. } => {
+            rewrite.name(variable);
+            rewrite_expr(iterable, rewrite);
+            rewrite_stmt(body, rewrite);
+            if let Some(else_branch) = else_branch {
+                rewrite_stmt(else_branch, rewrite);
+            }
+        }
+        Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+            if let Some(initializer) = initializer {
+                rewrite_stmt(initializer, rewrite);
+            }
+            for expr in condition.iter_mut().chain(increment) {
+                rewrite_expr(expr, rewrite);
+            }
+            rewrite_stmt(body, rewrite);
+            if let Some(else_branch) = else_branch {
+                rewrite_stmt(else_branch, rewrite);
+            }
+        }
+        Stmt::Switch { expr, cases, default } => {
+            rewrite_expr(expr, rewrite);
+            for case in cases {
+                case.exprs_mut().for_each(|value| rewrite_expr(value, rewrite));
+                rewrite_all(&mut case.body, rewrite);
+            }
+            if let Some(body) = default {
+                rewrite_all(body, rewrite);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            rewrite_all(try_block, rewrite);
+            for clause in catch_clauses {
+                rewrite.name(&mut clause.variable);
+                rewrite_all(&mut clause.body, rewrite);
+            }
+            if let Some(body) = finally_block {
+                rewrite_all(body, rewrite);
+            }
+        }
+        Stmt::Needs { condition, line, .. } => {
+            rewrite_expr(condition, rewrite);
+            rewrite.line(line);
+        }
+        Stmt::Pull { line, .. } => rewrite.line(line),
+        // `global` and `outer` point past the function at names it doesn't own
+        Stmt::Return(None) | Stmt::DramaType { .. } | Stmt::Break | Stmt::Continue | Stmt::Global(_) | Stmt::Outer(_) => {}
+    }
+}
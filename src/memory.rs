@@ -0,0 +1,75 @@
+//! ZLang Memory - `memStats()` counts the strings, arrays and objects a script is holding on to 🧠
+//! Bytes are an estimate from what each value stores, not what the allocator really handed out
+
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+
+use crate::ast::Literal;
+
+/// A tally of live values, nested ones included
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemStats {
+    pub strings: usize,
+    pub arrays: usize,
+    pub objects: usize,
+    pub bytes: usize,
+    // Heaps, queues, stacks and instances already counted - they're shared, not copied, and can even hold themselves
+    shared: HashSet<usize>,
+}
+
+impl MemStats {
+    /// Count `value` and everything inside it
+    pub fn add(&mut self, value: &Literal) {
+        self.bytes += size_of::<Literal>();
+        match value {
+            Literal::String(s) => {
+                self.strings += 1;
+                self.bytes += s.len();
+            }
+            Literal::Array(items) => {
+                self.arrays += 1;
+                items.iter().for_each(|item| self.add(item));
+            }
+            Literal::Object(fields) => {
+                self.objects += 1;
+                for (key, value) in fields {
+                    self.bytes += size_of::<String>() + key.len();
+                    self.add(value);
+                }
+            }
+            // The body is shared with the function's declaration, only the signature is its own
+            Literal::Function(function) => {
+                self.bytes += function.name.len() + function.params.iter().map(|param| size_of::<String>() + param.len()).sum::<usize>();
+            }
+            Literal::Heap(heap) => {
+                if self.shared.insert(heap.id()) {
+                    heap.values().iter().for_each(|value| self.add(value));
+                }
+            }
+            Literal::Deque(deque) => {
+                if self.shared.insert(deque.id()) {
+                    deque.values().iter().for_each(|value| self.add(value));
+                }
+            }
+            // An instance counts as an object of its fields, its squad is shared with the declaration
+            Literal::Instance(instance) => {
+                if self.shared.insert(instance.id()) {
+                    self.objects += 1;
+                    instance.values().iter().for_each(|value| self.add(value));
+                }
+            }
+            Literal::Squad(squad) => self.bytes += squad.name.len(),
+            Literal::Number(_) | Literal::Boolean(_) | Literal::Nil | Literal::Money(_) => {}
+        }
+    }
+    
+    /// The object `memStats()` hands back
+    pub fn to_object(self) -> HashMap<String, Literal> {
+        HashMap::from([
+            ("strings".to_string(), Literal::Number(self.strings as f64)),
+            ("arrays".to_string(), Literal::Number(self.arrays as f64)),
+            ("objects".to_string(), Literal::Number(self.objects as f64)),
+            ("bytes".to_string(), Literal::Number(self.bytes as f64)),
+        ])
+    }
+}
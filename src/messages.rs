@@ -0,0 +1,2645 @@
+//! ZLang Messages - The diagnostics catalog, every error keyed by a code and written once per language 🌍
+//! English, Spanish and Hindi to start, each with its own flavor - pick one with `lang` in .zlangrc or `--lang=es`
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::error::ZLangError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    English,
+    Spanish,
+    Hindi,
+}
+
+impl Lang {
+    pub const NAMES: [&'static str; 3] = ["en", "es", "hi"];
+    
+    /// By its code or its name, in English or in itself
+    pub fn named(name: &str) -> Option<Lang> {
+        match name.to_lowercase().as_str() {
+            "en" | "english" => Some(Lang::English),
+            "es" | "spanish" | "español" | "espanol" => Some(Lang::Spanish),
+            "hi" | "hindi" | "हिन्दी" | "हिंदी" => Some(Lang::Hindi),
+            _ => None,
+        }
+    }
+}
+
+// Diagnostics can come from any thread (the web playground runs each program on its own),
+// and they all speak the one language the CLI was started with
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::Spanish,
+        2 => Lang::Hindi,
+        _ => Lang::English,
+    }
+}
+
+/// One piece of text in every language. `{0}`, `{1}`... are filled in by `render`, in whatever
+/// order the language wants them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Text {
+    pub en: &'static str,
+    pub es: &'static str,
+    pub hi: &'static str,
+}
+
+impl Text {
+    pub fn render(&self, args: &[&dyn Display]) -> String {
+        let template = match lang() {
+            Lang::English => self.en,
+            Lang::Spanish => self.es,
+            Lang::Hindi => self.hi,
+        };
+        fill(template, args)
+    }
+}
+
+/// A diagnostic, with the code that stays the same whatever language it's shown in. Several can share
+/// a code when one kind of mistake has more exact wordings (`sqrt expects 1 argument` is a Z0035)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Message {
+    pub code: &'static str,
+    pub text: Text,
+}
+
+impl Message {
+    pub fn error(&self, args: &[&dyn Display]) -> ZLangError {
+        ZLangError::coded(self.code, &self.text.render(args))
+    }
+    
+    /// The text with its blanks left as `…`, for a heading when there's no actual mistake to fill them
+    pub fn title(&self) -> String {
+        let blank: &dyn Display = &"…";
+        self.text.render(&[blank; 4])
+    }
+}
+
+/// The catalog entry for `code`, `z0023` finds Z0023 too
+pub fn find(code: &str) -> Option<&'static Message> {
+    CATALOG.iter().find(|message| message.code.eq_ignore_ascii_case(code))
+}
+
+// What `zlang explain Z0023` prints, one `## Z0023` section per code. Only in English so far
+const EXPLANATIONS: &str = include_str!("explanations.md");
+
+/// The long version of `code` - what goes wrong, then the mistake and the fix as indented code
+pub fn explanation(code: &str) -> Option<&'static str> {
+    let code = find(code)?.code;
+    let start = EXPLANATIONS.find(&format!("## {}\n", code))? + code.len() + 4;
+    let section = &EXPLANATIONS[start..];
+    let end = section.find("\n## ").map_or(section.len(), |end| end + 1);
+    Some(section[..end].trim())
+}
+
+/// Swap each `{n}` for the nth argument - one pass, so an argument with braces in it stays as it is
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let arg = after.find('}')
+            .and_then(|close| Some((after[..close].parse::<usize>().ok()?, close)))
+            .and_then(|(index, close)| Some((args.get(index)?, close)));
+        match arg {
+            Some((arg, close)) => {
+                out.push_str(&arg.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// Around the diagnostics
+
+pub const NOT_IT: Text = Text {
+    en: "That's not it chief: {0}",
+    es: "Eso no es, jefe: {0}",
+    hi: "ये नहीं चलेगा बॉस: {0}",
+};
+
+pub const RUNNING: Text = Text {
+    en: "Running {0}...",
+    es: "Ejecutando {0}...",
+    hi: "{0} चल रहा है...",
+};
+
+pub const DID_YOU_MEAN: Text = Text {
+    en: " Did you mean '{0}'?",
+    es: " ¿Quisiste decir '{0}'?",
+    hi: " क्या आपका मतलब '{0}' था?",
+};
+
+pub const EXPLAIN_HINT: Text = Text {
+    en: "`zlang explain {0}` has the long version, with a fix",
+    es: "`zlang explain {0}` lo explica con calma, con un arreglo",
+    hi: "`zlang explain {0}` में पूरी बात है, fix के साथ",
+};
+
+// Lexer
+
+pub const UNEXPECTED_CHARACTER: Message = Message {
+    code: "Z0001",
+    text: Text {
+        en: "Unexpected character '{0}' at line {1}, this ain't valid bestie",
+        es: "Carácter inesperado '{0}' en la línea {1}, eso no es válido bestie",
+        hi: "लाइन {1} पर अनजान कैरेक्टर '{0}', ये valid नहीं है यार",
+    },
+};
+
+pub const INVALID_ESCAPE: Message = Message {
+    code: "Z0002",
+    text: Text {
+        en: "Invalid escape sequence '\\{0}' at line {1}, that's sus",
+        es: "Secuencia de escape inválida '\\{0}' en la línea {1}, qué sospechoso",
+        hi: "लाइन {1} पर गलत escape sequence '\\{0}', कुछ तो गड़बड़ है",
+    },
+};
+
+pub const UNTERMINATED_STRING: Message = Message {
+    code: "Z0003",
+    text: Text {
+        en: "Unterminated string at line {0}, where's the closing quote bestie?",
+        es: "String sin cerrar en la línea {0}, ¿dónde está la comilla de cierre bestie?",
+        hi: "लाइन {0} पर string बंद नहीं हुई, closing quote कहाँ है यार?",
+    },
+};
+
+pub const UNTERMINATED_TRIPLE_STRING: Message = Message {
+    code: "Z0004",
+    text: Text {
+        en: "Unterminated string at line {0}, where's the closing \"\"\" bestie?",
+        es: "String sin cerrar en la línea {0}, ¿dónde están las \"\"\" de cierre bestie?",
+        hi: "लाइन {0} पर string बंद नहीं हुई, closing \"\"\" कहाँ है यार?",
+    },
+};
+
+pub const INVALID_NUMBER: Message = Message {
+    code: "Z0005",
+    text: Text {
+        en: "Invalid number '{0}' at line {1}, that's not how numbers work chief",
+        es: "Número inválido '{0}' en la línea {1}, así no funcionan los números jefe",
+        hi: "लाइन {1} पर गलत नंबर '{0}', नंबर ऐसे नहीं लिखते बॉस",
+    },
+};
+
+// Parser
+
+pub const UNEXPECTED_TOKEN: Message = Message {
+    code: "Z0010",
+    text: Text {
+        en: "Unexpected token at line {0}, that's not valid in this context bestie 🤷‍♀️",
+        es: "Token inesperado en la línea {0}, eso no va aquí bestie 🤷‍♀️",
+        hi: "लाइन {0} पर अनजान token, यहाँ ये नहीं चलेगा यार 🤷‍♀️",
+    },
+};
+
+pub const EXPECTED_STATEMENT_END: Message = Message {
+    code: "Z0011",
+    text: Text {
+        en: "Expected ';' or newline after expression 📝",
+        es: "Se esperaba ';' o un salto de línea después de la expresión 📝",
+        hi: "expression के बाद ';' या नई लाइन चाहिए 📝",
+    },
+};
+
+pub const NESTED_TOO_DEEP: Message = Message {
+    code: "Z0012",
+    text: Text {
+        en: "Code nested way too deep at line {0}, flatten it out bestie 🪆",
+        es: "Código anidado demasiado profundo en la línea {0}, aplánalo bestie 🪆",
+        hi: "लाइन {0} पर code बहुत गहराई तक nested है, इसे थोड़ा flat करो यार 🪆",
+    },
+};
+
+pub const STRAY_ELSE: Message = Message {
+    code: "Z0013",
+    text: Text {
+        en: "'{0}' at line {1} has no 'sus' before it to be the else of 🤔",
+        es: "'{0}' en la línea {1} no tiene ningún 'sus' antes del que ser el else 🤔",
+        hi: "लाइन {1} पर '{0}' से पहले कोई 'sus' नहीं है जिसका ये else बने 🤔",
+    },
+};
+
+pub const INVALID_ASSIGNMENT_TARGET: Message = Message {
+    code: "Z0014",
+    text: Text {
+        en: "Invalid assignment target, can't assign to that bestie! 🎯",
+        es: "Destino de asignación inválido, ¡a eso no se le puede asignar bestie! 🎯",
+        hi: "गलत assignment target, इसमें value नहीं डाल सकते यार! 🎯",
+    },
+};
+
+pub const EXPECTED_TOKEN: Message = Message {
+    code: "Z0015",
+    text: Text {
+        en: "Something that has to be there is missing, like a name, a bracket or a keyword 🧩",
+        es: "Falta algo que tiene que estar ahí, como un nombre, un paréntesis o una palabra clave 🧩",
+        hi: "जो चीज़ होनी चाहिए वो गायब है, जैसे कोई नाम, bracket या keyword 🧩",
+    },
+};
+
+pub const REST_PARAMETER_TYPE: Message = Message {
+    code: "Z0016",
+    text: Text {
+        en: "A ...rest parameter is always an array, leave the type off 📦",
+        es: "Un parámetro ...rest siempre es un array, quítale el tipo 📦",
+        hi: "...rest parameter हमेशा array होता है, type हटा दो 📦",
+    },
+};
+
+pub const REST_PARAMETER_NOT_LAST: Message = Message {
+    code: "Z0017",
+    text: Text {
+        en: "The ...rest parameter goes last, it takes whatever arguments are left 📦",
+        es: "El parámetro ...rest va al final, se lleva los argumentos que sobren 📦",
+        hi: "...rest parameter आख़िर में आता है, जो arguments बचें वो सब उसके 📦",
+    },
+};
+
+pub const LAMBDA_TYPE_ANNOTATION: Message = Message {
+    code: "Z0018",
+    text: Text {
+        en: "Type annotations go on named functions, a flex(x) { ... } lambda takes plain names 🏷️",
+        es: "Los tipos van en funciones con nombre, una lambda flex(x) { ... } lleva nombres sin tipo 🏷️",
+        hi: "Type annotations नाम वाले functions पर लगते हैं, flex(x) { ... } lambda सिर्फ़ सादे नाम लेता है 🏷️",
+    },
+};
+
+pub const UNKNOWN_TYPE: Message = Message {
+    code: "Z0019",
+    text: Text {
+        en: "Unknown type '{0}' at line {1}, try number, string, bool, array, object, money, function, heap, queue, stack, nil or any 🏷️",
+        es: "Tipo desconocido '{0}' en la línea {1}, prueba number, string, bool, array, object, money, function, heap, queue, stack, nil o any 🏷️",
+        hi: "लाइन {1} पर अनजान type '{0}', number, string, bool, array, object, money, function, heap, queue, stack, nil या any आज़माओ 🏷️",
+    },
+};
+
+// What the parser was looking for, each a more exact Z0011 or Z0015
+
+pub const EXPECTED_MEME_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected the meme's name 🎭",
+        es: "Se esperaba el nombre del meme 🎭",
+        hi: "यहाँ meme का नाम चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_FUNCTION_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected function name bestie 📝",
+        es: "Se esperaba el nombre de la función bestie 📝",
+        hi: "यहाँ function का नाम चाहिए था यार 📝",
+    },
+};
+
+pub const EXPECTED_FUNCTION_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after function name, that's how functions work!",
+        es: "Se esperaba '(' después del nombre de la función, así funcionan las funciones!",
+        hi: "Function के नाम के बाद '(' चाहिए था, functions ऐसे ही चलते हैं!",
+    },
+};
+
+pub const EXPECTED_FUNCTION_BODY: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' before function body, gotta have that block!",
+        es: "Se esperaba '{' antes del cuerpo de la función, ese bloque tiene que estar!",
+        hi: "Function body से पहले '{' चाहिए था, वो block तो होना ही चाहिए!",
+    },
+};
+
+pub const EXPECTED_SQUAD_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected the squad's name, like squad Point { ... } 👥",
+        es: "Se esperaba el nombre del squad, tipo squad Point { ... } 👥",
+        hi: "यहाँ squad का नाम चाहिए था, जैसे squad Point { ... } 👥",
+    },
+};
+
+pub const EXPECTED_SQUAD_PARENT: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected the name of the squad it comes from, like squad Dog from Animal { ... } 👥",
+        es: "Se esperaba el nombre del squad del que viene, tipo squad Dog from Animal { ... } 👥",
+        hi: "यहाँ उस squad का नाम चाहिए था जिससे ये आता है, जैसे squad Dog from Animal { ... } 👥",
+    },
+};
+
+pub const EXPECTED_SQUAD_OPEN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' after the squad's name, its fields and methods go inside 👥",
+        es: "Se esperaba '{' después del nombre del squad, sus campos y métodos van adentro 👥",
+        hi: "Squad के नाम के बाद '{' चाहिए था, उसके fields और methods अंदर आते हैं 👥",
+    },
+};
+
+pub const EXPECTED_SQUAD_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after the squad's fields and methods, close it up! 👥",
+        es: "Se esperaba '}' después de los campos y métodos del squad, ciérralo! 👥",
+        hi: "Squad के fields और methods के बाद '}' चाहिए था, बंद करो! 👥",
+    },
+};
+
+pub const EXPECTED_MEME_DECLARATION: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected the meme's name, like meme double(x) => x * 2 🎭",
+        es: "Se esperaba el nombre del meme, tipo meme double(x) => x * 2 🎭",
+        hi: "यहाँ meme का नाम चाहिए था, जैसे meme double(x) => x * 2 🎭",
+    },
+};
+
+pub const EXPECTED_MEME_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after the meme's name 🎭",
+        es: "Se esperaba '(' después del nombre del meme 🎭",
+        hi: "Meme के नाम के बाद '(' चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_MEME_PARAMETER: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a parameter name in the meme 🎭",
+        es: "Se esperaba el nombre de un parámetro en el meme 🎭",
+        hi: "Meme में parameter का नाम चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_MEME_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after the meme's parameters, close it up! 🎭",
+        es: "Se esperaba ')' después de los parámetros del meme, ciérralo! 🎭",
+        hi: "Meme के parameters के बाद ')' चाहिए था, बंद करो! 🎭",
+    },
+};
+
+pub const EXPECTED_MEME_ARROW: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '=>' after the meme's parameters, then what it means - meme double(x) => x * 2 🎭",
+        es: "Se esperaba '=>' después de los parámetros del meme, y luego lo que significa - meme double(x) => x * 2 🎭",
+        hi: "Meme के parameters के बाद '=>' चाहिए था, फिर उसका मतलब - meme double(x) => x * 2 🎭",
+    },
+};
+
+pub const EXPECTED_PARAMETER: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected parameter name in function declaration 📋",
+        es: "Se esperaba el nombre de un parámetro en la declaración de la función 📋",
+        hi: "Function declaration में parameter का नाम चाहिए था 📋",
+    },
+};
+
+pub const EXPECTED_PARAMETERS_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after parameters, close it up!",
+        es: "Se esperaba ')' después de los parámetros, ciérralo!",
+        hi: "Parameters के बाद ')' चाहिए था, बंद करो!",
+    },
+};
+
+pub const EXPECTED_LAMBDA_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after flex, like flex(x) { vibe x * 2 }",
+        es: "Se esperaba '(' después de flex, tipo flex(x) { vibe x * 2 }",
+        hi: "flex के बाद '(' चाहिए था, जैसे flex(x) { vibe x * 2 }",
+    },
+};
+
+pub const EXPECTED_LAMBDA_BODY: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' before the lambda's body, like flex(x) { vibe x * 2 }",
+        es: "Se esperaba '{' antes del cuerpo de la lambda, tipo flex(x) { vibe x * 2 }",
+        hi: "Lambda की body से पहले '{' चाहिए था, जैसे flex(x) { vibe x * 2 }",
+    },
+};
+
+pub const EXPECTED_NEEDS_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after 'needs' condition 📜",
+        es: "Se esperaba ';' o salto de línea después de la condición de 'needs' 📜",
+        hi: "'needs' की condition के बाद ';' या नई line चाहिए थी 📜",
+    },
+};
+
+pub const EXPECTED_DECLARATION_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after variable declaration 📍",
+        es: "Se esperaba ';' o salto de línea después de declarar la variable 📍",
+        hi: "Variable declaration के बाद ';' या नई line चाहिए थी 📍",
+    },
+};
+
+pub const EXPECTED_VARIABLE_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected variable name after 'bet', gotta name your variables bestie 📛",
+        es: "Se esperaba el nombre de la variable después de 'bet', ponle nombre a tus variables bestie 📛",
+        hi: "'bet' के बाद variable का नाम चाहिए था, अपने variables को नाम दो यार 📛",
+    },
+};
+
+pub const EXPECTED_BREAK_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after 'slay'",
+        es: "Se esperaba ';' o salto de línea después de 'slay'",
+        hi: "'slay' के बाद ';' या नई line चाहिए थी",
+    },
+};
+
+pub const EXPECTED_CONTINUE_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after continue",
+        es: "Se esperaba ';' o salto de línea después de continue",
+        hi: "continue के बाद ';' या नई line चाहिए थी",
+    },
+};
+
+pub const EXPECTED_IF_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after 'sus'",
+        es: "Se esperaba '(' después de 'sus'",
+        hi: "'sus' के बाद '(' चाहिए था",
+    },
+};
+
+pub const EXPECTED_IF_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after condition",
+        es: "Se esperaba ')' después de la condición",
+        hi: "Condition के बाद ')' चाहिए था",
+    },
+};
+
+pub const EXPECTED_WHILE_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after 'lowkey', wrap that condition bestie! 🔄",
+        es: "Se esperaba '(' después de 'lowkey', envuelve esa condición bestie! 🔄",
+        hi: "'lowkey' के बाद '(' चाहिए था, condition को brackets में रखो यार! 🔄",
+    },
+};
+
+pub const EXPECTED_WHILE_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after condition 🔒",
+        es: "Se esperaba ')' después de la condición 🔒",
+        hi: "Condition के बाद ')' चाहिए था 🔒",
+    },
+};
+
+pub const EXPECTED_FOR_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after for loop",
+        es: "Se esperaba '(' después del bucle for",
+        hi: "for loop के बाद '(' चाहिए था",
+    },
+};
+
+pub const EXPECTED_FOR_VARIABLE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected variable name in for loop",
+        es: "Se esperaba el nombre de la variable en el bucle for",
+        hi: "for loop में variable का नाम चाहिए था",
+    },
+};
+
+pub const EXPECTED_FOR_IN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected 'in' after loop variable",
+        es: "Se esperaba 'in' después de la variable del bucle",
+        hi: "Loop variable के बाद 'in' चाहिए था",
+    },
+};
+
+pub const EXPECTED_FOR_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after iterable",
+        es: "Se esperaba ')' después de lo que se recorre",
+        hi: "जिस पर loop चल रहा है उसके बाद ')' चाहिए था",
+    },
+};
+
+pub const EXPECTED_LOOP_START_END: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ';' after the loop's start, it goes (bet i = 0; i < 10; i = i + 1) 🔁",
+        es: "Se esperaba ';' después del inicio del bucle, va así (bet i = 0; i < 10; i = i + 1) 🔁",
+        hi: "Loop की शुरुआत के बाद ';' चाहिए था, ऐसे लिखते हैं (bet i = 0; i < 10; i = i + 1) 🔁",
+    },
+};
+
+pub const EXPECTED_LOOP_CONDITION_END: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ';' after the loop condition 🔁",
+        es: "Se esperaba ';' después de la condición del bucle 🔁",
+        hi: "Loop की condition के बाद ';' चाहिए था 🔁",
+    },
+};
+
+pub const EXPECTED_LOOP_STEP_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after the loop's step 🔒",
+        es: "Se esperaba ')' después del paso del bucle 🔒",
+        hi: "Loop के step के बाद ')' चाहिए था 🔒",
+    },
+};
+
+pub const EXPECTED_SWITCH_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after 'vibe check'",
+        es: "Se esperaba '(' después de 'vibe check'",
+        hi: "'vibe check' के बाद '(' चाहिए था",
+    },
+};
+
+pub const EXPECTED_SWITCH_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after switch expression",
+        es: "Se esperaba ')' después de la expresión del switch",
+        hi: "Switch expression के बाद ')' चाहिए था",
+    },
+};
+
+pub const EXPECTED_SWITCH_OPEN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' after switch expression",
+        es: "Se esperaba '{' después de la expresión del switch",
+        hi: "Switch expression के बाद '{' चाहिए था",
+    },
+};
+
+pub const EXPECTED_DEFAULT_COLON: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ':' after default",
+        es: "Se esperaba ':' después de default",
+        hi: "default के बाद ':' चाहिए था",
+    },
+};
+
+pub const EXPECTED_CASE_COLON: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ':' after case value",
+        es: "Se esperaba ':' después del valor del case",
+        hi: "Case value के बाद ':' चाहिए था",
+    },
+};
+
+pub const EXPECTED_SWITCH_END: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after switch cases",
+        es: "Se esperaba '}' después de los cases del switch",
+        hi: "Switch cases के बाद '}' चाहिए था",
+    },
+};
+
+pub const EXPECTED_TRY_OPEN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' after 'manifest'",
+        es: "Se esperaba '{' después de 'manifest'",
+        hi: "'manifest' के बाद '{' चाहिए था",
+    },
+};
+
+pub const EXPECTED_TRY_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after try block",
+        es: "Se esperaba '}' después del bloque try",
+        hi: "try block के बाद '}' चाहिए था",
+    },
+};
+
+pub const EXPECTED_CATCH_PAREN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '(' after 'caught'",
+        es: "Se esperaba '(' después de 'caught'",
+        hi: "'caught' के बाद '(' चाहिए था",
+    },
+};
+
+pub const EXPECTED_CATCH_VARIABLE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected error variable name",
+        es: "Se esperaba el nombre de la variable del error",
+        hi: "Error variable का नाम चाहिए था",
+    },
+};
+
+pub const EXPECTED_CATCH_DRAMA_TYPE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a drama type after ':' in caught 🎭",
+        es: "Se esperaba un drama type después de ':' en caught 🎭",
+        hi: "caught में ':' के बाद drama type चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_CATCH_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after error variable",
+        es: "Se esperaba ')' después de la variable del error",
+        hi: "Error variable के बाद ')' चाहिए था",
+    },
+};
+
+pub const EXPECTED_CATCH_OPEN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' after catch clause",
+        es: "Se esperaba '{' después del catch",
+        hi: "catch के बाद '{' चाहिए था",
+    },
+};
+
+pub const EXPECTED_CATCH_END: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after catch block",
+        es: "Se esperaba '}' después del bloque catch",
+        hi: "catch block के बाद '}' चाहिए था",
+    },
+};
+
+pub const EXPECTED_FINALLY_OPEN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '{' after 'frfr'",
+        es: "Se esperaba '{' después de 'frfr'",
+        hi: "'frfr' के बाद '{' चाहिए था",
+    },
+};
+
+pub const EXPECTED_FINALLY_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after finally block",
+        es: "Se esperaba '}' después del bloque finally",
+        hi: "finally block के बाद '}' चाहिए था",
+    },
+};
+
+pub const EXPECTED_DRAMA_TYPE_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a name after 'drama type' 🎭",
+        es: "Se esperaba un nombre después de 'drama type' 🎭",
+        hi: "'drama type' के बाद नाम चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_DRAMA_TYPE_PARENT: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a parent drama type after ':' 🎭",
+        es: "Se esperaba un drama type padre después de ':' 🎭",
+        hi: "':' के बाद parent drama type चाहिए था 🎭",
+    },
+};
+
+pub const EXPECTED_DRAMA_TYPE_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after drama type declaration 🎭",
+        es: "Se esperaba ';' o salto de línea después de declarar el drama type 🎭",
+        hi: "Drama type declaration के बाद ';' या नई line चाहिए थी 🎭",
+    },
+};
+
+pub const EXPECTED_THROW_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after throw expression",
+        es: "Se esperaba ';' o salto de línea después de lo que se lanza",
+        hi: "Throw expression के बाद ';' या नई line चाहिए थी",
+    },
+};
+
+pub const EXPECTED_BLOCK_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after block, gotta close that block bestie! 🏁",
+        es: "Se esperaba '}' después del bloque, hay que cerrarlo bestie! 🏁",
+        hi: "Block के बाद '}' चाहिए था, block बंद करो यार! 🏁",
+    },
+};
+
+pub const EXPECTED_RETURN_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after return value 📤",
+        es: "Se esperaba ';' o salto de línea después del valor de retorno 📤",
+        hi: "Return value के बाद ';' या नई line चाहिए थी 📤",
+    },
+};
+
+pub const EXPECTED_PRINT_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after print statement 🖨️",
+        es: "Se esperaba ';' o salto de línea después del print 🖨️",
+        hi: "Print statement के बाद ';' या नई line चाहिए थी 🖨️",
+    },
+};
+
+pub const EXPECTED_INSPECT_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after 'bruh!' 🔍",
+        es: "Se esperaba ';' o salto de línea después de 'bruh!' 🔍",
+        hi: "'bruh!' के बाद ';' या नई line चाहिए थी 🔍",
+    },
+};
+
+pub const EXPECTED_PULL_ALIAS: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a name after 'as', like pull \"math.zlang\" as m 🧲",
+        es: "Se esperaba un nombre después de 'as', tipo pull \"math.zlang\" as m 🧲",
+        hi: "'as' के बाद नाम चाहिए था, जैसे pull \"math.zlang\" as m 🧲",
+    },
+};
+
+pub const EXPECTED_PULL_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after the file you pull 🧲",
+        es: "Se esperaba ';' o salto de línea después del archivo que traes 🧲",
+        hi: "Pull की गई file के बाद ';' या नई line चाहिए थी 🧲",
+    },
+};
+
+pub const EXPECTED_TERNARY_COLON: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ':' after the '?' branch, it goes cond ? this : that 🎯",
+        es: "Se esperaba ':' después de la rama del '?', va así cond ? esto : aquello 🎯",
+        hi: "'?' वाली branch के बाद ':' चाहिए था, ऐसे लिखते हैं cond ? this : that 🎯",
+    },
+};
+
+pub const EXPECTED_INDEX_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ']' after array index, close that bracket bestie! 📚",
+        es: "Se esperaba ']' después del índice, cierra ese corchete bestie! 📚",
+        hi: "Array index के बाद ']' चाहिए था, वो bracket बंद करो यार! 📚",
+    },
+};
+
+pub const EXPECTED_ARGUMENTS_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after arguments, close those parentheses! 📞",
+        es: "Se esperaba ')' después de los argumentos, cierra esos paréntesis! 📞",
+        hi: "Arguments के बाद ')' चाहिए था, वो brackets बंद करो! 📞",
+    },
+};
+
+pub const EXPECTED_GROUP_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ')' after expression, balance those parentheses! ⚖️",
+        es: "Se esperaba ')' después de la expresión, equilibra esos paréntesis! ⚖️",
+        hi: "Expression के बाद ')' चाहिए था, brackets का हिसाब बराबर करो! ⚖️",
+    },
+};
+
+pub const EXPECTED_ARRAY_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ']' after array elements, close that array bestie! 📝",
+        es: "Se esperaba ']' después de los elementos, cierra ese array bestie! 📝",
+        hi: "Array के elements के बाद ']' चाहिए था, वो array बंद करो यार! 📝",
+    },
+};
+
+pub const EXPECTED_PROPERTY_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected property name in object, objects need keys bestie! 🗝️",
+        es: "Se esperaba el nombre de una propiedad en el objeto, los objetos necesitan claves bestie! 🗝️",
+        hi: "Object में property का नाम चाहिए था, objects को keys चाहिए यार! 🗝️",
+    },
+};
+
+pub const EXPECTED_PROPERTY_COLON: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected ':' after property name, that's how objects work! 🎯",
+        es: "Se esperaba ':' después del nombre de la propiedad, así funcionan los objetos! 🎯",
+        hi: "Property के नाम के बाद ':' चाहिए था, objects ऐसे ही चलते हैं! 🎯",
+    },
+};
+
+pub const EXPECTED_OBJECT_CLOSE: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected '}' after object properties, close that object! 🏁",
+        es: "Se esperaba '}' después de las propiedades, cierra ese objeto! 🏁",
+        hi: "Object properties के बाद '}' चाहिए था, वो object बंद करो! 🏁",
+    },
+};
+
+pub const EXPECTED_TYPE_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a type name after ':' at line {0} 🏷️",
+        es: "Se esperaba un nombre de tipo después de ':' en la línea {0} 🏷️",
+        hi: "लाइन {0} पर ':' के बाद type का नाम चाहिए था 🏷️",
+    },
+};
+
+pub const OPTIONAL_FOR_NEEDS_IN: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "`grind?` skips a nil list, so it takes the (item in list) kind of loop at line {0} 🔁",
+        es: "`grind?` se salta una lista nil, así que usa el bucle de tipo (item in lista) en la línea {0} 🔁",
+        hi: "`grind?` nil list को छोड़ देता है, तो लाइन {0} पर इसे (item in list) वाला loop चाहिए 🔁",
+    },
+};
+
+pub const EXPECTED_SCOPE_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected variable name after '{0}' 🌍",
+        es: "Se esperaba el nombre de una variable después de '{0}' 🌍",
+        hi: "'{0}' के बाद variable का नाम चाहिए था 🌍",
+    },
+};
+
+pub const EXPECTED_SCOPE_END: Message = Message {
+    code: EXPECTED_STATEMENT_END.code,
+    text: Text {
+        en: "Expected ';' or newline after '{0}' names 📍",
+        es: "Se esperaba ';' o salto de línea después de los nombres de '{0}' 📍",
+        hi: "'{0}' के नामों के बाद ';' या नई line चाहिए थी 📍",
+    },
+};
+
+pub const EXPECTED_FIELD_NAME: Message = Message {
+    code: EXPECTED_TOKEN.code,
+    text: Text {
+        en: "Expected a field name after '.' at line {0}, like person.name 🔍",
+        es: "Se esperaba el nombre de un campo después de '.' en la línea {0}, tipo person.name 🔍",
+        hi: "लाइन {0} पर '.' के बाद field का नाम चाहिए था, जैसे person.name 🔍",
+    },
+};
+
+// Running
+
+pub const UNDEFINED_VARIABLE: Message = Message {
+    code: "Z0020",
+    text: Text {
+        en: "Undefined variable '{0}', you haven't declared this bestie! 🤔{1}",
+        es: "Variable no definida '{0}', ¡no la has declarado bestie! 🤔{1}",
+        hi: "Variable '{0}' define नहीं है, तुमने इसे declare ही नहीं किया यार! 🤔{1}",
+    },
+};
+
+pub const UNDEFINED_ASSIGNMENT: Message = Message {
+    code: "Z0021",
+    text: Text {
+        en: "Undefined variable '{0}', can't assign to something that doesn't exist! 🚫{1}",
+        es: "Variable no definida '{0}', ¡no puedes asignar a algo que no existe! 🚫{1}",
+        hi: "Variable '{0}' define नहीं है, जो है ही नहीं उसमें value कैसे डालोगे! 🚫{1}",
+    },
+};
+
+pub const UNDEFINED_FUNCTION: Message = Message {
+    code: "Z0022",
+    text: Text {
+        en: "Undefined function '{0}', that function doesn't exist bestie! 📞{1}",
+        es: "Función no definida '{0}', ¡esa función no existe bestie! 📞{1}",
+        hi: "Function '{0}' define नहीं है, ऐसा कोई function है ही नहीं यार! 📞{1}",
+    },
+};
+
+pub const DIVISION_BY_ZERO: Message = Message {
+    code: "Z0023",
+    text: Text {
+        en: "Division by zero bestie, that's undefined! ➗",
+        es: "División entre cero bestie, ¡eso no está definido! ➗",
+        hi: "Zero से भाग दे रहे हो यार, ये undefined है! ➗",
+    },
+};
+
+pub const MODULO_BY_ZERO: Message = Message {
+    code: "Z0024",
+    text: Text {
+        en: "Modulo by zero, that's not how math works! 🤓",
+        es: "Módulo entre cero, ¡así no funcionan las mates! 🤓",
+        hi: "Zero से modulo, maths ऐसे काम नहीं करता! 🤓",
+    },
+};
+
+pub const INDEX_OUT_OF_BOUNDS: Message = Message {
+    code: "Z0025",
+    text: Text {
+        en: "Array index out of bounds bestie! 📚",
+        es: "¡Índice del array fuera de rango bestie! 📚",
+        hi: "Array का index range से बाहर है यार! 📚",
+    },
+};
+
+pub const NOT_ITERABLE: Message = Message {
+    code: "Z0026",
+    text: Text {
+        en: "Can only iterate over arrays bestie, and `{0}` came out {1} 📚{2}",
+        es: "Solo se puede recorrer arrays bestie, y `{0}` salió {1} 📚{2}",
+        hi: "Loop सिर्फ arrays पर चल सकता है यार, और `{0}` {1} निकला 📚{2}",
+    },
+};
+
+pub const TYPE_MISMATCH: Message = Message {
+    code: "Z0027",
+    text: Text {
+        en: "Type mismatch in operation, these types don't work together! 🔀",
+        es: "Tipos incompatibles en la operación, ¡estos tipos no se llevan! 🔀",
+        hi: "Operation में types मेल नहीं खाते, ये types साथ नहीं चलते! 🔀",
+    },
+};
+
+pub const CONCATENATION: Message = Message {
+    code: "Z0028",
+    text: Text {
+        en: "Can only concatenate with strings using +, that's the vibe! 🔗",
+        es: "Con + solo se concatenan strings, ¡esa es la vibra! 🔗",
+        hi: "+ से सिर्फ strings जोड़ी जाती हैं, यही vibe है! 🔗",
+    },
+};
+
+pub const WRONG_ARGUMENT_COUNT: Message = Message {
+    code: "Z0029",
+    text: Text {
+        en: "Function '{0}' expects {1} arguments but got {2}, check your parameters bestie! 📊",
+        es: "La función '{0}' espera {1} argumentos pero recibió {2}, ¡revisa tus parámetros bestie! 📊",
+        hi: "Function '{0}' को {1} arguments चाहिए पर मिले {2}, अपने parameters check करो यार! 📊",
+    },
+};
+
+pub const TOO_FEW_ARGUMENTS: Message = Message {
+    code: "Z0030",
+    text: Text {
+        en: "Function '{0}' expects at least {1} arguments but got {2}, check your parameters bestie! 📊",
+        es: "La función '{0}' espera al menos {1} argumentos pero recibió {2}, ¡revisa tus parámetros bestie! 📊",
+        hi: "Function '{0}' को कम से कम {1} arguments चाहिए पर मिले {2}, अपने parameters check करो यार! 📊",
+    },
+};
+
+pub const TOO_MUCH_RECURSION: Message = Message {
+    code: "Z0031",
+    text: Text {
+        en: "Too much recursion in '{0}' ({1} calls deep), this is giving infinite loop energy 🌀",
+        es: "Demasiada recursión en '{0}' ({1} llamadas de profundidad), esto tiene energía de bucle infinito 🌀",
+        hi: "'{0}' में बहुत ज़्यादा recursion ({1} calls गहरा), ये तो infinite loop वाली vibe है 🌀",
+    },
+};
+
+pub const NOT_CALLABLE: Message = Message {
+    code: "Z0032",
+    text: Text {
+        en: "Can't call '{0}', it's a {1} and not a function 🤙",
+        es: "No se puede llamar a '{0}', es un {1} y no una función 🤙",
+        hi: "'{0}' को call नहीं कर सकते, ये {1} है, function नहीं 🤙",
+    },
+};
+
+pub const STRAY_CONTROL_FLOW: Message = Message {
+    code: "Z0033",
+    text: Text {
+        en: "'{0}' only works inside a loop (or vibecheck for slay), there's nothing to leave here 🛑",
+        es: "'{0}' solo funciona dentro de un bucle (o un vibecheck para slay), aquí no hay nada de qué salir 🛑",
+        hi: "'{0}' सिर्फ loop के अंदर चलता है (या slay के लिए vibecheck में), यहाँ से निकलने को कुछ है ही नहीं 🛑",
+    },
+};
+
+pub const NEGATE_NON_NUMBER: Message = Message {
+    code: "Z0034",
+    text: Text {
+        en: "Can only negate numbers, that's basic math! ➖",
+        es: "Solo se pueden negar números, ¡son mates básicas! ➖",
+        hi: "सिर्फ नंबर negative किए जा सकते हैं, ये तो basic maths है! ➖",
+    },
+};
+
+pub const BUILTIN_ARGUMENT_COUNT: Message = Message {
+    code: "Z0035",
+    text: Text {
+        en: "A builtin got the wrong number of arguments 🧰",
+        es: "Una función incorporada recibió un número equivocado de argumentos 🧰",
+        hi: "किसी builtin को ग़लत गिनती में arguments मिले 🧰",
+    },
+};
+
+pub const BUILTIN_ARGUMENT_TYPE: Message = Message {
+    code: "Z0036",
+    text: Text {
+        en: "A builtin got an argument of a kind it can't work with 🧰",
+        es: "Una función incorporada recibió un argumento con el que no puede trabajar 🧰",
+        hi: "किसी builtin को ऐसा argument मिला जिसके साथ वो काम नहीं कर सकता 🧰",
+    },
+};
+
+pub const INTERRUPTED: Message = Message {
+    code: "Z0037",
+    text: Text {
+        en: "Interrupted, you hit Ctrl+C so we stopped right there ✋",
+        es: "Interrumpido, presionaste Ctrl+C así que paramos justo ahí ✋",
+        hi: "रुक गए, तुमने Ctrl+C दबाया तो हम वहीं रुक गए ✋",
+    },
+};
+
+pub const INPUT_NOT_ALLOWED: Message = Message {
+    code: "Z0038",
+    text: Text {
+        en: "spill() can't ask for input here, reading input is switched off 🔒",
+        es: "spill() no puede pedir datos aquí, leer la entrada está desactivado 🔒",
+        hi: "spill() यहाँ input नहीं माँग सकता, input पढ़ना बंद है 🔒",
+    },
+};
+
+pub const NOT_A_RESULT: Message = Message {
+    code: "Z0039",
+    text: Text {
+        en: "'?' at line {0} only works on ok(...) or nah(...) results bestie 🎁",
+        es: "El '?' de la línea {0} solo funciona con resultados ok(...) o nah(...) bestie 🎁",
+        hi: "लाइन {0} वाला '?' सिर्फ़ ok(...) या nah(...) results पर चलता है यार 🎁",
+    },
+};
+
+// Builtins, each a more exact Z0019, Z0035 or Z0036
+
+pub const SQRT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "sqrt expects 1 argument bestie! 📊",
+        es: "sqrt espera 1 argumento bestie! 📊",
+        hi: "sqrt को 1 argument चाहिए यार! 📊",
+    },
+};
+
+pub const SQRT_NEGATIVE: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "Can't sqrt negative numbers, that's imaginary! 🤔",
+        es: "No se puede sacar sqrt de números negativos, eso es imaginario! 🤔",
+        hi: "Negative नंबर का sqrt नहीं होता, वो तो imaginary है! 🤔",
+    },
+};
+
+pub const SQRT_NOT_NUMBER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "sqrt only works with numbers! 🔢",
+        es: "sqrt solo funciona con números! 🔢",
+        hi: "sqrt सिर्फ नंबर पर चलता है! 🔢",
+    },
+};
+
+pub const ABS_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "abs expects 1 argument bestie! 📊",
+        es: "abs espera 1 argumento bestie! 📊",
+        hi: "abs को 1 argument चाहिए यार! 📊",
+    },
+};
+
+pub const ABS_NOT_NUMBER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "abs only works with numbers! 🔢",
+        es: "abs solo funciona con números! 🔢",
+        hi: "abs सिर्फ नंबर पर चलता है! 🔢",
+    },
+};
+
+pub const NUM_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "num expects 1 argument bestie! 🔢",
+        es: "num espera 1 argumento bestie! 🔢",
+        hi: "num को 1 argument चाहिए यार! 🔢",
+    },
+};
+
+pub const RANDOM_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "random takes no arguments bestie! 🎲",
+        es: "random no lleva argumentos bestie! 🎲",
+        hi: "random कोई argument नहीं लेता यार! 🎲",
+    },
+};
+
+pub const SPILL_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "spill takes at most 1 argument (the prompt) bestie! 🎤",
+        es: "spill lleva como mucho 1 argumento (la pregunta) bestie! 🎤",
+        hi: "spill ज़्यादा से ज़्यादा 1 argument लेता है (सवाल) यार! 🎤",
+    },
+};
+
+pub const LENGTH_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "length expects 1 argument bestie! 📏",
+        es: "length espera 1 argumento bestie! 📏",
+        hi: "length को 1 argument चाहिए यार! 📏",
+    },
+};
+
+pub const LENGTH_NOT_SIZED: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "length only works with strings and arrays! 📝",
+        es: "length solo funciona con strings y arrays! 📝",
+        hi: "length सिर्फ strings और arrays पर चलता है! 📝",
+    },
+};
+
+pub const UPPERCASE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "uppercase expects 1 argument bestie! 📝",
+        es: "uppercase espera 1 argumento bestie! 📝",
+        hi: "uppercase को 1 argument चाहिए यार! 📝",
+    },
+};
+
+pub const UPPERCASE_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "uppercase only works with strings! 📝",
+        es: "uppercase solo funciona con strings! 📝",
+        hi: "uppercase सिर्फ strings पर चलता है! 📝",
+    },
+};
+
+pub const SPLIT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "split expects 2 arguments (string, delimiter) bestie! ✂️",
+        es: "split espera 2 argumentos (string, separador) bestie! ✂️",
+        hi: "split को 2 arguments चाहिए (string, separator) यार! ✂️",
+    },
+};
+
+pub const SPLIT_NOT_STRINGS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "split needs two strings (text, delimiter)! ✂️",
+        es: "split necesita dos strings (texto, separador)! ✂️",
+        hi: "split को दो strings चाहिए (text, separator)! ✂️",
+    },
+};
+
+pub const INSPECT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "inspect expects 1 argument bestie! 🔍",
+        es: "inspect espera 1 argumento bestie! 🔍",
+        hi: "inspect को 1 argument चाहिए यार! 🔍",
+    },
+};
+
+pub const ATTEMPT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "attempt needs (tries, function, ...args) bestie! 🔁",
+        es: "attempt necesita (intentos, función, ...args) bestie! 🔁",
+        hi: "attempt को (tries, function, ...args) चाहिए यार! 🔁",
+    },
+};
+
+pub const ATTEMPT_TRIES: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "attempt wants at least 1 try, as a number! 🔁",
+        es: "attempt quiere al menos 1 intento, como número! 🔁",
+        hi: "attempt को कम से कम 1 try चाहिए, नंबर में! 🔁",
+    },
+};
+
+pub const MONEY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "money expects 1 argument (an amount) bestie! 💸",
+        es: "money espera 1 argumento (una cantidad) bestie! 💸",
+        hi: "money को 1 argument चाहिए (एक रकम) यार! 💸",
+    },
+};
+
+pub const DIVMOD_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "divmod needs 2 arguments (a, b) bestie! ➗",
+        es: "divmod necesita 2 argumentos (a, b) bestie! ➗",
+        hi: "divmod को 2 arguments चाहिए (a, b) यार! ➗",
+    },
+};
+
+pub const DIVMOD_NOT_NUMBERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "divmod only works with numbers! 🔢",
+        es: "divmod solo funciona con números! 🔢",
+        hi: "divmod सिर्फ नंबर पर चलता है! 🔢",
+    },
+};
+
+pub const GROUP_BY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "It's groupBy(array, function) bestie! 🗂️",
+        es: "Es groupBy(array, función) bestie! 🗂️",
+        hi: "ये groupBy(array, function) है यार! 🗂️",
+    },
+};
+
+pub const COUNT_BY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "It's countBy(array, function?) bestie! 🧮",
+        es: "Es countBy(array, función?) bestie! 🧮",
+        hi: "ये countBy(array, function?) है यार! 🧮",
+    },
+};
+
+pub const REVERSED_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "reversed expects 1 argument (an array) bestie! 🔃",
+        es: "reversed espera 1 argumento (un array) bestie! 🔃",
+        hi: "reversed को 1 argument चाहिए (एक array) यार! 🔃",
+    },
+};
+
+pub const UNIQUE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "unique expects 1 argument (an array) bestie! ❄️",
+        es: "unique espera 1 argumento (un array) bestie! ❄️",
+        hi: "unique को 1 argument चाहिए (एक array) यार! ❄️",
+    },
+};
+
+pub const TOKENIZE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "tokenize needs exactly 1 argument (the code) bestie! 🧩",
+        es: "tokenize necesita exactamente 1 argumento (el código) bestie! 🧩",
+        hi: "tokenize को ठीक 1 argument चाहिए (code) यार! 🧩",
+    },
+};
+
+pub const TOKENIZE_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "tokenize only works with strings of ZLang code! 🧩",
+        es: "tokenize solo funciona con strings de código ZLang! 🧩",
+        hi: "tokenize सिर्फ ZLang code की strings पर चलता है! 🧩",
+    },
+};
+
+pub const PARSE_CODE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "parseCode needs exactly 1 argument (the code) bestie! 🌳",
+        es: "parseCode necesita exactamente 1 argumento (el código) bestie! 🌳",
+        hi: "parseCode को ठीक 1 argument चाहिए (code) यार! 🌳",
+    },
+};
+
+pub const PARSE_CODE_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "parseCode only works with strings of ZLang code! 🌳",
+        es: "parseCode solo funciona con strings de código ZLang! 🌳",
+        hi: "parseCode सिर्फ ZLang code की strings पर चलता है! 🌳",
+    },
+};
+
+pub const RUN_AST_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "runAst needs exactly 1 argument (the AST from parseCode) bestie! 🌳",
+        es: "runAst necesita exactamente 1 argumento (el AST de parseCode) bestie! 🌳",
+        hi: "runAst को ठीक 1 argument चाहिए (parseCode वाला AST) यार! 🌳",
+    },
+};
+
+pub const MARKDOWN_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "markdown needs exactly 1 argument (the text) bestie! 📝",
+        es: "markdown necesita exactamente 1 argumento (el texto) bestie! 📝",
+        hi: "markdown को ठीक 1 argument चाहिए (text) यार! 📝",
+    },
+};
+
+pub const MARKDOWN_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "markdown only works with strings! 📝",
+        es: "markdown solo funciona con strings! 📝",
+        hi: "markdown सिर्फ strings पर चलता है! 📝",
+    },
+};
+
+pub const PARSE_URL_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "parseUrl needs exactly 1 argument (the URL) bestie! 🔗",
+        es: "parseUrl necesita exactamente 1 argumento (la URL) bestie! 🔗",
+        hi: "parseUrl को ठीक 1 argument चाहिए (URL) यार! 🔗",
+    },
+};
+
+pub const BUILD_URL_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "buildUrl needs exactly 1 argument (an object) bestie! 🔗",
+        es: "buildUrl necesita exactamente 1 argumento (un objeto) bestie! 🔗",
+        hi: "buildUrl को ठीक 1 argument चाहिए (एक object) यार! 🔗",
+    },
+};
+
+pub const COLORED_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "colored needs 2 arguments (text, color) bestie! 🌈",
+        es: "colored necesita 2 argumentos (texto, color) bestie! 🌈",
+        hi: "colored को 2 arguments चाहिए (text, color) यार! 🌈",
+    },
+};
+
+pub const COLOR_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "colored wants the color as a string, like \"red\" 🌈",
+        es: "colored quiere el color como string, tipo \"red\" 🌈",
+        hi: "colored को color एक string में चाहिए, जैसे \"red\" 🌈",
+    },
+};
+
+pub const BOLD_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "bold expects 1 argument bestie! 💪",
+        es: "bold espera 1 argumento bestie! 💪",
+        hi: "bold को 1 argument चाहिए यार! 💪",
+    },
+};
+
+pub const STASH_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "stash needs 2 arguments (key, value) bestie! 🐿️",
+        es: "stash necesita 2 argumentos (clave, valor) bestie! 🐿️",
+        hi: "stash को 2 arguments चाहिए (key, value) यार! 🐿️",
+    },
+};
+
+pub const UNSTASH_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "unstash expects 1 argument (the key) bestie! 🐿️",
+        es: "unstash espera 1 argumento (la clave) bestie! 🐿️",
+        hi: "unstash को 1 argument चाहिए (key) यार! 🐿️",
+    },
+};
+
+pub const CLEAR_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "draw.clear takes no arguments bestie! 🧽",
+        es: "draw.clear no lleva argumentos bestie! 🧽",
+        hi: "draw.clear कोई argument नहीं लेता यार! 🧽",
+    },
+};
+
+pub const SLEEP_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "sleep expects 1 argument (milliseconds) bestie! 😴",
+        es: "sleep espera 1 argumento (milisegundos) bestie! 😴",
+        hi: "sleep को 1 argument चाहिए (milliseconds) यार! 😴",
+    },
+};
+
+pub const CANCEL_TIMER_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "cancelTimer needs 1 argument (the timer from after or every) bestie! ⏰",
+        es: "cancelTimer necesita 1 argumento (el timer de after o every) bestie! ⏰",
+        hi: "cancelTimer को 1 argument चाहिए (after या every वाला timer) यार! ⏰",
+    },
+};
+
+pub const NOT_A_TIMER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "cancelTimer wants the number after or every handed back ⏰",
+        es: "cancelTimer quiere el número que devolvió after o every ⏰",
+        hi: "cancelTimer को वो नंबर चाहिए जो after या every ने लौटाया था ⏰",
+    },
+};
+
+pub const GAME_LOOP_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "gameLoop needs 2 arguments (fps, update function) bestie! 🎮",
+        es: "gameLoop necesita 2 argumentos (fps, función de update) bestie! 🎮",
+        hi: "gameLoop को 2 arguments चाहिए (fps, update function) यार! 🎮",
+    },
+};
+
+pub const SEED_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "seed needs 1 argument (a whole number) bestie! 🎲",
+        es: "seed necesita 1 argumento (un número entero) bestie! 🎲",
+        hi: "seed को 1 argument चाहिए (एक पूरा नंबर) यार! 🎲",
+    },
+};
+
+pub const SIMULATE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "simulate needs 2 or 3 arguments (runs, function, seed) bestie! 🎲",
+        es: "simulate necesita 2 o 3 argumentos (corridas, función, semilla) bestie! 🎲",
+        hi: "simulate को 2 या 3 arguments चाहिए (runs, function, seed) यार! 🎲",
+    },
+};
+
+pub const GRAPH_DIRECTED: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "graphNew wants fr for a directed graph or cap for one where edges go both ways 🕸️",
+        es: "graphNew quiere fr para un grafo dirigido o cap para uno donde las aristas van en los dos sentidos 🕸️",
+        hi: "graphNew को directed graph के लिए fr चाहिए, या cap जब edges दोनों तरफ जाएँ 🕸️",
+    },
+};
+
+pub const GRAPH_NEW_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "graphNew takes at most 1 argument (directed) bestie! 🕸️",
+        es: "graphNew lleva como mucho 1 argumento (dirigido) bestie! 🕸️",
+        hi: "graphNew ज़्यादा से ज़्यादा 1 argument लेता है (directed) यार! 🕸️",
+    },
+};
+
+pub const HEAP_NEW_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "heapNew takes no arguments bestie! 🏔️",
+        es: "heapNew no lleva argumentos bestie! 🏔️",
+        hi: "heapNew कोई argument नहीं लेता यार! 🏔️",
+    },
+};
+
+pub const HEAP_PUSH_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "heapPush needs 3 arguments (heap, value, priority) bestie! 🏔️",
+        es: "heapPush necesita 3 argumentos (heap, valor, prioridad) bestie! 🏔️",
+        hi: "heapPush को 3 arguments चाहिए (heap, value, priority) यार! 🏔️",
+    },
+};
+
+pub const PUSH_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "push needs 2 arguments (queue or stack, value) bestie! 🚏",
+        es: "push necesita 2 argumentos (queue o stack, valor) bestie! 🚏",
+        hi: "push को 2 arguments चाहिए (queue या stack, value) यार! 🚏",
+    },
+};
+
+pub const ON_KEY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "onKey needs 1 argument (the function that gets each key) bestie! ⌨️",
+        es: "onKey necesita 1 argumento (la función que recibe cada tecla) bestie! ⌨️",
+        hi: "onKey को 1 argument चाहिए (वो function जिसे हर key मिलती है) यार! ⌨️",
+    },
+};
+
+pub const RUN_LOOP_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "runLoop takes no arguments bestie! ⏰",
+        es: "runLoop no lleva argumentos bestie! ⏰",
+        hi: "runLoop कोई argument नहीं लेता यार! ⏰",
+    },
+};
+
+pub const SCOPE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "scope takes no arguments bestie! 🔭",
+        es: "scope no lleva argumentos bestie! 🔭",
+        hi: "scope कोई argument नहीं लेता यार! 🔭",
+    },
+};
+
+pub const BENCHMARK_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "benchmark needs 2 arguments (function, iterations) bestie! ⏱️",
+        es: "benchmark necesita 2 argumentos (función, iteraciones) bestie! ⏱️",
+        hi: "benchmark को 2 arguments चाहिए (function, iterations) यार! ⏱️",
+    },
+};
+
+pub const OPS_SO_FAR_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "opsSoFar takes no arguments bestie! 🧮",
+        es: "opsSoFar no lleva argumentos bestie! 🧮",
+        hi: "opsSoFar कोई argument नहीं लेता यार! 🧮",
+    },
+};
+
+pub const MEM_STATS_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "memStats takes no arguments bestie! 🧠",
+        es: "memStats no lleva argumentos bestie! 🧠",
+        hi: "memStats कोई argument नहीं लेता यार! 🧠",
+    },
+};
+
+pub const EXPECT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "expect needs 2 arguments (value, type name) bestie! 🏷️",
+        es: "expect necesita 2 argumentos (valor, nombre del tipo) bestie! 🏷️",
+        hi: "expect को 2 arguments चाहिए (value, type का नाम) यार! 🏷️",
+    },
+};
+
+pub const TYPE_NAME_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "expect wants the type as a string, like \"number\" 🏷️",
+        es: "expect quiere el tipo como string, tipo \"number\" 🏷️",
+        hi: "expect को type एक string में चाहिए, जैसे \"number\" 🏷️",
+    },
+};
+
+pub const SEED_NOT_WHOLE: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} wants the seed as a whole number, like 42 🎲",
+        es: "{0} quiere la semilla como número entero, tipo 42 🎲",
+        hi: "{0} को seed एक पूरे नंबर में चाहिए, जैसे 42 🎲",
+    },
+};
+
+pub const NOT_AN_ARRAY: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} works on arrays, not a {1} 📚",
+        es: "{0} funciona con arrays, no con un {1} 📚",
+        hi: "{0} arrays पर चलता है, {1} पर नहीं 📚",
+    },
+};
+
+pub const NOT_A_DEQUE: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} works on a queue() or stack(), not a {1} 🚏",
+        es: "{0} funciona con un queue() o stack(), no con un {1} 🚏",
+        hi: "{0} queue() या stack() पर चलता है, {1} पर नहीं 🚏",
+    },
+};
+
+pub const NOT_A_HEAP: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} works on a heap from heapNew(), not a {1} 🏔️",
+        es: "{0} funciona con un heap de heapNew(), no con un {1} 🏔️",
+        hi: "{0} heapNew() वाले heap पर चलता है, {1} पर नहीं 🏔️",
+    },
+};
+
+pub const NODE_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} wants graph nodes as strings like \"home\", not a {1} 🕸️",
+        es: "{0} quiere los nodos del grafo como strings tipo \"home\", no un {1} 🕸️",
+        hi: "{0} को graph के nodes strings में चाहिए जैसे \"home\", {1} नहीं 🕸️",
+    },
+};
+
+pub const KEY_FUNCTION_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} wants a function with 1 parameter (the item) and '{1}' has {2} 📊",
+        es: "{0} quiere una función con 1 parámetro (el elemento) y '{1}' tiene {2} 📊",
+        hi: "{0} को 1 parameter (item) वाला function चाहिए और '{1}' में {2} हैं 📊",
+    },
+};
+
+pub const COMPARE_NOT_NUMBER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "'{0}' should compare with a number (negative, 0 or positive), not a {1} 🔀",
+        es: "'{0}' debería comparar con un número (negativo, 0 o positivo), no con un {1} 🔀",
+        hi: "'{0}' को एक नंबर से compare करना चाहिए (negative, 0 या positive), {1} से नहीं 🔀",
+    },
+};
+
+pub const FUNCTION_EXPECTED: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "Expected a function name there bestie! 📞",
+        es: "Ahí va el nombre de una función bestie! 📞",
+        hi: "वहाँ एक function का नाम चाहिए था यार! 📞",
+    },
+};
+
+pub const RESULT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} takes at most 1 argument bestie! 🎁",
+        es: "{0} lleva como mucho 1 argumento bestie! 🎁",
+        hi: "{0} ज़्यादा से ज़्यादा 1 argument लेता है यार! 🎁",
+    },
+};
+
+pub const ATTEMPT_PASSES: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "Function '{0}' expects {1} arguments but attempt passes {2}, check your parameters bestie! 📊",
+        es: "La función '{0}' espera {1} argumentos pero attempt le pasa {2}, revisa tus parámetros bestie! 📊",
+        hi: "Function '{0}' को {1} arguments चाहिए पर attempt {2} दे रहा है, अपने parameters check करो यार! 📊",
+    },
+};
+
+pub const STATS_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} expects 1 argument (an array of numbers) bestie! 📊",
+        es: "{0} espera 1 argumento (un array de números) bestie! 📊",
+        hi: "{0} को 1 argument चाहिए (नंबरों का array) यार! 📊",
+    },
+};
+
+pub const STATS_NOT_NUMBER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} only works on numbers, but item {1} is a {2} ({3}) 🔢",
+        es: "{0} solo funciona con números, pero el elemento {1} es un {2} ({3}) 🔢",
+        hi: "{0} सिर्फ नंबरों पर चलता है, पर item {1} एक {2} है ({3}) 🔢",
+    },
+};
+
+pub const STATS_EMPTY: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} of an empty array? There's nothing to work with 🫙",
+        es: "{0} de un array vacío? No hay nada con qué trabajar 🫙",
+        hi: "खाली array का {0}? यहाँ तो कुछ है ही नहीं 🫙",
+    },
+};
+
+pub const MONEY_NOT_CONVERTIBLE: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "money wants a number or a string, not a {0} 💸",
+        es: "money quiere un número o un string, no un {0} 💸",
+        hi: "money को नंबर या string चाहिए, {0} नहीं 💸",
+    },
+};
+
+pub const SORT_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "It's {0} bestie! 🔀",
+        es: "Es {0} bestie! 🔀",
+        hi: "ये {0} है यार! 🔀",
+    },
+};
+
+pub const MIN_MAX_BY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "It's {0}(array, function) bestie! 🏆",
+        es: "Es {0}(array, función) bestie! 🏆",
+        hi: "ये {0}(array, function) है यार! 🏆",
+    },
+};
+
+pub const SIMILARITY_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 2 arguments (two strings) bestie! 🔍",
+        es: "{0} necesita 2 argumentos (dos strings) bestie! 🔍",
+        hi: "{0} को 2 arguments चाहिए (दो strings) यार! 🔍",
+    },
+};
+
+pub const SIMILARITY_NOT_STRINGS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} only works with strings! 📝",
+        es: "{0} solo funciona con strings! 📝",
+        hi: "{0} सिर्फ strings पर चलता है! 📝",
+    },
+};
+
+pub const PARSE_URL_NOT_STRING: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "parseUrl wants a string, not a {0} 🔗",
+        es: "parseUrl quiere un string, no un {0} 🔗",
+        hi: "parseUrl को string चाहिए, {0} नहीं 🔗",
+    },
+};
+
+pub const BUILD_URL_NOT_OBJECT: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "buildUrl wants an object, not a {0} 🔗",
+        es: "buildUrl quiere un objeto, no un {0} 🔗",
+        hi: "buildUrl को object चाहिए, {0} नहीं 🔗",
+    },
+};
+
+pub const UNKNOWN_COLOR: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "'{0}' isn't a color we know, try {1} 🎨",
+        es: "'{0}' no es un color que conozcamos, prueba {1} 🎨",
+        hi: "'{0}' कोई जाना-पहचाना color नहीं है, ये try करो: {1} 🎨",
+    },
+};
+
+pub const SLEEP_RANGE: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "sleep wants milliseconds from 0 to {0} 😴",
+        es: "sleep quiere milisegundos de 0 a {0} 😴",
+        hi: "sleep को 0 से {0} तक milliseconds चाहिए 😴",
+    },
+};
+
+pub const TIMER_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 2 arguments (milliseconds, function) bestie! ⏰",
+        es: "{0} necesita 2 argumentos (milisegundos, función) bestie! ⏰",
+        hi: "{0} को 2 arguments चाहिए (milliseconds, function) यार! ⏰",
+    },
+};
+
+pub const TIMER_TOO_SOON: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} wants at least {1} milliseconds ⏰",
+        es: "{0} quiere al menos {1} milisegundos ⏰",
+        hi: "{0} को कम से कम {1} milliseconds चाहिए ⏰",
+    },
+};
+
+pub const TIMER_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "{0} calls '{1}' with no arguments, so it can't have parameters ⏰",
+        es: "{0} llama a '{1}' sin argumentos, así que no puede tener parámetros ⏰",
+        hi: "{0} '{1}' को बिना arguments के call करता है, तो उसमें parameters नहीं हो सकते ⏰",
+    },
+};
+
+pub const BENCHMARK_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "benchmark calls '{0}' with no arguments, so it can't have parameters ⏱️",
+        es: "benchmark llama a '{0}' sin argumentos, así que no puede tener parámetros ⏱️",
+        hi: "benchmark '{0}' को बिना arguments के call करता है, तो उसमें parameters नहीं हो सकते ⏱️",
+    },
+};
+
+pub const GAME_LOOP_FPS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "gameLoop wants the fps as a number from 1 to {0} 🎮",
+        es: "gameLoop quiere los fps como un número de 1 a {0} 🎮",
+        hi: "gameLoop को fps 1 से {0} तक के नंबर में चाहिए 🎮",
+    },
+};
+
+pub const GAME_LOOP_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "gameLoop calls '{0}' with just the seconds since the last frame, and it wants {1} parameters 🎮",
+        es: "gameLoop llama a '{0}' solo con los segundos desde el último frame, y pide {1} parámetros 🎮",
+        hi: "gameLoop '{0}' को बस पिछले frame से बीते seconds देता है, और उसे {1} parameters चाहिए 🎮",
+    },
+};
+
+pub const SIMULATE_RUNS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "simulate wants the runs as a whole number from 1 to {0} 🎲",
+        es: "simulate quiere las corridas como número entero de 1 a {0} 🎲",
+        hi: "simulate को runs 1 से {0} तक के पूरे नंबर में चाहिए 🎲",
+    },
+};
+
+pub const SIMULATE_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "simulate calls '{0}' with just the run's number, and it wants {1} parameters 🎲",
+        es: "simulate llama a '{0}' solo con el número de la corrida, y pide {1} parámetros 🎲",
+        hi: "simulate '{0}' को बस run का नंबर देता है, और उसे {1} parameters चाहिए 🎲",
+    },
+};
+
+pub const EDGE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 3 arguments (graph, from, to) bestie! 🕸️",
+        es: "{0} necesita 3 argumentos (grafo, desde, hasta) bestie! 🕸️",
+        hi: "{0} को 3 arguments चाहिए (graph, from, to) यार! 🕸️",
+    },
+};
+
+pub const NODE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 2 arguments (graph, node) bestie! 🕸️",
+        es: "{0} necesita 2 argumentos (grafo, nodo) bestie! 🕸️",
+        hi: "{0} को 2 arguments चाहिए (graph, node) यार! 🕸️",
+    },
+};
+
+pub const PRIORITY_NOT_NUMBER: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "heapPush wants the priority as a number, smallest comes out first - not a {0} 🏔️",
+        es: "heapPush quiere la prioridad como número, la más chica sale primero - no un {0} 🏔️",
+        hi: "heapPush को priority नंबर में चाहिए, सबसे छोटी पहले निकलती है - {0} नहीं 🏔️",
+    },
+};
+
+pub const HEAP_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 1 argument (the heap) bestie! 🏔️",
+        es: "{0} necesita 1 argumento (el heap) bestie! 🏔️",
+        hi: "{0} को 1 argument चाहिए (heap) यार! 🏔️",
+    },
+};
+
+pub const DEQUE_NEW_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} takes at most 1 argument (what goes in first) bestie! 🚏",
+        es: "{0} lleva como mucho 1 argumento (lo que entra primero) bestie! 🚏",
+        hi: "{0} ज़्यादा से ज़्यादा 1 argument लेता है (जो पहले अंदर जाए) यार! 🚏",
+    },
+};
+
+pub const DEQUE_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} needs 1 argument (the queue or stack) bestie! 🚏",
+        es: "{0} necesita 1 argumento (la queue o stack) bestie! 🚏",
+        hi: "{0} को 1 argument चाहिए (queue या stack) यार! 🚏",
+    },
+};
+
+pub const ON_KEY_PARAMETERS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "onKey calls '{0}' with the key that got pressed, so it needs exactly 1 parameter ⌨️",
+        es: "onKey llama a '{0}' con la tecla que se pulsó, así que necesita exactamente 1 parámetro ⌨️",
+        hi: "onKey '{0}' को दबाई गई key देता है, तो उसमें ठीक 1 parameter चाहिए ⌨️",
+    },
+};
+
+pub const BENCHMARK_ITERATIONS: Message = Message {
+    code: BUILTIN_ARGUMENT_TYPE.code,
+    text: Text {
+        en: "benchmark wants the iterations as a whole number from 1 to {0} ⏱️",
+        es: "benchmark quiere las iteraciones como número entero de 1 a {0} ⏱️",
+        hi: "benchmark को iterations 1 से {0} तक के पूरे नंबर में चाहिए ⏱️",
+    },
+};
+
+pub const UNKNOWN_TYPE_NAME: Message = Message {
+    code: UNKNOWN_TYPE.code,
+    text: Text {
+        en: "Unknown type '{0}', try number, string, bool, array, object, money, function, heap, queue, stack, nil or any 🏷️",
+        es: "Tipo desconocido '{0}', prueba number, string, bool, array, object, money, function, heap, queue, stack, nil o any 🏷️",
+        hi: "अनजान type '{0}', ये try करो: number, string, bool, array, object, money, function, heap, queue, stack, nil या any 🏷️",
+    },
+};
+
+pub const DRAMA_ARGUMENTS: Message = Message {
+    code: BUILTIN_ARGUMENT_COUNT.code,
+    text: Text {
+        en: "{0} takes at most 1 argument (the message) bestie! 🎭",
+        es: "{0} lleva como mucho 1 argumento (el mensaje) bestie! 🎭",
+        hi: "{0} ज़्यादा से ज़्यादा 1 argument लेता है (message) यार! 🎭",
+    },
+};
+
+// Operators and calls, each a more exact Z0027 or Z0032
+
+pub const INVALID_INDEXING: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Invalid indexing operation, check your types! 🎯",
+        es: "Operación de índice inválida, revisa tus tipos! 🎯",
+        hi: "Indexing गलत है, अपने types check करो! 🎯",
+    },
+};
+
+pub const NUMBER_OPERATION: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Invalid operation for numbers, that's not it! 🔢",
+        es: "Operación inválida para números, eso no es! 🔢",
+        hi: "नंबरों के लिए ये operation नहीं चलता, ये नहीं है वो! 🔢",
+    },
+};
+
+pub const STRING_OPERATION: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Invalid operation for strings, strings don't do that! 📝",
+        es: "Operación inválida para strings, los strings no hacen eso! 📝",
+        hi: "Strings के लिए ये operation नहीं चलता, strings ये नहीं करतीं! 📝",
+    },
+};
+
+pub const BOOLEAN_OPERATION: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Invalid operation for booleans, booleans are limited bestie! ❌",
+        es: "Operación inválida para booleanos, los booleanos son limitados bestie! ❌",
+        hi: "Booleans के लिए ये operation नहीं चलता, booleans limited हैं यार! ❌",
+    },
+};
+
+pub const FUNCTION_COMPARISON: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Functions can only be compared with == and != bestie 🤙",
+        es: "Las funciones solo se comparan con == y != bestie 🤙",
+        hi: "Functions सिर्फ == और != से compare होते हैं यार 🤙",
+    },
+};
+
+pub const HEAP_COMPARISON: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Heaps can only be compared with == and != bestie 🏔️",
+        es: "Los heaps solo se comparan con == y != bestie 🏔️",
+        hi: "Heaps सिर्फ == और != से compare होते हैं यार 🏔️",
+    },
+};
+
+pub const DEQUE_COMPARISON: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Queues and stacks can only be compared with == and != bestie 🚏",
+        es: "Las queues y stacks solo se comparan con == y != bestie 🚏",
+        hi: "Queues और stacks सिर्फ == और != से compare होते हैं यार 🚏",
+    },
+};
+
+pub const SQUAD_COMPARISON: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Squads can only be compared with == and != bestie 👥",
+        es: "Los squads solo se comparan con == y != bestie 👥",
+        hi: "Squads सिर्फ == और != से compare होते हैं यार 👥",
+    },
+};
+
+pub const INSTANCE_COMPARISON: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Instances can only be compared with == and != bestie 👥",
+        es: "Las instancias solo se comparan con == y != bestie 👥",
+        hi: "Instances सिर्फ == और != से compare होते हैं यार 👥",
+    },
+};
+
+pub const FLIP_NOT_NUMBER: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "~ flips the bits of numbers only 🔢",
+        es: "~ solo voltea los bits de números 🔢",
+        hi: "~ सिर्फ नंबरों के bits पलटता है 🔢",
+    },
+};
+
+pub const CALLING_NON_FUNCTION: Message = Message {
+    code: NOT_CALLABLE.code,
+    text: Text {
+        en: "Can only call functions, and that's a {0} 🤙",
+        es: "Solo se pueden llamar funciones, y eso es un {0} 🤙",
+        hi: "सिर्फ functions call होते हैं, और ये एक {0} है 🤙",
+    },
+};
+
+pub const SHIFT_RANGE: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Can only shift by 0 to 63 bits, not {0} 🔢",
+        es: "Solo se puede desplazar de 0 a 63 bits, no {0} 🔢",
+        hi: "सिर्फ 0 से 63 bits तक shift हो सकता है, {0} नहीं 🔢",
+    },
+};
+
+pub const NOT_64_BITS: Message = Message {
+    code: TYPE_MISMATCH.code,
+    text: Text {
+        en: "Bitwise needs a whole number that fits in 64 bits, {0} doesn't 🔢",
+        es: "Las operaciones de bits necesitan un número entero que quepa en 64 bits, {0} no cabe 🔢",
+        hi: "Bitwise को ऐसा पूरा नंबर चाहिए जो 64 bits में आ जाए, {0} नहीं आता 🔢",
+    },
+};
+
+// Checking before running
+
+pub const NEVER_DECLARED: Message = Message {
+    code: "Z0040",
+    text: Text {
+        en: "'{0}' is never declared anywhere, typo bestie? 🤔{1}",
+        es: "'{0}' no se declara en ningún lado, ¿typo bestie? 🤔{1}",
+        hi: "'{0}' कहीं भी declare नहीं हुआ, typo है क्या यार? 🤔{1}",
+    },
+};
+
+pub const ASSIGN_UNDECLARED: Message = Message {
+    code: "Z0041",
+    text: Text {
+        en: "Can't assign to '{0}', it's never declared - use `bet {0} = ...` first 🚫{1}",
+        es: "No se puede asignar a '{0}', nunca se declara - usa `bet {0} = ...` primero 🚫{1}",
+        hi: "'{0}' में value नहीं डाल सकते, ये कभी declare नहीं हुआ - पहले `bet {0} = ...` लिखो 🚫{1}",
+    },
+};
+
+pub const DUPLICATE_PARAMETER: Message = Message {
+    code: "Z0042",
+    text: Text {
+        en: "Function '{0}' has two parameters called '{1}', pick different names bestie 👯",
+        es: "La función '{0}' tiene dos parámetros llamados '{1}', elige nombres distintos bestie 👯",
+        hi: "Function '{0}' में '{1}' नाम के दो parameters हैं, अलग-अलग नाम रखो यार 👯",
+    },
+};
+
+pub const DUPLICATE_LAMBDA_PARAMETER: Message = Message {
+    code: "Z0043",
+    text: Text {
+        en: "A lambda has two parameters called '{0}', pick different names bestie 👯",
+        es: "Una lambda tiene dos parámetros llamados '{0}', elige nombres distintos bestie 👯",
+        hi: "एक lambda में '{0}' नाम के दो parameters हैं, अलग-अलग नाम रखो यार 👯",
+    },
+};
+
+pub const UNDECLARED_DRAMA_TYPE: Message = Message {
+    code: "Z0044",
+    text: Text {
+        en: "Drama type '{0}' is never declared, add `drama type {0}` first 🎭",
+        es: "El tipo de drama '{0}' nunca se declara, agrega `drama type {0}` primero 🎭",
+        hi: "Drama type '{0}' कहीं declare नहीं है, पहले `drama type {0}` लिखो 🎭",
+    },
+};
+
+// Pulling in other files
+
+pub const PULL_NOT_FOUND: Message = Message {
+    code: "Z0050",
+    text: Text {
+        en: "Can't find '{0}' to pull at line {1}, check the path - it's relative to the file doing the pulling 🧲",
+        es: "No se encuentra '{0}' para el pull de la línea {1}, revisa la ruta - es relativa al archivo que hace el pull 🧲",
+        hi: "लाइन {1} पर pull के लिए '{0}' नहीं मिला, path check करो - ये pull करने वाली file के हिसाब से है 🧲",
+    },
+};
+
+pub const PULL_CYCLE: Message = Message {
+    code: "Z0051",
+    text: Text {
+        en: "These files pull each other in a circle: {0}, one of them has to let go 🔁",
+        es: "Estos archivos se hacen pull en círculo: {0}, uno tiene que soltar 🔁",
+        hi: "ये files एक-दूसरे को गोल-गोल pull कर रही हैं: {0}, किसी एक को छोड़ना पड़ेगा 🔁",
+    },
+};
+
+pub const PULL_NOT_TOP_LEVEL: Message = Message {
+    code: "Z0052",
+    text: Text {
+        en: "'pull' at line {0} goes at the top level of a file, not inside a block or function 🧲",
+        es: "El 'pull' de la línea {0} va en el nivel superior del archivo, no dentro de un bloque o función 🧲",
+        hi: "लाइन {0} वाला 'pull' file के top level पर आता है, किसी block या function के अंदर नहीं 🧲",
+    },
+};
+
+pub const PULL_NOT_ALLOWED: Message = Message {
+    code: "Z0053",
+    text: Text {
+        en: "'pull' at line {0} can't read files here, pulling is switched off 🔒",
+        es: "El 'pull' de la línea {0} no puede leer archivos aquí, el pull está desactivado 🔒",
+        hi: "लाइन {0} वाला 'pull' यहाँ files नहीं पढ़ सकता, pull बंद है 🔒",
+    },
+};
+
+pub const PULL_WITHOUT_FILE: Message = Message {
+    code: "Z0054",
+    text: Text {
+        en: "'pull' at line {0} wants a file in quotes, like pull \"utils.zlang\" 🧲",
+        es: "El 'pull' de la línea {0} quiere un archivo entre comillas, como pull \"utils.zlang\" 🧲",
+        hi: "लाइन {0} वाले 'pull' को quotes में file चाहिए, जैसे pull \"utils.zlang\" 🧲",
+    },
+};
+
+pub const PULL_OUTSIDE_ROOT: Message = Message {
+    code: "Z0055",
+    text: Text {
+        en: "'{0}' at line {1} is outside the program's folder, pull only reaches files in there 🧲",
+        es: "'{0}' en la línea {1} está fuera de la carpeta del programa, pull solo llega a los archivos de ahí 🧲",
+        hi: "लाइन {1} पर '{0}' program के folder के बाहर है, pull सिर्फ़ उसी folder की files तक पहुँचता है 🧲",
+    },
+};
+
+pub const FETCH_NOT_ALLOWED: Message = Message {
+    code: "Z0056",
+    text: Text {
+        en: "'pull' at line {1} can't fetch {0} here, pulling from links is switched off 🔒",
+        es: "El 'pull' de la línea {1} no puede traer {0} aquí, el pull desde enlaces está desactivado 🔒",
+        hi: "लाइन {1} वाला 'pull' यहाँ {0} नहीं ला सकता, links से pull बंद है 🔒",
+    },
+};
+
+pub const FETCH_FAILED: Message = Message {
+    code: "Z0057",
+    text: Text {
+        en: "Couldn't fetch {0} to pull at line {1}: {2} 🌐",
+        es: "No se pudo traer {0} para el pull de la línea {1}: {2} 🌐",
+        hi: "लाइन {1} पर pull के लिए {0} नहीं ला पाए: {2} 🌐",
+    },
+};
+
+pub const FETCH_CHANGED: Message = Message {
+    code: "Z0058",
+    text: Text {
+        en: "{0} (pulled at line {1}) serves something different from what {2} pinned - check it, then drop its line from {2} to take the new one 🔒",
+        es: "{0} (pull de la línea {1}) sirve algo distinto de lo que fijó {2} - revísalo y luego quita su línea de {2} para aceptar lo nuevo 🔒",
+        hi: "{0} (लाइन {1} का pull) अब कुछ और दे रहा है, {2} में कुछ और pinned है - check करो, फिर नया लेने के लिए {2} से उसकी line हटा दो 🔒",
+    },
+};
+
+// Contracts
+
+pub const NEEDS_OUTSIDE_FUNCTION: Message = Message {
+    code: "Z0060",
+    text: Text {
+        en: "'needs' only goes at the top of a function body at line {0}, that's where contracts live 📜",
+        es: "'needs' solo va al principio del cuerpo de una función, línea {0}, ahí viven los contratos 📜",
+        hi: "लाइन {0}: 'needs' सिर्फ़ function body की शुरुआत में आता है, contracts वहीं रहते हैं 📜",
+    },
+};
+
+pub const CONTRACT_BROKEN: Message = Message {
+    code: "Z0061",
+    text: Text {
+        en: "Contract broken at line {0}: '{1}' needs its precondition to hold, check what you passed in 📜",
+        es: "Contrato roto en la línea {0}: '{1}' necesita que se cumpla su precondición, revisa lo que le pasaste 📜",
+        hi: "लाइन {0} पर contract टूटा: '{1}' की precondition सच होनी चाहिए, देखो क्या pass किया 📜",
+    },
+};
+
+// Squads
+
+pub const SQUAD_MEMBER: Message = Message {
+    code: "Z0070",
+    text: Text {
+        en: "Squad '{0}' only holds `bet` fields and `flex` methods, line {1} is neither 👥",
+        es: "El squad '{0}' solo tiene campos `bet` y métodos `flex`, la línea {1} no es ninguno 👥",
+        hi: "Squad '{0}' में सिर्फ़ `bet` fields और `flex` methods आते हैं, लाइन {1} दोनों में से कुछ नहीं 👥",
+    },
+};
+
+pub const DUPLICATE_MEMBER: Message = Message {
+    code: "Z0071",
+    text: Text {
+        en: "Squad '{0}' has two members called '{1}', every field and method needs its own name 👥",
+        es: "El squad '{0}' tiene dos miembros llamados '{1}', cada campo y método necesita su propio nombre 👥",
+        hi: "Squad '{0}' में '{1}' नाम के दो members हैं, हर field और method का नाम अलग होना चाहिए 👥",
+    },
+};
+
+pub const TOO_MANY_FIELD_VALUES: Message = Message {
+    code: "Z0072",
+    text: Text {
+        en: "{0} has {1} fields but got {2} values, they fill the fields in the order they're declared 👥",
+        es: "{0} tiene {1} campos pero recibió {2} valores, llenan los campos en el orden en que se declaran 👥",
+        hi: "{0} के {1} fields हैं पर {2} values मिलीं, values fields को declare वाले क्रम में भरती हैं 👥",
+    },
+};
+
+pub const NO_SUCH_MEMBER: Message = Message {
+    code: "Z0073",
+    text: Text {
+        en: "A {0} has no field or method called '{1}' 🔍{2}",
+        es: "Un {0} no tiene ningún campo ni método llamado '{1}' 🔍{2}",
+        hi: "{0} में '{1}' नाम का कोई field या method नहीं है 🔍{2}",
+    },
+};
+
+pub const METHOD_NOT_CALLED: Message = Message {
+    code: "Z0074",
+    text: Text {
+        en: "'{1}' is a method of {0}, call it with .{1}(...) to use it 👥",
+        es: "'{1}' es un método de {0}, llámalo con .{1}(...) para usarlo 👥",
+        hi: "'{1}', {0} का method है, इसे .{1}(...) से call करो 👥",
+    },
+};
+
+pub const MEMBER_KIND_CHANGED: Message = Message {
+    code: "Z0075",
+    text: Text {
+        en: "{0} can't turn the '{1}' it comes with into a different kind of member, a field overrides a field and a method overrides a method 👥",
+        es: "{0} no puede convertir el '{1}' que hereda en otro tipo de miembro, un campo reemplaza a un campo y un método a un método 👥",
+        hi: "{0} अपने साथ आए '{1}' को दूसरी तरह का member नहीं बना सकता, field की जगह field और method की जगह method आता है 👥",
+    },
+};
+
+pub const NOT_A_SQUAD: Message = Message {
+    code: "Z0076",
+    text: Text {
+        en: "{0} can't come from '{1}', that's a {2} and not a squad 👥",
+        es: "{0} no puede venir de '{1}', eso es un {2} y no un squad 👥",
+        hi: "{0}, '{1}' से नहीं आ सकता, वो एक {2} है squad नहीं 👥",
+    },
+};
+
+pub const NOT_SETTABLE: Message = Message {
+    code: "Z0077",
+    text: Text {
+        en: "`.{0} = ...` sets a squad instance's field, and this {1} isn't one - give its variable a new value instead 👥",
+        es: "`.{0} = ...` cambia un campo de una instancia de squad, y este {1} no lo es - mejor dale un valor nuevo a su variable 👥",
+        hi: "`.{0} = ...` squad instance का field बदलता है, और ये {1} instance नहीं है - इसकी बजाय इसके variable को नई value दो 👥",
+    },
+};
+
+pub const METHOD_NOT_SETTABLE: Message = Message {
+    code: "Z0078",
+    text: Text {
+        en: "'{1}' is a method of {0}, only its fields can be set with a dot 👥",
+        es: "'{1}' es un método de {0}, con un punto solo se pueden cambiar sus campos 👥",
+        hi: "'{1}', {0} का method है, dot से सिर्फ़ इसके fields बदले जा सकते हैं 👥",
+    },
+};
+
+// Memes
+
+pub const MEME_NOT_TOP_LEVEL: Message = Message {
+    code: "Z0080",
+    text: Text {
+        en: "'meme {0}' at line {1} goes at the top level of a file, not inside a block or function 🎭",
+        es: "El 'meme {0}' de la línea {1} va en el nivel superior del archivo, no dentro de un bloque o función 🎭",
+        hi: "लाइन {1} वाला 'meme {0}' file के top level पर आता है, किसी block या function के अंदर नहीं 🎭",
+    },
+};
+
+pub const DUPLICATE_MEME: Message = Message {
+    code: "Z0081",
+    text: Text {
+        en: "There's already a meme called '{0}', line {1} can't make it mean something else 🎭",
+        es: "Ya hay un meme llamado '{0}', la línea {1} no puede cambiar lo que significa 🎭",
+        hi: "'{0}' नाम का meme पहले से है, लाइन {1} उसका मतलब नहीं बदल सकती 🎭",
+    },
+};
+
+pub const MEME_ARGUMENTS: Message = Message {
+    code: "Z0082",
+    text: Text {
+        en: "Meme '{0}' takes {1} argument(s) but line {2} gives it {3} 🎭",
+        es: "El meme '{0}' recibe {1} argumento(s) pero la línea {2} le da {3} 🎭",
+        hi: "Meme '{0}' {1} argument(s) लेता है पर लाइन {2} उसे {3} दे रही है 🎭",
+    },
+};
+
+pub const MEME_SPREAD: Message = Message {
+    code: "Z0083",
+    text: Text {
+        en: "Meme '{0}' swaps its arguments in one by one, so line {1} can't spread '...' into it 🎭",
+        es: "El meme '{0}' pone sus argumentos uno por uno, así que la línea {1} no puede usar '...' con él 🎭",
+        hi: "Meme '{0}' अपने arguments एक-एक करके रखता है, इसलिए लाइन {1} उसमें '...' नहीं फैला सकती 🎭",
+    },
+};
+
+pub const MEME_ASSIGN_TARGET: Message = Message {
+    code: "Z0084",
+    text: Text {
+        en: "Meme '{0}' assigns to its '{1}', so line {2} has to give it a variable there, not {3} 🎭",
+        es: "El meme '{0}' asigna a su '{1}', así que la línea {2} tiene que darle una variable ahí, no {3} 🎭",
+        hi: "Meme '{0}' अपने '{1}' में assign करता है, इसलिए लाइन {2} को वहाँ एक variable देना होगा, {3} नहीं 🎭",
+    },
+};
+
+// Conversions
+
+pub const NOT_A_NUMBER: Message = Message {
+    code: "Z0090",
+    text: Text {
+        en: "\"{0}\" doesn't read as a number - num() and + want digits like \"42\", \"-3.5\" or \"1e3\" 🔢",
+        es: "\"{0}\" no se lee como un número - num() y + quieren dígitos como \"42\", \"-3.5\" o \"1e3\" 🔢",
+        hi: "\"{0}\" number की तरह नहीं पढ़ा जाता - num() और + को \"42\", \"-3.5\" या \"1e3\" जैसे अंक चाहिए 🔢",
+    },
+};
+
+pub const CANT_BE_NUMBER: Message = Message {
+    code: "Z0091",
+    text: Text {
+        en: "Can't make a number out of this {0} - num() and + take numbers, strings of digits, fr/cap and money 🔢",
+        es: "No se puede sacar un número de este {0} - num() y + aceptan números, textos con dígitos, fr/cap y money 🔢",
+        hi: "इस {0} से number नहीं बन सकता - num() और + numbers, अंकों वाली strings, fr/cap और money लेते हैं 🔢",
+    },
+};
+
+pub const NOT_MONEY: Message = Message {
+    code: "Z0092",
+    text: Text {
+        en: "'{0}' isn't an amount of money, try something like \"12.50\" or \"$1,234.56\" 💸",
+        es: "'{0}' no es una cantidad de dinero, prueba algo tipo \"12.50\" o \"$1,234.56\" 💸",
+        hi: "'{0}' कोई रकम नहीं है, कुछ ऐसा try करो: \"12.50\" या \"$1,234.56\" 💸",
+    },
+};
+
+// Ranges
+
+pub const RANGE_NOT_NUMBERS: Message = Message {
+    code: "Z0100",
+    text: Text {
+        en: "A range counts from one number to another, and the ends here are {0} and {1} 🔢",
+        es: "Un rango cuenta de un número a otro, y aquí los extremos son {0} y {1} 🔢",
+        hi: "Range एक number से दूसरे number तक गिनती है, और यहाँ इसके सिरे {0} और {1} हैं 🔢",
+    },
+};
+
+pub const RANGE_TOO_BIG: Message = Message {
+    code: "Z0101",
+    text: Text {
+        en: "{0}..{1} is way too many numbers, a range holds {2} at most 📏",
+        es: "{0}..{1} son demasiados números, un rango tiene {2} como máximo 📏",
+        hi: "{0}..{1} बहुत सारे numbers हैं, एक range में ज़्यादा से ज़्यादा {2} होते हैं 📏",
+    },
+};
+
+// Stash
+
+pub const NOT_STASHABLE: Message = Message {
+    code: "Z0110",
+    text: Text {
+        en: "Can't stash this {0} - only plain values last between runs: numbers, strings, fr/cap, money, and arrays and objects of those 🐿️",
+        es: "No se puede guardar este {0} con stash - solo los valores simples duran entre ejecuciones: números, textos, fr/cap, money, y arrays y objetos de eso 🐿️",
+        hi: "ये {0} stash नहीं हो सकता - runs के बीच सिर्फ़ सादी values बचती हैं: numbers, strings, fr/cap, money, और इन्हीं के arrays और objects 🐿️",
+    },
+};
+
+pub const STASH_KEY: Message = Message {
+    code: "Z0111",
+    text: Text {
+        en: "Stash keys are non-empty strings, like \"high_score\" 🔑",
+        es: "Las claves de stash son strings que no están vacíos, tipo \"high_score\" 🔑",
+        hi: "Stash की keys ऐसी strings होती हैं जो खाली न हों, जैसे \"high_score\" 🔑",
+    },
+};
+
+pub const STASH_TOO_BIG: Message = Message {
+    code: "Z0112",
+    text: Text {
+        en: "That's too big to stash, {0} KB max 📦",
+        es: "Eso es demasiado grande para stash, {0} KB como máximo 📦",
+        hi: "ये stash करने के लिए बहुत बड़ा है, ज़्यादा से ज़्यादा {0} KB 📦",
+    },
+};
+
+// Values
+
+pub const THROWN_VALUE: Message = Message {
+    code: "Z0120",
+    text: Text {
+        en: "Thrown error",
+        es: "Error lanzado",
+        hi: "Error फेंका गया",
+    },
+};
+
+pub const NOT_AN_OBJECT: Message = Message {
+    code: "Z0121",
+    text: Text {
+        en: "Can't read .{0} off a {1}, dot access is for objects 🔍",
+        es: "No se puede leer .{0} de un {1}, el punto es para objetos 🔍",
+        hi: "{1} में से .{0} नहीं पढ़ सकते, dot access objects के लिए है 🔍",
+    },
+};
+
+pub const SPREAD_OUTSIDE: Message = Message {
+    code: "Z0122",
+    text: Text {
+        en: "... only spreads an array into [ ] or a call's ( ) bestie 📦",
+        es: "... solo esparce un array dentro de [ ] o de los ( ) de una llamada bestie 📦",
+        hi: "... सिर्फ किसी array को [ ] में या किसी call के ( ) में फैलाता है यार 📦",
+    },
+};
+
+pub const SPREAD_NOT_ARRAY: Message = Message {
+    code: "Z0123",
+    text: Text {
+        en: "Only arrays can be spread with ..., not a {0} 📦",
+        es: "Solo los arrays se pueden esparcir con ..., no un {0} 📦",
+        hi: "... से सिर्फ arrays फैलते हैं, {0} नहीं 📦",
+    },
+};
+
+pub const UNSORTABLE: Message = Message {
+    code: "Z0124",
+    text: Text {
+        en: "Can't sort a {0} against a {1}, keep the keys one type 🔀",
+        es: "No se puede ordenar un {0} contra un {1}, usa claves de un solo tipo 🔀",
+        hi: "{0} और {1} को आपस में sort नहीं कर सकते, keys एक ही type की रखो 🔀",
+    },
+};
+
+pub const TYPE_CHECK_FAILED: Message = Message {
+    code: "Z0125",
+    text: Text {
+        en: "Type check failed at line {0}: expected a {1} but got a {2} 🏷️",
+        es: "Falló la comprobación de tipo en la línea {0}: se esperaba un {1} pero llegó un {2} 🏷️",
+        hi: "लाइन {0} पर type check fail हुआ: {1} चाहिए था पर {2} मिला 🏷️",
+    },
+};
+
+// Drawing and timers
+
+pub const CANVAS_FULL: Message = Message {
+    code: "Z0130",
+    text: Text {
+        en: "That's {0} shapes already, the canvas is full 🖼️",
+        es: "Ya van {0} figuras, el lienzo está lleno 🖼️",
+        hi: "पहले से {0} shapes हैं, canvas भर गया 🖼️",
+    },
+};
+
+pub const LOOP_ALREADY_RUNNING: Message = Message {
+    code: "Z0131",
+    text: Text {
+        en: "runLoop is already running, a timer can't start another one ⏰",
+        es: "runLoop ya está corriendo, un timer no puede arrancar otro ⏰",
+        hi: "runLoop पहले से चल रहा है, एक timer दूसरा शुरू नहीं कर सकता ⏰",
+    },
+};
+
+/// One diagnostic per code, for looking one up by its code
+pub const CATALOG: &[Message] = &[
+    UNEXPECTED_CHARACTER,
+    INVALID_ESCAPE,
+    UNTERMINATED_STRING,
+    UNTERMINATED_TRIPLE_STRING,
+    INVALID_NUMBER,
+    UNEXPECTED_TOKEN,
+    EXPECTED_STATEMENT_END,
+    NESTED_TOO_DEEP,
+    STRAY_ELSE,
+    INVALID_ASSIGNMENT_TARGET,
+    EXPECTED_TOKEN,
+    REST_PARAMETER_TYPE,
+    REST_PARAMETER_NOT_LAST,
+    LAMBDA_TYPE_ANNOTATION,
+    UNKNOWN_TYPE,
+    UNDEFINED_VARIABLE,
+    UNDEFINED_ASSIGNMENT,
+    UNDEFINED_FUNCTION,
+    DIVISION_BY_ZERO,
+    MODULO_BY_ZERO,
+    INDEX_OUT_OF_BOUNDS,
+    NOT_ITERABLE,
+    TYPE_MISMATCH,
+    CONCATENATION,
+    WRONG_ARGUMENT_COUNT,
+    TOO_FEW_ARGUMENTS,
+    TOO_MUCH_RECURSION,
+    NOT_CALLABLE,
+    STRAY_CONTROL_FLOW,
+    NEGATE_NON_NUMBER,
+    BUILTIN_ARGUMENT_COUNT,
+    BUILTIN_ARGUMENT_TYPE,
+    INTERRUPTED,
+    INPUT_NOT_ALLOWED,
+    NOT_A_RESULT,
+    NEVER_DECLARED,
+    ASSIGN_UNDECLARED,
+    DUPLICATE_PARAMETER,
+    DUPLICATE_LAMBDA_PARAMETER,
+    UNDECLARED_DRAMA_TYPE,
+    PULL_NOT_FOUND,
+    PULL_CYCLE,
+    PULL_NOT_TOP_LEVEL,
+    PULL_NOT_ALLOWED,
+    PULL_WITHOUT_FILE,
+    PULL_OUTSIDE_ROOT,
+    FETCH_NOT_ALLOWED,
+    FETCH_FAILED,
+    FETCH_CHANGED,
+    NEEDS_OUTSIDE_FUNCTION,
+    CONTRACT_BROKEN,
+    SQUAD_MEMBER,
+    DUPLICATE_MEMBER,
+    TOO_MANY_FIELD_VALUES,
+    NO_SUCH_MEMBER,
+    METHOD_NOT_CALLED,
+    MEMBER_KIND_CHANGED,
+    NOT_A_SQUAD,
+    NOT_SETTABLE,
+    METHOD_NOT_SETTABLE,
+    MEME_NOT_TOP_LEVEL,
+    DUPLICATE_MEME,
+    MEME_ARGUMENTS,
+    MEME_SPREAD,
+    MEME_ASSIGN_TARGET,
+    NOT_A_NUMBER,
+    CANT_BE_NUMBER,
+    NOT_MONEY,
+    RANGE_NOT_NUMBERS,
+    RANGE_TOO_BIG,
+    NOT_STASHABLE,
+    STASH_KEY,
+    STASH_TOO_BIG,
+    THROWN_VALUE,
+    NOT_AN_OBJECT,
+    SPREAD_OUTSIDE,
+    SPREAD_NOT_ARRAY,
+    UNSORTABLE,
+    TYPE_CHECK_FAILED,
+    CANVAS_FULL,
+    LOOP_ALREADY_RUNNING,
+];
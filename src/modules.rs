@@ -0,0 +1,339 @@
+//! ZLang Modules - `pull "utils.zlang"` runs another file and keeps its functions and variables 🧲
+//! Paths are relative to the file doing the pulling and each file runs once, `pull "math.zlang" as m` keeps its names in `m`.
+//! The files come from a `FileProvider` - the disk for the CLI, the ones a request brought along for the playground
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use crate::ast::{Literal, Stmt};
+use crate::error::ZLangError;
+use crate::fetch;
+use crate::lexer::Lexer;
+use crate::messages;
+use crate::parser::Parser;
+use crate::pragmas::Pragmas;
+use crate::resolver;
+
+/// Where pulled files are read from
+pub trait FileProvider: fmt::Debug {
+    /// The one path the file or folder at `path` has however it's spelled, like `fs::canonicalize`
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+/// The real disk
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskFiles;
+
+impl FileProvider for DiskFiles {
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::canonicalize(path)
+    }
+    
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+}
+
+/// Files held in memory by their relative paths, like the ones a playground project sends along.
+/// A path that starts at `/` or climbs out with `..` can never be pulled, so it's left out
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFiles {
+    files: HashMap<PathBuf, String>,
+}
+
+impl MemoryFiles {
+    pub fn new(files: impl IntoIterator<Item = (String, String)>) -> Self {
+        let files = files.into_iter()
+            .filter_map(|(path, source)| Some((normalize(Path::new(&path))?, source)))
+            .collect();
+        Self { files }
+    }
+}
+
+impl FileProvider for MemoryFiles {
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        find(self.files.keys().map(PathBuf::as_path), path)
+    }
+    
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let path = self.canonicalize(path)?;
+        self.files.get(&path).cloned().ok_or_else(not_a_file)
+    }
+}
+
+/// Files built into the program with `include_str!`, for a build that has no disk to read from
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedFiles {
+    files: &'static [(&'static str, &'static str)],
+}
+
+impl EmbeddedFiles {
+    pub const fn new(files: &'static [(&'static str, &'static str)]) -> Self {
+        Self { files }
+    }
+}
+
+impl FileProvider for EmbeddedFiles {
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        find(self.files.iter().map(|(name, _)| Path::new(*name)), path)
+    }
+    
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let path = self.canonicalize(path)?;
+        self.files.iter()
+            .find(|(name, _)| Path::new(*name) == path)
+            .map(|(_, source)| source.to_string())
+            .ok_or_else(not_a_file)
+    }
+}
+
+/// `path` tidied up the way `normalize` does, when it's one of `files` or a folder some of them sit in
+fn find<'a>(mut files: impl Iterator<Item = &'a Path>, path: &Path) -> io::Result<PathBuf> {
+    let path = normalize(path).ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    if files.any(|file| file.starts_with(&path)) || path.as_os_str().is_empty() {
+        Ok(path)
+    } else {
+        Err(io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+/// `a/./b/../c` as `a/c` without asking any disk - None when it starts at `/` or climbs out past the top
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normal.push(part),
+            Component::CurDir => {}
+            Component::ParentDir if normal.pop() => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(normal)
+}
+
+fn not_a_file() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "that's a folder, not a file")
+}
+
+#[derive(Debug, Clone)]
+pub struct Modules {
+    // Where pulled files come from
+    files: Arc<dyn FileProvider>,
+    // Where the main program's pulls start from, the current directory when it's None
+    root: Option<PathBuf>,
+    // Every file a plain pull has run, so pulling one again does nothing
+    loaded: HashSet<PathBuf>,
+    // The object each `pull "..." as name` file made, handed out again to the next one
+    namespaces: HashMap<PathBuf, Literal>,
+    // Files part way through running, outermost first - pulling one of these would go round forever
+    loading: Vec<PathBuf>,
+}
+
+/// The files pulled so far and the namespaces they made - what a snapshot keeps of `Modules`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pulled {
+    loaded: HashSet<PathBuf>,
+    namespaces: HashMap<PathBuf, Literal>,
+}
+
+impl Pulled {
+    pub fn namespaces(&self) -> impl Iterator<Item = &Literal> {
+        self.namespaces.values()
+    }
+}
+
+impl Default for Modules {
+    fn default() -> Self {
+        Self {
+            files: Arc::new(DiskFiles),
+            root: None,
+            loaded: HashSet::new(),
+            namespaces: HashMap::new(),
+            loading: Vec::new(),
+        }
+    }
+}
+
+impl Modules {
+    /// Pulls in the program in `file` are relative to its folder, and pulling `file` itself is a cycle.
+    /// The program and everything it pulls are found in `files`
+    pub fn for_program(file: &Path, files: Arc<dyn FileProvider>) -> Self {
+        Self {
+            root: file.parent().map(Path::to_path_buf),
+            loading: files.canonicalize(file).into_iter().collect(),
+            files,
+            ..Self::default()
+        }
+    }
+    
+    pub fn files(&self) -> &dyn FileProvider {
+        self.files.as_ref()
+    }
+    
+    /// The folder the main program's pulls start from
+    pub fn root(&self) -> &Path {
+        self.root.as_deref().unwrap_or(Path::new(""))
+    }
+    
+    /// The file `path` means when it's pulled right now - next to whichever file is doing the pulling.
+    /// It has to be somewhere in the main program's folder, an absolute path or `../` doesn't get
+    /// to wander off around the rest of the disk
+    pub fn locate(&self, path: &str, line: usize) -> Result<PathBuf, ZLangError> {
+        let dir = self.loading.last().and_then(|file| file.parent()).unwrap_or(self.root());
+        let wanted = dir.join(path);
+        // One real path per file, however it was spelled, so `./a.zlang` and `a.zlang` are the same pull
+        let file = self.files.canonicalize(&wanted).map_err(|_| messages::PULL_NOT_FOUND.error(&[&path, &line]))?;
+        let root = if self.root().as_os_str().is_empty() { Path::new(".") } else { self.root() };
+        match self.files.canonicalize(root) {
+            Ok(root) if file.starts_with(&root) => Ok(file),
+            _ => Err(messages::PULL_OUTSIDE_ROOT.error(&[&path, &line])),
+        }
+    }
+    
+    /// Start running `file`, unless that would go round in a circle
+    pub fn enter(&mut self, file: &Path) -> Result<(), ZLangError> {
+        if let Some(start) = self.loading.iter().position(|loading| loading == file) {
+            let cycle: Vec<String> = self.loading[start..].iter()
+                .chain([&file.to_path_buf()])
+                .map(|file| short_name(file))
+                .collect();
+            return Err(messages::PULL_CYCLE.error(&[&cycle.join(" → ")]));
+        }
+        self.loading.push(file.to_path_buf());
+        Ok(())
+    }
+    
+    /// Done with the file `enter` last started, whether it worked or not
+    pub fn leave(&mut self) {
+        self.loading.pop();
+    }
+    
+    pub fn is_loaded(&self, file: &Path) -> bool {
+        self.loaded.contains(file)
+    }
+    
+    pub fn mark_loaded(&mut self, file: PathBuf) {
+        self.loaded.insert(file);
+    }
+    
+    pub fn namespace(&self, file: &Path) -> Option<&Literal> {
+        self.namespaces.get(file)
+    }
+    
+    pub fn keep_namespace(&mut self, file: PathBuf, namespace: Literal) {
+        self.namespaces.insert(file, namespace);
+    }
+    
+    pub fn pulled(&self) -> Pulled {
+        Pulled { loaded: self.loaded.clone(), namespaces: self.namespaces.clone() }
+    }
+    
+    /// Back to only having pulled what `pulled` says, so pulling anything since runs it again
+    pub fn restore(&mut self, pulled: Pulled) {
+        self.loaded = pulled.loaded;
+        self.namespaces = pulled.namespaces;
+    }
+    
+    /// The copy of the file at `link`, fetched into the program's cache when it isn't there already.
+    /// Both sit in the program's folder, on the disk whatever files the program reads otherwise
+    pub fn fetched(&self, link: &str, line: usize) -> Result<PathBuf, ZLangError> {
+        let file = fetch::fetched(self.root(), link, line)?;
+        self.files.canonicalize(&file).map_err(|_| messages::PULL_NOT_FOUND.error(&[&link, &line]))
+    }
+    
+    /// `pulled_names` for the main program's `statements`, read from this program's files. Links
+    /// only count when they're already cached, unless `fetch` says to go get them
+    pub fn pulled_names(&self, statements: &[Stmt], fetch: bool) -> HashSet<String> {
+        let mut names = HashSet::new();
+        let mut seen = HashSet::new();
+        let links = Links { root: self.root(), fetch };
+        collect_pulled(statements, self.root(), self.files(), &links, &mut names, &mut seen);
+        names
+    }
+}
+
+/// Read `file` out of `files` and parse it, along with the warnings its `// zlang-ignore` comments hush
+pub fn load(files: &dyn FileProvider, file: &Path) -> Result<(Vec<Stmt>, Pragmas), ZLangError> {
+    let source = files.read_to_string(file)
+        .map_err(|e| ZLangError::new(&format!("Couldn't read {}: {} 📁", file.display(), e)))?;
+    let statements = Parser::new(Lexer::new(&source).tokenize()?).parse()?;
+    Ok((statements, Pragmas::scan(&source, HashMap::new())))
+}
+
+/// `e` happened in the pulled `file` - its line numbers are that file's, so say which file
+pub fn in_file(file: &Path, e: ZLangError) -> ZLangError {
+    ZLangError { message: format!("{}: {}", short_name(file), e.message), ..e }
+}
+
+/// Every name `statements` get from their pulls, and from the pulls in those files - what the
+/// resolver should count as declared. Files that can't be read are skipped, running them says why
+/// Links count when there's a copy of them cached next to the program in `dir`
+pub fn pulled_names(statements: &[Stmt], dir: &Path, files: &dyn FileProvider) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut seen = HashSet::new();
+    collect_pulled(statements, dir, files, &Links { root: dir, fetch: false }, &mut names, &mut seen);
+    names
+}
+
+/// Where pulled links are cached, and whether ones that aren't can be fetched
+struct Links<'a> {
+    root: &'a Path,
+    fetch: bool,
+}
+
+impl Links<'_> {
+    /// The file a `pull` of `path` from a file in `dir` reads, when there is one
+    fn find(&self, path: &str, dir: &Path, files: &dyn FileProvider) -> Option<PathBuf> {
+        let file = if fetch::is_link(path) && self.fetch {
+            fetch::fetched(self.root, path, 0).ok()?
+        } else if fetch::is_link(path) {
+            fetch::cached(self.root, path)?
+        } else {
+            dir.join(path)
+        };
+        files.canonicalize(&file).ok()
+    }
+}
+
+/// The file a `pull` of `path` from a file in `dir` reads, without running or fetching anything -
+/// a link only when there's a copy of it cached next to the program in `root`
+pub fn find_pulled(path: &str, dir: &Path, root: &Path, files: &dyn FileProvider) -> Option<PathBuf> {
+    Links { root, fetch: false }.find(path, dir, files)
+}
+
+fn collect_pulled(
+    statements: &[Stmt],
+    dir: &Path,
+    files: &dyn FileProvider,
+    links: &Links,
+    names: &mut HashSet<String>,
+    seen: &mut HashSet<PathBuf>,
+) {
+    for statement in statements {
+        // An `as` pull's names stay inside its object
+        let Stmt::Pull { path, alias: None, .. } = statement else {
+            continue;
+        };
+        let Some(file) = links.find(path, dir, files) else {
+            continue;
+        };
+        if !seen.insert(file.clone()) {
+            continue;
+        }
+        if let Ok((pulled, _)) = load(files, &file) {
+            for statement in &pulled {
+                resolver::collect_declarations(statement, names);
+            }
+            collect_pulled(&pulled, file.parent().unwrap_or(dir), files, links, names, seen);
+        }
+    }
+}
+
+fn short_name(file: &Path) -> String {
+    file.file_name().map_or_else(|| file.display().to_string(), |name| name.to_string_lossy().into_owned())
+}
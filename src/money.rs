@@ -0,0 +1,104 @@
+//! ZLang Money - Exact dollars and cents, because 0.1 + 0.2 != 0.3 is not the lesson we're teaching 💸
+//! A money value is a whole number of cents under the hood, so adding it up never drifts
+
+use crate::ast::{BinaryOp, Literal};
+use crate::error::ZLangError;
+use crate::messages;
+
+/// Biggest amount in cents - well past any budget, and still exact when a multiply goes through f64
+pub const MAX_CENTS: i64 = 1 << 53;
+
+/// Cents for `amount` dollars, rounded to the nearest cent
+pub fn from_number(amount: f64) -> Result<i64, ZLangError> {
+    let cents = (amount * 100.0).round();
+    if !cents.is_finite() || cents.abs() > MAX_CENTS as f64 {
+        return Err(too_much());
+    }
+    Ok(cents as i64)
+}
+
+/// Cents for text like "12.50", "$1,234.56" or "-3" - at most two decimals, nothing else allowed
+pub fn parse(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let text = text.strip_prefix('$').unwrap_or(text).replace(',', "");
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().all(|c| c.is_ascii_digit()) || !fraction.chars().all(|c| c.is_ascii_digit()) || fraction.len() > 2 {
+        return None;
+    }
+    
+    let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let fraction: i64 = format!("{:0<2}", fraction).parse().ok()?;
+    let cents = whole.checked_mul(100)?.checked_add(fraction)?;
+    if cents > MAX_CENTS {
+        return None;
+    }
+    Some(if negative { -cents } else { cents })
+}
+
+/// `$1,234.56`, with the minus sign out front for negative amounts
+pub fn format(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    let digits = (cents / 100).to_string();
+    
+    let mut whole = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            whole.push(',');
+        }
+        whole.push(digit);
+    }
+    format!("{}${}.{:02}", sign, whole, cents % 100)
+}
+
+/// The amount as plain decimal text, `1234.56` - what `money(...)` reads back in
+pub fn plain(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.unsigned_abs() / 100, cents.unsigned_abs() % 100)
+}
+
+/// Arithmetic and comparisons with money on at least one side. Money adds to money, scales by plain
+/// numbers, and divided by money gives a plain ratio - anything rounding has to do lands on a whole cent
+pub fn apply(left: &Literal, operator: &BinaryOp, right: &Literal) -> Result<Literal, ZLangError> {
+    match (left, operator, right) {
+        (Literal::Money(l), BinaryOp::Add, Literal::Money(r)) => l.checked_add(*r).map(Literal::Money).ok_or_else(too_much),
+        (Literal::Money(l), BinaryOp::Subtract, Literal::Money(r)) => l.checked_sub(*r).map(Literal::Money).ok_or_else(too_much),
+        (Literal::Money(cents), BinaryOp::Multiply, Literal::Number(n)) | (Literal::Number(n), BinaryOp::Multiply, Literal::Money(cents)) => {
+            from_number(*cents as f64 * n / 100.0).map(Literal::Money)
+        }
+        (Literal::Money(_), BinaryOp::Divide, Literal::Number(n)) if *n == 0.0 => Err(divide_by_zero()),
+        (Literal::Money(cents), BinaryOp::Divide, Literal::Number(n)) => from_number(*cents as f64 / n / 100.0).map(Literal::Money),
+        (Literal::Money(_), BinaryOp::Divide, Literal::Money(0)) => Err(divide_by_zero()),
+        (Literal::Money(l), BinaryOp::Divide, Literal::Money(r)) => Ok(Literal::Number(*l as f64 / *r as f64)),
+        (Literal::Money(l), _, Literal::Money(r)) => match operator {
+            BinaryOp::Equal => Ok(Literal::Boolean(l == r)),
+            BinaryOp::NotEqual => Ok(Literal::Boolean(l != r)),
+            BinaryOp::Greater => Ok(Literal::Boolean(l > r)),
+            BinaryOp::GreaterEqual => Ok(Literal::Boolean(l >= r)),
+            BinaryOp::Less => Ok(Literal::Boolean(l < r)),
+            BinaryOp::LessEqual => Ok(Literal::Boolean(l <= r)),
+            _ => Err(ZLangError::new(&format!("Money doesn't do {}, try +, -, * or / 💸", operator.symbol()))),
+        },
+        (Literal::Money(_), BinaryOp::Add | BinaryOp::Subtract, Literal::Number(_)) | (Literal::Number(_), BinaryOp::Add | BinaryOp::Subtract, Literal::Money(_)) => {
+            Err(ZLangError::new("Can't mix money with a plain number, wrap it in money(...) first 💸"))
+        }
+        (_, BinaryOp::Equal, _) => Ok(Literal::Boolean(false)),
+        (_, BinaryOp::NotEqual, _) => Ok(Literal::Boolean(true)),
+        _ => Err(ZLangError::new(&format!("Money doesn't do {} with that, try +, -, * or / 💸", operator.symbol()))),
+    }
+}
+
+fn too_much() -> ZLangError {
+    ZLangError::new("That's more money than exists, even for a tech bro 🤑")
+}
+
+fn divide_by_zero() -> ZLangError {
+    messages::DIVISION_BY_ZERO.error(&[])
+}
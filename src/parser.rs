@@ -2,20 +2,64 @@
 //! This is where we figure out what the code actually means
 
 use crate::token::{Token, TokenType};
-use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Literal};
+use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Literal, TypeAnnotation, CatchClause, SwitchCase, CasePattern};
 use crate::error::ZLangError;
+use crate::memes::Meme;
+use crate::messages::{self, Message};
+use crate::steps;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// How deep statements and expressions may nest before we bail out.
+/// Keeps hostile input like 10k opening parens from blowing the stack.
+const MAX_NESTING_DEPTH: usize = 128;
+
+/// Builtins grouped under a name and called as `draw.line(...)`
+const BUILTIN_NAMESPACES: &[&str] = &["draw"];
+
+/// What's between a function's parentheses
+struct Parameters {
+    names: Vec<String>,
+    types: Vec<Option<TypeAnnotation>>,
+    // The last one is a `...rest`
+    rest: bool,
+}
 
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    depth: usize,
+    // Span of every statement in every statement list, in source order
+    statement_spans: Vec<Range<usize>>,
+    // Every `meme` declared so far, swapped in where it's called further down
+    memes: HashMap<String, Meme>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self { tokens, current: 0, depth: 0, statement_spans: Vec::new(), memes: HashMap::new() }
+    }
+    
+    /// Start out knowing `memes`, like the ones earlier REPL lines declared
+    pub fn with_memes(mut self, memes: HashMap<String, Meme>) -> Self {
+        self.memes = memes;
+        self
+    }
+    
+    /// The memes the source declared, along with any it started out knowing
+    pub fn memes(&self) -> &HashMap<String, Meme> {
+        &self.memes
     }
     
     pub fn parse(&mut self) -> Result<Vec<Stmt>, ZLangError> {
+        let statements = self.parse_with_spans()?;
+        Ok(statements.into_iter().map(|(statement, _)| statement).collect())
+    }
+    
+    /// Same as `parse`, plus where each top-level statement sits in the source
+    /// (char offsets, including the newline or `;` that ended it)
+    pub fn parse_with_spans(&mut self) -> Result<Vec<(Stmt, Range<usize>)>, ZLangError> {
         let mut statements = Vec::new();
         
         while !self.is_at_end() {
@@ -23,21 +67,54 @@ impl Parser {
             if self.match_token(&TokenType::Newline) {
                 continue;
             }
+            // A meme is gone once it's parsed, its calls are what's left of it
+            if self.at_meme() {
+                self.advance();
+                self.meme_declaration()?;
+                continue;
+            }
             
-            statements.push(self.declaration()?);
+            let start = self.peek().span.start;
+            let statement = self.declaration()?;
+            statements.push((statement, start..self.previous().span.end));
         }
         
         Ok(statements)
     }
     
+    /// Same as `parse`, plus the span of every statement that sits in a statement list
+    /// (the program, blocks, function bodies, cases, manifest/caught/frfr bodies) at any
+    /// depth, in source order - the order `steps` walks them back onto the tree
+    pub fn parse_with_statement_spans(&mut self) -> Result<(Vec<Stmt>, Vec<Range<usize>>), ZLangError> {
+        let statements = self.parse()?;
+        Ok((statements, std::mem::take(&mut self.statement_spans)))
+    }
+    
     fn declaration(&mut self) -> Result<Stmt, ZLangError> {
-        if self.match_token(&TokenType::Flex) {
-            self.function_declaration()
+        // Claim the slot up front so nested statements land after their parent
+        let index = self.statement_spans.len();
+        let start = self.peek().span.start;
+        self.statement_spans.push(start..start);
+        
+        // `flex(` starts a lambda, that's an expression statement
+        let lambda = matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::LeftParen));
+        let statement = if !lambda && self.match_token(&TokenType::Flex) {
+            self.function_declaration()?
         } else if self.match_token(&TokenType::Bet) {
-            self.var_declaration()
+            self.var_declaration()?
+        } else if self.at_squad() {
+            self.advance();
+            self.squad_declaration()?
+        } else if self.at_meme() {
+            let line = self.advance().line;
+            let name = self.identifier(messages::EXPECTED_MEME_NAME)?;
+            return Err(messages::MEME_NOT_TOP_LEVEL.error(&[&name, &line]));
         } else {
-            self.statement()
-        }
+            self.statement()?
+        };
+        
+        self.statement_spans[index].end = self.previous().span.end;
+        Ok(statement)
     }
     
     fn function_declaration(&mut self) -> Result<Stmt, ZLangError> {
@@ -46,66 +123,232 @@ impl Parser {
             self.advance();
             name
         } else {
-            return Err(ZLangError::new("Expected function name bestie 📝"));
+            return Err(messages::EXPECTED_FUNCTION_NAME.error(&[]));
         };
         
-        self.consume(&TokenType::LeftParen, "Expected '(' after function name, that's how functions work!")?;
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_FUNCTION_PAREN)?;
+        let Parameters { names: params, types: param_types, rest } = self.parameters()?;
+        let return_type = self.optional_type_annotation()?;
+        self.consume(&TokenType::LeftBrace, messages::EXPECTED_FUNCTION_BODY)?;
+        
+        let mut preconditions = self.preconditions(&name)?;
+        let body = self.block_statement()?;
+        
+        if let Stmt::Block(statements) = body {
+            preconditions.extend(statements);
+            Ok(Stmt::Function { name, params, param_types, rest, return_type, body: preconditions })
+        } else {
+            unreachable!("block_statement should always return a Block")
+        }
+    }
+    
+    /// `squad` is only a keyword when a name and a `{` follow it, or a name, `from` and a parent's
+    /// name, so scripts can keep their `squad` variables
+    fn at_squad(&self) -> bool {
+        let ahead = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        let from = matches!(ahead(2), Some(TokenType::Identifier(word)) if word == "from") && matches!(ahead(3), Some(TokenType::Identifier(_)));
+        self.check_identifier("squad") && matches!(ahead(1), Some(TokenType::Identifier(_)))
+            && matches!(ahead(if from { 4 } else { 2 }), Some(TokenType::LeftBrace))
+    }
+    
+    /// `Point { bet x = 0 ... flex move(dx) { ... } }` or `Dog from Animal { ... }` after the `squad`
+    fn squad_declaration(&mut self) -> Result<Stmt, ZLangError> {
+        let name = self.identifier(messages::EXPECTED_SQUAD_NAME)?;
+        let parent = if self.match_identifier("from") {
+            Some(self.identifier(messages::EXPECTED_SQUAD_PARENT)?)
+        } else {
+            None
+        };
+        self.consume(&TokenType::LeftBrace, messages::EXPECTED_SQUAD_OPEN)?;
         
+        let mut body = Vec::new();
+        let mut members = HashSet::new();
+        loop {
+            while self.match_token(&TokenType::Newline) {}
+            if self.check(&TokenType::RightBrace) || self.is_at_end() {
+                break;
+            }
+            
+            let line = self.peek().line;
+            let member = self.declaration()?;
+            let member_name = match &member {
+                Stmt::VarDeclaration { name, .. } | Stmt::Function { name, .. } => name.clone(),
+                _ => return Err(messages::SQUAD_MEMBER.error(&[&name, &line])),
+            };
+            if !members.insert(member_name.clone()) {
+                return Err(messages::DUPLICATE_MEMBER.error(&[&name, &member_name]));
+            }
+            body.push(member);
+        }
+        
+        self.consume(&TokenType::RightBrace, messages::EXPECTED_SQUAD_CLOSE)?;
+        Ok(Stmt::Squad { name, parent, body })
+    }
+    
+    /// `meme` is only a keyword with a name and a `(` after it, like `squad`
+    fn at_meme(&self) -> bool {
+        let ahead = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        self.check_identifier("meme") && matches!(ahead(1), Some(TokenType::Identifier(_))) && matches!(ahead(2), Some(TokenType::LeftParen))
+    }
+    
+    /// `double(x) => x * 2` after the `meme`
+    fn meme_declaration(&mut self) -> Result<(), ZLangError> {
+        let line = self.previous().line;
+        let name = self.identifier(messages::EXPECTED_MEME_DECLARATION)?;
+        if self.memes.contains_key(&name) {
+            return Err(messages::DUPLICATE_MEME.error(&[&name, &line]));
+        }
+        
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_MEME_PAREN)?;
+        let mut params: Vec<String> = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                let param = self.identifier(messages::EXPECTED_MEME_PARAMETER)?;
+                if params.contains(&param) {
+                    return Err(messages::DUPLICATE_PARAMETER.error(&[&name, &param]));
+                }
+                params.push(param);
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+        self.consume(&TokenType::RightParen, messages::EXPECTED_MEME_CLOSE)?;
+        self.consume(&TokenType::FatArrow, messages::EXPECTED_MEME_ARROW)?;
+        
+        // Lambdas in the expansion get their spans where the meme is called
+        let spans = self.statement_spans.len();
+        let expansion = self.expression()?;
+        self.statement_spans.truncate(spans);
+        if !self.end_statement() {
+            return Err(messages::EXPECTED_STATEMENT_END.error(&[]));
+        }
+        
+        self.memes.insert(name.clone(), Meme::new(name, params, expansion));
+        Ok(())
+    }
+    
+    /// `name: type, ...)` - the parameter list after its '(', through the closing ')'
+    /// The parameter list after `(`
+    fn parameters(&mut self) -> Result<Parameters, ZLangError> {
         let mut params = Vec::new();
+        let mut param_types = Vec::new();
+        let mut rest = false;
         if !self.check(&TokenType::RightParen) {
             loop {
+                rest = self.match_token(&TokenType::Ellipsis);
                 if let TokenType::Identifier(param) = &self.peek().token_type {
                     params.push(param.clone());
                     self.advance();
                 } else {
-                    return Err(ZLangError::new("Expected parameter name in function declaration 📋"));
+                    return Err(messages::EXPECTED_PARAMETER.error(&[]));
                 }
+                if rest && self.check(&TokenType::Colon) {
+                    return Err(messages::REST_PARAMETER_TYPE.error(&[]));
+                }
+                param_types.push(self.optional_type_annotation()?);
                 
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
+                if rest {
+                    return Err(messages::REST_PARAMETER_NOT_LAST.error(&[]));
+                }
             }
         }
         
-        self.consume(&TokenType::RightParen, "Expected ')' after parameters, close it up!")?;
-        self.consume(&TokenType::LeftBrace, "Expected '{' before function body, gotta have that block!")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_PARAMETERS_CLOSE)?;
+        Ok(Parameters { names: params, types: param_types, rest })
+    }
+    
+    /// `flex(x) { vibe x * 2 }`, after the `flex`
+    fn lambda(&mut self) -> Result<Expr, ZLangError> {
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_LAMBDA_PAREN)?;
+        let Parameters { names: params, types: param_types, rest } = self.parameters()?;
+        if param_types.iter().any(Option::is_some) || self.check(&TokenType::Colon) {
+            return Err(messages::LAMBDA_TYPE_ANNOTATION.error(&[]));
+        }
+        self.consume(&TokenType::LeftBrace, messages::EXPECTED_LAMBDA_BODY)?;
         
-        let body = self.block_statement()?;
+        match self.block_statement()? {
+            Stmt::Block(body) => Ok(Expr::Function { params, rest, body: Arc::new(body) }),
+            _ => unreachable!("block_statement should always return a Block"),
+        }
+    }
+    
+    /// Parse the `needs condition` lines that open a function body
+    fn preconditions(&mut self, function: &str) -> Result<Vec<Stmt>, ZLangError> {
+        let mut preconditions = Vec::new();
         
-        if let Stmt::Block(statements) = body {
-            Ok(Stmt::Function { name, params, body: statements })
-        } else {
-            unreachable!("block_statement should always return a Block")
+        loop {
+            while self.match_token(&TokenType::Newline) {}
+            
+            if !self.match_token(&TokenType::Needs) {
+                return Ok(preconditions);
+            }
+            
+            let line = self.previous().line;
+            let start = self.previous().span.start;
+            let condition = self.expression()?;
+            self.consume_statement_end(messages::EXPECTED_NEEDS_END)?;
+            self.statement_spans.push(start..self.previous().span.end);
+            preconditions.push(Stmt::Needs { function: function.to_string(), condition, line });
         }
     }
     
     fn var_declaration(&mut self) -> Result<Stmt, ZLangError> {
+        let declaration = self.var_declaration_head()?;
+        self.consume_statement_end(messages::EXPECTED_DECLARATION_END)?;
+        Ok(declaration)
+    }
+    
+    /// `name: type = value` after a `bet`, up to wherever the statement ends
+    fn var_declaration_head(&mut self) -> Result<Stmt, ZLangError> {
         let name = if let TokenType::Identifier(name) = &self.peek().token_type {
             let name = name.clone();
             self.advance();
             name
         } else {
-            return Err(ZLangError::new("Expected variable name after 'bet', gotta name your variables bestie 📛"));
+            return Err(messages::EXPECTED_VARIABLE_NAME.error(&[]));
         };
         
+        let type_annotation = self.optional_type_annotation()?;
         let initializer = if self.match_token(&TokenType::Equal) {
             Some(self.expression()?)
         } else {
             None
         };
         
-        self.consume_statement_end("Expected ';' or newline after variable declaration 📍")?;
+        Ok(Stmt::VarDeclaration { name, type_annotation, initializer })
+    }
+    
+    /// Parse `: type` if present - annotations are optional everywhere
+    fn optional_type_annotation(&mut self) -> Result<Option<TypeAnnotation>, ZLangError> {
+        if !self.match_token(&TokenType::Colon) {
+            return Ok(None);
+        }
         
-        Ok(Stmt::VarDeclaration { name, initializer })
+        if let TokenType::Identifier(name) = &self.peek().token_type {
+            let annotation = TypeAnnotation::from_name(name).ok_or_else(|| {
+                messages::UNKNOWN_TYPE.error(&[name, &self.peek().line])
+            })?;
+            self.advance();
+            Ok(Some(annotation))
+        } else {
+            Err(messages::EXPECTED_TYPE_NAME.error(&[&self.peek().line]))
+        }
     }
     
     fn statement(&mut self) -> Result<Stmt, ZLangError> {
+        self.nested(Self::statement_inner)
+    }
+    
+    fn statement_inner(&mut self) -> Result<Stmt, ZLangError> {
         if self.match_token(&TokenType::Sus) {
             self.if_statement()
-        } else if self.match_token(&TokenType::LowkeySus) {
-            self.if_statement()
-        } else if self.match_token(&TokenType::NoSus) {
-            self.if_statement()
+        } else if self.check(&TokenType::Bussin) || self.check(&TokenType::NoSus) || self.check(&TokenType::LowkeySus) {
+            let keyword = self.peek().token_type.keyword().unwrap_or_default();
+            Err(messages::STRAY_ELSE.error(&[&keyword, &self.peek().line]))
         } else if self.match_token(&TokenType::Lowkey) {
             self.while_statement()
         } else if self.match_token(&TokenType::Highkey) || self.match_token(&TokenType::Grind) {
@@ -121,31 +364,39 @@ impl Parser {
         } else if self.match_token(&TokenType::Vibe) {
             self.return_statement()
         } else if self.match_token(&TokenType::Slay) {
-            self.consume_statement_end("Expected ';' or newline after 'slay'")?;
+            self.consume_statement_end(messages::EXPECTED_BREAK_END)?;
             Ok(Stmt::Break)
         } else if self.match_token(&TokenType::Ghost) || self.match_token(&TokenType::NoChill) {
-            self.consume_statement_end("Expected ';' or newline after continue")?;
+            self.consume_statement_end(messages::EXPECTED_CONTINUE_END)?;
             Ok(Stmt::Continue)
         } else if self.match_token(&TokenType::Bruh) {
             self.print_statement()
+        } else if self.match_token(&TokenType::BruhBang) {
+            self.inspect_statement()
+        } else if self.match_token(&TokenType::Pull) {
+            self.pull_statement()
+        } else if self.match_token(&TokenType::Global) {
+            Ok(Stmt::Global(self.binding_names("global")?))
+        } else if self.match_token(&TokenType::Outer) {
+            Ok(Stmt::Outer(self.binding_names("outer")?))
+        } else if self.check(&TokenType::Needs) {
+            Err(messages::NEEDS_OUTSIDE_FUNCTION.error(&[&self.peek().line]))
         } else {
             self.expression_statement()
         }
     }
     
     fn if_statement(&mut self) -> Result<Stmt, ZLangError> {
-        self.consume(&TokenType::LeftParen, "Expected '(' after 'sus'")?;
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_IF_PAREN)?;
         let condition = self.expression()?;
-        self.consume(&TokenType::RightParen, "Expected ')' after condition")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_IF_CLOSE)?;
         
-        let then_branch = Box::new(self.statement()?);
-        let else_branch = if self.match_token(&TokenType::LowkeySus) {
+        let then_branch = self.body()?;
+        let else_branch = match self.else_keyword() {
             // Handle else if chain
-            Some(Box::new(self.if_statement()?))
-        } else if self.match_token(&TokenType::Bussin) || self.match_token(&TokenType::NoSus) {
-            Some(Box::new(self.statement()?))
-        } else {
-            None
+            Some(TokenType::LowkeySus) => Some(Box::new(self.if_statement()?)),
+            Some(_) => Some(self.body()?),
+            None => None,
         };
         
         Ok(Stmt::If {
@@ -155,41 +406,115 @@ impl Parser {
         })
     }
     
+    /// The statement a branch or loop runs, which can start on the line after its condition -
+    /// that's where the formatter puts it when it isn't a block
+    fn body(&mut self) -> Result<Box<Stmt>, ZLangError> {
+        while self.match_token(&TokenType::Newline) {}
+        Ok(Box::new(self.statement()?))
+    }
+    
+    /// Takes `bussin`, `no sus` or `lowkey sus` if one comes next, on this line or a later one, and
+    /// says which. An else always goes with the closest `sus` before it that doesn't have one yet
+    fn else_keyword(&mut self) -> Option<TokenType> {
+        let mut ahead = self.current;
+        while matches!(self.tokens.get(ahead).map(|token| &token.token_type), Some(TokenType::Newline)) {
+            ahead += 1;
+        }
+        let keyword = self.tokens.get(ahead).map(|token| token.token_type.clone())?;
+        if !matches!(keyword, TokenType::Bussin | TokenType::NoSus | TokenType::LowkeySus) {
+            return None;
+        }
+        self.current = ahead + 1;
+        Some(keyword)
+    }
+    
+    /// A loop's `no sus`, on this line or a later one. Only a loop with a `{}` body gets one, and
+    /// only spelled `no sus` - after `sus (...) grind (...) bruh n` an else still goes with the `sus`
+    fn loop_else(&mut self, body: &Stmt) -> Result<Option<Box<Stmt>>, ZLangError> {
+        if !matches!(body, Stmt::Block(_)) {
+            return Ok(None);
+        }
+        let mut ahead = self.current;
+        while matches!(self.tokens.get(ahead).map(|token| &token.token_type), Some(TokenType::Newline)) {
+            ahead += 1;
+        }
+        if !matches!(self.tokens.get(ahead).map(|token| &token.token_type), Some(TokenType::NoSus)) {
+            return Ok(None);
+        }
+        self.current = ahead + 1;
+        Ok(Some(self.body()?))
+    }
+    
     fn while_statement(&mut self) -> Result<Stmt, ZLangError> {
-        self.consume(&TokenType::LeftParen, "Expected '(' after 'lowkey', wrap that condition bestie! 🔄")?;
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_WHILE_PAREN)?;
         let condition = self.expression()?;
-        self.consume(&TokenType::RightParen, "Expected ')' after condition 🔒")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_WHILE_CLOSE)?;
         
-        let body = Box::new(self.statement()?);
+        let body = self.body()?;
+        let else_branch = self.loop_else(&body)?;
         
-        Ok(Stmt::While { condition, body })
+        Ok(Stmt::While { condition, body, else_branch })
     }
     
     fn for_statement(&mut self) -> Result<Stmt, ZLangError> {
-        self.consume(&TokenType::LeftParen, "Expected '(' after for loop")?;
+        let optional = self.match_token(&TokenType::Question);
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_FOR_PAREN)?;
+        
+        let for_in = matches!(self.peek().token_type, TokenType::Identifier(_))
+            && matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::In));
+        if !for_in && optional {
+            return Err(messages::OPTIONAL_FOR_NEEDS_IN.error(&[&self.peek().line]));
+        }
+        if !for_in {
+            return self.classic_for();
+        }
         
         let variable = if let TokenType::Identifier(name) = &self.peek().token_type {
             let name = name.clone();
             self.advance();
             name
         } else {
-            return Err(ZLangError::new("Expected variable name in for loop"));
+            return Err(messages::EXPECTED_FOR_VARIABLE.error(&[]));
         };
         
-        self.consume(&TokenType::In, "Expected 'in' after loop variable")?;
+        self.consume(&TokenType::In, messages::EXPECTED_FOR_IN)?;
         let iterable = self.expression()?;
-        self.consume(&TokenType::RightParen, "Expected ')' after iterable")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_FOR_CLOSE)?;
+        
+        let body = self.body()?;
+        let else_branch = self.loop_else(&body)?;
+        
+        Ok(Stmt::For { variable, iterable, body, optional, else_branch })
+    }
+    
+    /// The `(start; condition; step)` kind, with the `(` already read
+    fn classic_for(&mut self) -> Result<Stmt, ZLangError> {
+        let initializer = if self.check(&TokenType::Semicolon) {
+            None
+        } else if self.match_token(&TokenType::Bet) {
+            Some(Box::new(self.var_declaration_head()?))
+        } else {
+            Some(Box::new(Stmt::Expression(self.expression()?)))
+        };
+        self.consume(&TokenType::Semicolon, messages::EXPECTED_LOOP_START_END)?;
         
-        let body = Box::new(self.statement()?);
+        let condition = if self.check(&TokenType::Semicolon) { None } else { Some(self.expression()?) };
+        self.consume(&TokenType::Semicolon, messages::EXPECTED_LOOP_CONDITION_END)?;
         
-        Ok(Stmt::For { variable, iterable, body })
+        let increment = if self.check(&TokenType::RightParen) { None } else { Some(self.expression()?) };
+        self.consume(&TokenType::RightParen, messages::EXPECTED_LOOP_STEP_CLOSE)?;
+        
+        let body = self.body()?;
+        let else_branch = self.loop_else(&body)?;
+        
+        Ok(Stmt::ClassicFor { initializer, condition, increment, body, else_branch })
     }
     
     fn switch_statement(&mut self) -> Result<Stmt, ZLangError> {
-        self.consume(&TokenType::LeftParen, "Expected '(' after 'vibe check'")?;
+        self.consume(&TokenType::LeftParen, messages::EXPECTED_SWITCH_PAREN)?;
         let expr = self.expression()?;
-        self.consume(&TokenType::RightParen, "Expected ')' after switch expression")?;
-        self.consume(&TokenType::LeftBrace, "Expected '{' after switch expression")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_SWITCH_CLOSE)?;
+        self.consume(&TokenType::LeftBrace, messages::EXPECTED_SWITCH_OPEN)?;
         
         let mut cases = Vec::new();
         let mut default = None;
@@ -199,37 +524,78 @@ impl Parser {
                 continue;
             }
             
-            if self.check(&TokenType::Identifier("default".to_string())) {
-                self.advance();
-                self.consume(&TokenType::Colon, "Expected ':' after default")?;
-                let mut statements = Vec::new();
-                while !self.check(&TokenType::RightBrace) && !self.check(&TokenType::Identifier("case".to_string())) && !self.is_at_end() {
-                    if self.match_token(&TokenType::Newline) {
-                        continue;
-                    }
-                    statements.push(self.declaration()?);
-                }
-                default = Some(statements);
+            if self.match_identifier("default") {
+                self.consume(&TokenType::Colon, messages::EXPECTED_DEFAULT_COLON)?;
+                default = Some(self.case_body()?);
             } else {
-                let case_expr = self.expression()?;
-                self.consume(&TokenType::Colon, "Expected ':' after case value")?;
-                let mut statements = Vec::new();
-                while !self.check(&TokenType::RightBrace) && !self.check(&TokenType::Identifier("case".to_string())) && !self.check(&TokenType::Identifier("default".to_string())) && !self.is_at_end() {
-                    if self.match_token(&TokenType::Newline) {
-                        continue;
-                    }
-                    statements.push(self.declaration()?);
+                self.match_identifier("case");
+                let mut patterns = vec![self.case_pattern()?];
+                while self.match_token(&TokenType::Comma) {
+                    patterns.push(self.case_pattern()?);
                 }
-                cases.push((case_expr, statements));
+                self.consume(&TokenType::Colon, messages::EXPECTED_CASE_COLON)?;
+                cases.push(SwitchCase { patterns, body: self.case_body()? });
             }
         }
         
-        self.consume(&TokenType::RightBrace, "Expected '}' after switch cases")?;
+        self.consume(&TokenType::RightBrace, messages::EXPECTED_SWITCH_END)?;
         Ok(Stmt::Switch { expr, cases, default })
     }
     
+    /// `"happy"`, `80..90`, `90..=100` or `>= 50` - one of the comma-separated patterns in a case label
+    fn case_pattern(&mut self) -> Result<CasePattern, ZLangError> {
+        if let Some(operator) = self.match_comparison_op().or_else(|| self.match_equality_op()) {
+            return Ok(CasePattern::Compare { operator, value: self.expression()? });
+        }
+        
+        // In a label a range is what the value falls in, not the numbers it counts through
+        match self.expression()? {
+            Expr::Range { start, end, inclusive } => Ok(CasePattern::Range { low: *start, high: *end, inclusive }),
+            value => Ok(CasePattern::Value(value)),
+        }
+    }
+    
+    /// Statements of one case, up to the next `case`/`default`/`value:` label or the closing brace
+    fn case_body(&mut self) -> Result<Vec<Stmt>, ZLangError> {
+        let mut statements = Vec::new();
+        
+        while !self.check(&TokenType::RightBrace) && !self.at_case_label() && !self.is_at_end() {
+            if self.match_token(&TokenType::Newline) {
+                continue;
+            }
+            statements.push(self.declaration()?);
+        }
+        
+        Ok(statements)
+    }
+    
+    fn at_case_label(&self) -> bool {
+        if self.check_identifier("case") || self.check_identifier("default") {
+            return true;
+        }
+        
+        // No statement starts with a comparison, so `>= 50:` is always a label
+        if matches!(
+            self.peek().token_type,
+            TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual | TokenType::EqualEqual | TokenType::BangEqual
+        ) {
+            return true;
+        }
+        
+        // `-5..0:` starts with a minus, the value comes after it
+        let start = self.current + usize::from(self.peek().token_type == TokenType::Minus);
+        let simple_value = matches!(
+            self.tokens.get(start).map(|t| &t.token_type),
+            Some(TokenType::Number(_) | TokenType::String(_) | TokenType::Fr | TokenType::Cap | TokenType::Identifier(_))
+        );
+        simple_value && matches!(
+            self.tokens.get(start + 1).map(|t| &t.token_type),
+            Some(TokenType::Colon | TokenType::Comma | TokenType::DotDot | TokenType::DotDotEqual)
+        )
+    }
+    
     fn try_statement(&mut self) -> Result<Stmt, ZLangError> {
-        self.consume(&TokenType::LeftBrace, "Expected '{' after 'manifest'")?;
+        self.consume(&TokenType::LeftBrace, messages::EXPECTED_TRY_OPEN)?;
         let mut try_block = Vec::new();
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
             if self.match_token(&TokenType::Newline) {
@@ -237,19 +603,19 @@ impl Parser {
             }
             try_block.push(self.declaration()?);
         }
-        self.consume(&TokenType::RightBrace, "Expected '}' after try block")?;
+        self.consume(&TokenType::RightBrace, messages::EXPECTED_TRY_CLOSE)?;
         
-        let catch_block = if self.match_token(&TokenType::Caught) {
-            self.consume(&TokenType::LeftParen, "Expected '(' after 'caught'")?;
-            let error_var = if let TokenType::Identifier(name) = &self.peek().token_type {
-                let name = name.clone();
-                self.advance();
-                name
+        let mut catch_clauses = Vec::new();
+        while self.match_token(&TokenType::Caught) {
+            self.consume(&TokenType::LeftParen, messages::EXPECTED_CATCH_PAREN)?;
+            let variable = self.identifier(messages::EXPECTED_CATCH_VARIABLE)?;
+            let error_type = if self.match_token(&TokenType::Colon) {
+                Some(self.identifier(messages::EXPECTED_CATCH_DRAMA_TYPE)?)
             } else {
-                return Err(ZLangError::new("Expected error variable name"));
+                None
             };
-            self.consume(&TokenType::RightParen, "Expected ')' after error variable")?;
-            self.consume(&TokenType::LeftBrace, "Expected '{' after catch clause")?;
+            self.consume(&TokenType::RightParen, messages::EXPECTED_CATCH_CLOSE)?;
+            self.consume(&TokenType::LeftBrace, messages::EXPECTED_CATCH_OPEN)?;
             
             let mut catch_stmts = Vec::new();
             while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
@@ -258,14 +624,12 @@ impl Parser {
                 }
                 catch_stmts.push(self.declaration()?);
             }
-            self.consume(&TokenType::RightBrace, "Expected '}' after catch block")?;
-            Some((error_var, catch_stmts))
-        } else {
-            None
-        };
+            self.consume(&TokenType::RightBrace, messages::EXPECTED_CATCH_END)?;
+            catch_clauses.push(CatchClause { variable, error_type, body: catch_stmts });
+        }
         
         let finally_block = if self.match_token(&TokenType::Frfr) {
-            self.consume(&TokenType::LeftBrace, "Expected '{' after 'frfr'")?;
+            self.consume(&TokenType::LeftBrace, messages::EXPECTED_FINALLY_OPEN)?;
             let mut finally_stmts = Vec::new();
             while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
                 if self.match_token(&TokenType::Newline) {
@@ -273,18 +637,32 @@ impl Parser {
                 }
                 finally_stmts.push(self.declaration()?);
             }
-            self.consume(&TokenType::RightBrace, "Expected '}' after finally block")?;
+            self.consume(&TokenType::RightBrace, messages::EXPECTED_FINALLY_CLOSE)?;
             Some(finally_stmts)
         } else {
             None
         };
         
-        Ok(Stmt::Try { try_block, catch_block, finally_block })
+        Ok(Stmt::Try { try_block, catch_clauses, finally_block })
     }
     
     fn throw_statement(&mut self) -> Result<Stmt, ZLangError> {
+        let declares_type = self.check_identifier("type")
+            && matches!(self.tokens.get(self.current + 1).map(|t| &t.token_type), Some(TokenType::Identifier(_)));
+        if declares_type {
+            self.advance();
+            let name = self.identifier(messages::EXPECTED_DRAMA_TYPE_NAME)?;
+            let parent = if self.match_token(&TokenType::Colon) {
+                Some(self.identifier(messages::EXPECTED_DRAMA_TYPE_PARENT)?)
+            } else {
+                None
+            };
+            self.consume_statement_end(messages::EXPECTED_DRAMA_TYPE_END)?;
+            return Ok(Stmt::DramaType { name, parent });
+        }
+        
         let expr = self.expression()?;
-        self.consume_statement_end("Expected ';' or newline after throw expression")?;
+        self.consume_statement_end(messages::EXPECTED_THROW_END)?;
         Ok(Stmt::Throw(expr))
     }
     
@@ -300,7 +678,7 @@ impl Parser {
             statements.push(self.declaration()?);
         }
         
-        self.consume(&TokenType::RightBrace, "Expected '}' after block, gotta close that block bestie! 🏁")?;
+        self.consume(&TokenType::RightBrace, messages::EXPECTED_BLOCK_CLOSE)?;
         Ok(Stmt::Block(statements))
     }
     
@@ -311,45 +689,108 @@ impl Parser {
             Some(self.expression()?)
         };
         
-        self.consume_statement_end("Expected ';' or newline after return value 📤")?;
+        self.consume_statement_end(messages::EXPECTED_RETURN_END)?;
         Ok(Stmt::Return(value))
     }
     
+    fn binding_names(&mut self, keyword: &str) -> Result<Vec<String>, ZLangError> {
+        let mut names = Vec::new();
+        loop {
+            if let TokenType::Identifier(name) = &self.peek().token_type {
+                names.push(name.clone());
+                self.advance();
+            } else {
+                return Err(messages::EXPECTED_SCOPE_NAME.error(&[&keyword]));
+            }
+            
+            if !self.match_token(&TokenType::Comma) {
+                break;
+            }
+        }
+        
+        if !self.end_statement() {
+            return Err(messages::EXPECTED_SCOPE_END.error(&[&keyword]));
+        }
+        Ok(names)
+    }
+    
     fn print_statement(&mut self) -> Result<Stmt, ZLangError> {
         let expr = self.expression()?;
-        self.consume_statement_end("Expected ';' or newline after print statement 🖨️")?;
+        self.consume_statement_end(messages::EXPECTED_PRINT_END)?;
         Ok(Stmt::Print(expr))
     }
     
+    fn inspect_statement(&mut self) -> Result<Stmt, ZLangError> {
+        let expr = self.expression()?;
+        self.consume_statement_end(messages::EXPECTED_INSPECT_END)?;
+        Ok(Stmt::Inspect(expr))
+    }
+    
+    fn pull_statement(&mut self) -> Result<Stmt, ZLangError> {
+        let line = self.previous().line;
+        let TokenType::String(path) = self.peek().token_type.clone() else {
+            return Err(messages::PULL_WITHOUT_FILE.error(&[&line]));
+        };
+        self.advance();
+        // `as` only means something here, everywhere else it's a plain name
+        let alias = if self.match_identifier("as") {
+            Some(self.identifier(messages::EXPECTED_PULL_ALIAS)?)
+        } else {
+            None
+        };
+        self.consume_statement_end(messages::EXPECTED_PULL_END)?;
+        Ok(Stmt::Pull { path, alias, line })
+    }
+    
     fn expression_statement(&mut self) -> Result<Stmt, ZLangError> {
         let expr = self.expression()?;
-        self.consume_statement_end("Expected ';' or newline after expression 📝")?;
+        if !self.end_statement() {
+            return Err(messages::EXPECTED_STATEMENT_END.error(&[]));
+        }
         Ok(Stmt::Expression(expr))
     }
     
     fn expression(&mut self) -> Result<Expr, ZLangError> {
-        self.assignment()
+        self.nested(Self::assignment)
     }
     
     fn assignment(&mut self) -> Result<Expr, ZLangError> {
-        let expr = self.or()?;
+        let expr = self.conditional()?;
         
         if self.match_token(&TokenType::Equal) {
             let value = self.assignment()?;
             
-            if let Expr::Variable(name) = expr {
-                return Ok(Expr::Assign {
-                    name,
-                    value: Box::new(value),
-                });
+            match expr {
+                Expr::Variable(name) => return Ok(Expr::Assign { name, value: Box::new(value) }),
+                // `a?.b = 1` has nowhere to put the 1 when a is nil
+                Expr::Get { object, name, optional: false } => return Ok(Expr::Set { object, name, value: Box::new(value) }),
+                _ => {}
             }
             
-            return Err(ZLangError::new("Invalid assignment target, can't assign to that bestie! 🎯"));
+            return Err(messages::INVALID_ASSIGNMENT_TARGET.error(&[]));
         }
         
         Ok(expr)
     }
     
+    /// `cond ? a : b`, nesting to the right. A `?` that reaches here has an expression after it,
+    /// the postfix one was already taken by `call()`
+    fn conditional(&mut self) -> Result<Expr, ZLangError> {
+        let condition = self.or()?;
+        if !self.match_token(&TokenType::Question) {
+            return Ok(condition);
+        }
+        
+        let then_branch = self.conditional()?;
+        self.consume(&TokenType::Colon, messages::EXPECTED_TERNARY_COLON)?;
+        let else_branch = self.conditional()?;
+        Ok(Expr::Conditional {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch: Box::new(else_branch),
+        })
+    }
+    
     fn or(&mut self) -> Result<Expr, ZLangError> {
         let mut expr = self.and()?;
         
@@ -406,10 +847,10 @@ impl Parser {
     }
     
     fn comparison(&mut self) -> Result<Expr, ZLangError> {
-        let mut expr = self.term()?;
+        let mut expr = self.range()?;
         
         while let Some(op) = self.match_comparison_op() {
-            let right = self.term()?;
+            let right = self.range()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 operator: op,
@@ -434,6 +875,90 @@ impl Parser {
         }
     }
     
+    /// `1..10` or `1..=10`, just one - `1..2..3` has no meaning to give it
+    fn range(&mut self) -> Result<Expr, ZLangError> {
+        let start = self.bit_or()?;
+        let inclusive = if self.match_token(&TokenType::DotDot) {
+            false
+        } else if self.match_token(&TokenType::DotDotEqual) {
+            true
+        } else {
+            return Ok(start);
+        };
+        let end = self.bit_or()?;
+        Ok(Expr::Range { start: Box::new(start), end: Box::new(end), inclusive })
+    }
+    
+    fn bit_or(&mut self) -> Result<Expr, ZLangError> {
+        let mut expr = self.bit_xor()?;
+        
+        while self.match_token(&TokenType::Pipe) {
+            let right = self.bit_xor()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator: BinaryOp::BitOr,
+                right: Box::new(right),
+            };
+        }
+        
+        Ok(expr)
+    }
+    
+    fn bit_xor(&mut self) -> Result<Expr, ZLangError> {
+        let mut expr = self.bit_and()?;
+        
+        while self.match_token(&TokenType::Caret) {
+            let right = self.bit_and()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator: BinaryOp::BitXor,
+                right: Box::new(right),
+            };
+        }
+        
+        Ok(expr)
+    }
+    
+    fn bit_and(&mut self) -> Result<Expr, ZLangError> {
+        let mut expr = self.shift()?;
+        
+        while self.match_token(&TokenType::Ampersand) {
+            let right = self.shift()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator: BinaryOp::BitAnd,
+                right: Box::new(right),
+            };
+        }
+        
+        Ok(expr)
+    }
+    
+    fn shift(&mut self) -> Result<Expr, ZLangError> {
+        let mut expr = self.term()?;
+        
+        while let Some(op) = self.match_shift_op() {
+            let right = self.term()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator: op,
+                right: Box::new(right),
+            };
+        }
+        
+        Ok(expr)
+    }
+    
+    fn match_shift_op(&mut self) -> Option<BinaryOp> {
+        if self.match_token(&TokenType::LessLess) {
+            Some(BinaryOp::ShiftLeft)
+        } else if self.match_token(&TokenType::GreaterGreater) {
+            Some(BinaryOp::ShiftRight)
+        } else {
+            None
+        }
+    }
+    
     fn term(&mut self) -> Result<Expr, ZLangError> {
         let mut expr = self.factor()?;
         
@@ -481,6 +1006,8 @@ impl Parser {
             Some(BinaryOp::Multiply)
         } else if self.match_token(&TokenType::Percent) {
             Some(BinaryOp::Modulo)
+        } else if self.match_token(&TokenType::TildeSlash) {
+            Some(BinaryOp::IntDivide)
         } else {
             None
         }
@@ -488,7 +1015,7 @@ impl Parser {
     
     fn unary(&mut self) -> Result<Expr, ZLangError> {
         if let Some(op) = self.match_unary_op() {
-            let right = self.unary()?;
+            let right = self.nested(Self::unary)?;
             Ok(Expr::Unary {
                 operator: op,
                 right: Box::new(right),
@@ -503,6 +1030,10 @@ impl Parser {
             Some(UnaryOp::Not)
         } else if self.match_token(&TokenType::Minus) {
             Some(UnaryOp::Minus)
+        } else if self.match_token(&TokenType::Plus) {
+            Some(UnaryOp::Plus)
+        } else if self.match_token(&TokenType::Tilde) {
+            Some(UnaryOp::BitNot)
         } else {
             None
         }
@@ -510,16 +1041,43 @@ impl Parser {
     
     fn call(&mut self) -> Result<Expr, ZLangError> {
         let mut expr = self.primary()?;
+        if let Expr::Variable(name) = &expr {
+            if self.memes.contains_key(name) && self.check(&TokenType::LeftParen) {
+                expr = self.expand_meme(name.clone())?;
+            }
+        }
         
         loop {
             if self.match_token(&TokenType::LeftParen) {
                 expr = self.finish_call(expr)?;
-            } else if self.match_token(&TokenType::LeftBracket) {
+            } else if self.check(&TokenType::Question) && self.question_is_postfix() {
+                let line = self.advance().line;
+                expr = Expr::Propagate {
+                    value: Box::new(expr),
+                    line,
+                };
+            } else if self.match_token(&TokenType::LeftBracket) || self.match_token(&TokenType::QuestionBracket) {
+                let optional = self.previous().token_type == TokenType::QuestionBracket;
                 let index = self.expression()?;
-                self.consume(&TokenType::RightBracket, "Expected ']' after array index, close that bracket bestie! 📚")?;
+                self.consume(&TokenType::RightBracket, messages::EXPECTED_INDEX_CLOSE)?;
                 expr = Expr::Index {
                     object: Box::new(expr),
                     index: Box::new(index),
+                    optional,
+                };
+            } else if self.match_token(&TokenType::Dot) || self.match_token(&TokenType::QuestionDot) {
+                let optional = self.previous().token_type == TokenType::QuestionDot;
+                let name = match &self.peek().token_type {
+                    TokenType::Identifier(name) => name.clone(),
+                    _ => return Err(messages::EXPECTED_FIELD_NAME.error(&[&self.peek().line])),
+                };
+                self.advance();
+                expr = match expr {
+                    // `draw.line` names one builtin, the namespace isn't a value of its own
+                    Expr::Variable(namespace) if !optional && BUILTIN_NAMESPACES.contains(&namespace.as_str()) => {
+                        Expr::Variable(format!("{}.{}", namespace, name))
+                    }
+                    object => Expr::Get { object: Box::new(object), name, optional },
                 };
             } else {
                 break;
@@ -529,24 +1087,66 @@ impl Parser {
         Ok(expr)
     }
     
+    /// A `?` is the propagation operator only when nothing that could start an expression follows it
+    fn question_is_postfix(&self) -> bool {
+        matches!(
+            self.tokens.get(self.current + 1).map(|t| &t.token_type),
+            None | Some(TokenType::Newline) | Some(TokenType::Semicolon) | Some(TokenType::Eof)
+                | Some(TokenType::RightParen) | Some(TokenType::RightBracket) | Some(TokenType::RightBrace)
+                | Some(TokenType::Comma) | Some(TokenType::Question)
+        )
+    }
+    
+    /// One of an array's items or a call's arguments, which can be `...spread`
+    fn item(&mut self) -> Result<Expr, ZLangError> {
+        if self.match_token(&TokenType::Ellipsis) {
+            Ok(Expr::Spread(Box::new(self.expression()?)))
+        } else {
+            self.expression()
+        }
+    }
+    
     fn finish_call(&mut self, callee: Expr) -> Result<Expr, ZLangError> {
+        let line = self.previous().line;
+        let arguments = self.arguments()?;
+        
+        Ok(Expr::Call {
+            callee: Box::new(callee),
+            arguments,
+            line,
+        })
+    }
+    
+    /// A call's arguments after its '(', through the closing ')'
+    fn arguments(&mut self) -> Result<Vec<Expr>, ZLangError> {
         let mut arguments = Vec::new();
         
         if !self.check(&TokenType::RightParen) {
             loop {
-                arguments.push(self.expression()?);
+                arguments.push(self.item()?);
                 if !self.match_token(&TokenType::Comma) {
                     break;
                 }
             }
         }
         
-        self.consume(&TokenType::RightParen, "Expected ')' after arguments, close those parentheses! 📞")?;
+        self.consume(&TokenType::RightParen, messages::EXPECTED_ARGUMENTS_CLOSE)?;
+        Ok(arguments)
+    }
+    
+    /// `double(n + 1)` with the meme called `name` swapped in for it. Statements in lambdas the
+    /// expansion holds came from the meme, so stepping through one shows the call
+    fn expand_meme(&mut self, name: String) -> Result<Expr, ZLangError> {
+        let start = self.previous().span.start;
+        let spans = self.statement_spans.len();
+        let line = self.advance().line;
+        let arguments = self.arguments()?;
+        let expanded = self.memes[&name].expand(arguments, line)?;
         
-        Ok(Expr::Call {
-            callee: Box::new(callee),
-            arguments,
-        })
+        self.statement_spans.truncate(spans);
+        let call = start..self.previous().span.end;
+        self.statement_spans.extend(std::iter::repeat_n(call, steps::spans_in(&expanded)));
+        Ok(expanded)
     }
     
     fn primary(&mut self) -> Result<Expr, ZLangError> {
@@ -574,31 +1174,40 @@ impl Parser {
                 self.advance();
                 Ok(Expr::Variable(name))
             }
+            TokenType::Flex => {
+                self.advance();
+                self.lambda()
+            }
             TokenType::LeftParen => {
                 self.advance();
                 let expr = self.expression()?;
-                self.consume(&TokenType::RightParen, "Expected ')' after expression, balance those parentheses! ⚖️")?;
+                self.consume(&TokenType::RightParen, messages::EXPECTED_GROUP_CLOSE)?;
                 Ok(expr)
             }
             TokenType::LeftBracket => {
                 self.advance();
                 let mut elements = Vec::new();
+                while self.match_token(&TokenType::Newline) {}
                 
                 if !self.check(&TokenType::RightBracket) {
                     loop {
-                        elements.push(self.expression()?);
+                        elements.push(self.item()?);
+                        while self.match_token(&TokenType::Newline) {}
                         if !self.match_token(&TokenType::Comma) {
                             break;
                         }
+                        while self.match_token(&TokenType::Newline) {}
                     }
                 }
                 
-                self.consume(&TokenType::RightBracket, "Expected ']' after array elements, close that array bestie! 📝")?;
+                self.consume(&TokenType::RightBracket, messages::EXPECTED_ARRAY_CLOSE)?;
                 Ok(Expr::Array(elements))
             }
             TokenType::LeftBrace => {
                 self.advance();
                 let mut pairs = Vec::new();
+                // Newlines between the entries are just layout, a big object reads better one key a line
+                while self.match_token(&TokenType::Newline) {}
                 
                 if !self.check(&TokenType::RightBrace) {
                     loop {
@@ -611,30 +1220,40 @@ impl Parser {
                             self.advance();
                             s
                         } else {
-                            return Err(ZLangError::new("Expected property name in object, objects need keys bestie! 🗝️"));
+                            return Err(messages::EXPECTED_PROPERTY_NAME.error(&[]));
                         };
                         
-                        self.consume(&TokenType::Colon, "Expected ':' after property name, that's how objects work! 🎯")?;
+                        self.consume(&TokenType::Colon, messages::EXPECTED_PROPERTY_COLON)?;
                         let value = self.expression()?;
                         pairs.push((key, value));
+                        while self.match_token(&TokenType::Newline) {}
                         
                         if !self.match_token(&TokenType::Comma) {
                             break;
                         }
+                        while self.match_token(&TokenType::Newline) {}
                     }
                 }
                 
-                self.consume(&TokenType::RightBrace, "Expected '}' after object properties, close that object! 🏁")?;
+                self.consume(&TokenType::RightBrace, messages::EXPECTED_OBJECT_CLOSE)?;
                 Ok(Expr::Object(pairs))
             }
-            _ => Err(ZLangError::new(&format!(
-                "Unexpected token at line {}, that's not valid in this context bestie 🤷‍♀️",
-                self.peek().line
-            ))),
+            _ => Err(messages::UNEXPECTED_TOKEN.error(&[&self.peek().line])),
         }
     }
     
     // Helper methods
+    fn nested<T>(&mut self, parse: fn(&mut Self) -> Result<T, ZLangError>) -> Result<T, ZLangError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(messages::NESTED_TOO_DEEP.error(&[&self.peek().line]));
+        }
+        
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+    
     fn match_token(&mut self, token_type: &TokenType) -> bool {
         if self.check(token_type) {
             self.advance();
@@ -644,6 +1263,30 @@ impl Parser {
         }
     }
     
+    fn identifier(&mut self, message: Message) -> Result<String, ZLangError> {
+        if let TokenType::Identifier(name) = &self.peek().token_type {
+            let name = name.clone();
+            self.advance();
+            Ok(name)
+        } else {
+            Err(message.error(&[]))
+        }
+    }
+    
+    /// `check` only compares token kinds, this also compares the identifier's name
+    fn check_identifier(&self, name: &str) -> bool {
+        matches!(&self.peek().token_type, TokenType::Identifier(ident) if ident == name)
+    }
+    
+    fn match_identifier(&mut self, name: &str) -> bool {
+        if self.check_identifier(name) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+    
     fn check(&self, token_type: &TokenType) -> bool {
         if self.is_at_end() {
             false
@@ -671,27 +1314,31 @@ impl Parser {
         &self.tokens[self.current - 1]
     }
     
-    fn consume(&mut self, token_type: &TokenType, message: &str) -> Result<&Token, ZLangError> {
+    fn consume(&mut self, token_type: &TokenType, message: Message) -> Result<&Token, ZLangError> {
         if self.check(token_type) {
             Ok(self.advance())
         } else {
-            Err(ZLangError::new(message))
+            Err(message.error(&[]))
         }
     }
     
-    fn consume_statement_end(&mut self, message: &str) -> Result<(), ZLangError> {
-        if self.match_token(&TokenType::Semicolon) || self.match_token(&TokenType::Newline) || self.is_at_end() {
-            Ok(())
-        } else if self.check(&TokenType::RightBrace) || 
-                  self.check(&TokenType::Bussin) ||
-                  self.check(&TokenType::LowkeySus) ||
-                  self.check(&TokenType::NoSus) ||
-                  self.check(&TokenType::Caught) ||
-                  self.check(&TokenType::Frfr) {
-            // Allow statements to end before closing braces or else keywords
+    fn consume_statement_end(&mut self, message: Message) -> Result<(), ZLangError> {
+        if self.end_statement() {
             Ok(())
         } else {
-            Err(ZLangError::new(message))
+            Err(message.error(&[]))
         }
     }
+    
+    /// Takes the `;` or newline after a statement, false if something else is there instead
+    fn end_statement(&mut self) -> bool {
+        self.match_token(&TokenType::Semicolon) || self.match_token(&TokenType::Newline) || self.is_at_end()
+            // Allow statements to end before closing braces or else keywords
+            || self.check(&TokenType::RightBrace)
+            || self.check(&TokenType::Bussin)
+            || self.check(&TokenType::LowkeySus)
+            || self.check(&TokenType::NoSus)
+            || self.check(&TokenType::Caught)
+            || self.check(&TokenType::Frfr)
+    }
 }
@@ -0,0 +1,149 @@
+//! ZLang Pragmas - `// zlang-ignore unused` hushes a warning you meant to earn, so the real ones stand out 🤫
+//! A trailing one covers its own line, one on a line by itself covers the next line of code, and
+//! `// zlang-ignore-file shadow` covers the whole file. Leaving out the rules hushes every one of them
+
+use std::collections::{HashMap, HashSet};
+
+use crate::lexer::Lexer;
+use crate::similar;
+use crate::token::TokenType;
+
+/// The kinds of warning a pragma can hush, by the name it's written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// A function nobody calls or a variable nobody reads
+    Unused,
+    /// `bet` reusing a name from an outer scope
+    Shadow,
+    /// `bet` declaring the same name twice in one scope
+    Redeclared,
+    /// Reading a variable above the line that declares it
+    UsedEarly,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 4] = [Rule::Unused, Rule::Shadow, Rule::Redeclared, Rule::UsedEarly];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Unused => "unused",
+            Rule::Shadow => "shadow",
+            Rule::Redeclared => "redeclared",
+            Rule::UsedEarly => "used-early",
+        }
+    }
+    
+    pub fn named(name: &str) -> Option<Rule> {
+        Rule::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Pragmas {
+    // What `zlang-ignore-file` hushes everywhere
+    file: HashSet<Rule>,
+    // What each covered line hushes
+    lines: HashMap<usize, HashSet<Rule>>,
+    // The names written on each covered line, which is how a warning without a line finds its pragma
+    names: HashMap<usize, HashSet<String>>,
+    // Rules a pragma asked for that don't exist, with the line they're on, for the linter to point out
+    unknown: Vec<(usize, String)>,
+}
+
+impl Pragmas {
+    /// Every pragma in `source`. Comments are found with the lexer, so a `//` inside a string isn't
+    /// one, and a source that doesn't lex still gives up the pragmas before the bad part
+    pub fn scan(source: &str, aliases: HashMap<String, TokenType>) -> Self {
+        let tokens = Lexer::new(source).with_aliases(aliases).with_trivia().with_recovery().tokenize().unwrap_or_default();
+        let mut pragmas = Self::default();
+        let mut code_lines = HashSet::new();
+        let mut waiting: Vec<HashSet<Rule>> = Vec::new();
+        
+        for token in &tokens {
+            match &token.token_type {
+                TokenType::Whitespace(_) | TokenType::Newline | TokenType::Eof => {}
+                TokenType::Comment(text) => {
+                    let Some((whole_file, rules)) = pragmas.read(text, token.line) else {
+                        continue;
+                    };
+                    if whole_file {
+                        pragmas.file.extend(rules);
+                    } else if code_lines.contains(&token.line) {
+                        pragmas.lines.entry(token.line).or_default().extend(rules);
+                    } else {
+                        waiting.push(rules);
+                    }
+                }
+                other => {
+                    code_lines.insert(token.line);
+                    // The first code after pragmas on lines of their own is what they were for
+                    for rules in waiting.drain(..) {
+                        pragmas.lines.entry(token.line).or_default().extend(rules);
+                    }
+                    if let TokenType::Identifier(name) = other {
+                        pragmas.names.entry(token.line).or_default().insert(name.clone());
+                    }
+                }
+            }
+        }
+        pragmas.names.retain(|line, _| pragmas.lines.contains_key(line));
+        pragmas
+    }
+    
+    /// `// zlang-ignore-file unused, shadow -- why` as whether it covers the file and what it hushes,
+    /// None for a comment that isn't a pragma
+    fn read(&mut self, comment: &str, line: usize) -> Option<(bool, HashSet<Rule>)> {
+        let text = comment.trim_start_matches('/').trim();
+        let (whole_file, rest) = if let Some(rest) = text.strip_prefix("zlang-ignore-file") {
+            (true, rest)
+        } else {
+            (false, text.strip_prefix("zlang-ignore")?)
+        };
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        
+        // Anything after `--` is the reason, for whoever reads it next
+        let rules_part = rest.split("--").next().unwrap_or_default();
+        let words: Vec<&str> = rules_part.split(|c: char| c.is_whitespace() || c == ',').filter(|word| !word.is_empty()).collect();
+        if words.is_empty() {
+            return Some((whole_file, Rule::ALL.into_iter().collect()));
+        }
+        let mut rules = HashSet::new();
+        for word in words {
+            match Rule::named(word) {
+                Some(rule) => {
+                    rules.insert(rule);
+                }
+                None => self.unknown.push((line, word.to_string())),
+            }
+        }
+        Some((whole_file, rules))
+    }
+    
+    /// Whether a `rule` warning reported at `line` is hushed
+    pub fn covers(&self, rule: Rule, line: usize) -> bool {
+        self.file.contains(&rule) || self.lines.get(&line).is_some_and(|rules| rules.contains(&rule))
+    }
+    
+    /// Whether a `rule` warning about `name` is hushed - by the whole file, or by a pragma covering
+    /// a line that mentions the name
+    pub fn ignores(&self, rule: Rule, name: &str) -> bool {
+        self.file.contains(&rule) || self.names.iter()
+            .any(|(line, names)| names.contains(name) && self.covers(rule, *line))
+    }
+    
+    /// What the linter says about a rule a pragma asked for that doesn't exist
+    pub fn unknown_warnings(&self) -> Vec<String> {
+        self.unknown.iter()
+            .map(|(line, word)| {
+                let hint = similar::did_you_mean(word, Rule::ALL.iter().map(|rule| rule.name()));
+                format!("Line {}: zlang-ignore doesn't know the rule '{}', it takes {} 🤫{}", line, word, rule_list(), hint)
+            })
+            .collect()
+    }
+}
+
+fn rule_list() -> String {
+    Rule::ALL.iter().map(|rule| rule.name()).collect::<Vec<_>>().join(", ")
+}
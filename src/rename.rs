@@ -0,0 +1,451 @@
+//! ZLang Rename - Renaming things without find-and-replace energy ✏️
+//! Follows scopes over the lossless token stream, so strings, comments, object keys and shadowed names stay untouched
+
+use std::collections::HashMap;
+use crate::ast::TypeAnnotation;
+use crate::error::ZLangError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::{Token, TokenType};
+
+#[derive(Debug, Clone)]
+pub struct Renamed {
+    pub source: String,
+    /// How many places the name was rewritten, declarations included
+    pub occurrences: usize,
+}
+
+/// Rename every variable, function or drama type called `old` to `new`. With `line`, only
+/// the ones declared or used on that line - handy when two functions both have an `x`.
+/// Fails instead of producing code that would mean something different - a squad's fields and
+/// methods included, since there's no telling which `thing.hp` reads an instance of that squad
+pub fn rename(source: &str, old: &str, new: &str, line: Option<usize>) -> Result<Renamed, ZLangError> {
+    let new_tokens = Lexer::new(new).tokenize()?;
+    if !matches!(new_tokens.as_slice(), [Token { token_type: TokenType::Identifier(_), .. }, _]) {
+        return Err(ZLangError::new(&format!("'{}' can't be a name bestie, try something without spaces or keywords 🏷️", new)));
+    }
+    
+    // Refuse to touch code that doesn't even parse
+    Parser::new(Lexer::new(source).tokenize()?).parse()?;
+    
+    let tokens = Lexer::new(source).with_trivia().tokenize()?;
+    let before = Scopes::resolve(&tokens);
+    
+    let targets: Vec<usize> = (0..before.names.len())
+        .filter(|&binding| before.names[binding] == old)
+        .filter(|&binding| match line {
+            Some(line) => tokens.iter().zip(&before.bindings).any(|(token, b)| *b == Some(binding) && token.line == line),
+            None => true,
+        })
+        .collect();
+    if targets.is_empty() {
+        let place = line.map(|line| format!(" on line {}", line)).unwrap_or_default();
+        return Err(ZLangError::new(&format!("Nothing called '{}'{} to rename 🔍", old, place)));
+    }
+    if let Some(squad) = targets.iter().find_map(|binding| before.members.get(binding)) {
+        return Err(ZLangError::new(&format!(
+            "'{}' is a member of squad {}, and its `.{}` uses on instances can't be told apart from other objects' - rename it by hand 👥",
+            old, squad, old
+        )));
+    }
+    
+    let chars: Vec<char> = source.chars().collect();
+    let mut renamed = String::new();
+    let mut occurrences = 0;
+    for (token, binding) in tokens.iter().zip(&before.bindings) {
+        if binding.is_some_and(|binding| targets.contains(&binding)) {
+            renamed.push_str(new);
+            occurrences += 1;
+        } else {
+            renamed.push_str(&token.lexeme(&chars));
+        }
+    }
+    
+    // Resolve the result again - every name has to point at the same thing it did before
+    let renamed_tokens = Lexer::new(&renamed).with_trivia().tokenize()?;
+    if renamed_tokens.len() != tokens.len() {
+        return Err(ZLangError::new(&format!("Renaming to '{}' changes how the code reads, pick another name 💥", new)));
+    }
+    let after = Scopes::resolve(&renamed_tokens);
+    let mut old_to_new = HashMap::new();
+    let mut new_to_old = HashMap::new();
+    for (index, (was, now)) in before.bindings.iter().zip(&after.bindings).enumerate() {
+        let (Some(was), Some(now)) = (was, now) else { continue };
+        if *old_to_new.entry(*was).or_insert(*now) != *now || *new_to_old.entry(*now).or_insert(*was) != *was {
+            return Err(ZLangError::new(&format!(
+                "Renaming '{}' to '{}' would clash with another '{}' at line {}, pick another name 💥",
+                old, new, new, tokens[index].line
+            )));
+        }
+    }
+    
+    Ok(Renamed { source: renamed, occurrences })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FrameKind {
+    Block,
+    Switch,
+    Object,
+}
+
+struct Frame {
+    kind: FrameKind,
+    names: HashMap<String, usize>,
+    /// The squad whose body this is, what gets declared straight inside it is a member
+    squad: Option<String>,
+}
+
+/// A pass over the tokens that works out which declaration every name points at -
+/// the same scoping the resolver uses, but keeping track of where each name was written
+struct Scopes<'a> {
+    tokens: &'a [Token],
+    /// Indexes of the tokens that matter, trivia skipped
+    significant: Vec<usize>,
+    frames: Vec<Frame>,
+    /// Binding each token names, if it names one
+    bindings: Vec<Option<usize>>,
+    /// Tokens already dealt with by a lookahead (declarations, `type` in `drama type`)
+    handled: Vec<bool>,
+    /// The name of every binding
+    names: Vec<String>,
+    /// Bindings that are a squad's fields or methods, and which squad
+    members: HashMap<usize, String>,
+    /// Parameters, loop variables and caught errors waiting for the block they belong to
+    header_names: Vec<(String, usize)>,
+    header_paren: Option<usize>,
+    header_closed: bool,
+    /// Inside `grind (bet i = 0; i < 10; ...)` the header's own name is already in use
+    header_visible: bool,
+    /// `bet` names that only exist once their statement is over, and the frame they go in
+    pending: Vec<(String, usize, usize)>,
+    paren_depth: usize,
+    switch_next: bool,
+}
+
+impl<'a> Scopes<'a> {
+    fn resolve(tokens: &'a [Token]) -> Self {
+        let significant = tokens.iter()
+            .enumerate()
+            .filter(|(_, token)| !matches!(token.token_type, TokenType::Whitespace(_) | TokenType::Comment(_)))
+            .map(|(index, _)| index)
+            .collect();
+        
+        let mut scopes = Self {
+            tokens,
+            significant,
+            frames: vec![Frame { kind: FrameKind::Block, names: HashMap::new(), squad: None }],
+            bindings: vec![None; tokens.len()],
+            handled: vec![false; tokens.len()],
+            names: Vec::new(),
+            members: HashMap::new(),
+            header_names: Vec::new(),
+            header_paren: None,
+            header_closed: false,
+            header_visible: false,
+            pending: Vec::new(),
+            paren_depth: 0,
+            switch_next: false,
+        };
+        
+        for position in 0..scopes.significant.len() {
+            scopes.visit(position);
+        }
+        scopes.flush_pending(0);
+        scopes
+    }
+    
+    fn visit(&mut self, position: usize) {
+        let index = self.significant[position];
+        let token_type = self.tokens[index].token_type.clone();
+        
+        // A loop or function header just closed - its names go in the block that follows,
+        // or in the current scope if there's no block
+        if self.header_closed && !matches!(token_type, TokenType::LeftBrace | TokenType::Colon)
+            && !(self.previous_is(position, &TokenType::Colon) && matches!(token_type, TokenType::Identifier(_)))
+        {
+            let names = std::mem::take(&mut self.header_names);
+            let frame = self.frames.len() - 1;
+            self.frames[frame].names.extend(names);
+            self.header_closed = false;
+        }
+        
+        match token_type {
+            TokenType::Flex => {
+                if let Some(name_index) = self.identifier_at(position + 1) {
+                    let binding = self.declare_now(name_index);
+                    self.mark(name_index, binding);
+                    self.note_member(binding);
+                }
+                self.header_paren = Some(self.paren_depth);
+            }
+            TokenType::Bet if !self.handled[index] => {
+                if let Some(name_index) = self.identifier_at(position + 1) {
+                    let name = self.name_of(name_index);
+                    let frame = self.frames.len() - 1;
+                    let binding = match self.frames[frame].names.get(&name) {
+                        Some(&existing) => existing,
+                        None => self.new_binding(&name),
+                    };
+                    self.mark(name_index, binding);
+                    self.note_member(binding);
+                    self.pending.push((name, binding, frame));
+                }
+            }
+            TokenType::Grind | TokenType::Highkey | TokenType::Caught => {
+                let declared = match token_type {
+                    TokenType::Caught => self.identifier_at(position + 2),
+                    // `grind (bet i = 0; ...)` declares its counter with a bet, `grind (i = 0; ...)` reuses one
+                    _ if self.is_at(position + 2, &TokenType::Bet) => {
+                        self.handled[self.significant[position + 2]] = true;
+                        self.header_visible = true;
+                        self.identifier_at(position + 3)
+                    }
+                    _ => self.identifier_at(position + 2).filter(|_| self.is_at(position + 3, &TokenType::In)),
+                };
+                if let Some(name_index) = declared {
+                    let name = self.name_of(name_index);
+                    let binding = self.new_binding(&name);
+                    self.mark(name_index, binding);
+                    self.header_names.push((name, binding));
+                }
+                self.header_paren = Some(self.paren_depth);
+            }
+            TokenType::Drama => {
+                let declares_type = self.identifier_at(position + 1).is_some_and(|index| self.name_of(index) == "type");
+                if let (true, Some(name_index)) = (declares_type, self.identifier_at(position + 2)) {
+                    self.handled[self.significant[position + 1]] = true;
+                    let binding = self.declare_now(name_index);
+                    self.mark(name_index, binding);
+                }
+            }
+            TokenType::Global | TokenType::Outer => {
+                let global = token_type == TokenType::Global;
+                let mut next = position + 1;
+                while let Some(name_index) = self.identifier_at(next) {
+                    let name = self.name_of(name_index);
+                    let binding = if global {
+                        self.global_binding(&name)
+                    } else {
+                        let below = self.frames.len() - 1;
+                        self.lookup(&name, below)
+                    };
+                    let frame = self.frames.len() - 1;
+                    self.frames[frame].names.insert(name, binding);
+                    self.mark(name_index, binding);
+                    
+                    if !self.is_at(next + 1, &TokenType::Comma) {
+                        break;
+                    }
+                    next += 2;
+                }
+            }
+            TokenType::VibeCheck => self.switch_next = true,
+            TokenType::LeftParen => {
+                if self.params_follow(position) {
+                    self.mark_params(position);
+                }
+                self.paren_depth += 1;
+            }
+            TokenType::RightParen => {
+                self.paren_depth = self.paren_depth.saturating_sub(1);
+                if self.header_paren == Some(self.paren_depth) {
+                    self.header_paren = None;
+                    self.header_closed = true;
+                    self.header_visible = false;
+                }
+            }
+            TokenType::LeftBrace => {
+                let kind = if self.switch_next && self.previous_is(position, &TokenType::RightParen) && self.paren_depth == 0 {
+                    self.switch_next = false;
+                    FrameKind::Switch
+                } else if self.starts_object(position) {
+                    FrameKind::Object
+                } else {
+                    FrameKind::Block
+                };
+                
+                let mut names = HashMap::new();
+                if self.header_closed && kind == FrameKind::Block {
+                    names.extend(std::mem::take(&mut self.header_names));
+                    self.header_closed = false;
+                }
+                let squad = self.squad_before(position);
+                self.frames.push(Frame { kind, names, squad });
+            }
+            TokenType::RightBrace if self.frames.len() > 1 => {
+                self.flush_pending(self.frames.len() - 1);
+                self.frames.pop();
+            }
+            TokenType::Newline | TokenType::Semicolon => self.flush_pending(self.frames.len() - 1),
+            TokenType::Identifier(name) if !self.handled[index] && self.is_reference(position, &name) => {
+                let in_header = self.header_names.iter().find(|(declared, _)| self.header_visible && *declared == name);
+                let binding = match in_header {
+                    Some(&(_, binding)) => binding,
+                    None => self.lookup(&name, self.frames.len()),
+                };
+                self.mark(index, binding);
+            }
+            _ => {}
+        }
+    }
+    
+    /// Is this identifier a name at all, rather than a type annotation, object key, field or case label?
+    fn is_reference(&self, position: usize, name: &str) -> bool {
+        // `person.name` - a field, whatever variables happen to share its name
+        if self.previous_is(position, &TokenType::Dot) || self.previous_is(position, &TokenType::QuestionDot) {
+            return false;
+        }
+        let kind = self.frames.last().map_or(FrameKind::Block, |frame| frame.kind);
+        let starts_entry = position == 0 || matches!(
+            self.tokens[self.significant[position - 1]].token_type,
+            TokenType::LeftBrace | TokenType::RightBrace | TokenType::Comma | TokenType::Newline | TokenType::Semicolon
+        );
+        
+        if kind != FrameKind::Object && self.previous_is(position, &TokenType::Colon) && TypeAnnotation::from_name(name).is_some() {
+            return false;
+        }
+        if kind == FrameKind::Object && starts_entry && self.is_at(position + 1, &TokenType::Colon) {
+            return false;
+        }
+        !(kind == FrameKind::Switch && starts_entry && (name == "case" || name == "default"))
+    }
+    
+    /// `flex name(` or a lambda's `flex(` - the parameters are declarations, not reads
+    fn params_follow(&self, position: usize) -> bool {
+        let named = position >= 2 && self.identifier_at(position - 1).is_some() && self.is_at(position - 2, &TokenType::Flex);
+        named || (position >= 1 && self.is_at(position - 1, &TokenType::Flex))
+    }
+    
+    fn mark_params(&mut self, open_paren: usize) {
+        let mut position = open_paren + 1;
+        loop {
+            // A `...rest` parameter's dots
+            if self.is_at(position, &TokenType::Ellipsis) {
+                position += 1;
+            }
+            let Some(name_index) = self.identifier_at(position) else {
+                break;
+            };
+            let name = self.name_of(name_index);
+            let binding = self.new_binding(&name);
+            self.mark(name_index, binding);
+            self.header_names.push((name, binding));
+            
+            // Skip a `: type` annotation
+            position += 1;
+            if self.is_at(position, &TokenType::Colon) {
+                position += 2;
+            }
+            if !self.is_at(position, &TokenType::Comma) {
+                break;
+            }
+            position += 1;
+        }
+    }
+    
+    /// A `{` where an expression is expected starts an object literal, anywhere else a block
+    fn starts_object(&self, position: usize) -> bool {
+        if position == 0 {
+            return false;
+        }
+        let in_switch = self.frames.last().is_some_and(|frame| frame.kind == FrameKind::Switch);
+        match &self.tokens[self.significant[position - 1]].token_type {
+            TokenType::Colon => !in_switch,
+            TokenType::Equal | TokenType::LeftParen | TokenType::LeftBracket | TokenType::QuestionBracket | TokenType::Comma |
+            TokenType::Vibe | TokenType::Bruh | TokenType::BruhBang | TokenType::Drama | TokenType::In |
+            TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash | TokenType::Percent | TokenType::TildeSlash |
+            TokenType::Ampersand | TokenType::Pipe | TokenType::Caret | TokenType::Tilde | TokenType::LessLess | TokenType::GreaterGreater |
+            TokenType::EqualEqual | TokenType::BangEqual | TokenType::Greater | TokenType::GreaterEqual |
+            TokenType::Less | TokenType::LessEqual | TokenType::And | TokenType::Or | TokenType::Bang | TokenType::FatArrow => true,
+            _ => false,
+        }
+    }
+    
+    /// `bet` declarations take effect at the end of their statement, so `bet x = x + 1` reads the outer x
+    fn flush_pending(&mut self, frame: usize) {
+        let (ready, waiting): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|(_, _, at)| *at >= frame);
+        self.pending = waiting;
+        for (name, binding, at) in ready {
+            if let Some(frame) = self.frames.get_mut(at) {
+                frame.names.insert(name, binding);
+            }
+        }
+    }
+    
+    fn declare_now(&mut self, name_index: usize) -> usize {
+        let name = self.name_of(name_index);
+        let frame = self.frames.len() - 1;
+        if let Some(&existing) = self.frames[frame].names.get(&name) {
+            return existing;
+        }
+        let binding = self.new_binding(&name);
+        self.frames[frame].names.insert(name, binding);
+        binding
+    }
+    
+    /// The binding `name` means in the frames below `below`. Names nobody declared yet
+    /// are globals - functions can read globals declared after them
+    fn lookup(&mut self, name: &str, below: usize) -> usize {
+        let found = self.frames[..below].iter().rev().find_map(|frame| frame.names.get(name).copied());
+        found.unwrap_or_else(|| self.global_binding(name))
+    }
+    
+    fn global_binding(&mut self, name: &str) -> usize {
+        if let Some(&binding) = self.frames[0].names.get(name) {
+            return binding;
+        }
+        if let Some((_, binding, _)) = self.pending.iter().find(|(pending, _, frame)| pending == name && *frame == 0) {
+            return *binding;
+        }
+        let binding = self.new_binding(name);
+        self.frames[0].names.insert(name.to_string(), binding);
+        binding
+    }
+    
+    /// `squad Name {` or `squad Name from Parent {` just before the brace at `position`
+    fn squad_before(&self, position: usize) -> Option<String> {
+        let is_word = |position: usize, word: &str| self.identifier_at(position).is_some_and(|index| self.name_of(index) == word);
+        let name_at = [2, 4].into_iter()
+            .filter(|&back| position >= back && is_word(position - back, "squad"))
+            .map(|back| position - back + 1)
+            .find(|&name| name + 1 == position || is_word(name + 1, "from"))?;
+        self.identifier_at(name_at).map(|index| self.name_of(index))
+    }
+    
+    fn note_member(&mut self, binding: usize) {
+        if let Some(squad) = self.frames.last().and_then(|frame| frame.squad.clone()) {
+            self.members.insert(binding, squad);
+        }
+    }
+    
+    fn new_binding(&mut self, name: &str) -> usize {
+        self.names.push(name.to_string());
+        self.names.len() - 1
+    }
+    
+    fn mark(&mut self, index: usize, binding: usize) {
+        self.bindings[index] = Some(binding);
+        self.handled[index] = true;
+    }
+    
+    fn identifier_at(&self, position: usize) -> Option<usize> {
+        let index = *self.significant.get(position)?;
+        matches!(self.tokens[index].token_type, TokenType::Identifier(_)).then_some(index)
+    }
+    
+    fn name_of(&self, index: usize) -> String {
+        match &self.tokens[index].token_type {
+            TokenType::Identifier(name) => name.clone(),
+            _ => String::new(),
+        }
+    }
+    
+    fn is_at(&self, position: usize, token_type: &TokenType) -> bool {
+        self.significant.get(position).is_some_and(|&index| self.tokens[index].token_type == *token_type)
+    }
+    
+    fn previous_is(&self, position: usize, token_type: &TokenType) -> bool {
+        position > 0 && self.is_at(position - 1, token_type)
+    }
+}
@@ -0,0 +1,116 @@
+//! ZLang Replay - `--record` writes down every random(), spill() and clock reading a run gets 📼
+//! and `--replay` hands the exact same ones back, so a run that only fails sometimes fails every time
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::ast::Literal;
+use crate::error::ZLangError;
+use crate::json;
+
+/// What the first line of a recording says, bumped if the format ever changes
+const HEADER: &str = "{\"zlang_replay\": 1, \"events\": [";
+
+#[derive(Debug, Clone)]
+struct Event {
+    // "random", "input" or "clock"
+    kind: String,
+    value: Literal,
+}
+
+/// The run's outside inputs in the order it asked for them. Clones share the same events,
+/// so the host can still read a recording after the interpreter is done with it
+#[derive(Debug, Clone)]
+pub struct Tape {
+    events: Rc<RefCell<VecDeque<Event>>>,
+    replaying: bool,
+}
+
+impl Tape {
+    /// An empty tape that writes down everything the run gets
+    pub fn recording() -> Self {
+        Self { events: Rc::default(), replaying: false }
+    }
+    
+    /// A tape that plays back a recording `to_json` wrote
+    pub fn replaying(text: &str) -> Result<Self, ZLangError> {
+        let not_a_recording = || ZLangError::new("That's not a recording bestie, make one with --record 📼");
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some(HEADER) {
+            return Err(not_a_recording());
+        }
+        
+        let mut events = VecDeque::new();
+        for line in lines {
+            if line == "]}" {
+                return Ok(Self { events: Rc::new(RefCell::new(events)), replaying: true });
+            }
+            let event = line.strip_suffix(',').unwrap_or(line)
+                .strip_prefix('{')
+                .and_then(|line| line.strip_suffix('}'))
+                .and_then(parse_event)
+                .ok_or_else(|| ZLangError::new(&format!("This line of the recording doesn't make sense: {} 📼", line)))?;
+            events.push_back(event);
+        }
+        Err(not_a_recording())
+    }
+    
+    /// The recording as JSON, one event per line
+    pub fn to_json(&self) -> String {
+        let events: Vec<String> = self.events.borrow().iter()
+            .map(|event| format!("  {{{}: {}}}", json::string(&event.kind), json::value(&event.value)))
+            .collect();
+        format!("{}\n{}\n]}}\n", HEADER, events.join(",\n"))
+    }
+    
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+    
+    /// Events recorded so far, or still waiting to be replayed
+    pub fn len(&self) -> usize {
+        self.events.borrow().len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// The run wants a `kind` value. Recording, that's whatever `fresh` comes up with, written down.
+    /// Replaying, it's the next one on the tape and `fresh` never gets called
+    pub fn take(&self, kind: &str, fresh: impl FnOnce() -> Literal) -> Result<Literal, ZLangError> {
+        if !self.replaying {
+            let value = fresh();
+            self.events.borrow_mut().push_back(Event { kind: kind.to_string(), value: value.clone() });
+            return Ok(value);
+        }
+        
+        match self.events.borrow_mut().pop_front() {
+            Some(event) if event.kind == kind => Ok(event.value),
+            Some(event) => Err(ZLangError::new(&format!(
+                "The replay went off script, the run wanted {} but the recording has {} next. Did the script change? 📼",
+                kind, event.kind
+            ))),
+            None => Err(ZLangError::new(&format!(
+                "The recording ran out, the run wanted one more {}. Did the script change? 📼",
+                kind
+            ))),
+        }
+    }
+}
+
+/// `"kind": value` with the braces already off - the value is a number, a string or null
+fn parse_event(text: &str) -> Option<Event> {
+    let (kind, rest) = json::parse_string(text)?;
+    let value = rest.trim_start().strip_prefix(':')?.trim();
+    let value = match value {
+        "null" => Literal::Nil,
+        _ if value.starts_with('"') => match json::parse_string(value)? {
+            (text, "") => Literal::String(text),
+            _ => return None,
+        },
+        _ => Literal::Number(value.parse().ok()?),
+    };
+    Some(Event { kind, value })
+}
@@ -0,0 +1,419 @@
+//! ZLang Resolver - Static checks that run between parsing and interpreting
+//! Catches the classic beginner L's (typos, reading before declaring) before any code runs 🔍
+
+use std::collections::HashSet;
+use crate::ast::{Expr, Stmt};
+use crate::error::ZLangError;
+use crate::messages;
+use crate::pragmas::{Pragmas, Rule};
+use crate::similar;
+
+/// Everything the resolver found, split by how loud we should be about it
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    pub errors: Vec<ZLangError>,
+    pub warnings: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Every error as one, a line each, under the first one's code - None when there aren't any
+    pub fn error(&self) -> Option<ZLangError> {
+        let first = self.errors.first()?;
+        let lines: Vec<String> = self.errors.iter().map(ToString::to_string).collect();
+        Some(ZLangError { message: lines.join("\n"), ..first.clone() })
+    }
+}
+
+pub struct Resolver {
+    // Names declared in each lexical scope, innermost last
+    scopes: Vec<HashSet<String>>,
+    // Every name declared anywhere in the program (or already known, e.g. from earlier REPL lines)
+    program_names: HashSet<String>,
+    // Names declared so far in program order, for use-before-define checks
+    seen: HashSet<String>,
+    function_depth: usize,
+    diagnostics: Diagnostics,
+    // `// zlang-ignore` comments in the source, whose warnings stay quiet
+    pragmas: Pragmas,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashSet::new()],
+            program_names: HashSet::new(),
+            seen: HashSet::new(),
+            function_depth: 0,
+            diagnostics: Diagnostics::default(),
+            pragmas: Pragmas::default(),
+        }
+    }
+    
+    /// Seed the resolver with names that already exist at runtime (REPL history)
+    pub fn with_known_names(names: impl IntoIterator<Item = String>) -> Self {
+        let mut resolver = Self::new();
+        for name in names {
+            resolver.program_names.insert(name.clone());
+            resolver.seen.insert(name);
+        }
+        resolver
+    }
+    
+    /// Keep quiet about the warnings the source's `// zlang-ignore` comments ask to
+    pub fn with_pragmas(mut self, pragmas: Pragmas) -> Self {
+        self.pragmas = pragmas;
+        self
+    }
+    
+    pub fn resolve(mut self, statements: &[Stmt]) -> Diagnostics {
+        for stmt in statements {
+            collect_declarations(stmt, &mut self.program_names);
+        }
+        
+        for stmt in statements {
+            self.resolve_stmt(stmt);
+        }
+        
+        self.diagnostics
+    }
+    
+    fn resolve_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) => self.resolve_expr(expr),
+            Stmt::VarDeclaration { name, initializer, .. } => {
+                if let Some(init) = initializer {
+                    self.resolve_expr(init);
+                }
+                self.declare(name);
+            }
+            Stmt::Block(statements) => self.resolve_block(statements, &[]),
+            Stmt::If { condition, then_branch, else_branch } => {
+                self.resolve_expr(condition);
+                self.resolve_stmt(then_branch);
+                if let Some(else_stmt) = else_branch {
+                    self.resolve_stmt(else_stmt);
+                }
+            }
+            Stmt::While { condition, body, else_branch } => {
+                self.resolve_expr(condition);
+                self.resolve_stmt(body);
+                if let Some(else_stmt) = else_branch {
+                    self.resolve_stmt(else_stmt);
+                }
+            }
+            Stmt::For { variable, iterable, body, else_branch, .. } => {
+                self.resolve_expr(iterable);
+                self.resolve_block(std::slice::from_ref(body.as_ref()), std::slice::from_ref(variable));
+                if let Some(else_stmt) = else_branch {
+                    self.resolve_stmt(else_stmt);
+                }
+            }
+            Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+                self.scopes.push(HashSet::new());
+                if let Some(initializer) = initializer {
+                    self.resolve_stmt(initializer);
+                }
+                if let Some(condition) = condition {
+                    self.resolve_expr(condition);
+                }
+                self.resolve_stmt(body);
+                if let Some(increment) = increment {
+                    self.resolve_expr(increment);
+                }
+                self.scopes.pop();
+                // Runs after the loop, when its counter is gone
+                if let Some(else_stmt) = else_branch {
+                    self.resolve_stmt(else_stmt);
+                }
+            }
+            Stmt::Switch { expr, cases, default } => {
+                self.resolve_expr(expr);
+                for case in cases {
+                    case.exprs().for_each(|value| self.resolve_expr(value));
+                    self.resolve_block(&case.body, &[]);
+                }
+                if let Some(statements) = default {
+                    self.resolve_block(statements, &[]);
+                }
+            }
+            Stmt::Try { try_block, catch_clauses, finally_block } => {
+                self.resolve_block(try_block, &[]);
+                for clause in catch_clauses {
+                    if let Some(error_type) = &clause.error_type {
+                        self.check_drama_type(error_type);
+                    }
+                    self.resolve_block(&clause.body, std::slice::from_ref(&clause.variable));
+                }
+                if let Some(statements) = finally_block {
+                    self.resolve_block(statements, &[]);
+                }
+            }
+            Stmt::Function { name, params, body, .. } => {
+                self.declare(name);
+                self.resolve_function(name, params, body);
+            }
+            Stmt::Squad { name, parent, body } => {
+                if let Some(parent) = parent {
+                    self.check_read(parent);
+                }
+                self.declare(name);
+                
+                // Defaults and methods see the fields as variables, methods are only reached through a dot
+                self.scopes.push(HashSet::new());
+                for member in body {
+                    match member {
+                        Stmt::Function { name, params, body, .. } => self.resolve_function(name, params, body),
+                        field => self.resolve_stmt(field),
+                    }
+                }
+                self.scopes.pop();
+            }
+            Stmt::Return(expr) => {
+                if let Some(expr) = expr {
+                    self.resolve_expr(expr);
+                }
+            }
+            Stmt::DramaType { name, parent } => {
+                if let Some(parent) = parent {
+                    self.check_drama_type(parent);
+                }
+                self.declare(name);
+            }
+            Stmt::Needs { condition, .. } => self.resolve_expr(condition),
+            Stmt::Global(names) | Stmt::Outer(names) => {
+                for name in names {
+                    self.current_scope().insert(name.clone());
+                    self.seen.insert(name.clone());
+                }
+            }
+            Stmt::Pull { alias, line, .. } => {
+                if self.scopes.len() > 1 || self.function_depth > 0 {
+                    self.diagnostics.errors.push(messages::PULL_NOT_TOP_LEVEL.error(&[line]));
+                }
+                if let Some(alias) = alias {
+                    self.declare(alias);
+                }
+            }
+            Stmt::Break | Stmt::Continue => {}
+        }
+    }
+    
+    fn resolve_function(&mut self, name: &str, params: &[String], body: &[Stmt]) {
+        let mut unique = HashSet::new();
+        for param in params {
+            if !unique.insert(param) {
+                self.diagnostics.errors.push(messages::DUPLICATE_PARAMETER.error(&[&name, param]));
+            }
+        }
+        
+        self.function_depth += 1;
+        self.resolve_block(body, params);
+        self.function_depth -= 1;
+    }
+    
+    fn resolve_block(&mut self, statements: &[Stmt], bindings: &[String]) {
+        self.scopes.push(bindings.iter().cloned().collect());
+        for name in bindings {
+            self.seen.insert(name.clone());
+        }
+        
+        for stmt in statements {
+            self.resolve_stmt(stmt);
+        }
+        
+        self.scopes.pop();
+    }
+    
+    fn resolve_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(_) => {}
+            Expr::Variable(name) => self.check_read(name),
+            Expr::Assign { name, value } => {
+                self.resolve_expr(value);
+                if !self.program_names.contains(name) {
+                    self.diagnostics.errors.push(messages::ASSIGN_UNDECLARED.error(&[name, &self.did_you_mean(name)]));
+                }
+            }
+            Expr::Binary { left, right, .. } => {
+                self.resolve_expr(left);
+                self.resolve_expr(right);
+            }
+            Expr::Unary { right, .. } => self.resolve_expr(right),
+            Expr::Call { callee, arguments, .. } => {
+                // Function names are checked when the call happens, only the arguments are reads here
+                if !matches!(callee.as_ref(), Expr::Variable(_)) {
+                    self.resolve_expr(callee);
+                }
+                for argument in arguments {
+                    self.resolve_expr(argument);
+                }
+            }
+            Expr::Array(elements) => {
+                for element in elements {
+                    self.resolve_expr(element);
+                }
+            }
+            Expr::Object(pairs) => {
+                for (_, value) in pairs {
+                    self.resolve_expr(value);
+                }
+            }
+            Expr::Index { object, index, .. } | Expr::Set { object, value: index, .. } | Expr::Range { start: object, end: index, .. } => {
+                self.resolve_expr(object);
+                self.resolve_expr(index);
+            }
+            Expr::Get { object, .. } | Expr::Propagate { value: object, .. } | Expr::Spread(object) => self.resolve_expr(object),
+            Expr::Conditional { condition, then_branch, else_branch } => {
+                self.resolve_expr(condition);
+                self.resolve_expr(then_branch);
+                self.resolve_expr(else_branch);
+            }
+            Expr::Function { params, body, .. } => {
+                // A lambda's names aren't statements collect_declarations can see, they join here
+                self.program_names.extend(params.iter().cloned());
+                for stmt in body.iter() {
+                    collect_declarations(stmt, &mut self.program_names);
+                }
+                
+                let mut unique = HashSet::new();
+                for param in params {
+                    if !unique.insert(param) {
+                        self.diagnostics.errors.push(messages::DUPLICATE_LAMBDA_PARAMETER.error(&[param]));
+                    }
+                }
+                
+                self.function_depth += 1;
+                self.resolve_block(body, params);
+                self.function_depth -= 1;
+            }
+        }
+    }
+    
+    fn declare(&mut self, name: &str) {
+        if !self.current_scope().insert(name.to_string()) && !self.pragmas.ignores(Rule::Redeclared, name) {
+            self.diagnostics.warnings.push(format!(
+                "'{}' is declared twice in the same scope, the second one replaces the first 🔁",
+                name
+            ));
+        }
+        self.seen.insert(name.to_string());
+    }
+    
+    fn check_read(&mut self, name: &str) {
+        if !self.program_names.contains(name) {
+            self.diagnostics.errors.push(messages::NEVER_DECLARED.error(&[&name, &self.did_you_mean(name)]));
+        } else if self.function_depth == 0 && !self.seen.contains(name) && !self.pragmas.ignores(Rule::UsedEarly, name) {
+            // Inside functions the caller may declare it first, so only top-level code is checked
+            let warning = format!("'{}' is used before it's declared, that's gonna be undefined 👀", name);
+            if !self.diagnostics.warnings.contains(&warning) {
+                self.diagnostics.warnings.push(warning);
+            }
+        }
+    }
+    
+    fn check_drama_type(&mut self, name: &str) {
+        if name != "Drama" && !self.program_names.contains(name) {
+            self.diagnostics.errors.push(messages::UNDECLARED_DRAMA_TYPE.error(&[&name]));
+        }
+    }
+    
+    fn did_you_mean(&self, name: &str) -> String {
+        similar::did_you_mean(name, self.program_names.iter().map(String::as_str))
+    }
+    
+    fn current_scope(&mut self) -> &mut HashSet<String> {
+        self.scopes.last_mut().expect("resolver always has a global scope")
+    }
+}
+
+/// Gather every name the program declares, wherever it is declared
+pub(crate) fn collect_declarations(stmt: &Stmt, names: &mut HashSet<String>) {
+    match stmt {
+        Stmt::VarDeclaration { name, .. } => {
+            names.insert(name.clone());
+        }
+        Stmt::Block(statements) => {
+            for stmt in statements {
+                collect_declarations(stmt, names);
+            }
+        }
+        Stmt::If { then_branch, else_branch, .. } => {
+            collect_declarations(then_branch, names);
+            if let Some(else_stmt) = else_branch {
+                collect_declarations(else_stmt, names);
+            }
+        }
+        Stmt::While { body, else_branch, .. } => {
+            collect_declarations(body, names);
+            if let Some(else_stmt) = else_branch {
+                collect_declarations(else_stmt, names);
+            }
+        }
+        Stmt::For { variable, body, else_branch, .. } => {
+            names.insert(variable.clone());
+            collect_declarations(body, names);
+            if let Some(else_stmt) = else_branch {
+                collect_declarations(else_stmt, names);
+            }
+        }
+        Stmt::ClassicFor { initializer, body, else_branch, .. } => {
+            if let Some(initializer) = initializer {
+                collect_declarations(initializer, names);
+            }
+            collect_declarations(body, names);
+            if let Some(else_stmt) = else_branch {
+                collect_declarations(else_stmt, names);
+            }
+        }
+        Stmt::Switch { cases, default, .. } => {
+            for stmt in cases.iter().flat_map(|case| &case.body).chain(default.iter().flatten()) {
+                collect_declarations(stmt, names);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            for stmt in try_block.iter().chain(finally_block.iter().flatten()) {
+                collect_declarations(stmt, names);
+            }
+            for clause in catch_clauses {
+                names.insert(clause.variable.clone());
+                for stmt in &clause.body {
+                    collect_declarations(stmt, names);
+                }
+            }
+        }
+        Stmt::DramaType { name, .. } => {
+            names.insert(name.clone());
+        }
+        Stmt::Squad { name, parent, body } => {
+            names.insert(name.clone());
+            // Every method has its instance as `me` and `this`, and one of a squad that comes from
+            // another can reach the parent's version as `og`
+            names.extend(["me".to_string(), "this".to_string()]);
+            if parent.is_some() {
+                names.insert("og".to_string());
+            }
+            for stmt in body {
+                collect_declarations(stmt, names);
+            }
+        }
+        Stmt::Function { name, params, body, .. } => {
+            names.insert(name.clone());
+            names.extend(params.iter().cloned());
+            for stmt in body {
+                collect_declarations(stmt, names);
+            }
+        }
+        Stmt::Global(declared) | Stmt::Outer(declared) => names.extend(declared.iter().cloned()),
+        Stmt::Pull { alias: Some(alias), .. } => {
+            names.insert(alias.clone());
+        }
+        Stmt::Expression(_) | Stmt::Print(_) | Stmt::Inspect(_) | Stmt::Throw(_) | Stmt::Return(_) | Stmt::Needs { .. } | Stmt::Break | Stmt::Continue
+        | Stmt::Pull { alias: None, .. } => {}
+    }
+}
@@ -0,0 +1,42 @@
+//! ZLang Rng - where `random()` gets its numbers, seedable so a simulation can come out the same twice 🎲
+//! SplitMix64: tiny, quick and plenty random for games and statistics, just not for secrets
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_clock()
+    }
+}
+
+impl Rng {
+    /// Different numbers every run
+    pub fn from_clock() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        Self::seeded(nanos)
+    }
+    
+    /// The same numbers every time for the same seed
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    
+    /// From 0 up to, but not including, 1
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill an f64's mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
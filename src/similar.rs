@@ -0,0 +1,48 @@
+//! ZLang Similar - How alike two strings are, for `levenshtein()` and our "did you mean" hints 🔍
+//! User scripts and the error messages share this, so a suggestion is always one levenshtein() would back up
+
+use crate::messages;
+
+/// How many single-character inserts, deletes or swaps turn `a` into `b`. Counts characters, not bytes
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // One row of the table at a time, previous[j] is the distance from what's been read of `a` to b[..j]
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// 1.0 for identical strings down to 0.0 for nothing in common, scaled by the longer one's length
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+/// The candidate `name` was most likely meant to be - close enough to be a typo, not just any
+/// short word. Ties go to whichever sorts first, so the same typo always gets the same hint
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let allowed = (name.chars().count() / 3).max(1);
+    candidates.into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= allowed)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// " Did you mean 'x'?" to tack onto an error, or nothing when no candidate is close
+pub fn did_you_mean<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    closest(name, candidates).map_or(String::new(), |candidate| messages::DID_YOU_MEAN.render(&[&candidate]))
+}
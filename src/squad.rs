@@ -0,0 +1,147 @@
+//! ZLang Squads - `squad Point { bet x = 0  flex move(dx) { ... } }` bundles fields with the methods that use them 👥
+//! `Point(1, 2)` makes an instance and `p.move(3)` runs a method on it - instances are shared like heaps, not copied
+//! `squad Dog from Animal` starts from Animal's fields and methods, and a method Dog overrides can still reach Animal's as `og`
+//! Inside a method the instance itself is `me` (or `this`), so `me.x = x` sets a field a parameter hides
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::ast::{Expr, Function, Literal};
+use crate::environment::{Closure, Fields};
+
+/// What `squad Name { ... }` declares - calling it makes an instance
+#[derive(Debug, PartialEq)]
+pub struct Squad {
+    pub name: String,
+    // The squad it comes from, whose methods are looked up when it doesn't have its own
+    pub parent: Option<Arc<Squad>>,
+    // Each field with what it starts as when the call leaves it out, in declaration order - the
+    // parent's first. Worked out fresh for every instance, so two instances never share a default's heap or array
+    pub fields: Vec<(String, Option<Expr>)>,
+    // Only the methods it declares itself
+    pub methods: HashMap<String, Function>,
+    // Where the squad was declared, when that's inside a function or block - defaults are worked out there
+    pub closure: Option<Closure>,
+}
+
+impl Squad {
+    /// The method `name` means here: its own, or else the nearest one up the `from` chain. Comes
+    /// with the parent of the squad that declared it, which is where that method's `og` looks
+    pub fn lookup(&self, name: &str) -> Option<(&Function, Option<&Arc<Squad>>)> {
+        match self.methods.get(name) {
+            Some(method) => Some((method, self.parent.as_ref())),
+            None => self.parent.as_ref()?.lookup(name),
+        }
+    }
+    
+    pub fn method(&self, name: &str) -> Option<&Function> {
+        self.lookup(name).map(|(method, _)| method)
+    }
+    
+    /// The squads it comes from, nearest first - the order methods are looked up in after its own
+    pub fn ancestors(&self) -> impl Iterator<Item = &Arc<Squad>> {
+        std::iter::successors(self.parent.as_ref(), |squad| squad.parent.as_ref())
+    }
+    
+    /// Every field and method name, inherited ones included, for "did you mean" hints
+    pub fn member_names(&self) -> impl Iterator<Item = &str> {
+        let methods = std::iter::once(&self.methods)
+            .chain(self.ancestors().map(|squad| &squad.methods))
+            .flat_map(|methods| methods.keys().map(String::as_str));
+        self.fields.iter().map(|(name, _)| name.as_str()).chain(methods)
+    }
+}
+
+/// One `Point(1, 2)` - its squad and its own fields
+#[derive(Clone)]
+pub struct Instance {
+    squad: Arc<Squad>,
+    fields: Fields,
+    // Set on the `og` a method sees: the same instance, looking for methods from this squad up
+    methods_from: Option<Arc<Squad>>,
+}
+
+// An instance can hold itself, so neither of these looks at its fields
+impl std::fmt::Debug for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Instance({})", self.squad.name)
+    }
+}
+
+impl PartialEq for Instance {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
+}
+
+thread_local! {
+    // Instances part way through being shown, so one that holds itself comes out as `<Point>` inside
+    static SHOWING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// `Point {x: 1, y: 2}`, fields in the order the squad declares them
+impl std::fmt::Display for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id();
+        if SHOWING.with(|showing| showing.borrow().contains(&id)) {
+            return write!(f, "<{}>", self.squad.name);
+        }
+        
+        SHOWING.with(|showing| showing.borrow_mut().push(id));
+        let fields: Vec<String> = self.squad.fields.iter()
+            .map(|(name, _)| format!("{}: {}", name, self.field(name).unwrap_or(Literal::Nil)))
+            .collect();
+        SHOWING.with(|showing| showing.borrow_mut().pop());
+        write!(f, "{} {{{}}}", self.squad.name, fields.join(", "))
+    }
+}
+
+impl Instance {
+    pub fn new(squad: Arc<Squad>, fields: Fields) -> Self {
+        Self { squad, fields, methods_from: None }
+    }
+    
+    /// This instance as `og` - its fields, but methods looked up from `parent` on
+    pub fn as_parent(&self, parent: Arc<Squad>) -> Self {
+        Self { methods_from: Some(parent), ..self.clone() }
+    }
+    
+    /// This instance as `me` - the real thing, whose methods are its own squad's even inside a
+    /// method `og` called, so a method the child overrides is the one that runs
+    pub fn itself(&self) -> Self {
+        Self { methods_from: None, ..self.clone() }
+    }
+    
+    /// The method `p.name(...)` calls, and where its `og` looks - see `Squad::lookup`
+    pub fn method(&self, name: &str) -> Option<(&Function, Option<&Arc<Squad>>)> {
+        self.methods_from.as_ref().unwrap_or(&self.squad).lookup(name)
+    }
+    
+    pub fn squad(&self) -> &Arc<Squad> {
+        &self.squad
+    }
+    
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+    
+    pub fn field(&self, name: &str) -> Option<Literal> {
+        self.fields.get(name)
+    }
+    
+    /// `p.name = value`, false when p has no field called that
+    pub fn set_field(&self, name: &str, value: Literal) -> bool {
+        self.fields.set(name, value)
+    }
+    
+    /// Every field's value, in declaration order
+    pub fn values(&self) -> Vec<Literal> {
+        self.squad.fields.iter().filter_map(|(name, _)| self.field(name)).collect()
+    }
+    
+    /// Tells instances apart without looking inside, for walks that could loop back to the same one
+    pub fn id(&self) -> usize {
+        self.fields.id()
+    }
+}
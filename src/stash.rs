@@ -0,0 +1,190 @@
+//! ZLang Stash - A tiny key-value store that outlives the program, for high scores and notes 🐿️
+//! `stash("key", value)` puts something away and `unstash("key")` gets it back on a later run
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::ast::{Expr, Literal, Stmt, UnaryOp};
+use crate::error::ZLangError;
+use crate::lexer::Lexer;
+use crate::money;
+use crate::parser::Parser;
+
+/// The CLI's stash, in the home directory next to `.zlangrc`
+pub const FILE_NAME: &str = ".zlang_stash";
+/// Keys one stash holds, and how big a value can get written out as source
+pub const MAX_KEYS: usize = 100;
+pub const MAX_VALUE: usize = 64 * 1024;
+
+/// Where `stash()` puts things. Without one, an interpreter keeps them in memory for as long as it lives
+pub trait Stash {
+    fn get(&mut self, key: &str) -> Result<Option<Literal>, ZLangError>;
+    fn set(&mut self, key: &str, value: Literal) -> Result<(), ZLangError>;
+    fn remove(&mut self, key: &str) -> Result<(), ZLangError>;
+}
+
+/// Keeps values in memory. Clones share the same values, so a host can hand one out per run
+/// and still see what the last run left behind
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStash {
+    values: Arc<Mutex<HashMap<String, Literal>>>,
+}
+
+impl Stash for MemoryStash {
+    fn get(&mut self, key: &str) -> Result<Option<Literal>, ZLangError> {
+        let values = self.values.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(values.get(key).cloned())
+    }
+    
+    fn set(&mut self, key: &str, value: Literal) -> Result<(), ZLangError> {
+        let mut values = self.values.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        check_room(&values, key)?;
+        values.insert(key.to_string(), value);
+        Ok(())
+    }
+    
+    fn remove(&mut self, key: &str) -> Result<(), ZLangError> {
+        let mut values = self.values.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        values.remove(key);
+        Ok(())
+    }
+}
+
+/// One file holding every key as a single ZLang object literal. Read fresh on every `unstash`,
+/// so two programs running one after the other see each other's values
+#[derive(Debug, Clone)]
+pub struct FileStash {
+    path: PathBuf,
+}
+
+impl FileStash {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+    
+    /// `~/.zlang_stash`, or one in the current directory when there's no home to speak of
+    pub fn in_home() -> Self {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        Self::new(home.map(PathBuf::from).unwrap_or_default().join(FILE_NAME))
+    }
+    
+    fn load(&self) -> Result<HashMap<String, Literal>, ZLangError> {
+        let source = match fs::read_to_string(&self.path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(ZLangError::new(&format!("Couldn't read {}: {} 📂", self.path.display(), e))),
+        };
+        parse(&source).map_err(|e| ZLangError::new(&format!("{} is messed up ({}), delete it to start over 🗑️", self.path.display(), e)))
+    }
+    
+    fn save(&self, values: HashMap<String, Literal>) -> Result<(), ZLangError> {
+        fs::write(&self.path, Literal::Object(values).to_source() + "\n")
+            .map_err(|e| ZLangError::new(&format!("Couldn't save to {}: {} 💾", self.path.display(), e)))
+    }
+}
+
+impl Stash for FileStash {
+    fn get(&mut self, key: &str) -> Result<Option<Literal>, ZLangError> {
+        Ok(self.load()?.remove(key))
+    }
+    
+    fn set(&mut self, key: &str, value: Literal) -> Result<(), ZLangError> {
+        let mut values = self.load()?;
+        check_room(&values, key)?;
+        values.insert(key.to_string(), value);
+        self.save(values)
+    }
+    
+    fn remove(&mut self, key: &str) -> Result<(), ZLangError> {
+        let mut values = self.load()?;
+        if values.remove(key).is_some() {
+            self.save(values)?;
+        }
+        Ok(())
+    }
+}
+
+/// A stash per playground session, shared between the server's connections
+#[derive(Debug, Default)]
+pub struct Stashes {
+    stashes: Mutex<HashMap<String, MemoryStash>>,
+}
+
+impl Stashes {
+    /// Sessions remembered at once, past this an old one makes room
+    const MAX_SESSIONS: usize = 10_000;
+    
+    /// The session's stash, a fresh one the first time it's asked for
+    pub fn for_session(&self, session: &str) -> MemoryStash {
+        let mut stashes = self.stashes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if stashes.len() >= Self::MAX_SESSIONS && !stashes.contains_key(session) {
+            if let Some(evicted) = stashes.keys().next().cloned() {
+                stashes.remove(&evicted);
+            }
+        }
+        stashes.entry(session.to_string()).or_default().clone()
+    }
+}
+
+fn check_room(values: &HashMap<String, Literal>, key: &str) -> Result<(), ZLangError> {
+    if values.len() >= MAX_KEYS && !values.contains_key(key) {
+        return Err(ZLangError::new(&format!("The stash is full ({} keys), unstash some clutter first 🐿️", MAX_KEYS)));
+    }
+    Ok(())
+}
+
+/// The object literal a stash file holds. Only plain values make it in - it's read without
+/// running anything, a call or a variable in there gets turned away
+fn parse(source: &str) -> Result<HashMap<String, Literal>, ZLangError> {
+    let statements = Parser::new(Lexer::new(&format!("({})", source.trim())).tokenize()?).parse()?;
+    match statements.as_slice() {
+        [Stmt::Expression(expr)] => match plain_value(expr) {
+            Some(Literal::Object(values)) => Ok(values),
+            Some(_) => Err(ZLangError::new("expected one object")),
+            None => Err(ZLangError::new("only plain values belong in there")),
+        },
+        _ => Err(ZLangError::new("expected one object")),
+    }
+}
+
+/// The first part of `value` that a stash file couldn't hold - a function, a squad, an instance, a
+/// heap or a queue, anywhere inside it. None when it's plain all the way down, the same values
+/// `parse` lets back in
+pub fn unstashable(value: &Literal) -> Option<&Literal> {
+    match value {
+        Literal::Number(_) | Literal::String(_) | Literal::Boolean(_) | Literal::Nil | Literal::Money(_) => None,
+        Literal::Array(items) => items.iter().find_map(unstashable),
+        Literal::Object(values) => values.values().find_map(unstashable),
+        other => Some(other),
+    }
+}
+
+fn plain_value(expr: &Expr) -> Option<Literal> {
+    match expr {
+        Expr::Literal(literal) => Some(literal.clone()),
+        Expr::Array(items) => items.iter().map(plain_value).collect::<Option<_>>().map(Literal::Array),
+        Expr::Object(pairs) => pairs.iter()
+            .map(|(key, value)| plain_value(value).map(|value| (key.clone(), value)))
+            .collect::<Option<_>>()
+            .map(Literal::Object),
+        Expr::Unary { operator: UnaryOp::Minus, right } => match plain_value(right)? {
+            Literal::Number(n) => Some(Literal::Number(-n)),
+            _ => None,
+        },
+        // How money writes itself out, `money("12.50")`
+        Expr::Call { callee, arguments, .. } => match (callee.as_ref(), arguments.as_slice()) {
+            (Expr::Variable(name), [Expr::Literal(Literal::String(amount))]) if name == "money" => money::parse(amount).map(Literal::Money),
+            _ => None,
+        },
+        // The values that print as a bare word, there's no literal to write them as
+        Expr::Variable(name) => match name.as_str() {
+            "nil" => Some(Literal::Nil),
+            "inf" => Some(Literal::Number(f64::INFINITY)),
+            "NaN" => Some(Literal::Number(f64::NAN)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
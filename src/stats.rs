@@ -0,0 +1,139 @@
+//! ZLang Stats - Counting how hard your code is flexing
+//! Keyword counts, function sizes and nesting depth, straight from the tokens and AST 📊
+
+use std::collections::HashMap;
+use crate::ast::Stmt;
+use crate::error::ZLangError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::KEYWORDS;
+
+#[derive(Debug, Clone)]
+pub struct Stats {
+    /// How often each keyword shows up, most used first
+    pub keyword_counts: Vec<(&'static str, usize)>,
+    /// Every function and how many statements its body has, in declaration order
+    pub function_lengths: Vec<(String, usize)>,
+    pub max_nesting: usize,
+    /// Share of all the Gen Z keywords this program uses at least once, 0 to 100
+    pub slang_score: usize,
+}
+
+pub fn analyze(source: &str) -> Result<Stats, ZLangError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for token in &tokens {
+        if let Some(keyword) = token.token_type.keyword() {
+            *counts.entry(keyword).or_insert(0) += 1;
+        }
+    }
+    
+    // Every token is still needed by the parser, so count first and parse after
+    let statements = Parser::new(tokens).parse()?;
+    
+    let mut function_lengths = Vec::new();
+    collect_functions(&statements, &mut function_lengths);
+    
+    let slang_score = counts.len() * 100 / KEYWORDS.len();
+    let mut keyword_counts: Vec<_> = counts.into_iter().collect();
+    keyword_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    
+    Ok(Stats {
+        keyword_counts,
+        function_lengths,
+        max_nesting: nesting_depth(&statements),
+        slang_score,
+    })
+}
+
+/// Vibe-check a slang score, for the summary line
+pub fn slang_rating(score: usize) -> &'static str {
+    match score {
+        0..=19 => "giving corporate email 💼",
+        20..=39 => "mid, but we see you trying 😐",
+        40..=59 => "lowkey fluent 😎",
+        60..=79 => "certified yapper 🗣️",
+        _ => "main character energy, no cap 👑",
+    }
+}
+
+fn collect_functions(statements: &[Stmt], lengths: &mut Vec<(String, usize)>) {
+    for stmt in statements {
+        if let Stmt::Function { name, body, .. } = stmt {
+            lengths.push((name.clone(), count_statements(body)));
+        }
+        for_each_body(stmt, |body| collect_functions(body, lengths));
+    }
+}
+
+fn count_statements(statements: &[Stmt]) -> usize {
+    statements
+        .iter()
+        .map(|stmt| {
+            let mut nested = 0;
+            for_each_body(stmt, |body| nested += count_statements(body));
+            // A `{ ... }` is just a wrapper, only what's inside it counts
+            if matches!(stmt, Stmt::Block(_)) { nested } else { 1 + nested }
+        })
+        .sum()
+}
+
+/// How many bodies deep the most nested statement sits
+fn nesting_depth(statements: &[Stmt]) -> usize {
+    statements.iter().map(statement_depth).max().unwrap_or(0)
+}
+
+fn statement_depth(stmt: &Stmt) -> usize {
+    match stmt {
+        // Braces on their own (or around an if/loop body) don't add a level
+        Stmt::Block(statements) => nesting_depth(statements),
+        // `lowkey sus` chains sit at the same level as the `sus` they extend
+        Stmt::If { then_branch, else_branch: Some(else_stmt), .. } if matches!(else_stmt.as_ref(), Stmt::If { .. }) => {
+            (1 + statement_depth(then_branch)).max(statement_depth(else_stmt))
+        }
+        _ => {
+            let mut deepest = None;
+            for_each_body(stmt, |body| deepest = Some(deepest.unwrap_or(0).max(nesting_depth(body))));
+            deepest.map_or(0, |depth| depth + 1)
+        }
+    }
+}
+
+/// Call `visit` with every statement list nested directly inside `stmt`
+fn for_each_body(stmt: &Stmt, mut visit: impl FnMut(&[Stmt])) {
+    match stmt {
+        Stmt::Block(statements) => visit(statements),
+        Stmt::If { then_branch, else_branch, .. } => {
+            visit(std::slice::from_ref(then_branch.as_ref()));
+            if let Some(else_stmt) = else_branch {
+                visit(std::slice::from_ref(else_stmt.as_ref()));
+            }
+        }
+        Stmt::While { body, else_branch, .. } | Stmt::For { body, else_branch, .. } | Stmt::ClassicFor { body, else_branch, .. } => {
+            visit(std::slice::from_ref(body.as_ref()));
+            if let Some(else_stmt) = else_branch {
+                visit(std::slice::from_ref(else_stmt.as_ref()));
+            }
+        }
+        Stmt::Switch { cases, default, .. } => {
+            for case in cases {
+                visit(&case.body);
+            }
+            if let Some(statements) = default {
+                visit(statements);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            visit(try_block);
+            for clause in catch_clauses {
+                visit(&clause.body);
+            }
+            if let Some(statements) = finally_block {
+                visit(statements);
+            }
+        }
+        Stmt::Function { body, .. } | Stmt::Squad { body, .. } => visit(body),
+        _ => {}
+    }
+}
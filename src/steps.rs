@@ -0,0 +1,351 @@
+//! ZLang Steps - Replaying a run one statement at a time 👣
+//! Powers the playground's "step through my code" slider, Python Tutor style
+
+use std::collections::HashMap;
+use std::ops::Range;
+use crate::ast::{Expr, Literal, Stmt, TypeAnnotation};
+use crate::environment::Environment;
+use crate::error::ZLangError;
+use crate::interpreter::{Capabilities, Interpreter};
+use crate::json;
+
+/// What one statement did
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Char range of the statement, None for the odd one the parser doesn't track
+    /// (an unbraced `sus` body)
+    pub span: Option<Range<usize>>,
+    /// Variables that showed up or changed, with their new values
+    pub changed: Vec<(String, Literal)>,
+    /// Variables that went out of scope
+    pub removed: Vec<String>,
+    /// Lines printed by the statement
+    pub output: Vec<String>,
+    /// Every scope's variables right after the statement, global scope first -
+    /// the whole variables pane, not just what changed
+    pub scopes: Vec<Vec<(String, Literal)>>,
+}
+
+/// Where each statement of a parsed program came from in its source
+#[derive(Debug, Default)]
+pub struct SpanIndex {
+    // Keyed by address: the tree is borrowed for the whole run, so statements never move
+    spans: HashMap<*const Stmt, Range<usize>>,
+}
+
+impl SpanIndex {
+    /// `spans` comes from `Parser::parse_with_statement_spans` on the same source
+    pub fn new(statements: &[Stmt], spans: &[Range<usize>]) -> Self {
+        let mut index = SpanIndex::default();
+        index.index_list(statements, &mut spans.iter().cloned());
+        index
+    }
+    
+    /// None for the odd statement the parser doesn't track (an unbraced `sus` body)
+    pub fn get(&self, stmt: &Stmt) -> Option<Range<usize>> {
+        self.spans.get(&(stmt as *const Stmt)).cloned()
+    }
+    
+    /// A function's body gets copied when it's declared - give the copy the original's spans
+    pub(crate) fn alias(&mut self, original: &[Stmt], copy: &[Stmt]) {
+        let mut originals = Vec::new();
+        let mut copies = Vec::new();
+        collect_list(original, &mut originals);
+        collect_list(copy, &mut copies);
+        
+        for (original, copy) in originals.into_iter().zip(copies) {
+            match self.spans.get(&original).cloned() {
+                Some(span) => self.spans.insert(copy, span),
+                // Whatever used to live at this address is gone
+                None => self.spans.remove(&copy),
+            };
+        }
+    }
+    
+    /// Hand out spans in the order the parser recorded them: each list element, then its insides
+    fn index_list(&mut self, statements: &[Stmt], spans: &mut impl Iterator<Item = Range<usize>>) {
+        for statement in statements {
+            if let Some(span) = spans.next() {
+                self.spans.insert(statement, span);
+            }
+            self.index_nested(statement, spans);
+        }
+    }
+    
+    fn index_nested(&mut self, statement: &Stmt, spans: &mut impl Iterator<Item = Range<usize>>) {
+        match statement {
+            Stmt::Block(body) | Stmt::Function { body, .. } | Stmt::Squad { body, .. } => self.index_list(body, spans),
+            Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) | Stmt::Return(Some(expr)) |
+            Stmt::VarDeclaration { initializer: Some(expr), .. } | Stmt::Needs { condition: expr, .. } => self.index_lambdas(expr, spans),
+            Stmt::If { condition, then_branch, else_branch } => {
+                self.index_lambdas(condition, spans);
+                self.index_nested(then_branch, spans);
+                if let Some(else_branch) = else_branch {
+                    self.index_nested(else_branch, spans);
+                }
+            }
+            Stmt::While { condition: expr, body, else_branch } | Stmt::For { iterable: expr, body, else_branch, .. } => {
+                self.index_lambdas(expr, spans);
+                self.index_nested(body, spans);
+                if let Some(else_branch) = else_branch {
+                    self.index_nested(else_branch, spans);
+                }
+            }
+            Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+                if let Some(initializer) = initializer {
+                    self.index_nested(initializer, spans);
+                }
+                for expr in condition.iter().chain(increment) {
+                    self.index_lambdas(expr, spans);
+                }
+                self.index_nested(body, spans);
+                if let Some(else_branch) = else_branch {
+                    self.index_nested(else_branch, spans);
+                }
+            }
+            Stmt::Switch { expr, cases, default } => {
+                self.index_lambdas(expr, spans);
+                for case in cases {
+                    case.exprs().for_each(|value| self.index_lambdas(value, spans));
+                    self.index_list(&case.body, spans);
+                }
+                if let Some(body) = default {
+                    self.index_list(body, spans);
+                }
+            }
+            Stmt::Try { try_block, catch_clauses, finally_block } => {
+                self.index_list(try_block, spans);
+                for clause in catch_clauses {
+                    self.index_list(&clause.body, spans);
+                }
+                if let Some(body) = finally_block {
+                    self.index_list(body, spans);
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    /// The statements inside any lambdas `expr` writes down, they were parsed along with it
+    fn index_lambdas(&mut self, expr: &Expr, spans: &mut impl Iterator<Item = Range<usize>>) {
+        for body in expr.lambda_bodies() {
+            self.index_list(body, spans);
+        }
+    }
+}
+
+/// How many statement spans the parser records for the lambdas `expr` writes down
+pub(crate) fn spans_in(expr: &Expr) -> usize {
+    let mut count = 0;
+    SpanIndex::default().index_lambdas(expr, &mut std::iter::from_fn(|| {
+        count += 1;
+        Some(0..0)
+    }));
+    count
+}
+
+/// How much a playground step-through run gets to do before it's cut off
+pub const LIMIT: usize = 1000;
+
+/// Watches an interpreter run, one `Step` per finished statement. Blocks, ifs and loops
+/// aren't steps themselves, the statements inside them are
+#[derive(Debug)]
+pub struct Tracer {
+    spans: SpanIndex,
+    variables: HashMap<String, Literal>,
+    printed: usize,
+    limit: usize,
+    // Steps plus loop passes - `lowkey (fr) {}` never finishes a statement, but it still
+    // has to run out of room
+    spent: usize,
+    steps: Vec<Step>,
+}
+
+impl Tracer {
+    /// `spans` comes from `Parser::parse_with_statement_spans` on the same source
+    pub fn new(statements: &[Stmt], spans: &[Range<usize>], limit: usize) -> Self {
+        Tracer {
+            spans: SpanIndex::new(statements, spans),
+            variables: HashMap::new(),
+            printed: 0,
+            limit,
+            spent: 0,
+            steps: Vec::new(),
+        }
+    }
+    
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+    
+    /// Hit the step limit - the run gets stopped right there
+    pub fn is_full(&self) -> bool {
+        self.spent >= self.limit
+    }
+    
+    /// A loop going round again, condition check and all - it counts against the limit
+    /// even when its body has no statements to record
+    pub(crate) fn charge(&mut self) -> Result<(), ZLangError> {
+        if self.is_full() {
+            return Err(self.stopped());
+        }
+        self.spent += 1;
+        Ok(())
+    }
+    
+    pub(crate) fn record(&mut self, stmt: &Stmt, environment: &Environment, output: &[String]) -> Result<(), ZLangError> {
+        if matches!(stmt, Stmt::Block(_) | Stmt::If { .. } | Stmt::While { .. } | Stmt::For { .. } | Stmt::ClassicFor { .. } | Stmt::Switch { .. } | Stmt::Try { .. }) {
+            return Ok(());
+        }
+        if self.is_full() {
+            return Err(self.stopped());
+        }
+        self.spent += 1;
+        
+        let variables = environment.visible_variables();
+        let mut changed: Vec<(String, Literal)> = variables.iter()
+            .filter(|(name, value)| self.variables.get(*name) != Some(value))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        changed.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut removed: Vec<String> = self.variables.keys().filter(|name| !variables.contains_key(*name)).cloned().collect();
+        removed.sort();
+        
+        // The interpreter drains its output at the end of a run, never in the middle of one
+        let printed = output.len();
+        self.steps.push(Step {
+            span: self.spans.get(stmt),
+            changed,
+            removed,
+            output: output[self.printed.min(printed)..].to_vec(),
+            scopes: environment.scopes(),
+        });
+        self.variables = variables;
+        self.printed = printed;
+        Ok(())
+    }
+    
+    /// A function's body gets copied when it's declared - give the copy the original's spans
+    pub(crate) fn alias(&mut self, original: &[Stmt], copy: &[Stmt]) {
+        self.spans.alias(original, copy);
+    }
+    
+    fn stopped(&self) -> ZLangError {
+        ZLangError::new(&format!("Stopped after {} steps, that's all the slider can hold 🛑", self.limit))
+    }
+}
+
+/// A finished step-through run
+#[derive(Debug)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    /// The run hit the step limit and was cut short
+    pub truncated: bool,
+    /// The runtime error that ended the run, if any
+    pub error: Option<String>,
+}
+
+impl Trace {
+    /// `source` is only needed to turn spans into line numbers
+    pub fn to_json(&self, source: &str) -> String {
+        let chars: Vec<char> = source.chars().collect();
+        let steps: Vec<String> = self.steps.iter().map(|step| {
+            let (span, line) = match &step.span {
+                Some(span) => (
+                    format!("[{}, {}]", span.start, span.end),
+                    (1 + chars[..span.start.min(chars.len())].iter().filter(|c| **c == '\n').count()).to_string(),
+                ),
+                None => ("null".to_string(), "null".to_string()),
+            };
+            let changed: Vec<String> = step.changed.iter()
+                .map(|(name, value)| format!("{}: {}", json::string(name), json::string(&value.to_source())))
+                .collect();
+            let removed: Vec<String> = step.removed.iter().map(|name| json::string(name)).collect();
+            let output: Vec<String> = step.output.iter().map(|line| json::string(line)).collect();
+            let scopes: Vec<String> = step.scopes.iter().map(|variables| {
+                let variables: Vec<String> = variables.iter().map(|(name, value)| format!(
+                    "{{\"name\": {}, \"type\": {}, \"value\": {}, \"display\": {}}}",
+                    json::string(name),
+                    json::string(&TypeAnnotation::of(value).to_string()),
+                    json::value(value),
+                    json::string(&value.to_source())
+                )).collect();
+                format!("[{}]", variables.join(", "))
+            }).collect();
+            
+            format!(
+                "{{\"span\": {}, \"line\": {}, \"changed\": {{{}}}, \"removed\": [{}], \"output\": [{}], \"scopes\": [{}]}}",
+                span, line, changed.join(", "), removed.join(", "), output.join(", "), scopes.join(", ")
+            )
+        }).collect();
+        
+        format!(
+            "{{\"success\": true, \"steps\": [{}], \"truncated\": {}, \"error\": {}}}",
+            steps.join(", "),
+            self.truncated,
+            self.error.as_deref().map_or("null".to_string(), json::string)
+        )
+    }
+}
+
+/// Run `statements` with a tracer attached, stopping after `limit` steps
+pub fn run(statements: Vec<Stmt>, spans: &[Range<usize>], limit: usize) -> Trace {
+    let tracer = Tracer::new(&statements, spans, limit);
+    // Nobody's around to answer a spill() halfway through a recorded run
+    let mut interpreter = Interpreter::new()
+        .with_tracer(tracer)
+        .with_capabilities(Capabilities { input: false, pull: false, fetch: false });
+    let result = interpreter.interpret(statements);
+    
+    let tracer = interpreter.take_tracer().expect("tracer is attached for the whole run");
+    let truncated = tracer.is_full() && result.is_err();
+    Trace {
+        error: result.err().filter(|_| !truncated).map(|error| error.to_string()),
+        steps: tracer.steps,
+        truncated,
+    }
+}
+
+/// Every statement node in the tree, parents before children
+fn collect_list(statements: &[Stmt], out: &mut Vec<*const Stmt>) {
+    for statement in statements {
+        collect(statement, out);
+    }
+}
+
+fn collect(statement: &Stmt, out: &mut Vec<*const Stmt>) {
+    out.push(statement);
+    match statement {
+        Stmt::Block(body) | Stmt::Function { body, .. } | Stmt::Squad { body, .. } => collect_list(body, out),
+        Stmt::If { then_branch, else_branch, .. } => {
+            collect(then_branch, out);
+            if let Some(else_branch) = else_branch {
+                collect(else_branch, out);
+            }
+        }
+        Stmt::While { body, else_branch, .. } | Stmt::For { body, else_branch, .. } | Stmt::ClassicFor { body, else_branch, .. } => {
+            collect(body, out);
+            if let Some(else_branch) = else_branch {
+                collect(else_branch, out);
+            }
+        }
+        Stmt::Switch { cases, default, .. } => {
+            for case in cases {
+                collect_list(&case.body, out);
+            }
+            if let Some(body) = default {
+                collect_list(body, out);
+            }
+        }
+        Stmt::Try { try_block, catch_clauses, finally_block } => {
+            collect_list(try_block, out);
+            for clause in catch_clauses {
+                collect_list(&clause.body, out);
+            }
+            if let Some(body) = finally_block {
+                collect_list(body, out);
+            }
+        }
+        _ => {}
+    }
+}
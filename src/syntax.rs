@@ -0,0 +1,636 @@
+//! ZLang Syntax - The AST as plain ZLang values, so scripts can take code apart and put it back 🌳
+//! `parseCode()` turns source into nested objects and `runAst()` runs them. Every node is an object
+//! with a `type` like "Print" or "Binary" and its parts as fields - lists of nodes are arrays, and
+//! a part that isn't there (an `if` without an else, say) is nil
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::ast::{BinaryOp, CasePattern, CatchClause, Expr, Literal, Stmt, SwitchCase, TypeAnnotation, UnaryOp};
+use crate::error::ZLangError;
+
+/// A node before it becomes an object, its parts in the order they're written in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree {
+    pub kind: &'static str,
+    pub parts: Vec<(&'static str, Part)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Part {
+    Node(Tree),
+    Nodes(Vec<Tree>),
+    // A name, an operator, a literal's value - or nil for a part that isn't there
+    Value(Literal),
+}
+
+impl Tree {
+    /// The node object scripts see, with its kind under `type`
+    pub fn into_literal(self) -> Literal {
+        let mut object: HashMap<String, Literal> = self.parts.into_iter()
+            .map(|(name, part)| {
+                let value = match part {
+                    Part::Node(tree) => tree.into_literal(),
+                    Part::Nodes(trees) => Literal::Array(trees.into_iter().map(Tree::into_literal).collect()),
+                    Part::Value(value) => value,
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        object.insert("type".to_string(), Literal::String(self.kind.to_string()));
+        Literal::Object(object)
+    }
+}
+
+/// Statements as an array of node objects
+pub fn from_statements(statements: &[Stmt]) -> Literal {
+    Literal::Array(trees(statements).into_iter().map(Tree::into_literal).collect())
+}
+
+/// Statements back out of what `from_statements` made - or out of objects a script built or changed itself
+pub fn to_statements(value: &Literal) -> Result<Vec<Stmt>, ZLangError> {
+    match value {
+        Literal::Array(items) => items.iter().map(|item| Node::read(item)?.statement()).collect(),
+        // A single statement on its own is fine too
+        Literal::Object(_) => Ok(vec![Node::read(value)?.statement()?]),
+        other => Err(malformed(&format!("an AST is an array of statement objects, not a {}", TypeAnnotation::of(other)))),
+    }
+}
+
+/// Statements as trees, for walking in source order
+pub fn trees(statements: &[Stmt]) -> Vec<Tree> {
+    statements.iter().map(statement).collect()
+}
+
+fn node(kind: &'static str, parts: Vec<(&'static str, Part)>) -> Tree {
+    Tree { kind, parts }
+}
+
+fn text(s: &str) -> Part {
+    Part::Value(Literal::String(s.to_string()))
+}
+
+fn texts(strings: &[String]) -> Part {
+    Part::Value(Literal::Array(strings.iter().map(|s| Literal::String(s.clone())).collect()))
+}
+
+fn line(line: usize) -> Part {
+    Part::Value(Literal::Number(line as f64))
+}
+
+fn flag(b: bool) -> Part {
+    Part::Value(Literal::Boolean(b))
+}
+
+fn type_name(annotation: Option<TypeAnnotation>) -> Literal {
+    annotation.map_or(Literal::Nil, |annotation| Literal::String(annotation.to_string()))
+}
+
+fn child(tree: Tree) -> Part {
+    Part::Node(tree)
+}
+
+fn optional(tree: Option<Tree>) -> Part {
+    tree.map_or(Part::Value(Literal::Nil), Part::Node)
+}
+
+fn block(statements: &[Stmt]) -> Part {
+    Part::Nodes(trees(statements))
+}
+
+fn optional_block(statements: Option<&[Stmt]>) -> Part {
+    statements.map_or(Part::Value(Literal::Nil), block)
+}
+
+fn statement(stmt: &Stmt) -> Tree {
+    match stmt {
+        Stmt::Expression(expr) => node("Expression", vec![("expr", child(expression(expr)))]),
+        Stmt::VarDeclaration { name, type_annotation, initializer } => node("VarDeclaration", vec![
+            ("name", text(name)),
+            ("typeAnnotation", Part::Value(type_name(*type_annotation))),
+            ("initializer", optional(initializer.as_ref().map(expression))),
+        ]),
+        Stmt::Block(body) => node("Block", vec![("body", block(body))]),
+        Stmt::If { condition, then_branch, else_branch } => node("If", vec![
+            ("condition", child(expression(condition))),
+            ("thenBranch", child(statement(then_branch))),
+            ("elseBranch", optional(else_branch.as_deref().map(statement))),
+        ]),
+        Stmt::While { condition, body, else_branch } => node("While", vec![
+            ("condition", child(expression(condition))),
+            ("body", child(statement(body))),
+            ("elseBranch", optional(else_branch.as_deref().map(statement))),
+        ]),
+        Stmt::For { variable, iterable, body, optional: skips_nil, else_branch } => node("For", vec![
+            ("variable", text(variable)),
+            ("iterable", child(expression(iterable))),
+            ("body", child(statement(body))),
+            ("optional", flag(*skips_nil)),
+            ("elseBranch", optional(else_branch.as_deref().map(statement))),
+        ]),
+        Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => node("ClassicFor", vec![
+            ("initializer", optional(initializer.as_deref().map(statement))),
+            ("condition", optional(condition.as_ref().map(expression))),
+            ("increment", optional(increment.as_ref().map(expression))),
+            ("body", child(statement(body))),
+            ("elseBranch", optional(else_branch.as_deref().map(statement))),
+        ]),
+        Stmt::Switch { expr, cases, default } => node("Switch", vec![
+            ("expr", child(expression(expr))),
+            ("cases", Part::Nodes(cases.iter().map(|case| node("Case", vec![
+                ("patterns", Part::Nodes(case.patterns.iter().map(pattern).collect())),
+                ("body", block(&case.body)),
+            ])).collect())),
+            ("default", optional_block(default.as_deref())),
+        ]),
+        Stmt::Try { try_block, catch_clauses, finally_block } => node("Try", vec![
+            ("tryBlock", block(try_block)),
+            ("catchClauses", Part::Nodes(catch_clauses.iter().map(|clause| node("Catch", vec![
+                ("variable", text(&clause.variable)),
+                ("errorType", Part::Value(clause.error_type.clone().map_or(Literal::Nil, Literal::String))),
+                ("body", block(&clause.body)),
+            ])).collect())),
+            ("finallyBlock", optional_block(finally_block.as_deref())),
+        ]),
+        Stmt::Throw(value) => node("Throw", vec![("value", child(expression(value)))]),
+        Stmt::DramaType { name, parent } => node("DramaType", vec![
+            ("name", text(name)),
+            ("parent", Part::Value(parent.clone().map_or(Literal::Nil, Literal::String))),
+        ]),
+        Stmt::Function { name, params, param_types, rest, return_type, body } => node("Function", vec![
+            ("name", text(name)),
+            ("params", texts(params)),
+            ("paramTypes", Part::Value(Literal::Array(param_types.iter().map(|annotation| type_name(*annotation)).collect()))),
+            ("rest", flag(*rest)),
+            ("returnType", Part::Value(type_name(*return_type))),
+            ("body", block(body)),
+        ]),
+        Stmt::Return(value) => node("Return", vec![("value", optional(value.as_ref().map(expression)))]),
+        Stmt::Break => node("Break", Vec::new()),
+        Stmt::Continue => node("Continue", Vec::new()),
+        Stmt::Print(value) => node("Print", vec![("value", child(expression(value)))]),
+        Stmt::Inspect(value) => node("Inspect", vec![("value", child(expression(value)))]),
+        Stmt::Global(names) => node("Global", vec![("names", texts(names))]),
+        Stmt::Outer(names) => node("Outer", vec![("names", texts(names))]),
+        Stmt::Needs { function, condition, line: at } => node("Needs", vec![
+            ("function", text(function)),
+            ("condition", child(expression(condition))),
+            ("line", line(*at)),
+        ]),
+        Stmt::Pull { path, alias, line: at } => node("Pull", vec![
+            ("path", text(path)),
+            ("alias", Part::Value(alias.clone().map_or(Literal::Nil, Literal::String))),
+            ("line", line(*at)),
+        ]),
+        Stmt::Squad { name, parent, body } => node("Squad", vec![
+            ("name", text(name)),
+            ("parent", Part::Value(parent.clone().map_or(Literal::Nil, Literal::String))),
+            ("body", block(body)),
+        ]),
+    }
+}
+
+fn pattern(pattern: &CasePattern) -> Tree {
+    match pattern {
+        CasePattern::Value(value) => node("Equals", vec![("value", child(expression(value)))]),
+        CasePattern::Range { low, high, inclusive } => node("Range", vec![
+            ("low", child(expression(low))),
+            ("high", child(expression(high))),
+            ("inclusive", flag(*inclusive)),
+        ]),
+        CasePattern::Compare { operator, value } => node("Compare", vec![
+            ("operator", text(operator.symbol())),
+            ("value", child(expression(value))),
+        ]),
+    }
+}
+
+fn expressions(exprs: &[Expr]) -> Part {
+    Part::Nodes(exprs.iter().map(expression).collect())
+}
+
+fn expression(expr: &Expr) -> Tree {
+    match expr {
+        Expr::Binary { left, operator, right } => node("Binary", vec![
+            ("operator", text(operator.symbol())),
+            ("left", child(expression(left))),
+            ("right", child(expression(right))),
+        ]),
+        Expr::Unary { operator, right } => node("Unary", vec![
+            ("operator", text(operator.symbol())),
+            ("right", child(expression(right))),
+        ]),
+        Expr::Literal(value) => node("Literal", vec![("value", Part::Value(value.clone()))]),
+        Expr::Variable(name) => node("Variable", vec![("name", text(name))]),
+        Expr::Call { callee, arguments, line: at } => node("Call", vec![
+            ("callee", child(expression(callee))),
+            ("arguments", expressions(arguments)),
+            ("line", line(*at)),
+        ]),
+        Expr::Assign { name, value } => node("Assign", vec![
+            ("name", text(name)),
+            ("value", child(expression(value))),
+        ]),
+        Expr::Array(items) => node("Array", vec![("items", expressions(items))]),
+        // A list of fields rather than an object, so they keep their order
+        Expr::Object(fields) => node("Object", vec![
+            ("fields", Part::Nodes(fields.iter().map(|(key, value)| node("Field", vec![
+                ("key", text(key)),
+                ("value", child(expression(value))),
+            ])).collect())),
+        ]),
+        Expr::Index { object, index, optional } => node("Index", vec![
+            ("object", child(expression(object))),
+            ("index", child(expression(index))),
+            ("optional", flag(*optional)),
+        ]),
+        Expr::Get { object, name, optional } => node("Get", vec![
+            ("object", child(expression(object))),
+            ("name", text(name)),
+            ("optional", flag(*optional)),
+        ]),
+        Expr::Set { object, name, value } => node("Set", vec![
+            ("object", child(expression(object))),
+            ("name", text(name)),
+            ("value", child(expression(value))),
+        ]),
+        Expr::Propagate { value, line: at } => node("Propagate", vec![
+            ("value", child(expression(value))),
+            ("line", line(*at)),
+        ]),
+        Expr::Conditional { condition, then_branch, else_branch } => node("Conditional", vec![
+            ("condition", child(expression(condition))),
+            ("thenBranch", child(expression(then_branch))),
+            ("elseBranch", child(expression(else_branch))),
+        ]),
+        Expr::Range { start, end, inclusive } => node("Range", vec![
+            ("start", child(expression(start))),
+            ("end", child(expression(end))),
+            ("inclusive", flag(*inclusive)),
+        ]),
+        Expr::Function { params, rest, body } => node("Lambda", vec![
+            ("params", texts(params)),
+            ("rest", flag(*rest)),
+            ("body", block(body)),
+        ]),
+        Expr::Spread(value) => node("Spread", vec![("value", child(expression(value)))]),
+    }
+}
+
+fn malformed(problem: &str) -> ZLangError {
+    ZLangError::new(&format!("runAst can't read that AST, {} 🌳", problem))
+}
+
+/// One node object being read back - a field that's missing counts as nil
+struct Node<'a> {
+    kind: &'a str,
+    fields: &'a HashMap<String, Literal>,
+}
+
+impl<'a> Node<'a> {
+    fn read(value: &'a Literal) -> Result<Self, ZLangError> {
+        let Literal::Object(fields) = value else {
+            return Err(malformed(&format!("expected a node object but found a {}", TypeAnnotation::of(value))));
+        };
+        match fields.get("type") {
+            Some(Literal::String(kind)) => Ok(Self { kind, fields }),
+            _ => Err(malformed("every node needs a string `type` like \"Print\"")),
+        }
+    }
+    
+    fn get(&self, field: &str) -> &'a Literal {
+        self.fields.get(field).unwrap_or(&Literal::Nil)
+    }
+    
+    fn wrong(&self, field: &str, wanted: &str) -> ZLangError {
+        malformed(&format!("{}'s `{}` should be {}, not {}", self.kind, field, wanted, TypeAnnotation::of(self.get(field))))
+    }
+    
+    fn string(&self, field: &str) -> Result<String, ZLangError> {
+        match self.get(field) {
+            Literal::String(s) => Ok(s.clone()),
+            _ => Err(self.wrong(field, "a string")),
+        }
+    }
+    
+    fn optional_string(&self, field: &str) -> Result<Option<String>, ZLangError> {
+        match self.get(field) {
+            Literal::Nil => Ok(None),
+            _ => self.string(field).map(Some),
+        }
+    }
+    
+    fn strings(&self, field: &str) -> Result<Vec<String>, ZLangError> {
+        self.list(field)?.iter()
+            .map(|item| match item {
+                Literal::String(s) => Ok(s.clone()),
+                _ => Err(self.wrong(field, "an array of strings")),
+            })
+            .collect()
+    }
+    
+    fn flag(&self, field: &str) -> Result<bool, ZLangError> {
+        match self.get(field) {
+            Literal::Boolean(b) => Ok(*b),
+            Literal::Nil => Ok(false),
+            _ => Err(self.wrong(field, "fr or cap")),
+        }
+    }
+    
+    /// Whether the last of `params` is a ...rest parameter, which needs there to be a last one
+    fn rest(&self) -> Result<bool, ZLangError> {
+        let rest = self.flag("rest")?;
+        if rest && self.list("params")?.is_empty() {
+            return Err(malformed(&format!("a {} with `rest` needs a parameter to collect into", self.kind)));
+        }
+        Ok(rest)
+    }
+    
+    /// A line number for error messages, 0 when a hand-built node leaves it out
+    fn line(&self) -> Result<usize, ZLangError> {
+        match self.get("line") {
+            Literal::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+            Literal::Nil => Ok(0),
+            _ => Err(self.wrong("line", "a whole number")),
+        }
+    }
+    
+    fn list(&self, field: &str) -> Result<&'a [Literal], ZLangError> {
+        match self.get(field) {
+            Literal::Array(items) => Ok(items),
+            _ => Err(self.wrong(field, "an array")),
+        }
+    }
+    
+    fn type_annotation(&self, field: &str, value: &Literal) -> Result<Option<TypeAnnotation>, ZLangError> {
+        match value {
+            Literal::Nil => Ok(None),
+            Literal::String(name) => TypeAnnotation::from_name(name)
+                .map(Some)
+                .ok_or_else(|| malformed(&format!("{}'s `{}` has an unknown type '{}'", self.kind, field, name))),
+            _ => Err(self.wrong(field, "a type name")),
+        }
+    }
+    
+    fn child(&self, field: &str) -> Result<Node<'a>, ZLangError> {
+        match self.get(field) {
+            Literal::Nil => Err(malformed(&format!("{} needs a `{}`", self.kind, field))),
+            value => Node::read(value),
+        }
+    }
+    
+    fn expr(&self, field: &str) -> Result<Expr, ZLangError> {
+        self.child(field)?.expression()
+    }
+    
+    fn boxed(&self, field: &str) -> Result<Box<Expr>, ZLangError> {
+        self.expr(field).map(Box::new)
+    }
+    
+    fn optional_expr(&self, field: &str) -> Result<Option<Expr>, ZLangError> {
+        match self.get(field) {
+            Literal::Nil => Ok(None),
+            _ => self.expr(field).map(Some),
+        }
+    }
+    
+    fn exprs(&self, field: &str) -> Result<Vec<Expr>, ZLangError> {
+        self.list(field)?.iter().map(|item| Node::read(item)?.expression()).collect()
+    }
+    
+    fn stmt(&self, field: &str) -> Result<Box<Stmt>, ZLangError> {
+        self.child(field)?.statement().map(Box::new)
+    }
+    
+    fn optional_stmt(&self, field: &str) -> Result<Option<Box<Stmt>>, ZLangError> {
+        match self.get(field) {
+            Literal::Nil => Ok(None),
+            _ => self.stmt(field).map(Some),
+        }
+    }
+    
+    fn stmts(&self, field: &str) -> Result<Vec<Stmt>, ZLangError> {
+        self.list(field)?.iter().map(|item| Node::read(item)?.statement()).collect()
+    }
+    
+    fn optional_stmts(&self, field: &str) -> Result<Option<Vec<Stmt>>, ZLangError> {
+        match self.get(field) {
+            Literal::Nil => Ok(None),
+            _ => self.stmts(field).map(Some),
+        }
+    }
+    
+    fn statement(&self) -> Result<Stmt, ZLangError> {
+        let stmt = match self.kind {
+            "Expression" => Stmt::Expression(self.expr("expr")?),
+            "VarDeclaration" => Stmt::VarDeclaration {
+                name: self.string("name")?,
+                type_annotation: self.type_annotation("typeAnnotation", self.get("typeAnnotation"))?,
+                initializer: self.optional_expr("initializer")?,
+            },
+            "Block" => Stmt::Block(self.stmts("body")?),
+            "If" => Stmt::If {
+                condition: self.expr("condition")?,
+                then_branch: self.stmt("thenBranch")?,
+                else_branch: self.optional_stmt("elseBranch")?,
+            },
+            "While" => Stmt::While {
+                condition: self.expr("condition")?,
+                body: self.stmt("body")?,
+                else_branch: self.optional_stmt("elseBranch")?,
+            },
+            "For" => Stmt::For {
+                variable: self.string("variable")?,
+                iterable: self.expr("iterable")?,
+                body: self.stmt("body")?,
+                optional: self.flag("optional")?,
+                else_branch: self.optional_stmt("elseBranch")?,
+            },
+            "ClassicFor" => Stmt::ClassicFor {
+                initializer: self.optional_stmt("initializer")?,
+                condition: self.optional_expr("condition")?,
+                increment: self.optional_expr("increment")?,
+                body: self.stmt("body")?,
+                else_branch: self.optional_stmt("elseBranch")?,
+            },
+            "Switch" => Stmt::Switch {
+                expr: self.expr("expr")?,
+                cases: self.list("cases")?.iter()
+                    .map(|case| {
+                        let case = Node::read(case)?;
+                        let patterns = case.list("patterns")?.iter()
+                            .map(|pattern| Node::read(pattern)?.pattern())
+                            .collect::<Result<Vec<_>, ZLangError>>()?;
+                        if patterns.is_empty() {
+                            return Err(malformed("a Case needs at least one pattern"));
+                        }
+                        Ok(SwitchCase { patterns, body: case.stmts("body")? })
+                    })
+                    .collect::<Result<_, ZLangError>>()?,
+                default: self.optional_stmts("default")?,
+            },
+            "Try" => Stmt::Try {
+                try_block: self.stmts("tryBlock")?,
+                catch_clauses: self.list("catchClauses")?.iter()
+                    .map(|clause| {
+                        let clause = Node::read(clause)?;
+                        Ok(CatchClause {
+                            variable: clause.string("variable")?,
+                            error_type: clause.optional_string("errorType")?,
+                            body: clause.stmts("body")?,
+                        })
+                    })
+                    .collect::<Result<_, ZLangError>>()?,
+                finally_block: self.optional_stmts("finallyBlock")?,
+            },
+            "Throw" => Stmt::Throw(self.expr("value")?),
+            "DramaType" => Stmt::DramaType {
+                name: self.string("name")?,
+                parent: self.optional_string("parent")?,
+            },
+            "Function" => {
+                let params = self.strings("params")?;
+                // Leaving the types off leaves every parameter untyped
+                let param_types = match self.get("paramTypes") {
+                    Literal::Nil => vec![None; params.len()],
+                    _ => self.list("paramTypes")?.iter()
+                        .map(|annotation| self.type_annotation("paramTypes", annotation))
+                        .collect::<Result<Vec<_>, ZLangError>>()?,
+                };
+                if param_types.len() != params.len() {
+                    return Err(malformed(&format!("Function has {} params but {} paramTypes", params.len(), param_types.len())));
+                }
+                Stmt::Function {
+                    name: self.string("name")?,
+                    params,
+                    param_types,
+                    rest: self.rest()?,
+                    return_type: self.type_annotation("returnType", self.get("returnType"))?,
+                    body: self.stmts("body")?,
+                }
+            }
+            "Return" => Stmt::Return(self.optional_expr("value")?),
+            "Break" => Stmt::Break,
+            "Continue" => Stmt::Continue,
+            "Print" => Stmt::Print(self.expr("value")?),
+            "Inspect" => Stmt::Inspect(self.expr("value")?),
+            "Global" => Stmt::Global(self.strings("names")?),
+            "Outer" => Stmt::Outer(self.strings("names")?),
+            "Needs" => Stmt::Needs {
+                function: self.string("function")?,
+                condition: self.expr("condition")?,
+                line: self.line()?,
+            },
+            "Pull" => Stmt::Pull { path: self.string("path")?, alias: self.optional_string("alias")?, line: self.line()? },
+            "Squad" => {
+                let body = self.stmts("body")?;
+                if !body.iter().all(|member| matches!(member, Stmt::VarDeclaration { .. } | Stmt::Function { .. })) {
+                    return Err(malformed("a Squad's body holds only VarDeclaration fields and Function methods"));
+                }
+                Stmt::Squad { name: self.string("name")?, parent: self.optional_string("parent")?, body }
+            }
+            // An expression where a statement goes is an expression statement
+            _ => Stmt::Expression(self.expression()?),
+        };
+        Ok(stmt)
+    }
+    
+    fn pattern(&self) -> Result<CasePattern, ZLangError> {
+        match self.kind {
+            "Equals" => Ok(CasePattern::Value(self.expr("value")?)),
+            "Range" => Ok(CasePattern::Range {
+                low: self.expr("low")?,
+                high: self.expr("high")?,
+                inclusive: self.flag("inclusive")?,
+            }),
+            "Compare" => {
+                let operator = self.string("operator")?;
+                match BinaryOp::from_symbol(&operator) {
+                    Some(operator @ (BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::Greater | BinaryOp::GreaterEqual | BinaryOp::Less | BinaryOp::LessEqual)) => {
+                        Ok(CasePattern::Compare { operator, value: self.expr("value")? })
+                    }
+                    _ => Err(malformed(&format!("'{}' isn't a comparison a case can use", operator))),
+                }
+            }
+            other => Err(malformed(&format!("a case pattern is Equals, Range or Compare, not '{}'", other))),
+        }
+    }
+    
+    fn expression(&self) -> Result<Expr, ZLangError> {
+        let expr = match self.kind {
+            "Binary" => {
+                let operator = self.string("operator")?;
+                Expr::Binary {
+                    left: self.boxed("left")?,
+                    operator: BinaryOp::from_symbol(&operator).ok_or_else(|| malformed(&format!("'{}' isn't a binary operator", operator)))?,
+                    right: self.boxed("right")?,
+                }
+            }
+            "Unary" => {
+                let operator = self.string("operator")?;
+                Expr::Unary {
+                    operator: UnaryOp::from_symbol(&operator).ok_or_else(|| malformed(&format!("'{}' isn't a unary operator", operator)))?,
+                    right: self.boxed("right")?,
+                }
+            }
+            "Literal" => Expr::Literal(self.get("value").clone()),
+            "Variable" => Expr::Variable(self.string("name")?),
+            "Call" => Expr::Call {
+                callee: self.boxed("callee")?,
+                arguments: self.exprs("arguments")?,
+                line: self.line()?,
+            },
+            "Assign" => Expr::Assign {
+                name: self.string("name")?,
+                value: self.boxed("value")?,
+            },
+            "Array" => Expr::Array(self.exprs("items")?),
+            "Object" => Expr::Object(self.list("fields")?.iter()
+                .map(|field| match field {
+                    Literal::Object(pair) => match (pair.get("key"), pair.get("value")) {
+                        (Some(Literal::String(key)), Some(value)) => Ok((key.clone(), Node::read(value)?.expression()?)),
+                        _ => Err(malformed("each of an Object's fields is a {key, value} Field")),
+                    },
+                    _ => Err(malformed("each of an Object's fields is a {key, value} Field")),
+                })
+                .collect::<Result<_, ZLangError>>()?),
+            "Index" => Expr::Index {
+                object: self.boxed("object")?,
+                index: self.boxed("index")?,
+                optional: self.flag("optional")?,
+            },
+            "Get" => Expr::Get {
+                object: self.boxed("object")?,
+                name: self.string("name")?,
+                optional: self.flag("optional")?,
+            },
+            "Set" => Expr::Set {
+                object: self.boxed("object")?,
+                name: self.string("name")?,
+                value: self.boxed("value")?,
+            },
+            "Propagate" => Expr::Propagate {
+                value: self.boxed("value")?,
+                line: self.line()?,
+            },
+            "Conditional" => Expr::Conditional {
+                condition: self.boxed("condition")?,
+                then_branch: self.boxed("thenBranch")?,
+                else_branch: self.boxed("elseBranch")?,
+            },
+            "Range" => Expr::Range {
+                start: self.boxed("start")?,
+                end: self.boxed("end")?,
+                inclusive: self.flag("inclusive")?,
+            },
+            "Lambda" => Expr::Function {
+                params: self.strings("params")?,
+                rest: self.rest()?,
+                body: Arc::new(self.stmts("body")?),
+            },
+            "Spread" => Expr::Spread(self.boxed("value")?),
+            other => return Err(malformed(&format!("'{}' isn't a kind of node ZLang knows", other))),
+        };
+        Ok(expr)
+    }
+}
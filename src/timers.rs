@@ -0,0 +1,78 @@
+//! ZLang Timers - `after()` and `every()` line up callbacks, `runLoop()` plays them out in time order ⏰
+//! Nothing fires on its own, a script sets its timers up and then hands the wheel to runLoop()
+
+use std::time::{Duration, Instant};
+
+use crate::error::ZLangError;
+use crate::interpreter::Function;
+
+/// Timers waiting at once, past this a script is probably scheduling in a loop by accident
+pub const MAX_TIMERS: usize = 1_000;
+
+#[derive(Debug, Clone)]
+struct Timer {
+    id: usize,
+    function: Function,
+    due: Instant,
+    // Set for every(), after() timers go away once they fire
+    interval: Option<Duration>,
+}
+
+/// Everything scheduled and not yet fired (or cancelled)
+#[derive(Debug, Clone, Default)]
+pub struct Timers {
+    timers: Vec<Timer>,
+    next_id: usize,
+}
+
+impl Timers {
+    /// Line `function` up to run after `delay`, and every `delay` after that when it repeats.
+    /// Hands back the id `cancel` takes
+    pub fn schedule(&mut self, function: Function, delay: Duration, repeat: bool) -> Result<usize, ZLangError> {
+        if self.timers.len() >= MAX_TIMERS {
+            return Err(ZLangError::new(&format!("That's {} timers waiting already, cancel some first ⏰", MAX_TIMERS)));
+        }
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id: self.next_id,
+            function,
+            due: Instant::now() + delay,
+            interval: repeat.then_some(delay),
+        });
+        Ok(self.next_id)
+    }
+    
+    /// Whether there was a timer with that id left to cancel
+    pub fn cancel(&mut self, id: usize) -> bool {
+        let before = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != before
+    }
+    
+    /// When the next timer wants to fire, None once nothing's left
+    pub fn next_due(&self) -> Option<Instant> {
+        self.timers.iter().map(|timer| timer.due).min()
+    }
+    
+    /// The timer that's been due longest by `now` - ties go to whichever was scheduled first.
+    /// A repeating one gets lined up again, skipping any beats a slow callback made it miss
+    pub fn take_due(&mut self, now: Instant) -> Option<Function> {
+        let index = self.timers.iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.due <= now)
+            .min_by_key(|(_, timer)| (timer.due, timer.id))
+            .map(|(index, _)| index)?;
+        
+        match self.timers[index].interval {
+            Some(interval) => {
+                let timer = &mut self.timers[index];
+                timer.due += interval;
+                if timer.due < now {
+                    timer.due = now + interval;
+                }
+                Some(timer.function.clone())
+            }
+            None => Some(self.timers.remove(index).function),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Token definitions for ZLang
 //! All the different pieces we can break code into
 
+use std::ops::Range;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Literals
@@ -23,6 +25,7 @@ pub enum TokenType {
     Grind,     // for
     Highkey,   // for (alternative)
     Bruh,      // print
+    BruhBang,  // pretty-print (`bruh!`)
     Slay,      // break
     NoChill,   // continue
     Ghost,     // continue (alternative)
@@ -31,6 +34,10 @@ pub enum TokenType {
     Caught,    // catch
     Drama,     // throw
     Frfr,      // finally
+    Global,    // rebind a global variable
+    Outer,     // rebind an enclosing (nonlocal) variable
+    Needs,     // function precondition
+    Pull,      // import another file
     
     // Operators
     Plus,
@@ -38,6 +45,13 @@ pub enum TokenType {
     Star,
     Slash,
     Percent,
+    TildeSlash, // integer division
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    LessLess,
+    GreaterGreater,
     Equal,
     EqualEqual,
     BangEqual,
@@ -48,6 +62,8 @@ pub enum TokenType {
     And,
     Or,
     Bang,
+    // `=>` between a meme's name and what it means
+    FatArrow,
     
     // Delimiters
     LeftParen,
@@ -57,13 +73,184 @@ pub enum TokenType {
     LeftBracket,
     RightBracket,
     Comma,
+    Dot,
+    // `...` before a rest parameter
+    Ellipsis,
+    // `80..90` and `90..=100` in a vibe check case
+    DotDot,
+    DotDotEqual,
     Semicolon,
     Colon,
+    Question,
+    // `?.` and `?[`, which give nil instead of looking inside a nil
+    QuestionDot,
+    QuestionBracket,
     In,
     
     // Special
     Newline,
     Eof,
+    
+    // Trivia - only produced by `Lexer::with_trivia`
+    Whitespace(String),
+    Comment(String),
+    
+    // Recovery - only produced by `Lexer::with_recovery`, holds what was wrong with the text it covers
+    Error(String),
+}
+
+/// Every Gen Z keyword, spelled the way you type it
+pub const KEYWORDS: &[&str] = &[
+    "fr", "cap", "bet", "sus", "bussin", "lowkey sus", "no sus", "periodt", "flex", "vibe",
+    "lowkey", "grind", "highkey", "bruh", "bruh!", "slay", "no chill", "ghost", "vibe check",
+    "manifest", "caught", "drama", "frfr", "in", "global", "outer", "needs", "pull",
+];
+
+/// Operators that can be spelled as a word, with the symbol each one stands for - `x is 3 and y aint 4`
+pub const WORD_OPERATORS: &[(&str, &str)] = &[("is", "=="), ("aint", "!="), ("and", "&&"), ("or", "||")];
+
+/// One single-character emoji per keyword, so `zlang emojify` can always find its way back
+pub const EMOJI_KEYWORDS: &[(char, &str)] = &[
+    ('✅', "fr"), ('❌', "cap"), ('📦', "bet"), ('🤔', "sus"), ('🙃', "bussin"),
+    ('🤨', "lowkey sus"), ('😇', "no sus"), ('🔚', "periodt"), ('💪', "flex"), ('📤', "vibe"),
+    ('🔄', "lowkey"), ('🔂', "grind"), ('🔁', "highkey"), ('📢', "bruh"), ('🔍', "bruh!"),
+    ('💅', "slay"), ('😤', "no chill"), ('👻', "ghost"), ('🎯', "vibe check"), ('🙏', "manifest"),
+    ('🧤', "caught"), ('🎭', "drama"), ('🧹', "frfr"), ('📥', "in"), ('🌍', "global"),
+    ('🔭', "outer"), ('📜', "needs"), ('🧲', "pull"),
+];
+
+impl TokenType {
+    /// The keyword token spelled `word`, including the two-word ones like "vibe check"
+    pub fn from_keyword(word: &str) -> Option<TokenType> {
+        let token_type = match word {
+            "fr" => TokenType::Fr,
+            "cap" => TokenType::Cap,
+            "bet" => TokenType::Bet,
+            "sus" => TokenType::Sus,
+            "bussin" => TokenType::Bussin,
+            "lowkey sus" => TokenType::LowkeySus,
+            "no sus" => TokenType::NoSus,
+            "periodt" => TokenType::Periodt,
+            "flex" => TokenType::Flex,
+            "vibe" => TokenType::Vibe,
+            "lowkey" => TokenType::Lowkey,
+            "grind" => TokenType::Grind,
+            "highkey" => TokenType::Highkey,
+            "bruh" => TokenType::Bruh,
+            "bruh!" => TokenType::BruhBang,
+            "slay" => TokenType::Slay,
+            "no chill" => TokenType::NoChill,
+            "ghost" => TokenType::Ghost,
+            "vibe check" => TokenType::VibeCheck,
+            "manifest" => TokenType::Manifest,
+            "caught" => TokenType::Caught,
+            "drama" => TokenType::Drama,
+            "frfr" => TokenType::Frfr,
+            "in" => TokenType::In,
+            "global" => TokenType::Global,
+            "outer" => TokenType::Outer,
+            "needs" => TokenType::Needs,
+            "pull" => TokenType::Pull,
+            _ => return None,
+        };
+        Some(token_type)
+    }
+    
+    /// The operator token spelled `word`, either as its word or its symbol - what `alias.equals = is` points at
+    pub fn from_operator(word: &str) -> Option<TokenType> {
+        let (_, symbol) = WORD_OPERATORS.iter().find(|(name, symbol)| *name == word || *symbol == word)?;
+        let token_type = match *symbol {
+            "==" => TokenType::EqualEqual,
+            "!=" => TokenType::BangEqual,
+            "&&" => TokenType::And,
+            _ => TokenType::Or,
+        };
+        Some(token_type)
+    }
+    
+    /// The word this operator can be written as, or None for the ones that only come as symbols
+    pub fn operator_word(&self) -> Option<&'static str> {
+        let word = match self {
+            TokenType::EqualEqual => "is",
+            TokenType::BangEqual => "aint",
+            TokenType::And => "and",
+            TokenType::Or => "or",
+            _ => return None,
+        };
+        Some(word)
+    }
+    
+    /// The keyword token an emoji alias stands for
+    pub fn from_emoji(emoji: char) -> Option<TokenType> {
+        let (_, word) = EMOJI_KEYWORDS.iter().find(|(e, _)| *e == emoji)?;
+        TokenType::from_keyword(word)
+    }
+    
+    pub fn emoji(&self) -> Option<char> {
+        let keyword = self.keyword()?;
+        EMOJI_KEYWORDS.iter().find(|(_, word)| *word == keyword).map(|(emoji, _)| *emoji)
+    }
+    
+    /// The keyword this token was written as, or None for literals, operators and punctuation
+    pub fn keyword(&self) -> Option<&'static str> {
+        let keyword = match self {
+            TokenType::Fr => "fr",
+            TokenType::Cap => "cap",
+            TokenType::Bet => "bet",
+            TokenType::Sus => "sus",
+            TokenType::Bussin => "bussin",
+            TokenType::LowkeySus => "lowkey sus",
+            TokenType::NoSus => "no sus",
+            TokenType::Periodt => "periodt",
+            TokenType::Flex => "flex",
+            TokenType::Vibe => "vibe",
+            TokenType::Lowkey => "lowkey",
+            TokenType::Grind => "grind",
+            TokenType::Highkey => "highkey",
+            TokenType::Bruh => "bruh",
+            TokenType::BruhBang => "bruh!",
+            TokenType::Slay => "slay",
+            TokenType::NoChill => "no chill",
+            TokenType::Ghost => "ghost",
+            TokenType::VibeCheck => "vibe check",
+            TokenType::Manifest => "manifest",
+            TokenType::Caught => "caught",
+            TokenType::Drama => "drama",
+            TokenType::Frfr => "frfr",
+            TokenType::In => "in",
+            TokenType::Global => "global",
+            TokenType::Outer => "outer",
+            TokenType::Needs => "needs",
+            TokenType::Pull => "pull",
+            _ => return None,
+        };
+        Some(keyword)
+    }
+    
+    /// What sort of token this is, the `type` that `tokenize()` hands scripts
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TokenType::Number(_) => "number",
+            TokenType::String(_) => "string",
+            TokenType::Identifier(_) => "identifier",
+            TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash | TokenType::Percent |
+            TokenType::TildeSlash | TokenType::Ampersand | TokenType::Pipe | TokenType::Caret | TokenType::Tilde |
+            TokenType::LessLess | TokenType::GreaterGreater | TokenType::Equal | TokenType::EqualEqual |
+            TokenType::BangEqual | TokenType::Greater | TokenType::GreaterEqual | TokenType::Less |
+            TokenType::LessEqual | TokenType::And | TokenType::Or | TokenType::Bang | TokenType::Question |
+            TokenType::DotDot | TokenType::DotDotEqual | TokenType::FatArrow => "operator",
+            TokenType::LeftParen | TokenType::RightParen | TokenType::LeftBrace | TokenType::RightBrace |
+            TokenType::LeftBracket | TokenType::RightBracket | TokenType::Comma | TokenType::Dot |
+            TokenType::Ellipsis | TokenType::Semicolon | TokenType::Colon | TokenType::QuestionDot | TokenType::QuestionBracket => "punctuation",
+            TokenType::Newline => "newline",
+            TokenType::Eof => "eof",
+            TokenType::Whitespace(_) => "whitespace",
+            TokenType::Comment(_) => "comment",
+            TokenType::Error(_) => "error",
+            // Everything else is spelled with a Gen Z word
+            _ => "keyword",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -72,14 +259,23 @@ pub struct Token {
     pub line: usize,
     #[allow(dead_code)]
     pub column: usize,
+    /// Where the token sits in the source, as char offsets
+    pub span: Range<usize>,
 }
 
 impl Token {
-    pub fn new(token_type: TokenType, line: usize, column: usize) -> Self {
+    pub fn new(token_type: TokenType, line: usize, column: usize, span: Range<usize>) -> Self {
         Self {
             token_type,
             line,
             column,
+            span,
         }
     }
+    
+    /// The exact text this token was written as - numbers and strings before they were parsed,
+    /// multi-word keywords with whatever spacing they had. `source` is the lexed source as chars
+    pub fn lexeme(&self, source: &[char]) -> String {
+        source[self.span.clone()].iter().collect()
+    }
 }
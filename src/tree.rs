@@ -0,0 +1,101 @@
+//! ZLang Tree - `zlang ast --pretty` draws the syntax tree with box lines, readable off a projector 🌲
+//! Each node shows its kind and its names, operators and values on one line, the nodes inside it below
+
+use crate::ansi;
+use crate::ast::Literal;
+use crate::syntax::{Part, Tree};
+
+/// The whole program under a `Program` root, colored when `colors` is on
+pub fn pretty(program: &[Tree], colors: bool) -> String {
+    let painter = Painter { colors };
+    let mut out = painter.kind("Program");
+    out.push('\n');
+    for (index, tree) in program.iter().enumerate() {
+        painter.node(&mut out, "", None, tree, index + 1 == program.len());
+    }
+    out
+}
+
+struct Painter {
+    colors: bool,
+}
+
+impl Painter {
+    fn paint(&self, text: &str, color: &str) -> String {
+        match (self.colors, ansi::colored(text, color)) {
+            (true, Some(painted)) => painted,
+            _ => text.to_string(),
+        }
+    }
+    
+    fn kind(&self, kind: &str) -> String {
+        if self.colors { ansi::bold(&self.paint(kind, "cyan")) } else { kind.to_string() }
+    }
+    
+    /// One `├── name: Kind field: value` line, then whatever's inside the node under it
+    fn node(&self, out: &mut String, indent: &str, name: Option<&str>, tree: &Tree, last: bool) {
+        out.push_str(&self.branch(indent, name, last));
+        out.push_str(&self.kind(tree.kind));
+        for (field, part) in &tree.parts {
+            if let Part::Value(value) = part {
+                match shown(tree.kind, field, value) {
+                    // A literal is nothing but its value, `Literal 4` says it all
+                    Some(shown) if tree.kind == "Literal" => out.push_str(&format!(" {}", self.paint(&shown, "green"))),
+                    Some(shown) => out.push_str(&format!("  {} {}", self.paint(&format!("{}:", field), "gray"), self.paint(&shown, "yellow"))),
+                    None => {}
+                }
+            }
+        }
+        out.push('\n');
+        
+        let indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+        let inside: Vec<(&str, &Part)> = tree.parts.iter()
+            .filter(|(_, part)| match part {
+                Part::Node(_) => true,
+                Part::Nodes(trees) => !trees.is_empty(),
+                Part::Value(_) => false,
+            })
+            .map(|(field, part)| (*field, part))
+            .collect();
+        for (index, (field, part)) in inside.iter().enumerate() {
+            let last = index + 1 == inside.len();
+            match part {
+                Part::Node(tree) => self.node(out, &indent, Some(field), tree, last),
+                Part::Nodes(trees) => {
+                    out.push_str(&self.branch(&indent, None, last));
+                    out.push_str(&self.paint(field, "gray"));
+                    out.push('\n');
+                    let indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+                    for (index, tree) in trees.iter().enumerate() {
+                        self.node(out, &indent, None, tree, index + 1 == trees.len());
+                    }
+                }
+                Part::Value(_) => {}
+            }
+        }
+    }
+    
+    fn branch(&self, indent: &str, name: Option<&str>, last: bool) -> String {
+        let line = if last { "└── " } else { "├── " };
+        let name = name.map_or(String::new(), |name| self.paint(&format!("{}: ", name), "gray"));
+        format!("{}{}{}", indent, line, name)
+    }
+}
+
+/// How a value part reads on its node's line - None for ones that would only be noise, like a
+/// missing else, `rest: cap` or the line number every call carries
+fn shown(kind: &str, field: &str, value: &Literal) -> Option<String> {
+    match value {
+        Literal::Nil | Literal::Boolean(false) => None,
+        _ if field == "line" => None,
+        // A literal's value is data, so it's shown the way it's written - strings in quotes
+        _ if kind == "Literal" => Some(value.to_source()),
+        Literal::String(s) => Some(s.clone()),
+        Literal::Array(items) if items.iter().all(|item| *item == Literal::Nil) => None,
+        Literal::Array(items) => Some(items.iter()
+            .map(|item| if *item == Literal::Nil { "_".to_string() } else { item.to_string() })
+            .collect::<Vec<_>>()
+            .join(", ")),
+        other => Some(other.to_string()),
+    }
+}
@@ -0,0 +1,462 @@
+//! ZLang Type Checker - Optional static typing for code that opts in
+//! Only annotated code gets checked and runtime never cares, so it's all vibes until you ask 🏷️
+
+use std::collections::HashMap;
+use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, TypeAnnotation};
+
+#[derive(Debug, Clone)]
+struct Signature {
+    param_types: Vec<Option<TypeAnnotation>>,
+    rest: bool,
+    return_type: Option<TypeAnnotation>,
+}
+
+pub struct TypeChecker {
+    // Declared types of annotated variables, innermost scope last
+    scopes: Vec<HashMap<String, TypeAnnotation>>,
+    functions: HashMap<String, Signature>,
+    // Return type of each function we're currently inside
+    return_types: Vec<Option<TypeAnnotation>>,
+    errors: Vec<String>,
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            functions: HashMap::new(),
+            return_types: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+    
+    /// Check a whole program, returning every obvious type mismatch we spotted
+    pub fn check(mut self, statements: &[Stmt]) -> Vec<String> {
+        // Signatures first so calls before the declaration still get checked
+        for stmt in statements {
+            self.collect_signatures(stmt);
+        }
+        
+        for stmt in statements {
+            self.check_stmt(stmt);
+        }
+        
+        self.errors
+    }
+    
+    fn collect_signatures(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Function { name, param_types, rest, return_type, body, .. } => {
+                self.functions.insert(name.clone(), Signature {
+                    param_types: param_types.clone(),
+                    rest: *rest,
+                    return_type: *return_type,
+                });
+                for stmt in body {
+                    self.collect_signatures(stmt);
+                }
+            }
+            Stmt::Block(statements) => {
+                for stmt in statements {
+                    self.collect_signatures(stmt);
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    fn check_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expression(expr) | Stmt::Print(expr) | Stmt::Inspect(expr) | Stmt::Throw(expr) => {
+                self.infer(expr);
+            }
+            Stmt::VarDeclaration { name, type_annotation, initializer } => {
+                let actual = initializer.as_ref().map(|init| self.infer(init));
+                if let (Some(expected), Some(actual)) = (type_annotation, actual) {
+                    if !compatible(*expected, actual) {
+                        self.errors.push(format!(
+                            "'{}' is declared as {} but gets a {} 🏷️",
+                            name, expected, actual
+                        ));
+                    }
+                }
+                
+                let declared = type_annotation.unwrap_or(TypeAnnotation::Any);
+                self.current_scope().insert(name.clone(), declared);
+            }
+            Stmt::Block(statements) => self.check_block(statements, Vec::new()),
+            Stmt::If { condition, then_branch, else_branch } => {
+                self.infer(condition);
+                self.check_stmt(then_branch);
+                if let Some(else_stmt) = else_branch {
+                    self.check_stmt(else_stmt);
+                }
+            }
+            Stmt::While { condition, body, else_branch } => {
+                self.infer(condition);
+                self.check_stmt(body);
+                if let Some(else_stmt) = else_branch {
+                    self.check_stmt(else_stmt);
+                }
+            }
+            Stmt::For { variable, iterable, body, optional, else_branch } => {
+                let iterable_type = self.infer(iterable);
+                let nil_allowed = *optional && iterable_type == TypeAnnotation::Nil;
+                if !compatible(TypeAnnotation::Array, iterable_type) && !nil_allowed {
+                    self.errors.push(format!("Can only grind over arrays, not a {} 📚", iterable_type));
+                }
+                self.check_block(std::slice::from_ref(body.as_ref()), vec![(variable.clone(), TypeAnnotation::Any)]);
+                if let Some(else_stmt) = else_branch {
+                    self.check_stmt(else_stmt);
+                }
+            }
+            Stmt::ClassicFor { initializer, condition, increment, body, else_branch } => {
+                self.scopes.push(HashMap::new());
+                if let Some(initializer) = initializer {
+                    self.check_stmt(initializer);
+                }
+                if let Some(condition) = condition {
+                    self.infer(condition);
+                }
+                self.check_stmt(body);
+                if let Some(increment) = increment {
+                    self.infer(increment);
+                }
+                self.scopes.pop();
+                if let Some(else_stmt) = else_branch {
+                    self.check_stmt(else_stmt);
+                }
+            }
+            Stmt::Switch { expr, cases, default } => {
+                self.infer(expr);
+                for case in cases {
+                    for value in case.exprs() {
+                        self.infer(value);
+                    }
+                    self.check_block(&case.body, Vec::new());
+                }
+                if let Some(statements) = default {
+                    self.check_block(statements, Vec::new());
+                }
+            }
+            Stmt::Try { try_block, catch_clauses, finally_block } => {
+                self.check_block(try_block, Vec::new());
+                for clause in catch_clauses {
+                    self.check_block(&clause.body, vec![(clause.variable.clone(), TypeAnnotation::String)]);
+                }
+                if let Some(statements) = finally_block {
+                    self.check_block(statements, Vec::new());
+                }
+            }
+            Stmt::Function { params, param_types, rest, return_type, body, .. } => {
+                let mut bindings: Vec<(String, TypeAnnotation)> = params
+                    .iter()
+                    .zip(param_types)
+                    .map(|(param, ty)| (param.clone(), ty.unwrap_or(TypeAnnotation::Any)))
+                    .collect();
+                if let (true, Some(last)) = (*rest, bindings.last_mut()) {
+                    last.1 = TypeAnnotation::Array;
+                }
+                
+                self.return_types.push(*return_type);
+                self.check_block(body, bindings);
+                self.return_types.pop();
+            }
+            // Fields and methods share a scope, the way methods see the fields at runtime
+            Stmt::Squad { body, .. } => self.check_block(body, Vec::new()),
+            Stmt::Return(expr) => {
+                let actual = expr.as_ref().map(|e| self.infer(e)).unwrap_or(TypeAnnotation::Nil);
+                if let Some(Some(expected)) = self.return_types.last().copied() {
+                    if !compatible(expected, actual) {
+                        self.errors.push(format!(
+                            "Function should vibe back a {} but returns a {} 📤",
+                            expected, actual
+                        ));
+                    }
+                }
+            }
+            Stmt::Needs { condition, .. } => {
+                self.infer(condition);
+            }
+            Stmt::Global(_) | Stmt::Outer(_) | Stmt::DramaType { .. } | Stmt::Break | Stmt::Continue | Stmt::Pull { .. } => {}
+        }
+    }
+    
+    fn check_block(&mut self, statements: &[Stmt], bindings: Vec<(String, TypeAnnotation)>) {
+        self.scopes.push(bindings.into_iter().collect());
+        for stmt in statements {
+            self.check_stmt(stmt);
+        }
+        self.scopes.pop();
+    }
+    
+    /// Best-effort type of an expression - Any whenever we can't be sure
+    fn infer(&mut self, expr: &Expr) -> TypeAnnotation {
+        match expr {
+            Expr::Literal(literal) => TypeAnnotation::of(literal),
+            Expr::Variable(name) => self.lookup(name),
+            Expr::Assign { name, value } => {
+                let actual = self.infer(value);
+                let expected = self.lookup(name);
+                if !compatible(expected, actual) {
+                    self.errors.push(format!(
+                        "'{}' is declared as {} but gets assigned a {} 🏷️",
+                        name, expected, actual
+                    ));
+                }
+                actual
+            }
+            Expr::Binary { left, operator, right } => {
+                let left = self.infer(left);
+                let right = self.infer(right);
+                self.binary_type(left, operator, right)
+            }
+            Expr::Unary { operator, right } => {
+                let operand = self.infer(right);
+                match operator {
+                    UnaryOp::Minus if operand == TypeAnnotation::Money => TypeAnnotation::Money,
+                    UnaryOp::Minus => {
+                        if !compatible(TypeAnnotation::Number, operand) {
+                            self.errors.push(format!("Can't negate a {}, only numbers ➖", operand));
+                        }
+                        TypeAnnotation::Number
+                    }
+                    UnaryOp::Plus => {
+                        if !number_like(operand) {
+                            self.errors.push(format!("Can't turn a {} into a number ➕", operand));
+                        }
+                        TypeAnnotation::Number
+                    }
+                    UnaryOp::Not => TypeAnnotation::Bool,
+                    UnaryOp::BitNot => {
+                        if !compatible(TypeAnnotation::Number, operand) {
+                            self.errors.push(format!("Can't flip the bits of a {}, only numbers 🔢", operand));
+                        }
+                        TypeAnnotation::Number
+                    }
+                }
+            }
+            Expr::Call { callee, arguments, .. } => {
+                let argument_types: Vec<TypeAnnotation> = arguments.iter().map(|arg| self.infer(arg)).collect();
+                // A ...spread's length is only known once it runs, so its arguments can't be lined up with parameters
+                let spread = arguments.iter().any(|arg| matches!(arg, Expr::Spread(_)));
+                match callee.as_ref() {
+                    Expr::Variable(name) => self.call_type(name, (!spread).then_some(argument_types.as_slice())),
+                    other => {
+                        self.infer(other);
+                        TypeAnnotation::Any
+                    }
+                }
+            }
+            Expr::Array(elements) => {
+                for element in elements {
+                    self.infer(element);
+                }
+                TypeAnnotation::Array
+            }
+            Expr::Object(pairs) => {
+                for (_, value) in pairs {
+                    self.infer(value);
+                }
+                TypeAnnotation::Object
+            }
+            Expr::Index { object, index, .. } => {
+                self.infer(object);
+                self.infer(index);
+                TypeAnnotation::Any
+            }
+            Expr::Get { object, name, optional } => {
+                let object_type = self.infer(object);
+                // `?.` is there for the nils
+                let nil_allowed = *optional && object_type == TypeAnnotation::Nil;
+                if !compatible(TypeAnnotation::Object, object_type) && !nil_allowed {
+                    self.errors.push(format!("Can't read .{} off a {}, dot access is for objects 🔍", name, object_type));
+                }
+                TypeAnnotation::Any
+            }
+            Expr::Set { object, name, value } => {
+                let object_type = self.infer(object);
+                if !compatible(TypeAnnotation::Object, object_type) {
+                    self.errors.push(format!("Can't set .{} on a {}, only squad instances have fields to set 🔍", name, object_type));
+                }
+                self.infer(value)
+            }
+            Expr::Range { start, end, .. } => {
+                for bound in [start, end] {
+                    let bound_type = self.infer(bound);
+                    if !compatible(TypeAnnotation::Number, bound_type) {
+                        self.errors.push(format!("A range counts between numbers, not from a {} 🔢", bound_type));
+                    }
+                }
+                TypeAnnotation::Array
+            }
+            // Whichever branch runs, so only a type both agree on is certain
+            Expr::Conditional { condition, then_branch, else_branch } => {
+                self.infer(condition);
+                let then_type = self.infer(then_branch);
+                let else_type = self.infer(else_branch);
+                if then_type == else_type { then_type } else { TypeAnnotation::Any }
+            }
+            Expr::Function { params, rest, body } => {
+                let mut bindings: Vec<(String, TypeAnnotation)> = params.iter().map(|param| (param.clone(), TypeAnnotation::Any)).collect();
+                if let (true, Some(last)) = (*rest, bindings.last_mut()) {
+                    last.1 = TypeAnnotation::Array;
+                }
+                self.return_types.push(None);
+                self.check_block(body, bindings);
+                self.return_types.pop();
+                TypeAnnotation::Function
+            }
+            Expr::Propagate { value, .. } => {
+                let result = self.infer(value);
+                if !compatible(TypeAnnotation::Object, result) {
+                    self.errors.push(format!("'?' needs an ok(...)/nah(...) result, not a {} 🎁", result));
+                }
+                TypeAnnotation::Any
+            }
+            Expr::Spread(value) => {
+                let spread = self.infer(value);
+                if !compatible(TypeAnnotation::Array, spread) {
+                    self.errors.push(format!("Only arrays can be spread with ..., not a {} 📦", spread));
+                }
+                TypeAnnotation::Any
+            }
+        }
+    }
+    
+    fn binary_type(&mut self, left: TypeAnnotation, operator: &BinaryOp, right: TypeAnnotation) -> TypeAnnotation {
+        use TypeAnnotation::{Any, Bool, Money, Number, String};
+        
+        match operator {
+            BinaryOp::Add => match (left, right) {
+                (String, _) | (_, String) => String,
+                (Number, Number) => Number,
+                (Money, Money) => Money,
+                (Any, _) | (_, Any) => Any,
+                _ => {
+                    self.errors.push(format!("Can't add a {} and a {} ➕", left, right));
+                    Any
+                }
+            },
+            // Money keeps its own rules: money - money, money * number, money / number or money
+            BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide if left == Money || right == Money => {
+                match (operator, left, right) {
+                    (BinaryOp::Subtract, Money, Money | Any) | (BinaryOp::Subtract, Any, Money) => Money,
+                    (BinaryOp::Multiply, Money, Number | Any) | (BinaryOp::Multiply, Number | Any, Money) => Money,
+                    (BinaryOp::Divide, Money, Money) => Number,
+                    (BinaryOp::Divide, Money, Number) => Money,
+                    (BinaryOp::Divide, Money | Any, Any | Money) => Any,
+                    _ => {
+                        self.errors.push(format!("Money math doesn't work with a {} and a {} 💸", left, right));
+                        Any
+                    }
+                }
+            }
+            BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::IntDivide | BinaryOp::Modulo |
+            BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor | BinaryOp::ShiftLeft | BinaryOp::ShiftRight => {
+                if !compatible(Number, left) || !compatible(Number, right) {
+                    self.errors.push(format!("Math needs numbers, got a {} and a {} 🔢", left, right));
+                }
+                Number
+            }
+            BinaryOp::Greater | BinaryOp::GreaterEqual | BinaryOp::Less | BinaryOp::LessEqual => {
+                let money = left == Money || right == Money;
+                let fits = |side| if money { compatible(Money, side) } else { compatible(Number, side) };
+                if !fits(left) || !fits(right) {
+                    self.errors.push(format!("Can only compare numbers, got a {} and a {} ⚖️", left, right));
+                }
+                Bool
+            }
+            BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::And | BinaryOp::Or => Bool,
+        }
+    }
+    
+    /// What calling `name` hands back, after checking the arguments against its signature -
+    /// None for arguments that can't be checked before running
+    fn call_type(&mut self, name: &str, argument_types: Option<&[TypeAnnotation]>) -> TypeAnnotation {
+        // A variable holding a lambda wins over both, and what it hands back isn't known
+        if self.lookup(name) == TypeAnnotation::Function && !self.functions.contains_key(name) {
+            return TypeAnnotation::Any;
+        }
+        // The script's own function wins over a builtin of the same name
+        let Some(signature) = self.functions.get(name).cloned() else {
+            return builtin_return_type(name).unwrap_or(TypeAnnotation::Any);
+        };
+        let Some(argument_types) = argument_types else {
+            return signature.return_type.unwrap_or(TypeAnnotation::Any);
+        };
+        
+        // A ...rest parameter takes any number of arguments, none of them typed
+        let fixed = &signature.param_types[..signature.param_types.len() - usize::from(signature.rest)];
+        if signature.rest && argument_types.len() < fixed.len() {
+            self.errors.push(format!(
+                "'{}' takes at least {} arguments but gets {} 📊",
+                name, fixed.len(), argument_types.len()
+            ));
+        }
+        if !signature.rest && fixed.len() != argument_types.len() {
+            self.errors.push(format!(
+                "'{}' takes {} arguments but gets {} 📊",
+                name, fixed.len(), argument_types.len()
+            ));
+        }
+        
+        for (index, (expected, actual)) in fixed.iter().zip(argument_types).enumerate() {
+            if let Some(expected) = expected {
+                if !compatible(*expected, *actual) {
+                    self.errors.push(format!(
+                        "Argument {} of '{}' should be a {} but is a {} 📋",
+                        index + 1, name, expected, actual
+                    ));
+                }
+            }
+        }
+        
+        signature.return_type.unwrap_or(TypeAnnotation::Any)
+    }
+    
+    fn lookup(&self, name: &str) -> TypeAnnotation {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+            .or_else(|| self.functions.contains_key(name).then_some(TypeAnnotation::Function))
+            .unwrap_or(TypeAnnotation::Any)
+    }
+    
+    fn current_scope(&mut self) -> &mut HashMap<String, TypeAnnotation> {
+        self.scopes.last_mut().expect("type checker always has a global scope")
+    }
+}
+
+/// What `+x` and num() can make a number out of
+fn number_like(annotation: TypeAnnotation) -> bool {
+    matches!(annotation, TypeAnnotation::Number | TypeAnnotation::String | TypeAnnotation::Bool | TypeAnnotation::Money | TypeAnnotation::Any)
+}
+
+fn builtin_return_type(name: &str) -> Option<TypeAnnotation> {
+    match name {
+        "sqrt" | "num" | "abs" | "random" | "length" | "levenshtein" | "similarity" | "sum" | "avg" | "median" | "stddev" | "minOf" | "maxOf" |
+        "after" | "every" | "opsSoFar" | "heapSize" | "size" => Some(TypeAnnotation::Number),
+        "cancelTimer" => Some(TypeAnnotation::Bool),
+        "uppercase" | "inspect" | "markdown" | "buildUrl" | "colored" | "bold" => Some(TypeAnnotation::String),
+        "money" => Some(TypeAnnotation::Money),
+        "heapNew" => Some(TypeAnnotation::Heap),
+        "queue" => Some(TypeAnnotation::Queue),
+        "stack" => Some(TypeAnnotation::Stack),
+        "split" | "tokenize" | "parseCode" | "simulate" | "neighbors" | "bfs" | "dfs" | "sortBy" | "sortDesc" | "reversed" | "unique" | "divmod" => Some(TypeAnnotation::Array),
+        "scope" | "memStats" | "benchmark" | "graphNew" | "addEdge" | "attempt" | "ok" | "nah" | "groupBy" | "countBy" | "parseUrl" => Some(TypeAnnotation::Object),
+        _ => None,
+    }
+}
+
+/// `any` on either side always fits, otherwise the types have to match exactly
+fn compatible(expected: TypeAnnotation, actual: TypeAnnotation) -> bool {
+    expected == TypeAnnotation::Any || actual == TypeAnnotation::Any || expected == actual
+}
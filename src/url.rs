@@ -0,0 +1,138 @@
+//! ZLang URL - `parseUrl()` takes a link apart and `buildUrl()` puts one together, no string surgery 🔗
+//! A URL is an object of scheme, host, port, path, query and fragment, the query values already decoded
+
+use std::collections::HashMap;
+
+use crate::ast::{Literal, TypeAnnotation};
+use crate::error::ZLangError;
+
+/// The pieces of `text`. Port is nil when the URL doesn't name one, and a key that shows up
+/// twice in the query keeps its last value
+pub fn parse(text: &str) -> Result<HashMap<String, Literal>, ZLangError> {
+    let text = text.trim();
+    let not_a_url = |why: &str| ZLangError::new(&format!("'{}' isn't a URL bestie, {} 🔗", text, why));
+    
+    let (rest, fragment) = text.split_once('#').unwrap_or((text, ""));
+    let (scheme, rest) = rest.split_once("://").ok_or_else(|| not_a_url("it needs a scheme like https://"))?;
+    let scheme_ok = scheme.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !scheme_ok {
+        return Err(not_a_url("the scheme is messed up"));
+    }
+    
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, rest) = rest.split_at(authority_end);
+    // An IPv6 host like [::1] has colons of its own, only one after the ] starts a port
+    let (host, port) = match authority.rsplit_once(':').filter(|(_, port)| !port.ends_with(']')) {
+        Some((host, port)) => {
+            let port: u16 = port.parse().map_err(|_| not_a_url("the port has to be a number up to 65535"))?;
+            (host, Literal::Number(port as f64))
+        }
+        None => (authority, Literal::Nil),
+    };
+    if host.is_empty() {
+        return Err(not_a_url("there's no host"));
+    }
+    
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let query = query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), Literal::String(decode(value)))
+        })
+        .collect();
+    
+    let mut fields = HashMap::new();
+    fields.insert("scheme".to_string(), Literal::String(scheme.to_ascii_lowercase()));
+    fields.insert("host".to_string(), Literal::String(host.to_ascii_lowercase()));
+    fields.insert("port".to_string(), port);
+    fields.insert("path".to_string(), Literal::String(if path.is_empty() { "/".to_string() } else { path.to_string() }));
+    fields.insert("query".to_string(), Literal::Object(query));
+    fields.insert("fragment".to_string(), Literal::String(fragment.to_string()));
+    Ok(fields)
+}
+
+/// The URL for an object shaped like what `parse` gives back. Scheme and host are a must,
+/// the rest can be left out. Query keys go in sorted, so the same object always builds the same URL
+pub fn build(fields: &HashMap<String, Literal>) -> Result<String, ZLangError> {
+    let text = |name: &str, required: bool| match fields.get(name) {
+        Some(Literal::String(value)) if !(required && value.is_empty()) => Ok(value.clone()),
+        None | Some(Literal::Nil) if !required => Ok(String::new()),
+        Some(other) if !required => Err(ZLangError::new(&format!("buildUrl wants {} as a string, not a {} 🔗", name, TypeAnnotation::of(other)))),
+        _ => Err(ZLangError::new(&format!("buildUrl needs a {}, like {{scheme: \"https\", host: \"example.com\"}} 🔗", name))),
+    };
+    
+    let mut url = format!("{}://{}", text("scheme", true)?, text("host", true)?);
+    match fields.get("port") {
+        None | Some(Literal::Nil) => {}
+        Some(Literal::Number(port)) if port.fract() == 0.0 && (1.0..=65535.0).contains(port) => url.push_str(&format!(":{}", port)),
+        Some(other) => return Err(ZLangError::new(&format!("buildUrl wants the port as a number from 1 to 65535, not {} 🔗", other))),
+    }
+    
+    let path = text("path", false)?;
+    if !path.is_empty() && !path.starts_with('/') {
+        url.push('/');
+    }
+    url.push_str(&path);
+    
+    match fields.get("query") {
+        None | Some(Literal::Nil) => {}
+        Some(Literal::Object(query)) if query.is_empty() => {}
+        Some(Literal::Object(query)) => {
+            let mut pairs: Vec<(&String, &Literal)> = query.iter().collect();
+            pairs.sort_by_key(|(key, _)| *key);
+            let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("{}={}", encode(key), encode(&value.to_string()))).collect();
+            url.push('?');
+            url.push_str(&pairs.join("&"));
+        }
+        Some(other) => return Err(ZLangError::new(&format!("buildUrl wants the query as an object, not a {} 🔗", TypeAnnotation::of(other)))),
+    }
+    
+    let fragment = text("fragment", false)?;
+    if !fragment.is_empty() {
+        url.push('#');
+        url.push_str(&fragment);
+    }
+    Ok(url)
+}
+
+/// Query text made safe for a URL - letters, digits and `-_.~` stay, everything else becomes %XX
+fn encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Query text back to what it says - %XX escapes and `+` for spaces. A broken escape stays as written
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
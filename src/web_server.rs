@@ -1,6 +1,52 @@
 use std::io::prelude::*;
 use std::net::{TcpListener, TcpStream};
-use crate::{Lexer, Parser, Interpreter};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use crate::ast::Stmt;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::interpreter::{Capabilities, Interpreter, INTERPRETER_STACK_SIZE};
+use crate::modules::MemoryFiles;
+use crate::pragmas::Pragmas;
+use crate::resolver::Resolver;
+use crate::json;
+use crate::gallery;
+use crate::steps;
+use crate::achievements::{self, Sessions};
+use crate::challenges;
+use crate::collab::Rooms;
+use crate::draw::{self, Shape};
+use crate::stash::{MemoryStash, Stashes};
+use crate::console::Console;
+use crate::websocket::{self, WebSocket};
+
+/// Key presses a run holds on to while it's busy, past this the oldest ones get dropped
+const MAX_PENDING_KEYS: usize = 64;
+
+/// Everything the server remembers between requests, shared by every connection
+#[derive(Default)]
+struct ServerState {
+    sessions: Sessions,
+    rooms: Rooms,
+    stashes: Stashes,
+}
+
+impl ServerState {
+    /// The session's `stash()` values, or a throwaway stash for a request without a usable session
+    fn stash(&self, session: Option<&str>) -> MemoryStash {
+        match session {
+            Some(session) if achievements::is_valid_id(session) => self.stashes.for_session(session),
+            _ => MemoryStash::default(),
+        }
+    }
+}
 
 pub fn start_web_server() -> Result<(), Box<dyn std::error::Error>> {
     let port = std::env::var("PORT").unwrap_or_else(|_| "5003".to_string());
@@ -8,21 +54,25 @@ pub fn start_web_server() -> Result<(), Box<dyn std::error::Error>> {
     let listener = TcpListener::bind(&addr)?;
     println!("🌐 ZLang Web Server running on http://{}", addr);
     
+    let state = Arc::new(ServerState::default());
     for stream in listener.incoming() {
         let stream = stream?;
-        handle_connection(stream)?;
+        handle_connection(stream, &state)?;
     }
     
     Ok(())
 }
 
-fn handle_connection(mut stream: TcpStream) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_connection(mut stream: TcpStream, state: &Arc<ServerState>) -> Result<(), Box<dyn std::error::Error>> {
     use std::io::BufReader;
     use std::io::BufRead;
     
     let mut reader = BufReader::new(&mut stream);
     let mut request_lines = Vec::new();
     let mut content_length = 0;
+    let mut websocket_key = None;
+    // Which playground tab this is, for achievements - the page makes one up and keeps it
+    let mut session = None;
     
     // Read headers
     loop {
@@ -38,6 +88,13 @@ fn handle_connection(mut stream: TcpStream) -> Result<(), Box<dyn std::error::Er
             }
         }
         
+        if line.to_lowercase().starts_with("sec-websocket-key:") {
+            websocket_key = line.split_once(':').map(|(_, key)| key.trim().to_string());
+        }
+        if line.to_lowercase().starts_with("x-zlang-session:") {
+            session = line.split_once(':').map(|(_, id)| id.trim().to_string());
+        }
+        
         request_lines.push(line);
     }
     
@@ -52,28 +109,114 @@ fn handle_connection(mut stream: TcpStream) -> Result<(), Box<dyn std::error::Er
     let request = format!("{}\r\n\r\n{}", request_lines.join(""), body);
     let request_line = request_lines.first().map(|s| s.as_str()).unwrap_or("");
     
+    // The terminal and rooms upgrade to a WebSocket and live on their own thread, the rest of the server keeps going
+    let path = request_path(request_line);
+    if let (true, Some(key)) = (request_line.starts_with("GET") && matches!(path, "/terminal" | "/collab" | "/run"), websocket_key) {
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            websocket::accept_key(&key)
+        );
+        stream.write_all(response.as_bytes())?;
+        stream.flush()?;
+        
+        // Browsers can't set headers on a WebSocket, so the session and room ride in the query
+        let socket = WebSocket::new(stream)?;
+        if path == "/run" {
+            let session = query_param(request_line, "session");
+            let state = Arc::clone(state);
+            thread::spawn(move || run_session(socket, session, state));
+        } else if path == "/collab" {
+            let room = query_param(request_line, "room").unwrap_or_default();
+            let state = Arc::clone(state);
+            thread::Builder::new()
+                .stack_size(INTERPRETER_STACK_SIZE)
+                .spawn(move || collab_session(socket, &room, &state.rooms))?;
+        } else {
+            let session = query_param(request_line, "session");
+            let state = Arc::clone(state);
+            thread::Builder::new()
+                .stack_size(INTERPRETER_STACK_SIZE)
+                .spawn(move || terminal_session(socket, session, &state))?;
+        }
+        return Ok(());
+    }
+    
     let (status_line, contents) = if request_line.starts_with("OPTIONS") {
         ("HTTP/1.1 200 OK", String::new())
     } else if request_line.starts_with("GET / ") {
         ("HTTP/1.1 200 OK", get_html_page())
-    } else if request_line.starts_with("POST /execute") {
+    } else if request_line.starts_with("GET /achievements") {
+        let session = query_param(request_line, "session").unwrap_or_default();
+        ("HTTP/1.1 200 OK", state.sessions.to_json(&session))
+    } else if request_line.starts_with("GET /challenges") {
+        ("HTTP/1.1 200 OK", challenges::list_json())
+    } else if request_line.starts_with("GET /challenge/") || request_line.starts_with("POST /challenge/") {
+        let id = request_path(request_line).trim_start_matches("/challenge/");
+        match challenges::find(id) {
+            Some(challenge) if request_line.starts_with("GET") => ("HTTP/1.1 200 OK", challenge.to_json()),
+            Some(challenge) => {
+                let code = extract_post_body(&request);
+                let results = challenge.submit(&code);
+                ("HTTP/1.1 200 OK", challenges::results_json(&results, code.chars().count()))
+            }
+            None => (
+                "HTTP/1.1 404 NOT FOUND",
+                format_json_response(&Err(format!("No challenge called '{}' 🔍", id))),
+            ),
+        }
+    } else if request_line.starts_with("POST /execute?mode=steps") {
         let body = extract_post_body(&request);
+        match execute_guarded(body, execute_steps) {
+            Some(Ok(steps)) => ("HTTP/1.1 200 OK", steps),
+            Some(Err(error)) => ("HTTP/1.1 200 OK", format_json_response(&Err(error))),
+            None => (
+                "HTTP/1.1 500 INTERNAL SERVER ERROR",
+                format_json_response(&Err("The interpreter had a moment 💀 That input broke something on our end, not yours.".to_string())),
+            ),
+        }
+    } else if request_line.starts_with("POST /execute") {
+        // A project's entry file is the code that counts toward the session
+        let project = extract_project(&body);
+        let body = match &project {
+            Some(project) => project.files.get(&project.entry).cloned().unwrap_or_default(),
+            None => extract_post_body(&request),
+        };
         eprintln!("DEBUG: Extracted body from request: '{}'", body);
-        let result = execute_zlang_code(&body);
-        ("HTTP/1.1 200 OK", format_json_response(&result))
+        let stash = state.stash(session.as_deref());
+        let result = execute_guarded(body.clone(), move |code| {
+            let interpreter = Interpreter::new().with_stash(Box::new(stash));
+            match project {
+                Some(project) => execute_project(code, project, interpreter),
+                None => execute_zlang_code(code, interpreter, false),
+            }
+        });
+        if let Some(session) = &session {
+            state.sessions.record(session, &body, matches!(result, Some(Ok(_))));
+        }
+        match result {
+            Some(result) => ("HTTP/1.1 200 OK", format_run_response(&result)),
+            None => (
+                "HTTP/1.1 500 INTERNAL SERVER ERROR",
+                format_json_response(&Err("The interpreter had a moment 💀 That input broke something on our end, not yours.".to_string())),
+            ),
+        }
     } else {
         ("HTTP/1.1 404 NOT FOUND", "404 Not Found".to_string())
     };
     
     let response = format!(
-        "{}\r\nContent-Type: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\nAccess-Control-Allow-Headers: Content-Type\r\nContent-Length: {}\r\n\r\n{}",
+        "{}\r\nContent-Type: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\nAccess-Control-Allow-Headers: Content-Type, X-ZLang-Session\r\nContent-Length: {}\r\n\r\n{}",
         status_line,
-        if request_line.starts_with("POST") { "application/json" } else { "text/html" },
+        if request_line.starts_with("POST") || request_line.starts_with("GET /achievements") || request_line.starts_with("GET /challenge") {
+            "application/json"
+        } else {
+            "text/html"
+        },
         contents.len(),
         contents
     );
     
-    stream.write(response.as_bytes())?;
+    stream.write_all(response.as_bytes())?;
     stream.flush()?;
     
     Ok(())
@@ -93,14 +236,12 @@ fn extract_post_body(request: &str) -> String {
             let after_code = &body[code_pos + 7..]; // Skip "code":
             let after_code = after_code.trim_start();
             
-            if after_code.starts_with('"') {
+            if let Some(content) = after_code.strip_prefix('"') {
                 // Find the closing quote, handling escaped quotes
-                let content = &after_code[1..]; // Skip opening quote
-                let mut chars = content.chars();
                 let mut result = String::new();
                 let mut escaped = false;
                 
-                while let Some(ch) = chars.next() {
+                for ch in content.chars() {
                     if escaped {
                         match ch {
                             'n' => result.push('\n'),
@@ -132,57 +273,393 @@ fn extract_post_body(request: &str) -> String {
     String::new()
 }
 
-fn execute_zlang_code(code: &str) -> Result<String, String> {
+/// A playground project - `{"files": {"main.zlang": "...", "utils.zlang": "..."}, "entry": "main.zlang"}` -
+/// whose entry file can pull the others
+struct Project {
+    files: HashMap<String, String>,
+    entry: String,
+}
+
+/// The project in an `/execute` body, None when it's the plain `{"code": "..."}` kind
+fn extract_project(body: &str) -> Option<Project> {
+    let after_files = &body[body.find("\"files\":")? + 8..];
+    let mut rest = after_files.trim_start().strip_prefix('{')?.trim_start();
+    let mut files = HashMap::new();
+    while !rest.starts_with('}') {
+        let (name, after_name) = json::parse_string(rest)?;
+        let (source, after_source) = json::parse_string(after_name.trim_start().strip_prefix(':')?.trim_start())?;
+        files.insert(name, source);
+        rest = after_source.trim_start();
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma.trim_start();
+        } else if !rest.starts_with('}') {
+            return None;
+        }
+    }
+    
+    let (entry, _) = json::parse_string(body[body.find("\"entry\":")? + 8..].trim_start())?;
+    Some(Project { files, entry })
+}
+
+/// Run playground code on its own worker thread behind a panic boundary.
+/// Returns None if the interpreter panicked, so one bad input can't take the server down.
+fn execute_guarded<T, F>(code: String, execute: F) -> Option<Result<T, String>>
+where
+    T: Send + 'static,
+    F: FnOnce(&str) -> Result<T, String> + Send + 'static,
+{
+    let worker = thread::Builder::new()
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(move || panic::catch_unwind(AssertUnwindSafe(|| execute(&code))).ok());
+    
+    match worker {
+        Ok(handle) => handle.join().ok().flatten(),
+        Err(_) => None,
+    }
+}
+
+/// What a playground run hands back: its printed output and anything it drew
+struct PlaygroundRun {
+    output: String,
+    drawing: Vec<Shape>,
+}
+
+/// Run a project's entry file, `code`, with its pulls reaching the other files the project
+/// brought along and nothing on the server's disk
+fn execute_project(code: &str, project: Project, interpreter: Interpreter) -> Result<PlaygroundRun, String> {
+    if !project.files.contains_key(&project.entry) {
+        return Err(format!("The project has no file called '{}' to start from 📁", project.entry));
+    }
+    let files = Arc::new(MemoryFiles::new(project.files));
+    execute_zlang_code(code, interpreter.with_program_files(Path::new(&project.entry), files), true)
+}
+
+/// Check and run `code` on `interpreter`, which brings whatever console and stash the caller wants.
+/// `pull` only goes on when the interpreter's files are ones the request sent
+fn execute_zlang_code(code: &str, mut interpreter: Interpreter, pull: bool) -> Result<PlaygroundRun, String> {
     if code.trim().is_empty() {
-        return Ok("// Enter some ZLang code and hit Run!".to_string());
+        return Ok(PlaygroundRun { output: "// Enter some ZLang code and hit Run!".to_string(), drawing: Vec::new() });
     }
     
-    let mut lexer = Lexer::new(code);
-    let tokens = match lexer.tokenize() {
-        Ok(tokens) => tokens,
-        Err(e) => return Err(format!("Lexer Error: {}", e)),
+    let statements = parse_program(code, &mut interpreter)?;
+    
+    let pragmas = Pragmas::scan(code, HashMap::new());
+    let pulled = if pull { interpreter.pulled_names(&statements) } else { HashSet::new() };
+    let diagnostics = Resolver::with_known_names(pulled).with_pragmas(pragmas.clone()).resolve(&statements);
+    if let Some(error) = diagnostics.error() {
+        return Err(format!("Resolver Error: {}", error));
+    }
+    
+    // A one-shot run has no terminal to answer spill(), it'd be stuck reading the server's stdin
+    interpreter.follow_pragmas(pragmas);
+    let result = interpreter
+        .with_capabilities(Capabilities { input: false, pull, fetch: false })
+        .interpret(statements)
+        .map_err(|e| format!("Runtime Error: {}", e))?;
+    let mut output = result.output.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    
+    for warning in diagnostics.warnings.into_iter().chain(result.warnings) {
+        output.push_str(&format!("⚠️ {}\n", warning));
+    }
+    
+    if output.is_empty() {
+        output = if result.drawing.is_empty() {
+            "// Code executed successfully (no output)".to_string()
+        } else {
+            format!("// Drew {} shape(s), check the canvas 🎨", result.drawing.len())
+        };
+    }
+    
+    Ok(PlaygroundRun { output: output.trim_end().to_string(), drawing: result.drawing })
+}
+
+/// Like `execute_zlang_code`, but answers with the JSON list of steps the run took
+fn execute_steps(code: &str) -> Result<String, String> {
+    let tokens = Lexer::new(code).tokenize().map_err(|e| format!("Lexer Error: {}", e))?;
+    let (statements, spans) = Parser::new(tokens)
+        .parse_with_statement_spans()
+        .map_err(|e| format!("Parser Error: {}", e))?;
+    
+    let diagnostics = Resolver::new().resolve(&statements);
+    if let Some(error) = diagnostics.error() {
+        return Err(format!("Resolver Error: {}", error));
+    }
+    
+    Ok(steps::run(statements, &spans, steps::LIMIT).to_json(code))
+}
+
+/// Prints, `spill()` prompts and `sleep()` frames for a terminal session or a streamed run,
+/// sent down the socket as they happen
+struct SocketConsole {
+    socket: Rc<RefCell<WebSocket>>,
+    // Filled by the connection's reader thread, only a streaming run gets any
+    keys: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl Console for SocketConsole {
+    fn print(&mut self, line: &str) {
+        let _ = self.socket.borrow_mut().send(&terminal_message("output", line));
+    }
+    
+    fn read_line(&mut self, prompt: &str) -> Option<String> {
+        let mut socket = self.socket.borrow_mut();
+        socket.send(&format!("{{\"type\": \"input\", \"prompt\": {}}}", json::string(prompt))).ok()?;
+        socket.receive()
+    }
+    
+    fn draw(&mut self, shapes: &[Shape]) {
+        let _ = self.socket.borrow().send(&format!("{{\"type\": \"drawing\", \"shapes\": {}}}", draw::to_json(shapes)));
+    }
+    
+    fn keys(&mut self) -> Vec<String> {
+        self.keys.lock().map(|mut keys| keys.drain(..).collect()).unwrap_or_default()
+    }
+    
+    /// The terminal is xterm.js and the output pane turns escapes into styled spans, both can take it
+    fn colors(&self) -> bool {
+        true
+    }
+}
+
+/// One interpreter per connection, so variables and functions stick around between lines like the REPL.
+/// Every text message from the client is one line of code. spill() gets answered through the socket,
+/// but the server's files are nobody's business
+fn terminal_session(socket: WebSocket, session: Option<String>, state: &ServerState) {
+    let socket = Rc::new(RefCell::new(socket));
+    let mut interpreter = Interpreter::new()
+        .with_console(Box::new(SocketConsole { socket: Rc::clone(&socket), keys: Arc::default() }))
+        .with_stash(Box::new(state.stash(session.as_deref())))
+        .with_capabilities(Capabilities { input: true, pull: false, fetch: false });
+    
+    loop {
+        if socket.borrow_mut().send("{\"type\": \"prompt\"}").is_err() {
+            return;
+        }
+        let Some(line) = socket.borrow_mut().receive() else {
+            return;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        
+        let (messages, succeeded) = match panic::catch_unwind(AssertUnwindSafe(|| run_terminal_line(&mut interpreter, &line))) {
+            Ok(outcome) => outcome,
+            Err(_) => (vec![terminal_message("error", "The interpreter had a moment 💀 That input broke something on our end, not yours.")], false),
+        };
+        if let Some(session) = &session {
+            state.sessions.record(session, &line, succeeded);
+        }
+        for message in messages {
+            if socket.borrow_mut().send(&message).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// The playground's Run button: the first message is the code, prints and `sleep()` frames
+/// stream back while it runs, and a `result` message ends it. `key` followed by a newline and
+/// the key's name goes to the script's onKey() handler. Sending `stop` or closing the
+/// socket interrupts the run, so an endless animation loop dies with its tab
+fn run_session(socket: WebSocket, session: Option<String>, state: Arc<ServerState>) {
+    let mut socket = socket;
+    let Some(code) = socket.receive() else {
+        return;
     };
+    let interrupt = Arc::new(AtomicBool::new(false));
+    let keys: Arc<Mutex<VecDeque<String>>> = Arc::default();
     
-    let mut parser = Parser::new(tokens);
-    let statements = match parser.parse() {
-        Ok(statements) => statements,
-        Err(e) => return Err(format!("Parser Error: {}", e)),
+    let worker = {
+        let (socket, interrupt, keys, state) = match socket.try_clone() {
+            Ok(handle) => (handle, Arc::clone(&interrupt), Arc::clone(&keys), Arc::clone(&state)),
+            Err(_) => return,
+        };
+        thread::Builder::new()
+            .stack_size(INTERPRETER_STACK_SIZE)
+            .spawn(move || {
+                let socket = Rc::new(RefCell::new(socket));
+                let interpreter = Interpreter::new()
+                    .with_console(Box::new(SocketConsole { socket: Rc::clone(&socket), keys }))
+                    .with_stash(Box::new(state.stash(session.as_deref())))
+                    .with_interrupt(Arc::clone(&interrupt));
+                let result = panic::catch_unwind(AssertUnwindSafe(|| execute_zlang_code(&code, interpreter, false)))
+                    .unwrap_or_else(|_| Err("The interpreter had a moment 💀 That input broke something on our end, not yours.".to_string()))
+                    // The interpreter's message is about Ctrl+C, the playground has a button for it
+                    .map_err(|error| if interrupt.load(Ordering::Relaxed) {
+                        "Stopped, you hit Stop so we stopped right there ✋".to_string()
+                    } else {
+                        error
+                    });
+                if let Some(session) = &session {
+                    state.sessions.record(session, &code, result.is_ok());
+                }
+                
+                let socket = socket.borrow();
+                let _ = socket.send(&run_result_message(&result));
+                socket.close();
+            })
     };
+    if worker.is_err() {
+        return;
+    }
     
-    let mut interpreter = Interpreter::new();
-    let mut output = String::new();
+    // Key presses get queued up for the run, whatever else comes next - a stop, the tab closing,
+    // or our own close once the run's done - means the run is over
+    while let Some(message) = socket.receive() {
+        let Some(key) = message.strip_prefix("key\n") else {
+            break;
+        };
+        if key.is_empty() || key.chars().count() > 32 {
+            continue;
+        }
+        if let Ok(mut keys) = keys.lock() {
+            if keys.len() >= MAX_PENDING_KEYS {
+                keys.pop_front();
+            }
+            keys.push_back(key.to_string());
+        }
+    }
+    interrupt.store(true, Ordering::Relaxed);
+}
+
+/// One browser in a live-coding room. It sends `edit` followed by a newline and the whole
+/// editor, or just `run` - the room's current code runs and everyone gets the result
+fn collab_session(socket: WebSocket, room: &str, rooms: &Rooms) {
+    let close_with = |error: &str| {
+        let _ = socket.send(&format!("{{\"type\": \"error\", \"text\": {}}}", json::string(error)));
+    };
+    if !achievements::is_valid_id(room) {
+        close_with("Room names are letters, numbers and dashes, 64 max 🏷️");
+        return;
+    }
+    // One stuck browser shouldn't hold up the rest of the room
+    let _ = socket.set_write_timeout(Some(Duration::from_secs(5)));
+    let member = match socket.try_clone().map_err(|e| e.to_string()).and_then(|handle| rooms.join(room, handle)) {
+        Ok(member) => member,
+        Err(error) => {
+            close_with(&error);
+            return;
+        }
+    };
     
-    for statement in &statements {
-        match interpreter.execute_stmt(statement) {
-            Ok(Some(result)) => {
-                output.push_str(&result);
-                output.push('\n');
-            },
-            Ok(None) => {},
-            Err(e) => return Err(format!("Runtime Error: {}", e)),
+    let mut socket = socket;
+    while let Some(message) = socket.receive() {
+        if let Some(code) = message.strip_prefix("edit\n") {
+            rooms.edit(room, member, code);
+        } else if message == "run" {
+            rooms.broadcast(room, &format!("{{\"type\": \"running\", \"by\": {}}}", member));
+            // The room has no session of its own, stashed values last for the one run
+            let result = execute_guarded(rooms.code(room), |code| execute_zlang_code(code, Interpreter::new(), false)).unwrap_or_else(|| {
+                Err("The interpreter had a moment 💀 That input broke something on our end, not yours.".to_string())
+            });
+            rooms.broadcast(room, &run_result_message(&result));
         }
     }
+    rooms.leave(room, member);
+}
+
+/// Run one terminal line, answering with whatever didn't already stream out while it ran
+/// and whether it got through without an error
+fn run_terminal_line(interpreter: &mut Interpreter, line: &str) -> (Vec<String>, bool) {
+    let statements = match parse_program(line, interpreter) {
+        Ok(statements) => statements,
+        Err(error) => return (vec![terminal_message("error", &error)], false),
+    };
     
-    if output.is_empty() {
-        output = "// Code executed successfully (no output)".to_string();
+    let pragmas = Pragmas::scan(line, HashMap::new());
+    let diagnostics = Resolver::with_known_names(interpreter.known_names()).with_pragmas(pragmas.clone()).resolve(&statements);
+    interpreter.follow_pragmas(pragmas);
+    let mut messages: Vec<String> = diagnostics.warnings.iter()
+        .map(|warning| terminal_message("warning", warning))
+        .collect();
+    if let Some(error) = diagnostics.error() {
+        messages.push(terminal_message("error", &format!("Resolver Error: {}", error)));
+        return (messages, false);
     }
     
-    Ok(output.trim_end().to_string())
+    let mut succeeded = false;
+    match interpreter.interpret(statements) {
+        Ok(result) => {
+            succeeded = true;
+            messages.extend(result.warnings.iter().map(|warning| terminal_message("warning", warning)));
+            if let Some(value) = result.value {
+                messages.push(terminal_message("value", &value.inspect()));
+            }
+            if !result.drawing.is_empty() {
+                messages.push(format!("{{\"type\": \"drawing\", \"shapes\": {}}}", draw::to_json(&result.drawing)));
+            }
+        }
+        Err(error) => {
+            messages.extend(interpreter.take_warnings().iter().map(|warning| terminal_message("warning", warning)));
+            messages.push(terminal_message("error", &format!("Runtime Error: {}", error)));
+        }
+    }
+    (messages, succeeded)
 }
 
-fn format_json_response(result: &Result<String, String>) -> String {
+/// The request line's path, without its query string
+fn request_path(request_line: &str) -> &str {
+    let target = request_line.split_whitespace().nth(1).unwrap_or("");
+    target.split_once('?').map_or(target, |(path, _)| path)
+}
+
+/// Value of `name` in the request line's query string
+fn query_param(request_line: &str, name: &str) -> Option<String> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+fn terminal_message(kind: &str, text: &str) -> String {
+    format!("{{\"type\": \"{}\", \"text\": {}}}", kind, json::string(text))
+}
+
+/// Parse `code` to run on `interpreter`, knowing the memes its earlier code declared
+fn parse_program(code: &str, interpreter: &mut Interpreter) -> Result<Vec<Stmt>, String> {
+    let mut lexer = Lexer::new(code);
+    let tokens = lexer.tokenize().map_err(|e| format!("Lexer Error: {}", e))?;
+    
+    let mut parser = Parser::new(tokens).with_memes(interpreter.memes().clone());
+    let statements = parser.parse().map_err(|e| format!("Parser Error: {}", e))?;
+    interpreter.remember_memes(parser.memes());
+    Ok(statements)
+}
+
+/// A finished run as a WebSocket message, for the streaming Run button and rooms
+fn run_result_message(result: &Result<PlaygroundRun, String>) -> String {
+    match result {
+        Ok(run) => format!(
+            "{{\"type\": \"result\", \"success\": true, \"output\": {}, \"drawing\": {}}}",
+            json::string(&run.output),
+            draw::to_json(&run.drawing)
+        ),
+        Err(error) => format!("{{\"type\": \"result\", \"success\": false, \"error\": {}}}", json::string(error)),
+    }
+}
+
+/// Same as `format_json_response`, plus the shapes to put on the canvas
+fn format_run_response(result: &Result<PlaygroundRun, String>) -> String {
     match result {
-        Ok(output) => format!("{{\"success\": true, \"output\": \"{}\"}}", escape_json(output)),
-        Err(error) => format!("{{\"success\": false, \"error\": \"{}\"}}", escape_json(error)),
+        Ok(run) => format!(
+            "{{\"success\": true, \"output\": {}, \"drawing\": {}}}",
+            json::string(&run.output),
+            draw::to_json(&run.drawing)
+        ),
+        Err(error) => format_json_response(&Err(error.clone())),
     }
 }
 
-fn escape_json(s: &str) -> String {
-    s.replace("\\", "\\\\")
-     .replace("\"", "\\\"")
-     .replace("\n", "\\n")
-     .replace("\r", "\\r")
-     .replace("\t", "\\t")
+fn format_json_response(result: &Result<String, String>) -> String {
+    match result {
+        Ok(output) => format!("{{\"success\": true, \"output\": \"{}\"}}", json::escape(output)),
+        Err(error) => format!("{{\"success\": false, \"error\": \"{}\"}}", json::escape(error)),
+    }
 }
 
 fn get_html_page() -> String {
@@ -196,6 +673,8 @@ fn get_html_page() -> String {
     <link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/codemirror/5.65.2/theme/monokai.min.css">
     <script src="https://cdnjs.cloudflare.com/ajax/libs/codemirror/5.65.2/codemirror.min.js"></script>
     <script src="https://cdnjs.cloudflare.com/ajax/libs/codemirror/5.65.2/mode/javascript/javascript.min.js"></script>
+    <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/xterm@5.3.0/css/xterm.css">
+    <script src="https://cdn.jsdelivr.net/npm/xterm@5.3.0/lib/xterm.js"></script>
     <style>
         * {
             margin: 0;
@@ -252,6 +731,42 @@ fn get_html_page() -> String {
             backdrop-filter: blur(10px);
         }
         
+        .terminal-panel {
+            background: rgba(0,0,0,0.2);
+            border-radius: 10px;
+            padding: 20px;
+            margin-bottom: 30px;
+            backdrop-filter: blur(10px);
+        }
+        
+        .badges {
+            display: flex;
+            flex-wrap: wrap;
+            gap: 10px;
+        }
+        
+        .badge {
+            background: rgba(255,255,255,0.1);
+            border-radius: 20px;
+            padding: 8px 14px;
+            opacity: 0.35;
+        }
+        
+        .badge.earned {
+            opacity: 1;
+        }
+        
+        .badge.fresh {
+            box-shadow: 0 0 12px #ffd700;
+        }
+        
+        #terminal {
+            background: #1a1a1a;
+            border-radius: 5px;
+            padding: 10px;
+            height: 350px;
+        }
+        
         .panel-header {
             display: flex;
             justify-content: space-between;
@@ -327,6 +842,14 @@ fn get_html_page() -> String {
             font-style: italic !important;
         }
         
+        #canvas {
+            display: none;
+            background: white;
+            border-radius: 5px;
+            margin-top: 15px;
+            max-width: 100%;
+        }
+        
         #output {
             background: #1a1a1a;
             color: #f8f8f2;
@@ -418,13 +941,7 @@ fn get_html_page() -> String {
         <section class="examples">
             <h3>Quick Examples</h3>
             <div class="example-buttons">
-                <button class="example-button" onclick="loadExample('hello')">Hello World</button>
-                <button class="example-button" onclick="loadExample('variables')">Variables</button>
-                <button class="example-button" onclick="loadExample('functions')">Functions</button>
-                <button class="example-button" onclick="loadExample('loops')">Loops</button>
-                <button class="example-button" onclick="loadExample('conditions')">If/Else</button>
-                <button class="example-button" onclick="loadExample('errors')">Error Handling</button>
-                <button class="example-button" onclick="loadExample('comprehensive')">All Features</button>
+__EXAMPLE_BUTTONS__
                 <button class="example-button" onclick="clearEditor()">New File</button>
             </div>
         </section>
@@ -440,12 +957,39 @@ fn get_html_page() -> String {
                 </div>
             </div>
             
-            <div class="output-panel">
+            <div class="output-panel" id="outputPanel">
                 <div class="panel-header">
                     <h3>Output</h3>
                 </div>
-                <div id="output">// Click 'Run Code' to see output here</div>
+                <div id="output" tabindex="0">// Click 'Run Code' to see output here</div>
+                <canvas id="canvas" tabindex="0" width="__CANVAS_WIDTH__" height="__CANVAS_HEIGHT__"></canvas>
+            </div>
+        </section>
+        
+        <section class="terminal-panel">
+            <div class="panel-header">
+                <h3>Terminal</h3>
+                <button class="run-button" onclick="startTerminal()" id="terminalBtn">Start Terminal</button>
+            </div>
+            <div id="terminal"></div>
+        </section>
+        
+        <section class="terminal-panel">
+            <div class="panel-header">
+                <h3>Live Room</h3>
+                <div>
+                    <input type="text" id="roomInput" placeholder="room name, e.g. cs101">
+                    <button class="run-button" onclick="joinRoom()" id="roomBtn">Join Room</button>
+                </div>
             </div>
+            <div id="roomStatus">Join the same room as your class and everyone edits and runs one shared file 👯</div>
+        </section>
+        
+        <section class="terminal-panel">
+            <div class="panel-header">
+                <h3>Achievements</h3>
+            </div>
+            <div class="badges" id="badges"></div>
         </section>
         
         <section class="keywords">
@@ -501,9 +1045,9 @@ fn get_html_page() -> String {
                     </a>
                 </div>
             </div>
-
+            
             <div class="tech-content" style="background: #ffffff; border: 2px solid #e9ecef; padding: 20px; border-radius: 10px; margin: 20px 0; color: #333333;">
-                
+            
                 <!-- Simple Explanation Section -->
                 <div class="tech-section" style="background: #f8f9fa; padding: 20px; border-radius: 10px; margin-bottom: 25px; border-left: 5px solid #667eea;">
                     <h4 style="color: #2c3e50; margin-bottom: 15px; font-size: 20px;">📚 Simple Explanation: How We Built ZLang</h4>
@@ -516,7 +1060,7 @@ fn get_html_page() -> String {
                             Computer sees: <span style="color: #27ae60;">bet</span> (keyword) + <span style="color: #3498db;">name</span> (identifier) + <span style="color: #f39c12;">=</span> (operator) + <span style="color: #27ae60;">"Alex"</span> (text)
                         </div>
                     </div>
-
+                    
                     <div style="margin-bottom: 20px;">
                         <h5 style="color: #667eea; margin-bottom: 8px;">Step 2: Understanding Grammar Rules</h5>
                         <p style="margin-bottom: 10px; line-height: 1.6;">We built rules so the computer knows how words fit together.</p>
@@ -527,7 +1071,7 @@ fn get_html_page() -> String {
                             • <span style="color: #e74c3c;">bruh [message]</span> → Print output
                         </div>
                     </div>
-
+                    
                     <div style="margin-bottom: 20px;">
                         <h5 style="color: #667eea; margin-bottom: 8px;">Step 3: Making It Actually Work</h5>
                         <p style="margin-bottom: 10px; line-height: 1.6;">We built an executor that does what the code says.</p>
@@ -539,7 +1083,7 @@ fn get_html_page() -> String {
                             4. Computer prints: Alex
                         </div>
                     </div>
-
+                    
                     <div>
                         <h5 style="color: #667eea; margin-bottom: 8px;">Step 4: Which Files Do What</h5>
                         <div style="display: grid; grid-template-columns: 1fr 1fr; gap: 15px; margin-top: 10px;">
@@ -562,7 +1106,7 @@ fn get_html_page() -> String {
                         </div>
                     </div>
                 </div>
-
+                
                 <div class="tech-section">
                     <h4 style="color: #2c3e50; margin-bottom: 10px;">🔧 Architecture Overview</h4>
                     <p>ZLang is a complete programming language interpreter built entirely in Rust, featuring a three-stage compilation pipeline:</p>
@@ -657,175 +1201,8 @@ fn get_html_page() -> String {
     </footer>
     
     <script>
-        const examples = {
-            hello: `bruh "Hello, World! ZLang hits different!"
-bet name = "Future Programmer"
-bruh "Welcome " + name + " to ZLang!"`,
-            
-            variables: `bet name = "Alex"
-bet age = 21
-bet is_student = fr
-bet gpa = 3.8
-
-bruh "Student: " + name
-bruh "Age: " + age
-bruh "GPA: " + gpa`,
-            
-            functions: `flex greet_squad(member) {
-    vibe "What's good " + member + "!"
-}
-
-flex calculate_grade(score) {
-    sus (score >= 90) {
-        vibe "A - Absolutely slaying!"
-    } no sus {
-        vibe "Need to grind harder!"
-    }
-}
-
-bet greeting = greet_squad("Bestie")
-bruh greeting
-
-bet grade = calculate_grade(95)
-bruh grade`,
-            
-            loops: `// Simple counting loop
-bet counter = 1;
-bruh "Starting loop:";
-
-lowkey (counter <= 3) {
-    bruh "Count: " + counter;
-    bet counter = counter + 1;
-}
-bruh "Loop finished!";
-
-// Loop with break
-bet number = 1;
-bruh "Break example:";
-lowkey (number <= 10) {
-    sus (number == 3) {
-        bruh "Breaking at 3!";
-        slay;
-    }
-    bruh "Number: " + number;
-    bet number = number + 1;
-}`,
-            
-            conditions: `// If/Else If/Else with sus/lowkey sus/no sus
-bet score = 87;
-
-sus (score >= 90) {
-    bruh "A+ grade! Absolutely crushing it!";
-} lowkey sus (score >= 80) {
-    bruh "B grade! Still pretty solid!";
-} lowkey sus (score >= 70) {
-    bruh "C grade! You can do better!";
-} no sus {
-    bruh "Time to hit the books harder!";
-}
-
-// Boolean conditions
-bet is_weekend = fr;
-bet energy_level = 85;
-
-sus (is_weekend == fr) {
-    bruh "Weekend vibes!";
-    sus (energy_level > 80) {
-        bruh "High energy weekend - time to party!";
-    } no sus {
-        bruh "Low energy weekend - Netflix time!";
-    }
-} no sus {
-    bruh "Weekday grind continues!";
-}`,
-
-            errors: `// Error Handling with manifest/caught/drama/frfr
-bruh "=== Error Handling Demo ==="
-
-// Basic try-catch with manifest/caught
-manifest {
-    bruh "Trying something risky..."
-    bet risky_number = 100
-    sus (risky_number > 50) {
-        drama "Number too high! Drama incoming!"
-    }
-    bruh "This won't print if error thrown"
-} caught (error) {
-    bruh "Caught the drama: " + error
-}
-
-// Try-catch-finally with frfr
-bruh "\\nTry-catch-finally example:"
-manifest {
-    bruh "Attempting operation..."
-    drama "Intentional error for demo"
-} caught (err) {
-    bruh "Error handled: " + err
-} frfr {
-    bruh "This always runs - cleanup time!"
-}
-
-// Nested error handling
-bruh "\\nNested error handling:"
-manifest {
-    manifest {
-        bruh "Inner try block"
-        drama "Inner error"
-    } caught (inner) {
-        bruh "Inner catch: " + inner
-        drama "Outer error triggered"
-    }
-} caught (outer) {
-    bruh "Outer catch: " + outer
-} frfr {
-    bruh "Final cleanup complete!"
-}`,
-
-            comprehensive: `// ZLang - All Gen Z Keywords Demo
-bruh "=== ZLang Comprehensive Demo ===";
-
-// Variables (bet)
-bet name = "Gen Z Coder";
-bet age = 20;
-bet is_coding = fr;
-
-bruh "Programmer: " + name;
-bruh "Age: " + age;
-
-// If/Else If/Else (sus/lowkey sus/no sus)
-bet score = 95;
-sus (score >= 90) {
-    bruh "A+ grade! Absolutely crushing it!";
-} lowkey sus (score >= 80) {
-    bruh "B grade! Still pretty solid!";
-} no sus {
-    bruh "Time to hit the books harder!";
-}
-
-// Simple loops
-bruh "=== Loop Demo ===";
-bet counter = 1;
-lowkey (counter <= 3) {
-    bruh "Counter: " + counter;
-    bet counter = counter + 1;
-}
-
-// Functions (flex/vibe)
-flex calculateGrade(score) {
-    sus (score >= 90) {
-        vibe "A+";
-    } lowkey sus (score >= 80) {
-        vibe "B";
-    } no sus {
-        vibe "F";
-    }
-}
-
-bet grade = calculateGrade(87);
-bruh "Your grade is: " + grade;
-
-bruh "=== All Keywords Working! ZLang hits different! ===";`
-        };
+        // Filled in from examples/playground by get_html_page
+        const examples = __EXAMPLES__;
         
         // Initialize CodeMirror
         let editor;
@@ -913,6 +1290,7 @@ bruh "=== All Keywords Working! ZLang hits different! ===";`
             
             // Set initial content
             editor.setValue(examples.hello);
+            editor.on('change', sendRoomEdit);
         }
         
         function loadExample(type) {
@@ -926,6 +1304,21 @@ bruh "=== All Keywords Working! ZLang hits different! ===";`
             const output = document.getElementById('output');
             const runBtn = document.getElementById('runBtn');
             
+            if (runSocket) {
+                if (runSocket.readyState === WebSocket.OPEN) {
+                    runSocket.send('stop');
+                } else {
+                    runSocket.close();
+                }
+                return;
+            }
+            
+            // In a room the server runs the shared code and tells everybody how it went
+            if (roomSocket && roomSocket.readyState === WebSocket.OPEN) {
+                roomSocket.send('run');
+                return;
+            }
+            
             // Debug: Show what code we're trying to send
             console.log('Code to execute:', code);
             console.log('Code length:', code.length);
@@ -936,41 +1329,145 @@ bruh "=== All Keywords Working! ZLang hits different! ===";`
                 return;
             }
             
-            runBtn.disabled = true;
-            runBtn.textContent = 'Running...';
+            runBtn.textContent = 'Stop';
             output.textContent = 'Executing ZLang code...';
             output.style.color = '#f8f8f2';
             
-            try {
-                const requestBody = JSON.stringify({ code: code });
-                console.log('Sending request body:', requestBody);
-                
-                const response = await fetch(window.location.origin + '/execute', {
-                    method: 'POST',
-                    headers: {
-                        'Content-Type': 'application/json',
-                    },
-                    body: requestBody
-                });
-                
-                const result = await response.json();
-                console.log('Received response:', result);
-                
-                if (result.success) {
-                    output.textContent = result.output;
-                    output.style.color = '#f8f8f2';
-                } else {
-                    output.textContent = 'Error: ' + result.error;
+            // Runs stream over a WebSocket, so prints and sleep() frames show up while the code is still going
+            let started = false;
+            const style = {};
+            const show = function(text) {
+                if (!started) {
+                    output.textContent = '';
+                    started = true;
+                }
+                appendAnsi(output, text, style);
+            };
+            const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+            runSocket = new WebSocket(protocol + '//' + window.location.host + '/run?session=' + sessionId);
+            runSocket.onopen = function() {
+                runSocket.send(code);
+            };
+            runSocket.onmessage = function(event) {
+                const message = JSON.parse(event.data);
+                if (message.type === 'output') {
+                    show(message.text + '\n');
+                } else if (message.type === 'drawing') {
+                    renderDrawing(message.shapes);
+                } else if (message.type === 'result' && message.success) {
+                    // Placeholders like "no output" only make sense when nothing printed
+                    if (!started || !message.output.startsWith('//')) {
+                        show(message.output);
+                    }
+                    renderDrawing(message.drawing);
+                } else if (message.type === 'result') {
+                    show('Error: ' + message.error);
                     output.style.color = '#ff6b6b';
                 }
-            } catch (error) {
-                console.error('Network error:', error);
-                output.textContent = 'Network Error: ' + error.message;
+            };
+            runSocket.onerror = function() {
+                show('Network Error: lost the connection to the server');
                 output.style.color = '#ff6b6b';
+            };
+            runSocket.onclose = function() {
+                runSocket = null;
+                runBtn.textContent = 'Run Code';
+                refreshBadges();
+            };
+        }
+        
+        // A run that's still going (an animation loop, say) - hitting the button again stops it
+        let runSocket = null;
+        
+        // Click the output or the canvas while a run is going and key presses go to its onKey() handler
+        document.getElementById('outputPanel').addEventListener('keydown', function(event) {
+            if (!runSocket || runSocket.readyState !== WebSocket.OPEN || event.ctrlKey || event.metaKey || event.altKey) {
+                return;
+            }
+            runSocket.send('key\n' + event.key);
+            // Arrows and space would scroll the page out from under the game
+            event.preventDefault();
+        });
+        
+        // colored() and bold() arrive as ANSI escapes, this turns them into styled spans.
+        // `style` carries over between calls, a color can start on one line and end on the next
+        const ANSI_COLORS = {
+            30: '#6272a4', 31: '#ff6b6b', 32: '#50fa7b', 33: '#f1fa8c', 34: '#6b9bff',
+            35: '#ff79c6', 36: '#8be9fd', 37: '#f8f8f2', 90: '#999999'
+        };
+        
+        function appendAnsi(element, text, style) {
+            const escape = /\x1b\[([\d;]*)m/g;
+            let last = 0;
+            let match;
+            while ((match = escape.exec(text)) !== null) {
+                appendStyled(element, text.slice(last, match.index), style);
+                for (const code of match[1].split(';').map(Number)) {
+                    if (code === 0) {
+                        style.color = null;
+                        style.bold = false;
+                    } else if (code === 1) {
+                        style.bold = true;
+                    } else if (code === 22) {
+                        style.bold = false;
+                    } else if (code === 39) {
+                        style.color = null;
+                    } else if (ANSI_COLORS[code]) {
+                        style.color = ANSI_COLORS[code];
+                    }
+                }
+                last = escape.lastIndex;
+            }
+            appendStyled(element, text.slice(last), style);
+        }
+        
+        function appendStyled(element, text, style) {
+            if (!text) {
+                return;
+            }
+            if (!style.color && !style.bold) {
+                element.appendChild(document.createTextNode(text));
+                return;
+            }
+            const span = document.createElement('span');
+            span.textContent = text;
+            if (style.color) {
+                span.style.color = style.color;
+            }
+            if (style.bold) {
+                span.style.fontWeight = 'bold';
+            }
+            element.appendChild(span);
+        }
+        
+        // draw.line(...) and friends come back as a list of shapes, painted fresh after every run
+        function renderDrawing(shapes) {
+            const canvas = document.getElementById('canvas');
+            if (!shapes || shapes.length === 0) {
+                canvas.style.display = 'none';
+                return;
+            }
+            canvas.style.display = 'block';
+            const context = canvas.getContext('2d');
+            context.clearRect(0, 0, canvas.width, canvas.height);
+            context.lineWidth = 2;
+            context.font = '16px sans-serif';
+            for (const shape of shapes) {
+                context.strokeStyle = shape.color;
+                context.fillStyle = shape.color;
+                if (shape.shape === 'line') {
+                    context.beginPath();
+                    context.moveTo(shape.x1, shape.y1);
+                    context.lineTo(shape.x2, shape.y2);
+                    context.stroke();
+                } else if (shape.shape === 'circle') {
+                    context.beginPath();
+                    context.arc(shape.x, shape.y, shape.radius, 0, 2 * Math.PI);
+                    context.stroke();
+                } else if (shape.shape === 'text') {
+                    context.fillText(shape.text, shape.x, shape.y);
+                }
             }
-            
-            runBtn.disabled = false;
-            runBtn.textContent = 'Run Code';
         }
         
         function clearEditor() {
@@ -982,11 +1479,202 @@ bruh "=== All Keywords Working! ZLang hits different! ===";`
             output.style.color = '#4a90e2';
         }
         
+        // Achievements - the server counts runs per session, this tab's id lives in localStorage
+        const sessionId = localStorage.getItem('zlangSession') || (function() {
+            const id = Math.random().toString(36).slice(2) + Date.now().toString(36);
+            localStorage.setItem('zlangSession', id);
+            return id;
+        })();
+        let earnedBadges = null;
+        
+        async function refreshBadges() {
+            try {
+                const response = await fetch(window.location.origin + '/achievements?session=' + sessionId);
+                const result = await response.json();
+                const container = document.getElementById('badges');
+                container.innerHTML = '';
+                for (const badge of result.badges) {
+                    const element = document.createElement('span');
+                    element.className = 'badge' + (badge.earned ? ' earned' : '');
+                    if (badge.earned && earnedBadges && !earnedBadges.has(badge.id)) {
+                        element.className += ' fresh';
+                    }
+                    element.textContent = badge.emoji + ' ' + badge.title;
+                    element.title = badge.description;
+                    container.appendChild(element);
+                }
+                earnedBadges = new Set(result.badges.filter(badge => badge.earned).map(badge => badge.id));
+            } catch (error) {
+                console.error('Could not load achievements:', error);
+            }
+        }
+        
+        // Live rooms - every edit goes out whole and the last one wins, runs happen on the server
+        let roomSocket = null;
+        let applyingRoomEdit = false;
+        
+        function joinRoom() {
+            const room = document.getElementById('roomInput').value.trim();
+            const status = document.getElementById('roomStatus');
+            if (!room) {
+                status.textContent = 'Pick a room name first 🏷️';
+                return;
+            }
+            if (roomSocket) {
+                roomSocket.close();
+            }
+            
+            const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+            roomSocket = new WebSocket(protocol + '//' + window.location.host + '/collab?room=' + encodeURIComponent(room));
+            roomSocket.onopen = function() {
+                document.getElementById('roomBtn').textContent = 'Switch Room';
+            };
+            roomSocket.onmessage = function(event) {
+                const message = JSON.parse(event.data);
+                const output = document.getElementById('output');
+                switch (message.type) {
+                    case 'sync':
+                        // First one in sets the code, everyone after picks up what's there
+                        if (message.code) {
+                            applyRoomEdit(message.code);
+                        } else {
+                            roomSocket.send('edit\n' + editor.getValue());
+                        }
+                        break;
+                    case 'edit': applyRoomEdit(message.code); break;
+                    case 'members': status.textContent = '🟢 In room ' + room + ' with ' + message.count + ' ' + (message.count === 1 ? 'person' : 'people'); break;
+                    case 'running':
+                        output.textContent = "Executing the room's code...";
+                        output.style.color = '#f8f8f2';
+                        break;
+                    case 'result':
+                        output.textContent = message.success ? message.output : 'Error: ' + message.error;
+                        output.style.color = message.success ? '#f8f8f2' : '#ff6b6b';
+                        if (message.success) {
+                            renderDrawing(message.drawing);
+                        }
+                        break;
+                    case 'error': status.textContent = message.text; break;
+                }
+            };
+            roomSocket.onclose = function() {
+                document.getElementById('roomBtn').textContent = 'Join Room';
+                if (status.textContent.startsWith('🟢')) {
+                    status.textContent = '🔌 Left the room, your code stays put';
+                }
+            };
+        }
+        
+        function applyRoomEdit(code) {
+            if (code === editor.getValue()) {
+                return;
+            }
+            const cursor = editor.getCursor();
+            applyingRoomEdit = true;
+            editor.setValue(code);
+            editor.setCursor(cursor);
+            applyingRoomEdit = false;
+        }
+        
+        function sendRoomEdit() {
+            if (!applyingRoomEdit && roomSocket && roomSocket.readyState === WebSocket.OPEN) {
+                roomSocket.send('edit\n' + editor.getValue());
+            }
+        }
+        
+        // Terminal mode - one live interpreter per connection, so your bets stick around between lines
+        let terminal = null;
+        let terminalSocket = null;
+        let terminalLine = '';
+        
+        function startTerminal() {
+            if (!terminal) {
+                terminal = new Terminal({ cursorBlink: true, convertEol: true, theme: { background: '#1a1a1a' } });
+                terminal.open(document.getElementById('terminal'));
+                terminal.onData(handleTerminalData);
+            }
+            if (terminalSocket && terminalSocket.readyState <= WebSocket.OPEN) {
+                terminal.focus();
+                return;
+            }
+            
+            const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+            terminalSocket = new WebSocket(protocol + '//' + window.location.host + '/terminal?session=' + sessionId);
+            terminalLine = '';
+            
+            terminalSocket.onopen = function() {
+                document.getElementById('terminalBtn').textContent = 'Connected';
+                terminal.writeln('🔌 Connected! Your variables stick around until you leave, spill() asks you for input');
+                terminal.focus();
+            };
+            terminalSocket.onmessage = function(event) {
+                const message = JSON.parse(event.data);
+                switch (message.type) {
+                    case 'prompt': terminal.write('zlang> '); refreshBadges(); break;
+                    case 'input': terminal.write(message.prompt); break;
+                    case 'output': terminal.writeln(message.text); break;
+                    case 'value': terminal.writeln('👉 ' + message.text); break;
+                    case 'warning': terminal.writeln('\x1b[33m⚠️ ' + message.text + '\x1b[0m'); break;
+                    case 'error': terminal.writeln('\x1b[31m' + message.text + '\x1b[0m'); break;
+                    case 'drawing': renderDrawing(message.shapes); break;
+                }
+            };
+            terminalSocket.onclose = function() {
+                document.getElementById('terminalBtn').textContent = 'Reconnect';
+                terminal.writeln('\r\n🔌 Disconnected, hit Reconnect for a fresh session');
+            };
+        }
+        
+        function handleTerminalData(data) {
+            if (!terminalSocket || terminalSocket.readyState !== WebSocket.OPEN) {
+                return;
+            }
+            for (const ch of data) {
+                if (ch === '\r') {
+                    terminal.write('\r\n');
+                    terminalSocket.send(terminalLine);
+                    terminalLine = '';
+                } else if (ch === '\x7f') {
+                    if (terminalLine.length > 0) {
+                        terminalLine = Array.from(terminalLine).slice(0, -1).join('');
+                        terminal.write('\b \b');
+                    }
+                } else if (ch >= ' ') {
+                    terminalLine += ch;
+                    terminal.write(ch);
+                }
+            }
+        }
+        
         // Initialize CodeMirror when page loads
         document.addEventListener('DOMContentLoaded', function() {
             initCodeMirror();
+            refreshBadges();
         });
     </script>
 </body>
-</html>"#.to_string()
+</html>"#
+        .replace("__EXAMPLE_BUTTONS__", &example_buttons())
+        .replace("__EXAMPLES__", &examples_json())
+        .replace("__CANVAS_WIDTH__", &draw::WIDTH.to_string())
+        .replace("__CANVAS_HEIGHT__", &draw::HEIGHT.to_string())
+}
+
+fn example_buttons() -> String {
+    gallery::EXAMPLES.iter()
+        .map(|example| format!(
+            "                <button class=\"example-button\" onclick=\"loadExample('{}')\">{}</button>",
+            example.name, example.title
+        ))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The examples as a JS object literal - JSON strings are valid JS strings.
+/// `</` is escaped too so a snippet can never close the surrounding script tag
+fn examples_json() -> String {
+    let entries: Vec<String> = gallery::EXAMPLES.iter()
+        .map(|example| format!("{}: {}", json::string(example.name), json::string(example.source).replace("</", "<\\/")))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
 }
\ No newline at end of file
@@ -0,0 +1,204 @@
+//! ZLang WebSocket - Just enough RFC 6455 for the playground terminal and rooms 🔌
+//! Text frames in, text frames out, no extensions. Hand-rolled like the rest of the server
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Biggest message we'll accept, nobody types a megabyte into a terminal
+const MAX_MESSAGE: u64 = 1 << 20;
+
+/// The `Sec-WebSocket-Accept` value answering a client's `Sec-WebSocket-Key`
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{}{}", key.trim(), GUID).as_bytes()))
+}
+
+pub struct WebSocket {
+    stream: TcpStream,
+    /// Every handle on this connection writes through here, so frames never interleave
+    writer: Arc<Mutex<TcpStream>>,
+}
+
+impl WebSocket {
+    /// Wrap a connection whose handshake has already been answered
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        let writer = Arc::new(Mutex::new(stream.try_clone()?));
+        Ok(Self { stream, writer })
+    }
+    
+    /// Another handle on the same connection, for sending from a different thread.
+    /// Only one of them should be receiving
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self { stream: self.stream.try_clone()?, writer: Arc::clone(&self.writer) })
+    }
+    
+    /// Next text message, None once the client closes or the connection drops.
+    /// Pings are answered on the way
+    pub fn receive(&mut self) -> Option<String> {
+        let mut message = Vec::new();
+        loop {
+            let (fin, opcode, payload) = self.read_frame().ok()?;
+            match opcode {
+                // Continuation, text or binary - all of it is just more message
+                0x0..=0x2 => {
+                    message.extend(payload);
+                    if message.len() as u64 > MAX_MESSAGE {
+                        return None;
+                    }
+                    if fin {
+                        return Some(String::from_utf8_lossy(&message).to_string());
+                    }
+                }
+                0x9 => self.write_frame(0xA, &payload).ok()?,
+                0xA => {}
+                // 0x8 is close, anything else is a protocol error - either way we're done
+                _ => {
+                    let _ = self.write_frame(0x8, &[]);
+                    return None;
+                }
+            }
+        }
+    }
+    
+    /// Say goodbye and hang up. A `receive` waiting on another handle returns None
+    pub fn close(&self) {
+        let _ = self.write_frame(0x8, &[]);
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+    
+    /// Give up on a send after `timeout`, instead of waiting on a client that stopped reading
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_write_timeout(timeout)
+    }
+    
+    pub fn send(&self, text: &str) -> io::Result<()> {
+        self.write_frame(0x1, text.as_bytes())
+    }
+    
+    fn read_frame(&mut self) -> io::Result<(bool, u8, Vec<u8>)> {
+        let mut header = [0u8; 2];
+        self.stream.read_exact(&mut header)?;
+        let fin = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        let masked = header[1] & 0x80 != 0;
+        
+        let length = match header[1] & 0x7F {
+            126 => {
+                let mut extended = [0u8; 2];
+                self.stream.read_exact(&mut extended)?;
+                u16::from_be_bytes(extended) as u64
+            }
+            127 => {
+                let mut extended = [0u8; 8];
+                self.stream.read_exact(&mut extended)?;
+                u64::from_be_bytes(extended)
+            }
+            length => length as u64,
+        };
+        if length > MAX_MESSAGE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too big"));
+        }
+        
+        let mut mask = [0u8; 4];
+        if masked {
+            self.stream.read_exact(&mut mask)?;
+        }
+        let mut payload = vec![0u8; length as usize];
+        self.stream.read_exact(&mut payload)?;
+        if masked {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+        }
+        
+        Ok((fin, opcode, payload))
+    }
+    
+    /// Server frames are never masked
+    fn write_frame(&self, opcode: u8, payload: &[u8]) -> io::Result<()> {
+        let mut frame = vec![0x80 | opcode];
+        match payload.len() {
+            length if length < 126 => frame.push(length as u8),
+            length if length <= u16::MAX as usize => {
+                frame.push(126);
+                frame.extend((length as u16).to_be_bytes());
+            }
+            length => {
+                frame.push(127);
+                frame.extend((length as u64).to_be_bytes());
+            }
+        }
+        frame.extend(payload);
+        
+        let mut writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.write_all(&frame)?;
+        writer.flush()
+    }
+}
+
+/// SHA-1, only because the handshake demands it - not for anything security related
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend((data.len() as u64 * 8).to_be_bytes());
+    
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+    
+    let mut digest = [0u8; 20];
+    for (i, state) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&state.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}